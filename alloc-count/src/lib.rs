@@ -0,0 +1,98 @@
+//! Counts heap allocations, for tests and benchmarks that check how much rendering
+//! allocates. Not published.
+//!
+//! `crabular` itself forbids `unsafe` code, which a global allocator needs, so the
+//! counting allocator lives in this crate. A test binary installs it and measures a
+//! closure:
+//!
+//! ```ignore
+//! use alloc_count::{CountingAlloc, measure};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAlloc = CountingAlloc;
+//!
+//! let (output, stats) = measure(|| table.render());
+//! assert_eq!(stats.allocations, 1);
+//! ```
+//!
+//! Counts are kept per thread, so tests running in parallel do not see each other's
+//! allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocations counted on one thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Calls to `alloc`, `alloc_zeroed` and `realloc`
+    pub allocations: usize,
+    /// Bytes requested by those calls; a `realloc` counts its new size
+    pub bytes: usize,
+}
+
+thread_local! {
+    static COUNTS: Cell<AllocStats> = const {
+        Cell::new(AllocStats {
+            allocations: 0,
+            bytes: 0,
+        })
+    };
+}
+
+fn record(bytes: usize) {
+    // The counter is gone while the thread shuts down; those allocations are not counted
+    let _ = COUNTS.try_with(|counts| {
+        let mut stats = counts.get();
+        stats.allocations += 1;
+        stats.bytes += bytes;
+        counts.set(stats);
+    });
+}
+
+fn current() -> AllocStats {
+    COUNTS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// The system allocator, counting the allocations of each thread. Install it with
+/// `#[global_allocator]` in the test or benchmark binary.
+pub struct CountingAlloc;
+
+// SAFETY: every call is forwarded unchanged to the system allocator; counting only
+// touches a const-initialized thread-local, which never allocates.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        // SAFETY: the caller upholds `GlobalAlloc::alloc`'s contract
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        // SAFETY: the caller upholds `GlobalAlloc::alloc_zeroed`'s contract
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds `GlobalAlloc::dealloc`'s contract
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        // SAFETY: the caller upholds `GlobalAlloc::realloc`'s contract
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Runs `f` and returns its result with the allocations it made on this thread.
+/// Everything is zero unless [`CountingAlloc`] is the global allocator.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let before = current();
+    let result = f();
+    let after = current();
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+    };
+    (result, stats)
+}
@@ -0,0 +1,52 @@
+use alloc_count::{CountingAlloc, measure};
+use crabular::Table;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+fn table(rows: usize) -> Table {
+    let mut table = Table::new().header(["id", "name", "score"]);
+    for index in 0..rows {
+        table.add_row([
+            index.to_string(),
+            format!("user {index}"),
+            "9.5".to_string(),
+        ]);
+    }
+    table
+}
+
+#[test]
+fn counts_allocations() {
+    let (buffer, stats) = measure(|| Vec::<u8>::with_capacity(64));
+    assert_eq!(stats.allocations, 1);
+    assert_eq!(stats.bytes, 64);
+    drop(buffer);
+
+    let ((), stats) = measure(|| {});
+    assert_eq!(stats.allocations, 0);
+}
+
+#[test]
+fn memoized_render_allocates_only_the_copy() {
+    let mut table = table(100);
+    table.set_memoize(true);
+    let _ = table.render();
+    let (output, stats) = measure(|| table.render());
+    assert_eq!(stats.allocations, 1);
+    assert_eq!(stats.bytes, output.len());
+}
+
+#[test]
+fn render_allocations_grow_linearly() {
+    let allocations = |rows: usize| {
+        let table = table(rows);
+        let _ = table.render();
+        measure(|| table.render()).1.allocations
+    };
+    let (small, medium, large) = (allocations(100), allocations(200), allocations(400));
+    // Doubling the rows at most doubles the allocations, and then some for the
+    // output buffer growing once more
+    assert!(medium <= 2 * small + 8, "{small} -> {medium}");
+    assert!(large <= 2 * medium + 8, "{medium} -> {large}");
+}
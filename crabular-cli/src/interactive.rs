@@ -0,0 +1,237 @@
+//! Full-screen pager for `--interactive`: scrolling, sorting on a keypress and an
+//! incremental fuzzy filter, all done with the table's own sort and filter methods.
+
+use std::io::{self, IsTerminal, Write};
+
+use crabular::Table;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::is_numeric_column;
+
+const KEYS: &str = "↑↓ scroll  ←→ column  s sort  / filter  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Ascending,
+    Descending,
+}
+
+struct Pager {
+    /// The table as loaded, which every view is derived from.
+    table: Table,
+    /// The filtered and sorted rows on screen.
+    view: Table,
+    column: usize,
+    sort: Option<(usize, Order)>,
+    filter: String,
+    editing: bool,
+    top: usize,
+    /// Body rows that fit on the last drawn screen, used for paging.
+    page: usize,
+}
+
+impl Pager {
+    fn new(table: Table) -> Self {
+        let view = table.filtered(|_| true);
+        Self {
+            table,
+            view,
+            column: 0,
+            sort: None,
+            filter: String::new(),
+            editing: false,
+            top: 0,
+            page: 1,
+        }
+    }
+
+    /// Rebuilds the view after the filter or the sort changed.
+    fn refresh(&mut self) {
+        // Rows are ranked best match first unless a sort is active
+        let mut view = self.table.filtered(|_| true);
+        view.filter_fuzzy(&self.filter);
+        if let Some((column, order)) = self.sort {
+            match (is_numeric_column(&self.table, column), order) {
+                (false, Order::Ascending) => view.sort(column),
+                (false, Order::Descending) => view.sort_desc(column),
+                (true, Order::Ascending) => view.sort_num(column),
+                (true, Order::Descending) => view.sort_num_desc(column),
+            }
+            if let Some(headers) = view.headers() {
+                let marker = match order {
+                    Order::Ascending => "▲",
+                    Order::Descending => "▼",
+                };
+                let labels: Vec<String> = headers
+                    .cells()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, cell)| {
+                        if index == column {
+                            format!("{} {marker}", cell.content())
+                        } else {
+                            cell.content().to_string()
+                        }
+                    })
+                    .collect();
+                view.set_headers(labels);
+            }
+        }
+        self.view = view;
+        self.top = self.top.min(self.view.len().saturating_sub(1));
+    }
+
+    fn column_name(&self) -> String {
+        self.table
+            .headers()
+            .and_then(|headers| headers.cells().get(self.column))
+            .map_or_else(
+                || self.column.to_string(),
+                |cell| cell.content().to_string(),
+            )
+    }
+
+    fn draw(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (usize::from(width), usize::from(height));
+
+        // Widths are measured over the whole view, so columns stay put while scrolling.
+        let mut stream = self.view.stream();
+        let head = stream.header();
+        let frame = head.lines().count() + stream.footer().lines().count() + 1;
+        self.page = height.saturating_sub(frame).max(1);
+        let end = (self.top + self.page).min(self.view.len());
+        let body = stream.rows(self.view.rows()[self.top..end].to_vec());
+        let foot = stream.footer();
+
+        queue!(out, Clear(ClearType::All))?;
+        let lines = head.lines().chain(body.lines()).chain(foot.lines());
+        for (y, line) in lines.take(height.saturating_sub(1)).enumerate() {
+            let line: String = line.chars().take(width).collect();
+            queue!(out, MoveTo(0, screen_row(y)), Print(line))?;
+        }
+
+        let position = if self.view.rows().is_empty() {
+            "no rows".to_string()
+        } else {
+            format!("{}-{} of {}", self.top + 1, end, self.view.len())
+        };
+        let filter = if self.editing {
+            format!("  filter: {}_", self.filter)
+        } else if self.filter.is_empty() {
+            String::new()
+        } else {
+            format!("  filter: {}", self.filter)
+        };
+        let status = format!(
+            " {position}  column: {}{filter}  {KEYS}",
+            self.column_name()
+        );
+        let status: String = status.chars().take(width).collect();
+        queue!(
+            out,
+            MoveTo(0, screen_row(height.saturating_sub(1))),
+            SetAttribute(Attribute::Reverse),
+            Print(format!("{status:width$}")),
+            SetAttribute(Attribute::Reset),
+        )?;
+        out.flush()
+    }
+
+    /// Handles a key press, returning `false` when the pager should close.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        if self.editing {
+            match key.code {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.editing = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.editing = false;
+                }
+                _ => return true,
+            }
+            self.top = 0;
+            self.refresh();
+            return true;
+        }
+
+        let last = self.view.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.top = self.top.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.top = (self.top + 1).min(last),
+            KeyCode::PageUp | KeyCode::Char('b') => self.top = self.top.saturating_sub(self.page),
+            KeyCode::PageDown | KeyCode::Char(' ') => {
+                self.top = (self.top + self.page).min(last);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.top = 0,
+            KeyCode::End | KeyCode::Char('G') => self.top = last.saturating_sub(self.page - 1),
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.column = (self.column + 1).min(self.table.cols().saturating_sub(1));
+            }
+            KeyCode::Char('s') => {
+                self.sort = match self.sort {
+                    Some((column, Order::Ascending)) if column == self.column => {
+                        Some((column, Order::Descending))
+                    }
+                    Some((column, Order::Descending)) if column == self.column => None,
+                    _ => Some((self.column, Order::Ascending)),
+                };
+                self.refresh();
+            }
+            KeyCode::Char('/') => self.editing = true,
+            _ => {}
+        }
+        true
+    }
+}
+
+fn screen_row(y: usize) -> u16 {
+    u16::try_from(y).unwrap_or(u16::MAX)
+}
+
+/// Restores the terminal when the pager exits, including on errors.
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Shows `table` in the pager until the user quits.
+pub fn run(table: Table) -> io::Result<()> {
+    let mut out = io::stdout();
+    if !out.is_terminal() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--interactive needs a terminal on stdout",
+        ));
+    }
+
+    let mut pager = Pager::new(table);
+    terminal::enable_raw_mode()?;
+    let _raw_mode = RawMode;
+    execute!(out, EnterAlternateScreen, Hide)?;
+    loop {
+        pager.draw(&mut out)?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !pager.handle(key)
+        {
+            return Ok(());
+        }
+    }
+}
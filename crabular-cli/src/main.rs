@@ -1,18 +1,41 @@
+use std::cell::{Cell, OnceCell, RefCell};
 use std::fs;
-use std::io::{self, Read};
-use std::path::PathBuf;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 
-use clap::{Parser, ValueEnum};
-use crabular::{TableBuilder, TableStyle};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use crabular::{
+    Alignment, Badges, ColorMode, CsvOptions, FormatterRegistry, Row, RowProvider, Table,
+    TableBuilder, TableSpec, TableStyle, Theme,
+};
+use regex::Regex;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use yaml_rust2::{Yaml, YamlLoader};
+
+mod interactive;
+mod report;
 
 #[derive(Debug, Parser)]
 #[command(name = "crabular")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(short, long, value_enum, default_value = "modern")]
     style: StyleArg,
 
+    /// Color theme: border style, header emphasis, zebra stripes and colors by column
+    /// type. Its border style replaces --style; colors are only written to a terminal
+    #[arg(long, value_enum, value_name = "THEME")]
+    theme: Option<ThemeArg>,
+
+    /// Input file, or `-` for stdin. Without it, piped stdin is read and a terminal
+    /// gets this help
     #[arg(short, long)]
     input: Option<PathBuf>,
 
@@ -22,8 +45,14 @@ struct Cli {
     #[arg(short = 'S', long, default_value = ",")]
     separator: String,
 
-    #[arg(long, value_enum, default_value = "csv")]
-    format: DataFormat,
+    /// Input format. Defaults to csv for files; for stdin it is guessed from the first
+    /// bytes: JSON, JSON Lines, TSV, or else CSV
+    #[arg(long, value_enum)]
+    format: Option<DataFormat>,
+
+    /// Input format when reading stdin, instead of --format or the guess
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    stdin_format: Option<DataFormat>,
 
     #[arg(long, default_value = "false")]
     no_header: bool,
@@ -31,23 +60,395 @@ struct Cli {
     #[arg(long, default_value = "false")]
     skip_header: bool,
 
+    /// Comma-separated column names. For csv, tsv and ssv input the first row is then
+    /// read as data, unless --skip-header drops it
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    headers: Option<Vec<String>>,
+
+    /// Relabel a column, given by header name or 0-based index (repeatable)
+    #[arg(long, value_name = "OLD=NEW", value_parser = parse_rename)]
+    rename: Vec<FilterSpec>,
+
     #[arg(long, value_name = "N")]
     truncate: Option<usize>,
+
+    /// Remove ANSI escape codes (colors, cursor movement) from the input
+    #[arg(long, default_value = "false")]
+    strip_ansi: bool,
+
+    /// Regex applied to each input line; named capture groups become columns and
+    /// lines that do not match are skipped. Overrides --format.
+    #[arg(long, value_name = "REGEX")]
+    pattern: Option<String>,
+
+    /// Output format; everything but `table` ignores --style
+    #[arg(long, value_enum, default_value = "table")]
+    to: OutputFormat,
+
+    /// Sort rows by a column (header name or 0-based index), optionally
+    /// numerically and/or descending
+    #[arg(long, value_name = "COL[:num][:desc]", value_parser = parse_sort)]
+    sort: Option<SortSpec>,
+
+    /// Keep rows whose column equals the value; may be repeated
+    #[arg(long, value_name = "COL=VALUE", value_parser = parse_filter)]
+    filter: Vec<FilterSpec>,
+
+    /// Keep rows whose column contains the substring; may be repeated
+    #[arg(long, value_name = "COL~SUBSTRING", value_parser = parse_grep)]
+    grep: Vec<FilterSpec>,
+
+    /// Show only this page of rows (1-based). CSV, TSV and SSV files are indexed and
+    /// read from disk instead of loaded when no --sort, --filter or --grep is given
+    #[arg(long, value_name = "N")]
+    page: Option<NonZeroUsize>,
+
+    /// Rows per page for --page
+    #[arg(long, value_name = "N", default_value = "50")]
+    page_size: NonZeroUsize,
+
+    /// Column alignments as COL:left|center|right|decimal, comma-separated
+    #[arg(long, value_name = "COL:ALIGN,...", value_delimiter = ',', value_parser = parse_align)]
+    align: Vec<AlignSpec>,
+
+    /// Right-align columns whose values are all numbers; --align takes precedence
+    #[arg(long, default_value = "false")]
+    align_numeric_right: bool,
+
+    /// Display a column through a named formatter (bytes, duration, mask[:N],
+    /// template:TEXT, badge, number:thousands,decimals=N,percent,currency=SYM); may be
+    /// repeated
+    #[arg(long, value_name = "COL=FORMATTER", value_parser = parse_format_col)]
+    format_col: Vec<FilterSpec>,
+
+    /// Link a column to a URL, where {value} stands for the cell content. Used by
+    /// --to html, and by the table output when writing to a terminal; may be repeated
+    #[arg(long, value_name = "COL=TEMPLATE", value_parser = parse_link_col)]
+    link_col: Vec<FilterSpec>,
+
+    /// Text shown in empty cells, including JSON nulls
+    #[arg(long, value_name = "TEXT")]
+    na_string: Option<String>,
+
+    /// Table spec file (.toml, .json, .yaml) declaring style, columns, filters and
+    /// sorts; applied before the other flags
+    #[arg(long, value_name = "FILE")]
+    spec: Option<PathBuf>,
+
+    /// Render while reading, holding only one batch of rows in memory. Column widths
+    /// are fixed by the first --sample rows; later content that does not fit is
+    /// truncated. Supports csv, tsv, ssv and jsonl input
+    #[arg(long, default_value = "false")]
+    stream: bool,
+
+    /// Rows read to fix column widths with --stream, and the batch size after that
+    #[arg(long, value_name = "N", default_value = "1000")]
+    sample: NonZeroUsize,
+
+    /// Browse the table in a full-screen pager: arrows or hjkl to scroll and pick a
+    /// column, s to sort by it, / to filter, q to quit
+    #[arg(long, default_value = "false")]
+    interactive: bool,
+
+    /// Fail when a row has more or fewer fields than the header (or, without one, the
+    /// first row), listing the offending rows numbered from 1
+    #[arg(long, default_value = "false")]
+    strict: bool,
+
+    /// Choose the columns to show from a checkbox list before rendering. Applies
+    /// before --sort, --filter and the other column flags, which then only see the
+    /// chosen columns
+    #[arg(long, default_value = "false")]
+    pick_interactive: bool,
+}
+
+#[derive(Debug, Clone)]
+struct AlignSpec {
+    column: String,
+    alignment: Alignment,
+}
+
+fn parse_align(s: &str) -> Result<AlignSpec, String> {
+    let Some((column, alignment)) = s.rsplit_once(':') else {
+        return Err("expected COL:ALIGN".to_string());
+    };
+    let alignment = alignment.parse().map_err(|()| {
+        format!("unknown alignment `{alignment}`, expected left, center, right or decimal")
+    })?;
+    Ok(AlignSpec {
+        column: column.to_string(),
+        alignment,
+    })
+}
+
+/// Returns true if every non-empty cell of `column` is a number, and there is at least one.
+fn is_numeric_column(table: &Table, column: usize) -> bool {
+    table.column_type(column).is_numeric()
+}
+
+#[derive(Debug, Clone)]
+struct SortSpec {
+    column: String,
+    numeric: bool,
+    descending: bool,
+}
+
+fn parse_sort(s: &str) -> Result<SortSpec, String> {
+    let mut parts = s.split(':');
+    let column = parts.next().unwrap_or_default();
+    if column.is_empty() {
+        return Err("missing column".to_string());
+    }
+    let mut spec = SortSpec {
+        column: column.to_string(),
+        numeric: false,
+        descending: false,
+    };
+    for flag in parts {
+        match flag {
+            "num" => spec.numeric = true,
+            "desc" => spec.descending = true,
+            other => return Err(format!("unknown sort flag `{other}`, expected num or desc")),
+        }
+    }
+    Ok(spec)
+}
+
+#[derive(Debug, Clone)]
+struct FilterSpec {
+    column: String,
+    value: String,
+}
+
+fn parse_filter(s: &str) -> Result<FilterSpec, String> {
+    split_filter(s, '=')
+}
+
+fn parse_grep(s: &str) -> Result<FilterSpec, String> {
+    split_filter(s, '~')
+}
+
+fn parse_rename(s: &str) -> Result<FilterSpec, String> {
+    split_filter(s, '=')
+}
+
+fn parse_format_col(s: &str) -> Result<FilterSpec, String> {
+    let spec = split_filter(s, '=')?;
+    if FormatterRegistry::new().resolve(&spec.value).is_none() {
+        return Err(format!("unknown formatter `{}`", spec.value));
+    }
+    Ok(spec)
+}
+
+fn parse_link_col(s: &str) -> Result<FilterSpec, String> {
+    let spec = split_filter(s, '=')?;
+    if !spec.value.contains("{value}") {
+        return Err("the link template needs a {value} placeholder".to_string());
+    }
+    Ok(spec)
+}
+
+fn split_filter(s: &str, operator: char) -> Result<FilterSpec, String> {
+    match s.split_once(operator) {
+        Some((column, value)) if !column.is_empty() => Ok(FilterSpec {
+            column: column.to_string(),
+            value: value.to_string(),
+        }),
+        _ => Err(format!("expected COL{operator}VALUE")),
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Render a report from a spec file (.toml, .json, .yaml) that names the input,
+    /// the inputs joined to it, and the table spec settings: style, columns, filters,
+    /// sorts, grouping and aggregates
+    Report {
+        #[arg(value_name = "SPEC")]
+        spec: PathBuf,
+
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format; everything but `table` ignores the spec's style
+        #[arg(long, value_enum, default_value = "table")]
+        to: OutputFormat,
+    },
+}
+
+/// Reads a .toml, .json or .yaml settings file, such as a table spec.
+fn load_settings<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+    let content = fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&content).map_err(|e| invalid(e.to_string())),
+        Some("json") => serde_json::from_str(&content).map_err(|e| invalid(e.to_string())),
+        Some("yaml" | "yml") => {
+            let mut documents =
+                YamlLoader::load_from_str(&content).map_err(|e| invalid(e.to_string()))?;
+            let document = if documents.is_empty() {
+                Value::Object(serde_json::Map::new())
+            } else {
+                yaml_to_json(documents.remove(0))
+            };
+            serde_json::from_value(document).map_err(|e| invalid(e.to_string()))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Spec file must end in .toml, .json, .yaml or .yml",
+        )),
+    }
+}
+
+/// Applies --headers and --rename to the headers read from the input.
+fn relabel(args: &Cli, headers: Option<Vec<String>>) -> io::Result<Option<Vec<String>>> {
+    let mut headers = args.headers.clone().or(headers);
+    for rename in &args.rename {
+        let Some(names) = headers.as_mut() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--rename needs a header row; name the columns with --headers instead",
+            ));
+        };
+        let column = resolve_column(&rename.column, Some(names))?;
+        if column >= names.len() {
+            names.resize(column + 1, String::new());
+        }
+        names[column].clone_from(&rename.value);
+    }
+    Ok(headers)
+}
+
+/// Asks which columns to keep with a checkbox list, all checked to start with, and
+/// returns their indexes in order.
+fn pick_columns(headers: Option<&[String]>, rows: &[Vec<String>]) -> io::Result<Vec<usize>> {
+    if !io::stderr().is_terminal() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--pick-interactive needs a terminal",
+        ));
+    }
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain(headers.map(<[String]>::len))
+        .max()
+        .unwrap_or(0);
+    let labels: Vec<String> = (0..columns)
+        .map(
+            |column| match headers.and_then(|headers| headers.get(column)) {
+                Some(header) if !header.is_empty() => header.clone(),
+                _ => format!("Column {column}"),
+            },
+        )
+        .collect();
+    let picked = dialoguer::MultiSelect::new()
+        .with_prompt("Columns to show (space toggles, enter confirms)")
+        .items(&labels)
+        .defaults(&vec![true; columns])
+        .interact_opt()?;
+    match picked {
+        Some(picked) if !picked.is_empty() => Ok(picked),
+        Some(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No columns selected",
+        )),
+        None => Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            "Column selection cancelled",
+        )),
+    }
+}
+
+/// Keeps the `picked` fields of `fields`, in the order given.
+fn keep_columns(fields: &[String], picked: &[usize]) -> Vec<String> {
+    picked
+        .iter()
+        .map(|&column| fields.get(column).cloned().unwrap_or_default())
+        .collect()
+}
+
+/// Resolves a column given by header name, falling back to a 0-based index.
+fn resolve_column(column: &str, headers: Option<&[String]>) -> io::Result<usize> {
+    headers
+        .and_then(|headers| headers.iter().position(|header| header == column))
+        .or_else(|| column.parse().ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown column `{column}`"),
+            )
+        })
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Markdown,
+    Html,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum DataFormat {
     Csv,
     Tsv,
     Ssv,
     Json,
     Jsonl,
+    Logfmt,
+    Yaml,
+    Toml,
 }
 
 impl DataFormat {
+    /// Guesses the format of piped input from its first bytes: `[` starts JSON, `{`
+    /// starts JSON Lines when the first line is a whole object, a tab outside quotes in
+    /// the first line means TSV, and anything else is read as CSV.
+    fn sniff(head: &[u8]) -> Self {
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        let first_line = head.lines().next().unwrap_or_default();
+        if head.starts_with('[') {
+            DataFormat::Json
+        } else if head.starts_with('{') {
+            if serde_json::from_str::<Value>(first_line).is_ok() {
+                DataFormat::Jsonl
+            } else {
+                DataFormat::Json
+            }
+        } else if first_line
+            .split('"')
+            .step_by(2)
+            .any(|unquoted| unquoted.contains('\t'))
+        {
+            DataFormat::Tsv
+        } else {
+            DataFormat::Csv
+        }
+    }
+
+    /// Picks the format named by a file extension, or CSV.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("tsv") => DataFormat::Tsv,
+            Some("json") => DataFormat::Json,
+            Some("jsonl" | "ndjson") => DataFormat::Jsonl,
+            Some("yaml" | "yml") => DataFormat::Yaml,
+            Some("toml") => DataFormat::Toml,
+            _ => DataFormat::Csv,
+        }
+    }
+
     fn default_separator(self) -> &'static str {
         match self {
-            DataFormat::Csv | DataFormat::Json | DataFormat::Jsonl => ",",
+            DataFormat::Csv
+            | DataFormat::Json
+            | DataFormat::Jsonl
+            | DataFormat::Logfmt
+            | DataFormat::Yaml
+            | DataFormat::Toml => ",",
             DataFormat::Tsv => "\t",
             DataFormat::Ssv => " ",
         }
@@ -61,6 +462,28 @@ enum StyleArg {
     Minimal,
     Compact,
     Markdown,
+    #[value(alias = "rst")]
+    Rest,
+    #[value(name = "asciidoc", alias = "adoc")]
+    AsciiDoc,
+    Org,
+    #[value(name = "plainspace", alias = "plain")]
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ThemeArg {
+    Plain,
+    Dracula,
+}
+
+impl From<ThemeArg> for Theme {
+    fn from(val: ThemeArg) -> Self {
+        match val {
+            ThemeArg::Plain => Theme::plain(),
+            ThemeArg::Dracula => Theme::dracula(),
+        }
+    }
 }
 
 impl From<StyleArg> for TableStyle {
@@ -71,6 +494,10 @@ impl From<StyleArg> for TableStyle {
             StyleArg::Minimal => TableStyle::Minimal,
             StyleArg::Compact => TableStyle::Compact,
             StyleArg::Markdown => TableStyle::Markdown,
+            StyleArg::Rest => TableStyle::Rest,
+            StyleArg::AsciiDoc => TableStyle::AsciiDoc,
+            StyleArg::Org => TableStyle::Org,
+            StyleArg::Plain => TableStyle::Plain,
         }
     }
 }
@@ -85,6 +512,10 @@ enum DataParser {
     Csv(CsvParser),
     Json(JsonParser),
     Jsonl(JsonlParser),
+    Logfmt(LogfmtParser),
+    Yaml(YamlParser),
+    Toml(TomlParser),
+    Pattern(PatternParser),
 }
 
 impl DataParser {
@@ -93,6 +524,10 @@ impl DataParser {
             DataParser::Csv(p) => p.parse(reader),
             DataParser::Json(_) => JsonParser::parse(reader),
             DataParser::Jsonl(_) => JsonlParser::parse(reader),
+            DataParser::Logfmt(_) => LogfmtParser::parse(reader),
+            DataParser::Yaml(_) => YamlParser::parse(reader),
+            DataParser::Toml(_) => TomlParser::parse(reader),
+            DataParser::Pattern(p) => p.parse(reader),
         }
     }
 }
@@ -117,6 +552,7 @@ impl CsvParser {
 
         let mut rdr: csv::Reader<_> = csv::ReaderBuilder::new()
             .has_headers(false)
+            .flexible(true)
             .delimiter(separator_char as u8)
             .from_reader(&mut *reader);
 
@@ -158,8 +594,8 @@ fn extract_row(obj: &serde_json::Map<String, Value>, keys: &mut Vec<String>) ->
             let v = obj.get(k);
             match v {
                 Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => String::new(),
                 Some(v) => serde_json::to_string(v).unwrap_or_default(),
-                None => String::new(),
             }
         })
         .collect()
@@ -186,27 +622,141 @@ impl JsonParser {
             }
         };
 
-        let mut keys: Vec<String> = Vec::new();
-        let rows: Vec<Vec<String>> = match value {
-            Value::Array(arr) => arr
-                .iter()
-                .filter_map(|item| {
-                    if let Value::Object(obj) = item {
-                        Some(extract_row(obj, &mut keys))
-                    } else {
-                        None
-                    }
+        Ok(rows_from_value(&value))
+    }
+}
+
+/// Turns an array of objects into rows, or a single object into one row. Keys of the
+/// first object become the headers.
+fn rows_from_value(value: &Value) -> RowData {
+    let mut keys: Vec<String> = Vec::new();
+    let rows: Vec<Vec<String>> = match value {
+        Value::Array(arr) => arr
+            .iter()
+            .filter_map(|item| {
+                if let Value::Object(obj) = item {
+                    Some(extract_row(obj, &mut keys))
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        Value::Object(obj) => {
+            vec![extract_row(obj, &mut keys)]
+        }
+        _ => vec![],
+    };
+
+    let headers = if keys.is_empty() { None } else { Some(keys) };
+
+    RowData { headers, rows }
+}
+
+struct YamlParser;
+
+impl YamlParser {
+    fn new() -> Self {
+        Self
+    }
+
+    /// Reads a sequence of mappings, a single mapping, or a stream of `---`
+    /// separated mappings with one row per document.
+    fn parse(mut reader: Box<dyn Read>) -> io::Result<RowData> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut documents = YamlLoader::load_from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let value = if documents.len() == 1 {
+            yaml_to_json(documents.remove(0))
+        } else {
+            Value::Array(documents.into_iter().map(yaml_to_json).collect())
+        };
+
+        Ok(rows_from_value(&value))
+    }
+}
+
+fn yaml_to_json(yaml: Yaml) -> Value {
+    match yaml {
+        Yaml::Real(s) | Yaml::String(s) => Value::String(s),
+        Yaml::Integer(i) => Value::from(i),
+        Yaml::Boolean(b) => Value::Bool(b),
+        Yaml::Array(items) => Value::Array(items.into_iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => Value::Object(
+            hash.into_iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(value))
                 })
                 .collect(),
-            Value::Object(obj) => {
-                vec![extract_row(&obj, &mut keys)]
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => Value::Null,
+    }
+}
+
+struct TomlParser;
+
+impl TomlParser {
+    fn new() -> Self {
+        Self
+    }
+
+    /// Reads the rows of a document's only array of tables (`[[item]]`), or one row
+    /// per sub-table when every top-level value is a table, with the table name in a
+    /// `key` column. Any other document becomes a single row.
+    fn parse(mut reader: Box<dyn Read>) -> io::Result<RowData> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let document: toml::Table = content.parse().map_err(|e: toml::de::Error| {
+            io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+        })?;
+
+        let mut values = document.values();
+        let value = match (values.next(), values.next()) {
+            (Some(toml::Value::Array(items)), None) => {
+                Value::Array(items.iter().cloned().map(toml_to_json).collect())
+            }
+            _ if !document.is_empty() && document.values().all(toml::Value::is_table) => {
+                Value::Array(
+                    document
+                        .into_iter()
+                        .map(|(name, table)| {
+                            let mut row = serde_json::Map::new();
+                            row.insert("key".to_string(), Value::String(name));
+                            if let Value::Object(fields) = toml_to_json(table) {
+                                row.extend(fields);
+                            }
+                            Value::Object(row)
+                        })
+                        .collect(),
+                )
             }
-            _ => vec![],
+            _ => toml_to_json(toml::Value::Table(document)),
         };
 
-        let headers = if keys.is_empty() { None } else { Some(keys) };
+        Ok(rows_from_value(&value))
+    }
+}
 
-        Ok(RowData { headers, rows })
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
     }
 }
 
@@ -241,6 +791,553 @@ impl JsonlParser {
     }
 }
 
+struct LogfmtParser;
+
+impl LogfmtParser {
+    fn new() -> Self {
+        Self
+    }
+
+    fn parse(mut reader: Box<dyn Read>) -> io::Result<RowData> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let mut keys: Vec<String> = Vec::new();
+        let records: Vec<Vec<(String, String)>> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let pairs = parse_logfmt_line(line);
+                for (key, _) in &pairs {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+                pairs
+            })
+            .collect();
+
+        let rows: Vec<Vec<String>> = records
+            .iter()
+            .map(|pairs| {
+                keys.iter()
+                    .map(|k| {
+                        pairs
+                            .iter()
+                            .find(|(key, _)| key == k)
+                            .map(|(_, value)| value.clone())
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let headers = if keys.is_empty() { None } else { Some(keys) };
+
+        Ok(RowData { headers, rows })
+    }
+}
+
+/// Splits a logfmt line such as `level=info msg="user logged in" cached` into
+/// key/value pairs. A bare key has the value `true`.
+fn parse_logfmt_line(line: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+
+        let value = if chars.next_if_eq(&'=').is_none() {
+            "true".to_string()
+        } else if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(escaped) => value.push(escaped),
+                        None => {}
+                    },
+                    c => value.push(c),
+                }
+            }
+            value
+        } else {
+            let mut value = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                value.push(c);
+            }
+            value
+        };
+
+        if !key.is_empty() {
+            pairs.push((key, value));
+        }
+    }
+
+    pairs
+}
+
+struct PatternParser {
+    regex: Regex,
+    names: Vec<String>,
+}
+
+impl PatternParser {
+    fn new(pattern: &str) -> io::Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        let names: Vec<String> = regex.capture_names().flatten().map(String::from).collect();
+        if names.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Pattern has no named capture groups. Use (?P<name>...) to name columns",
+            ));
+        }
+        Ok(Self { regex, names })
+    }
+
+    fn parse(&self, mut reader: Box<dyn Read>) -> io::Result<RowData> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+
+        let rows: Vec<Vec<String>> = content
+            .lines()
+            .filter_map(|line| self.regex.captures(line))
+            .map(|captures| {
+                self.names
+                    .iter()
+                    .map(|name| {
+                        captures
+                            .name(name)
+                            .map_or_else(String::new, |m| m.as_str().to_string())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(RowData {
+            headers: Some(self.names.clone()),
+            rows,
+        })
+    }
+}
+
+/// Records between two entries of the [`CsvFile`] offset index.
+const CSV_INDEX_STRIDE: usize = 1024;
+
+/// The records of a delimited file as a [`RowProvider`], read from disk on demand
+/// rather than loaded.
+///
+/// The file position of every [`CSV_INDEX_STRIDE`]th record is indexed as far as the
+/// rows asked for, so a row is found by seeking to the nearest indexed record and
+/// reading forward, and consecutive rows are read without seeking. Counting the rows
+/// indexes the whole file once, keeping nothing but the offsets.
+///
+/// `RowProvider` cannot fail, so a read error ends the rows early and is returned by
+/// [`CsvFile::finish`].
+struct CsvFile {
+    headers: Option<Vec<String>>,
+    reader: RefCell<csv::Reader<BufReader<fs::File>>>,
+    /// Index of the record the reader is positioned at
+    next: Cell<usize>,
+    /// Positions of records 0, `CSV_INDEX_STRIDE`, 2 × `CSV_INDEX_STRIDE`, ...
+    index: RefCell<Vec<csv::Position>>,
+    len: OnceCell<usize>,
+    error: RefCell<Option<csv::Error>>,
+}
+
+impl CsvFile {
+    fn open(path: &Path, delimiter: u8, header: bool) -> io::Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .delimiter(delimiter)
+            .from_reader(BufReader::new(fs::File::open(path)?));
+        let mut record = csv::ByteRecord::new();
+        let headers = if header && reader.read_byte_record(&mut record)? {
+            Some(
+                record
+                    .iter()
+                    .map(|field| String::from_utf8_lossy(field).into_owned())
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        let first = reader.position().clone();
+        Ok(Self {
+            headers,
+            reader: RefCell::new(reader),
+            next: Cell::new(0),
+            index: RefCell::new(vec![first]),
+            len: OnceCell::new(),
+            error: RefCell::new(None),
+        })
+    }
+
+    /// Returns the first read error, if any.
+    fn finish(self) -> io::Result<()> {
+        self.error
+            .into_inner()
+            .map_or(Ok(()), |error| Err(error.into()))
+    }
+
+    /// Reads the record the reader is positioned at into `record`, recording it in the
+    /// index when it starts a stride. Returns `false` at the end of the file or on an
+    /// error.
+    fn read(
+        &self,
+        reader: &mut csv::Reader<BufReader<fs::File>>,
+        record: &mut csv::ByteRecord,
+    ) -> bool {
+        let current = self.next.get();
+        if current.is_multiple_of(CSV_INDEX_STRIDE) {
+            let mut index = self.index.borrow_mut();
+            if index.len() == current / CSV_INDEX_STRIDE {
+                index.push(reader.position().clone());
+            }
+        }
+        match reader.read_byte_record(record) {
+            Ok(true) => {
+                self.next.set(current + 1);
+                true
+            }
+            Ok(false) => {
+                let _ = self.len.set(current);
+                false
+            }
+            Err(error) => {
+                self.error.borrow_mut().get_or_insert(error);
+                let _ = self.len.set(current);
+                false
+            }
+        }
+    }
+
+    /// Positions the reader at record `target`, indexing the records on the way.
+    /// Returns `false` when the file has fewer records.
+    fn seek(&self, target: usize) -> bool {
+        let mut reader = self.reader.borrow_mut();
+        let checkpoint = (target / CSV_INDEX_STRIDE).min(self.index.borrow().len() - 1);
+        let from = checkpoint * CSV_INDEX_STRIDE;
+        // Keep reading forward unless the nearest indexed record is closer
+        if !(from..=target).contains(&self.next.get()) {
+            let position = self.index.borrow()[checkpoint].clone();
+            if let Err(error) = reader.seek(position) {
+                self.error.borrow_mut().get_or_insert(error);
+                return false;
+            }
+            self.next.set(from);
+        }
+        let mut skipped = csv::ByteRecord::new();
+        while self.next.get() < target {
+            if !self.read(&mut reader, &mut skipped) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl RowProvider for CsvFile {
+    fn len(&self) -> usize {
+        if let Some(&len) = self.len.get() {
+            return len;
+        }
+        self.seek(usize::MAX);
+        self.len.get().copied().unwrap_or(self.next.get())
+    }
+
+    fn get(&self, index: usize) -> Row {
+        let mut record = csv::ByteRecord::new();
+        if self.seek(index) {
+            let mut reader = self.reader.borrow_mut();
+            self.read(&mut reader, &mut record);
+        }
+        Row::from(
+            record
+                .iter()
+                .map(|field| String::from_utf8_lossy(field).into_owned())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Returns the rows of `page` (1-based) from `source`.
+fn page_rows<P: RowProvider + ?Sized>(
+    source: &P,
+    page: NonZeroUsize,
+    size: NonZeroUsize,
+) -> Vec<Row> {
+    let start = (page.get() - 1).saturating_mul(size.get());
+    let end = start.saturating_add(size.get()).min(source.len());
+    (start.min(end)..end)
+        .map(|index| source.get(index))
+        .collect()
+}
+
+type Records = Box<dyn Iterator<Item = io::Result<Vec<String>>>>;
+
+/// Reads CSV records one at a time, returning the header row and the data rows.
+fn csv_records(
+    reader: Box<dyn Read>,
+    separator: &str,
+    no_header: bool,
+    skip_header: bool,
+) -> io::Result<(Option<Vec<String>>, Records)> {
+    let separator_char = separator.chars().next().unwrap_or(',');
+    let mut records = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(separator_char as u8)
+        .from_reader(reader)
+        .into_records()
+        .map(|result| {
+            result
+                .map(|record| record.iter().map(ToString::to_string).collect())
+                .map_err(io::Error::from)
+        });
+    let first = if no_header && !skip_header {
+        None
+    } else {
+        records.next().transpose()?
+    };
+    let headers = if skip_header { None } else { first };
+    Ok((headers, Box::new(records)))
+}
+
+/// Reads JSONL objects one at a time. The keys of the first object become the headers.
+fn jsonl_records(reader: Box<dyn Read>) -> io::Result<(Option<Vec<String>>, Records)> {
+    let mut objects = BufReader::new(reader)
+        .lines()
+        .filter_map(|line| match line {
+            Ok(line) => match serde_json::from_str(&line) {
+                Ok(Value::Object(obj)) => Some(Ok(obj)),
+                _ => None,
+            },
+            Err(e) => Some(Err(e)),
+        });
+    let mut keys: Vec<String> = Vec::new();
+    let first = objects
+        .next()
+        .transpose()?
+        .map(|obj| extract_row(&obj, &mut keys));
+    let headers = if keys.is_empty() {
+        None
+    } else {
+        Some(keys.clone())
+    };
+    let rest = objects.map(move |obj| obj.map(|obj| extract_row(&obj, &mut keys)));
+    Ok((headers, Box::new(first.map(Ok).into_iter().chain(rest))))
+}
+
+/// Rows listed by a --strict failure before the rest are only counted.
+const MAX_REPORTED_ROWS: usize = 20;
+
+/// Describes a row whose field count differs from the expected one.
+fn ragged_row(index: usize, len: usize, expected: usize) -> String {
+    let fields = if len == 1 { "field" } else { "fields" };
+    format!("row {} has {len} {fields}, expected {expected}", index + 1)
+}
+
+/// Fails if any row's field count differs from the header's, or else the first row's.
+fn check_rectangular(headers: Option<&[String]>, rows: &[Vec<String>]) -> io::Result<()> {
+    let Some(expected) = headers
+        .map(<[String]>::len)
+        .or_else(|| rows.first().map(Vec::len))
+    else {
+        return Ok(());
+    };
+    let ragged: Vec<(usize, usize)> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.len() != expected)
+        .map(|(index, row)| (index, row.len()))
+        .collect();
+    if ragged.is_empty() {
+        return Ok(());
+    }
+    let mut lines: Vec<String> = ragged
+        .iter()
+        .take(MAX_REPORTED_ROWS)
+        .map(|&(index, len)| ragged_row(index, len, expected))
+        .collect();
+    if ragged.len() > MAX_REPORTED_ROWS {
+        lines.push(format!(
+            "and {} more rows",
+            ragged.len() - MAX_REPORTED_ROWS
+        ));
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, lines.join("\n")))
+}
+
+/// Fails on the first streamed record whose field count differs from the header's, or
+/// else the first record's.
+fn check_records(records: Records, headers: Option<usize>) -> Records {
+    let mut expected = headers;
+    Box::new(records.enumerate().map(move |(index, record)| {
+        let record = record?;
+        let expected = *expected.get_or_insert(record.len());
+        if record.len() == expected {
+            Ok(record)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                ragged_row(index, record.len(), expected),
+            ))
+        }
+    }))
+}
+
+/// Applies the row filters from the spec and the command line.
+fn filter_rows(
+    table: &mut Table,
+    args: &Cli,
+    spec: Option<&TableSpec>,
+    headers: Option<&[String]>,
+) -> io::Result<()> {
+    if let Some(spec) = spec {
+        table
+            .apply_spec(spec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    }
+    for filter in &args.filter {
+        table.filter_eq(resolve_column(&filter.column, headers)?, &filter.value);
+    }
+    for grep in &args.grep {
+        table.filter_has(resolve_column(&grep.column, headers)?, &grep.value);
+    }
+    Ok(())
+}
+
+/// Applies the column alignments and formatters from the command line.
+fn style_columns(table: &mut Table, args: &Cli, headers: Option<&[String]>) -> io::Result<()> {
+    if args.align_numeric_right {
+        let columns = table.rows().iter().map(|row| row.cells().len()).max();
+        for column in 0..columns.unwrap_or(0) {
+            if is_numeric_column(table, column) {
+                table.align(column, Alignment::Right);
+            }
+        }
+    }
+    for align in &args.align {
+        table.align(resolve_column(&align.column, headers)?, align.alignment);
+    }
+    for format in &args.format_col {
+        table.format_column(resolve_column(&format.column, headers)?, &format.value);
+    }
+    if let Some(placeholder) = &args.na_string {
+        table.set_empty_placeholder(placeholder);
+    }
+    for link in &args.link_col {
+        table.link_column(resolve_column(&link.column, headers)?, &link.value);
+    }
+    // Escape sequences would end up in files and confuse the pager's line cutting
+    let terminal = matches!(args.to, OutputFormat::Table)
+        && args.output.is_none()
+        && !args.interactive
+        && io::stdout().is_terminal();
+    if terminal && !args.link_col.is_empty() {
+        let options = table.render_options().clone().hyperlinks(true);
+        table.set_render_options(options);
+    }
+    if !terminal && args.theme.is_some() {
+        let options = table.render_options().clone().color_mode(ColorMode::None);
+        table.set_render_options(options);
+    }
+    if !terminal {
+        let mut formatters = table.formatters().clone();
+        formatters.register_badges("badge", Badges::status().without_colors());
+        table.set_formatters(formatters);
+    }
+    Ok(())
+}
+
+/// Renders the input batch by batch for --stream.
+fn stream(
+    args: &Cli,
+    mut builder: TableBuilder,
+    spec: Option<&TableSpec>,
+    reader: Box<dyn Read>,
+    format: DataFormat,
+    separator: &str,
+) -> io::Result<()> {
+    let unsupported = |message: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+    if args.sort.is_some() || spec.is_some_and(|spec| !spec.sort.is_empty()) {
+        return unsupported("--stream cannot sort, since rows are written as they are read");
+    }
+    if args.page.is_some() || args.pattern.is_some() {
+        return unsupported("--stream cannot be combined with --page or --pattern");
+    }
+    if !matches!(args.to, OutputFormat::Table | OutputFormat::Markdown) {
+        return unsupported("--stream supports --to table and --to markdown");
+    }
+
+    let (headers, mut records) = match format {
+        DataFormat::Csv | DataFormat::Tsv | DataFormat::Ssv => {
+            csv_records(reader, separator, args.no_header, args.skip_header)?
+        }
+        DataFormat::Jsonl => jsonl_records(reader)?,
+        _ => return unsupported("--stream supports csv, tsv, ssv and jsonl input"),
+    };
+    if args.strict {
+        records = check_records(records, headers.as_ref().map(Vec::len));
+    }
+    let headers = relabel(args, headers)?;
+    let headers = headers.as_deref();
+    if let Some(headers) = headers {
+        builder = builder.header(headers.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+    if matches!(args.to, OutputFormat::Markdown) {
+        builder = builder.style(TableStyle::Markdown);
+    }
+
+    let batch_size = args.sample.get();
+    let sample = records
+        .by_ref()
+        .take(batch_size)
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut template = builder.rows(sample).build();
+    filter_rows(&mut template, args, spec, headers)?;
+    style_columns(&mut template, args, headers)?;
+
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(io::BufWriter::new(io::stdout().lock())),
+    };
+    let mut stream = template.stream();
+    output.write_all(stream.header().as_bytes())?;
+    output.write_all(stream.rows(template.rows().to_vec()).as_bytes())?;
+    loop {
+        let rows = records
+            .by_ref()
+            .take(batch_size)
+            .collect::<io::Result<Vec<_>>>()?;
+        if rows.is_empty() {
+            break;
+        }
+        let mut batch = template.filtered(|_| false);
+        for row in rows {
+            batch.add_row(row);
+        }
+        filter_rows(&mut batch, args, spec, headers)?;
+        output.write_all(stream.rows(batch.rows().to_vec()).as_bytes())?;
+    }
+    output.write_all(stream.footer().as_bytes())?;
+    output.flush()
+}
+
 fn create_parser(
     format: DataFormat,
     separator: String,
@@ -253,56 +1350,443 @@ fn create_parser(
         }
         DataFormat::Json => DataParser::Json(JsonParser::new()),
         DataFormat::Jsonl => DataParser::Jsonl(JsonlParser::new()),
+        DataFormat::Logfmt => DataParser::Logfmt(LogfmtParser::new()),
+        DataFormat::Yaml => DataParser::Yaml(YamlParser::new()),
+        DataFormat::Toml => DataParser::Toml(TomlParser::new()),
     }
 }
 
+/// Renders the table in the requested format to the output file, or stdout.
+fn write_output(mut table: Table, to: OutputFormat, output: Option<&Path>) -> io::Result<()> {
+    let output_text = match to {
+        OutputFormat::Table => table.render(),
+        OutputFormat::Markdown => {
+            table.set_style(TableStyle::Markdown);
+            table.render()
+        }
+        OutputFormat::Html => table.to_html(),
+        OutputFormat::Csv => table.to_csv(&CsvOptions::new()),
+        OutputFormat::Json => table.to_json(),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(output_path, &output_text)?;
+    } else {
+        print!("{output_text}");
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
 fn main() -> io::Result<()> {
-    let args = Cli::parse();
+    let mut args = Cli::parse();
+    if let Some(Command::Report { spec, output, to }) = &args.command {
+        return report::run(spec, *to, output.as_deref());
+    }
+    if args.headers.is_some() && !args.skip_header {
+        args.no_header = true;
+    }
 
     let style: TableStyle = args.style.into();
 
     let mut builder = TableBuilder::new().style(style);
+    if let Some(theme) = args.theme {
+        builder = builder.theme(theme.into());
+    }
+    if args.strip_ansi {
+        builder = builder.strip_ansi();
+    }
     if let Some(limit) = args.truncate {
         builder = builder.truncate(limit);
     }
 
-    let file: Box<dyn Read> = if let Some(input_path) = &args.input {
-        if input_path.as_os_str() == "-" {
-            Box::new(io::stdin())
-        } else {
-            Box::new(fs::File::open(input_path)?)
+    let piped = PathBuf::from("-");
+    let input_path = match &args.input {
+        Some(path) => path,
+        None if io::stdin().is_terminal() => {
+            Cli::command().print_help()?;
+            return Ok(());
+        }
+        None => &piped,
+    };
+    let from_stdin = input_path.as_os_str() == "-";
+
+    let spec = args.spec.as_deref().map(load_settings).transpose()?;
+
+    let mut stdin = from_stdin.then(|| BufReader::new(io::stdin()));
+    let explicit = args.stdin_format.filter(|_| from_stdin).or(args.format);
+    let format = match (explicit, &mut stdin) {
+        (Some(format), _) => format,
+        (None, Some(stdin)) => DataFormat::sniff(stdin.fill_buf()?),
+        (None, None) => DataFormat::Csv,
+    };
+    let input = move || -> io::Result<Box<dyn Read>> {
+        match stdin {
+            Some(stdin) => Ok(Box::new(stdin)),
+            None => Ok(Box::new(fs::File::open(input_path)?)),
         }
-    } else {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "No input specified. Use -i FILE or pipe data via stdin",
-        ));
     };
 
     let separator = if args.separator == "," {
-        args.format.default_separator().to_string()
+        format.default_separator().to_string()
     } else {
         args.separator.clone()
     };
 
-    let mut data_parser = create_parser(args.format, separator, args.no_header, args.skip_header);
-    let data = data_parser.parse(file)?;
+    if args.interactive
+        && (args.stream
+            || args.page.is_some()
+            || args.output.is_some()
+            || !matches!(args.to, OutputFormat::Table))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--interactive cannot be combined with --stream, --page, --output or --to",
+        ));
+    }
+
+    if args.pick_interactive && args.stream {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--pick-interactive cannot be combined with --stream",
+        ));
+    }
+
+    if args.stream {
+        return stream(&args, builder, spec.as_ref(), input()?, format, &separator);
+    }
 
-    if let Some(headers) = data.headers {
+    // Pages of delimited files are read straight from the file
+    let read_page = !from_stdin
+        && args.pattern.is_none()
+        && args.sort.is_none()
+        && args.filter.is_empty()
+        && args.grep.is_empty()
+        && !args.strict
+        && !args.pick_interactive
+        && spec
+            .as_ref()
+            .is_none_or(|spec| spec.filters.is_empty() && spec.sort.is_empty())
+        && matches!(format, DataFormat::Csv | DataFormat::Tsv | DataFormat::Ssv);
+
+    let (data, paged) = if let Some(page) = args.page.filter(|_| read_page) {
+        let delimiter = separator.chars().next().unwrap_or(',') as u8;
+        let header = !args.no_header || args.skip_header;
+        let mut file = CsvFile::open(input_path, delimiter, header)?;
+        let rows = page_rows(&file, page, args.page_size);
+        let data = RowData {
+            headers: file.headers.take().filter(|_| !args.skip_header),
+            rows: Vec::new(),
+        };
+        file.finish()?;
+        (data, Some(rows))
+    } else {
+        let mut data_parser = match &args.pattern {
+            Some(pattern) => DataParser::Pattern(PatternParser::new(pattern)?),
+            None => create_parser(format, separator, args.no_header, args.skip_header),
+        };
+        (data_parser.parse(input()?)?, None)
+    };
+    if args.strict {
+        check_rectangular(data.headers.as_deref(), &data.rows)?;
+    }
+
+    let mut headers = relabel(&args, data.headers)?;
+    let mut rows = data.rows;
+    if args.pick_interactive {
+        let picked = pick_columns(headers.as_deref(), &rows)?;
+        headers = headers.map(|headers| keep_columns(&headers, &picked));
+        for row in &mut rows {
+            *row = keep_columns(row, &picked);
+        }
+    }
+    let headers = headers.as_deref();
+    if let Some(headers) = headers {
         builder = builder.header(headers.iter().map(String::as_str).collect::<Vec<_>>());
     }
 
-    for row in &data.rows {
+    for row in &rows {
         builder = builder.row(row.iter().map(String::as_str).collect::<Vec<_>>());
     }
 
-    let output = builder.render();
+    let mut table = builder.build();
+    filter_rows(&mut table, &args, spec.as_ref(), headers)?;
+    if let Some(sort) = &args.sort {
+        let column = resolve_column(&sort.column, headers)?;
+        match (sort.numeric, sort.descending) {
+            (false, false) => table.sort(column),
+            (false, true) => table.sort_desc(column),
+            (true, false) => table.sort_num(column),
+            (true, true) => table.sort_num_desc(column),
+        }
+    }
+    if let Some(page) = args.page {
+        let rows = match paged {
+            Some(rows) => rows,
+            None => page_rows(&table, page, args.page_size),
+        };
+        table = table.filtered(|_| false);
+        for row in rows {
+            table.add_row(row);
+        }
+    }
+    style_columns(&mut table, &args, headers)?;
+    if args.interactive {
+        return interactive::run(table);
+    }
 
-    if let Some(output_path) = args.output {
-        fs::write(output_path, &output)?;
-    } else {
-        print!("{output}");
+    write_output(table, args.to, args.output.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+    use std::fs;
+    use std::num::NonZeroUsize;
+    use std::path::PathBuf;
+
+    use crabular::{Row, RowProvider};
+
+    use super::{
+        CSV_INDEX_STRIDE, CsvFile, DataFormat, LogfmtParser, MAX_REPORTED_ROWS, PatternParser,
+        Records, check_records, check_rectangular, page_rows, parse_logfmt_line,
+    };
+
+    /// Writes `content` to a file in the temporary directory that is unique to `name`.
+    fn temp_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("crabular-cli-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
     }
 
-    Ok(())
+    fn cells(row: &Row) -> Vec<&str> {
+        row.cells().iter().map(crabular::Cell::content).collect()
+    }
+
+    #[test]
+    fn csv_file_reads_rows_in_any_order() {
+        let rows = 3 * CSV_INDEX_STRIDE + 7;
+        let mut content = String::from("id,note\n");
+        for index in 0..rows {
+            writeln!(content, "{index},\"line {index}\nwith, comma\"").unwrap();
+        }
+        let path = temp_file("any-order", &content);
+        let file = CsvFile::open(&path, b',', true).unwrap();
+
+        assert_eq!(
+            file.headers.as_deref(),
+            Some(&["id".into(), "note".into()][..])
+        );
+        for index in [
+            2 * CSV_INDEX_STRIDE + 5,
+            3,
+            rows - 1,
+            0,
+            1,
+            CSV_INDEX_STRIDE,
+        ] {
+            let expected = format!("line {index}\nwith, comma");
+            assert_eq!(
+                cells(&file.get(index)),
+                [index.to_string().as_str(), &expected]
+            );
+        }
+        assert_eq!(file.len(), rows);
+        assert_eq!(file.index.borrow().len(), 4);
+        file.finish().unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn csv_file_pages() {
+        let path = temp_file("pages", "a;1\nb;2\nc;3\nd;4\ne;5\n");
+        let file = CsvFile::open(&path, b';', false).unwrap();
+        let size = NonZeroUsize::new(2).unwrap();
+        let page = |n| {
+            page_rows(&file, NonZeroUsize::new(n).unwrap(), size)
+                .iter()
+                .map(|row| row.cells()[0].content().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(file.headers.is_none());
+        assert_eq!(page(3), ["e"]);
+        assert_eq!(page(1), ["a", "b"]);
+        assert_eq!(page(2), ["c", "d"]);
+        assert!(page(4).is_empty());
+        assert_eq!(file.len(), 5);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn sniff() {
+        let cases = [
+            ("\u{feff}[{\"a\": 1}]", DataFormat::Json),
+            ("\u{feff}a\tb\n1\t2\n", DataFormat::Tsv),
+            ("\u{feff}{\"a\": 1}\n", DataFormat::Jsonl),
+            ("  \n[1, 2]", DataFormat::Json),
+            ("{\n  \"a\": 1,\n  \"b\": [1, 2]\n}\n", DataFormat::Json),
+            ("{\"a\": 1}\n{\"a\": 2}\n", DataFormat::Jsonl),
+            ("{\"a\": 1}", DataFormat::Jsonl),
+            ("name\tage\nKata\t30\n", DataFormat::Tsv),
+            ("name,age\nKata,30\n", DataFormat::Csv),
+            ("name,\"a\tb\"\nKata,30\n", DataFormat::Csv),
+            ("\"a,b\"\tc\n", DataFormat::Tsv),
+            ("", DataFormat::Csv),
+        ];
+        for (head, expected) in cases {
+            assert_eq!(DataFormat::sniff(head.as_bytes()), expected, "{head:?}");
+        }
+    }
+
+    #[test]
+    fn logfmt_line() {
+        let cases: [(&str, &[(&str, &str)]); 9] = [
+            (
+                "level=info port=8080",
+                &[("level", "info"), ("port", "8080")],
+            ),
+            (
+                r#"msg="user logged in" id=7"#,
+                &[("msg", "user logged in"), ("id", "7")],
+            ),
+            (
+                r#"msg="say \"hi\"\nbye\tnow\\""#,
+                &[("msg", "say \"hi\"\nbye\tnow\\")],
+            ),
+            (
+                r#"a=1 msg="never closed"#,
+                &[("a", "1"), ("msg", "never closed")],
+            ),
+            (r#"msg="ends in \"#, &[("msg", "ends in ")]),
+            (
+                "cached level=warn",
+                &[("cached", "true"), ("level", "warn")],
+            ),
+            ("a= b=2", &[("a", ""), ("b", "2")]),
+            ("=x level=info", &[("level", "info")]),
+            ("  \t ", &[]),
+        ];
+        for (line, expected) in cases {
+            let expected: Vec<(String, String)> = expected
+                .iter()
+                .map(|&(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            assert_eq!(parse_logfmt_line(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn logfmt_keys_from_later_lines() {
+        let input = "level=info\n\nlevel=warn msg=slow\nmsg=done user=kata\n";
+        let data = LogfmtParser::parse(Box::new(input.as_bytes())).unwrap();
+        assert_eq!(
+            data.headers.unwrap(),
+            ["level", "msg", "user"].map(String::from)
+        );
+        assert_eq!(
+            data.rows,
+            [
+                ["info", "", ""].map(String::from),
+                ["warn", "slow", ""].map(String::from),
+                ["", "done", "kata"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_skips_lines_that_do_not_match() {
+        let parser =
+            PatternParser::new(r"^(?P<level>\w+): (?P<msg>.*?)(?: \[(?P<code>\d+)\])?$").unwrap();
+        let input = "INFO: started\n--- noise ---\nERROR: failed [42]\n";
+        let data = parser.parse(Box::new(input.as_bytes())).unwrap();
+        assert_eq!(
+            data.headers.unwrap(),
+            ["level", "msg", "code"].map(String::from)
+        );
+        assert_eq!(
+            data.rows,
+            [
+                ["INFO", "started", ""].map(String::from),
+                ["ERROR", "failed", "42"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_needs_named_groups() {
+        for pattern in [r"(\w+) (\d+)", "(unclosed"] {
+            let error = PatternParser::new(pattern).err().unwrap();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput, "{pattern}");
+        }
+        let error = PatternParser::new(r"(\w+) (\d+)").err().unwrap();
+        assert!(error.to_string().contains("no named capture groups"));
+    }
+
+    /// Rows of the given field counts, each field holding its row's number.
+    fn rows(lens: &[usize]) -> Vec<Vec<String>> {
+        lens.iter()
+            .enumerate()
+            .map(|(index, &len)| vec![index.to_string(); len])
+            .collect()
+    }
+
+    #[test]
+    fn strict_reports_ragged_rows() {
+        let headers = ["a", "b"].map(String::from);
+        assert!(check_rectangular(Some(&headers), &rows(&[2, 2])).is_ok());
+        assert!(check_rectangular(None, &[]).is_ok());
+
+        let error = check_rectangular(Some(&headers), &rows(&[2, 1, 2, 3])).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "row 2 has 1 field, expected 2\nrow 4 has 3 fields, expected 2"
+        );
+    }
+
+    #[test]
+    fn strict_without_header_expects_the_first_row_width() {
+        assert!(check_rectangular(None, &rows(&[3, 3])).is_ok());
+        let error = check_rectangular(None, &rows(&[3, 3, 0])).unwrap_err();
+        assert_eq!(error.to_string(), "row 3 has 0 fields, expected 3");
+    }
+
+    #[test]
+    fn strict_counts_rows_past_the_report_limit() {
+        let mut lens = vec![1; MAX_REPORTED_ROWS + 4];
+        lens.insert(0, 2);
+        let message = check_rectangular(None, &rows(&lens))
+            .unwrap_err()
+            .to_string();
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines.len(), MAX_REPORTED_ROWS + 1);
+        assert_eq!(lines[0], "row 2 has 1 field, expected 2");
+        assert_eq!(
+            lines[MAX_REPORTED_ROWS - 1],
+            format!("row {} has 1 field, expected 2", MAX_REPORTED_ROWS + 1)
+        );
+        assert_eq!(lines[MAX_REPORTED_ROWS], "and 4 more rows");
+    }
+
+    #[test]
+    fn strict_stream_stops_at_the_first_ragged_record() {
+        let records = |lens: &[usize]| -> Records { Box::new(rows(lens).into_iter().map(Ok)) };
+
+        let checked: Vec<_> = check_records(records(&[2, 2, 3, 1]), Some(2)).collect();
+        assert_eq!(checked.len(), 4);
+        assert!(checked[..2].iter().all(Result::is_ok));
+        assert_eq!(
+            checked[2].as_ref().unwrap_err().to_string(),
+            "row 3 has 3 fields, expected 2"
+        );
+
+        // Without a header the first record sets the width
+        let checked: Vec<_> = check_records(records(&[1, 1, 2]), None).collect();
+        assert!(checked[..2].iter().all(Result::is_ok));
+        assert_eq!(
+            checked[2].as_ref().unwrap_err().to_string(),
+            "row 3 has 2 fields, expected 1"
+        );
+    }
 }
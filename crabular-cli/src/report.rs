@@ -0,0 +1,106 @@
+//! `crabular report SPEC`: reads the inputs a report spec names, joins them, and
+//! renders the result through the spec's table settings, so a report can be rerun
+//! from one file.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crabular::{ColumnRef, JoinKind, Table, TableSpec, TableStyle};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{DataFormat, OutputFormat, create_parser, load_settings, write_output};
+
+/// The keys of a report spec that say where the rows come from; every other key is
+/// part of the [`TableSpec`].
+const SOURCE_KEYS: [&str; 2] = ["input", "joins"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Sources {
+    /// The first table, which the joins extend.
+    input: PathBuf,
+    #[serde(default)]
+    joins: Vec<JoinSpec>,
+}
+
+/// Joins another input on equal keys, see [`Table::join`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JoinSpec {
+    input: PathBuf,
+    /// Key column of the rows joined so far
+    left: ColumnRef,
+    /// Key column of `input`
+    right: ColumnRef,
+    #[serde(default)]
+    kind: JoinKind,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Renders the report described by the spec at `path`. Input paths are relative to
+/// the spec's directory.
+pub fn run(path: &Path, to: OutputFormat, output: Option<&Path>) -> io::Result<()> {
+    let Value::Object(mut document) = load_settings(path)? else {
+        return Err(invalid(
+            "Report spec must be a table of settings".to_string(),
+        ));
+    };
+    let sources = SOURCE_KEYS
+        .iter()
+        .filter_map(|key| document.remove_entry(*key))
+        .collect();
+    let sources: Sources =
+        serde_json::from_value(Value::Object(sources)).map_err(|e| invalid(e.to_string()))?;
+    let spec: TableSpec =
+        serde_json::from_value(Value::Object(document)).map_err(|e| invalid(e.to_string()))?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut table = read_table(&dir.join(&sources.input))?;
+    for join in &sources.joins {
+        let other = read_table(&dir.join(&join.input))?;
+        let left = column_index(&table, &join.left)?;
+        let right = column_index(&other, &join.right)?;
+        table = table.join(&other, left, right, join.kind);
+    }
+    table
+        .apply_spec(&spec)
+        .map_err(|e| invalid(e.to_string()))?;
+    write_output(table, to, output)
+}
+
+/// Reads a file in the format its extension names, with its first row as headers.
+fn read_table(path: &Path) -> io::Result<Table> {
+    let with_path = |e: io::Error| io::Error::new(e.kind(), format!("{}: {e}", path.display()));
+    let format = DataFormat::from_path(path);
+    let mut parser = create_parser(format, format.default_separator().to_string(), false, false);
+    let data = parser
+        .parse(Box::new(fs::File::open(path).map_err(with_path)?))
+        .map_err(with_path)?;
+
+    let mut table = Table::new();
+    table.set_style(TableStyle::Modern);
+    if let Some(headers) = data.headers {
+        table.set_headers(headers);
+    }
+    for row in data.rows {
+        table.add_row(row);
+    }
+    Ok(table)
+}
+
+fn column_index(table: &Table, column: &ColumnRef) -> io::Result<usize> {
+    match column {
+        ColumnRef::Index(index) => Ok(*index),
+        ColumnRef::Name(name) => table.column_index(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown column `{name}`"),
+            )
+        }),
+    }
+}
@@ -3,21 +3,33 @@
 //! This crate provides JavaScript bindings for the crabular ASCII table library,
 //! enabling browser and Node.js usage.
 
-use core::cell::RefCell;
-use crabular::{Alignment, Padding, Table, TableBuilder, TableStyle, VerticalAlignment};
-use js_sys::Array;
+use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::str::FromStr;
+use crabular::{
+    Alignment, Color, ColorMode, ColumnRef, ColumnSpec, HeaderStyle, Padding, RenderOptions, Row,
+    Table, TableBuilder, TableSpec, TableStyle, VerticalAlignment, WidthConstraint,
+};
+use js_sys::{Array, Function, JSON, Object, Reflect};
 use wasm_bindgen::prelude::*;
 
 /// WASM-friendly table builder for JavaScript
 #[wasm_bindgen]
 pub struct JsTable {
     builder: RefCell<TableBuilder>,
+    /// Throw on unknown style and alignment names instead of using the default
+    strict: Cell<bool>,
+    /// Cells expected per row: the header's count, or the first row's without one
+    columns: Cell<Option<usize>>,
+    rows: Cell<usize>,
+    warnings: RefCell<Vec<String>>,
 }
 
 /// A built table that can be manipulated
 #[wasm_bindgen]
 pub struct JsTableObject {
     table: RefCell<Table>,
+    warnings: RefCell<Vec<String>>,
 }
 
 impl Default for JsTable {
@@ -34,22 +46,43 @@ impl JsTable {
     pub fn new() -> Self {
         Self {
             builder: RefCell::new(TableBuilder::new()),
+            strict: Cell::new(false),
+            columns: Cell::new(None),
+            rows: Cell::new(0),
+            warnings: RefCell::new(Vec::new()),
         }
     }
 
+    /// Throw on unknown style and alignment names instead of falling back to the default
+    #[wasm_bindgen]
+    pub fn strict(&self, enabled: bool) {
+        self.strict.set(enabled);
+    }
+
+    /// Problems found in the data so far, such as rows with too few or too many cells
+    #[wasm_bindgen(getter)]
+    pub fn warnings(&self) -> Array {
+        self.warnings.borrow().iter().map(JsValue::from).collect()
+    }
+
     /// Set the table style
+    ///
+    /// # Errors
+    /// Throws on an unknown style in strict mode
     #[wasm_bindgen(js_name = style)]
-    pub fn set_style(&self, style: &str) {
-        let table_style = parse_style(style);
+    pub fn set_style(&self, style: &str) -> Result<(), JsError> {
+        let table_style = self.parse("style", style, parse_style)?;
         let builder = self.builder.take();
         let new_builder = builder.style(table_style);
         self.builder.replace(new_builder);
+        Ok(())
     }
 
     /// Add a header row
     #[wasm_bindgen(js_name = header)]
     pub fn set_header(&self, headers: &Array) {
         let headers_vec = array_to_vec(headers);
+        self.columns.set(Some(headers_vec.len()));
         let builder = self.builder.take();
         let new_builder = builder.header(headers_vec);
         self.builder.replace(new_builder);
@@ -59,6 +92,7 @@ impl JsTable {
     #[wasm_bindgen(js_name = row)]
     pub fn add_row(&self, row: &Array) {
         let row_vec = array_to_vec(row);
+        self.check_row(row_vec.len());
         let builder = self.builder.take();
         let new_builder = builder.row(row_vec);
         self.builder.replace(new_builder);
@@ -71,6 +105,7 @@ impl JsTable {
         for row in rows.iter() {
             if let Ok(arr) = row.dyn_into::<Array>() {
                 let row_vec = array_to_vec(&arr);
+                self.check_row(row_vec.len());
                 builder = builder.row(row_vec);
             }
         }
@@ -78,21 +113,29 @@ impl JsTable {
     }
 
     /// Set alignment for a specific column
+    ///
+    /// # Errors
+    /// Throws on an unknown alignment in strict mode
     #[wasm_bindgen(js_name = align)]
-    pub fn set_align(&self, column: usize, alignment: &str) {
-        let align = parse_alignment(alignment);
+    pub fn set_align(&self, column: usize, alignment: &str) -> Result<(), JsError> {
+        let align = self.parse("alignment", alignment, parse_alignment)?;
         let builder = self.builder.take();
         let new_builder = builder.align(column, align);
         self.builder.replace(new_builder);
+        Ok(())
     }
 
     /// Set vertical alignment for all cells
+    ///
+    /// # Errors
+    /// Throws on an unknown alignment in strict mode
     #[wasm_bindgen(js_name = valign)]
-    pub fn set_valign(&self, alignment: &str) {
-        let valign = parse_vertical_alignment(alignment);
+    pub fn set_valign(&self, alignment: &str) -> Result<(), JsError> {
+        let valign = self.parse("vertical alignment", alignment, parse_vertical_alignment)?;
         let builder = self.builder.take();
         let new_builder = builder.valign(valign);
         self.builder.replace(new_builder);
+        Ok(())
     }
 
     /// Set cell padding
@@ -111,12 +154,105 @@ impl JsTable {
         self.builder.replace(new_builder);
     }
 
+    /// Style the header row; `options` may set `bold` and a `color` name or `#rrggbb`
+    ///
+    /// # Errors
+    /// Throws on an unknown color in strict mode
+    #[wasm_bindgen(js_name = headerStyle)]
+    pub fn set_header_style(&self, options: &JsValue) -> Result<(), JsError> {
+        let mut style = HeaderStyle::new();
+        if option(options, "bold").as_bool() == Some(true) {
+            style = style.bold();
+        }
+        if let Some(name) = option(options, "color").as_string() {
+            match name.parse::<Color>() {
+                Ok(color) => style = style.color(color),
+                Err(()) if self.strict.get() => {
+                    return Err(JsError::new(&format!("unknown color `{name}`")));
+                }
+                Err(()) => {}
+            }
+        }
+        let builder = self.builder.take();
+        let new_builder = builder.header_style(style);
+        self.builder.replace(new_builder);
+        Ok(())
+    }
+
+    /// Display a column through a named formatter, such as `bytes` or `badge`
+    #[wasm_bindgen(js_name = formatColumn)]
+    pub fn format_column(&self, column: usize, spec: &str) {
+        let builder = self.builder.take();
+        let new_builder = builder.format_column(column, spec);
+        self.builder.replace(new_builder);
+    }
+
+    /// How colors are written: `ansi` for terminals such as xterm.js, `html` for
+    /// `<span>` tags to show in a `<pre>` element, or `none`
+    ///
+    /// # Errors
+    /// Throws on an unknown mode in strict mode
+    #[wasm_bindgen(js_name = colorMode)]
+    pub fn set_color_mode(&self, mode: &str) -> Result<(), JsError> {
+        let mode = self.parse("color mode", mode, parse_color_mode)?;
+        let builder = self.builder.take();
+        let new_builder = builder.render_options(RenderOptions::new().color_mode(mode));
+        self.builder.replace(new_builder);
+        Ok(())
+    }
+
+    /// Fix a column to exactly `width` characters
+    #[wasm_bindgen(js_name = fixedWidth)]
+    pub fn fixed_width(&self, column: usize, width: usize) {
+        self.constrain(column, WidthConstraint::Fixed(width));
+    }
+
+    /// Make a column at least `width` characters wide
+    #[wasm_bindgen(js_name = minWidth)]
+    pub fn min_width(&self, column: usize, width: usize) {
+        self.constrain(column, WidthConstraint::Min(width));
+    }
+
+    /// Limit a column to `width` characters, truncating longer content
+    #[wasm_bindgen(js_name = maxWidth)]
+    pub fn max_width(&self, column: usize, width: usize) {
+        self.constrain(column, WidthConstraint::Max(width));
+    }
+
+    /// Wrap a column's content onto lines of at most `width` characters
+    #[wasm_bindgen]
+    pub fn wrap(&self, column: usize, width: usize) {
+        self.constrain(column, WidthConstraint::Wrap(width));
+    }
+
+    /// Give a column `percent` of the table width
+    #[wasm_bindgen]
+    pub fn proportional(&self, column: usize, percent: u8) {
+        self.constrain(column, WidthConstraint::Proportional(percent));
+    }
+
+    /// Give a column a `weight` share of the width left over by the other columns
+    #[wasm_bindgen]
+    pub fn weight(&self, column: usize, weight: u32) {
+        self.constrain(column, WidthConstraint::Weight(weight));
+    }
+
+    /// Cut cell content longer than `limit` characters in rows added afterwards
+    #[wasm_bindgen]
+    pub fn truncate(&self, limit: usize) {
+        let builder = self.builder.take();
+        let new_builder = builder.truncate(limit);
+        self.builder.replace(new_builder);
+    }
+
     /// Render the table to a string
     #[wasm_bindgen]
     pub fn render(&self) -> String {
         let builder = self.builder.take();
         let result = builder.render();
         self.builder.replace(TableBuilder::new());
+        self.columns.set(None);
+        self.rows.set(0);
         result
     }
 
@@ -124,8 +260,11 @@ impl JsTable {
     #[wasm_bindgen(js_name = build)]
     pub fn build_table(&self) -> JsTableObject {
         let builder = self.builder.take();
+        self.columns.set(None);
+        self.rows.set(0);
         JsTableObject {
             table: RefCell::new(builder.build()),
+            warnings: RefCell::new(self.warnings.take()),
         }
     }
 }
@@ -150,11 +289,31 @@ impl JsTableObject {
         self.table.borrow().cols()
     }
 
+    /// Problems found in the data, such as rows with too few or too many cells
+    #[wasm_bindgen(getter)]
+    pub fn warnings(&self) -> Array {
+        self.warnings.borrow().iter().map(JsValue::from).collect()
+    }
+
     /// Add a row to the table
     #[wasm_bindgen(js_name = addRow)]
     pub fn add_row(&self, row: &Array) {
         let row_vec = array_to_vec(row);
-        self.table.borrow_mut().add_row(row_vec);
+        let mut table = self.table.borrow_mut();
+        let expected = table
+            .headers()
+            .or_else(|| table.rows().first())
+            .map(Row::len);
+        if let Some(warning) = row_length_warning(table.len(), row_vec.len(), expected) {
+            self.warnings.borrow_mut().push(warning);
+        }
+        table.add_row(row_vec);
+    }
+
+    /// Right-align numeric columns and center boolean ones
+    #[wasm_bindgen(js_name = autoAlign)]
+    pub fn auto_align(&self) {
+        self.table.borrow_mut().auto_align();
     }
 
     /// Sort by a column (ascending)
@@ -181,6 +340,88 @@ impl JsTableObject {
         self.table.borrow_mut().sort_num_desc(column);
     }
 
+    /// How colors are written: `ansi`, `html` or `none`
+    ///
+    /// # Errors
+    /// Throws on an unknown mode
+    #[wasm_bindgen(js_name = colorMode)]
+    pub fn set_color_mode(&self, mode: &str) -> Result<(), JsError> {
+        let mode = mode
+            .parse::<ColorMode>()
+            .map_err(|()| JsError::new(&format!("unknown color mode `{mode}`")))?;
+        let mut table = self.table.borrow_mut();
+        let options = table.render_options().clone().color_mode(mode);
+        table.set_render_options(options);
+        Ok(())
+    }
+
+    /// Sort by the column with this header; `options` may set `numeric` and `desc`
+    ///
+    /// # Errors
+    /// Throws if no header has this name
+    #[wasm_bindgen(js_name = sortByName)]
+    pub fn sort_by_name(&self, name: &str, options: &JsValue) -> Result<(), JsError> {
+        let column = self.column_named(name)?;
+        let flag = |key: &str| option(options, key).as_bool().unwrap_or(false);
+        let mut table = self.table.borrow_mut();
+        match (flag("numeric"), flag("desc")) {
+            (false, false) => table.sort(column),
+            (false, true) => table.sort_desc(column),
+            (true, false) => table.sort_num(column),
+            (true, true) => table.sort_num_desc(column),
+        }
+        Ok(())
+    }
+
+    /// Set width constraints for several columns at once, keyed by column index or
+    /// header name: `{0: {fixed: 10}, name: {wrap: 30}}`. A constraint may also be
+    /// written as a string such as `"max:30"`, `"auto"` or `"25%"`
+    ///
+    /// # Errors
+    /// Throws on an unknown column or constraint; the table is then left unchanged
+    #[wasm_bindgen(js_name = setConstraints)]
+    pub fn set_constraints(&self, constraints: &Object) -> Result<(), JsError> {
+        let mut spec = TableSpec::default();
+        for entry in Object::entries(constraints).iter() {
+            let entry: Array = entry.unchecked_into();
+            let key = entry.get(0).as_string().unwrap_or_default();
+            let column = match key.parse() {
+                Ok(index) => ColumnRef::Index(index),
+                Err(_) => ColumnRef::Name(key),
+            };
+            spec.columns.push(ColumnSpec {
+                width: Some(parse_constraint(&entry.get(1))?),
+                ..ColumnSpec::new(column)
+            });
+        }
+        self.table
+            .borrow_mut()
+            .apply_spec(&spec)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    /// Filter rows by exact match in the column with this header
+    ///
+    /// # Errors
+    /// Throws if no header has this name
+    #[wasm_bindgen(js_name = filterEqByName)]
+    pub fn filter_eq_by_name(&self, name: &str, value: &str) -> Result<(), JsError> {
+        let column = self.column_named(name)?;
+        self.table.borrow_mut().filter_eq(column, value);
+        Ok(())
+    }
+
+    /// Filter rows where the column with this header contains substring
+    ///
+    /// # Errors
+    /// Throws if no header has this name
+    #[wasm_bindgen(js_name = filterHasByName)]
+    pub fn filter_has_by_name(&self, name: &str, substring: &str) -> Result<(), JsError> {
+        let column = self.column_named(name)?;
+        self.table.borrow_mut().filter_has(column, substring);
+        Ok(())
+    }
+
     /// Filter rows by exact column match
     #[wasm_bindgen(js_name = filterEq)]
     pub fn filter_eq(&self, column: usize, value: &str) {
@@ -193,6 +434,136 @@ impl JsTableObject {
         self.table.borrow_mut().filter_has(column, substring);
     }
 
+    /// Sort by a column with a JavaScript comparator, called with two cell values and
+    /// returning a negative number, zero or a positive number like `Array.sort`'s
+    ///
+    /// # Errors
+    /// Rethrows the first exception thrown by `compare`; rows may then be partly sorted
+    #[wasm_bindgen(js_name = sortWith)]
+    pub fn sort_with(&self, column: usize, compare: &Function) -> Result<(), JsValue> {
+        let mut error = None;
+        self.table.borrow_mut().sort_by(|a, b| {
+            if error.is_some() {
+                return Ordering::Equal;
+            }
+            let a = JsValue::from(row_fields(a).get(column).copied().unwrap_or_default());
+            let b = JsValue::from(row_fields(b).get(column).copied().unwrap_or_default());
+            match compare.call2(&JsValue::NULL, &a, &b) {
+                Ok(result) => ordering(result.as_f64()),
+                Err(thrown) => {
+                    error = Some(thrown);
+                    Ordering::Equal
+                }
+            }
+        });
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Keep rows for which a JavaScript predicate, called with the row as an array of
+    /// strings and its index, returns a truthy value
+    ///
+    /// # Errors
+    /// Rethrows the first exception thrown by `predicate`; the remaining rows are kept
+    #[wasm_bindgen(js_name = filterWith)]
+    pub fn filter_with(&self, predicate: &Function) -> Result<(), JsValue> {
+        let mut error = None;
+        let mut index = 0;
+        self.table.borrow_mut().filter(|row| {
+            let row_index = JsValue::from(index);
+            index += 1;
+            if error.is_some() {
+                return true;
+            }
+            let fields: Array = row_fields(row).into_iter().map(JsValue::from).collect();
+            match predicate.call2(&JsValue::NULL, &fields, &row_index) {
+                Ok(keep) => keep.is_truthy(),
+                Err(thrown) => {
+                    error = Some(thrown);
+                    true
+                }
+            }
+        });
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Rank rows against an fzf-style query; returns `[rowIndex, score]` pairs, best first
+    #[wasm_bindgen(js_name = searchFuzzy)]
+    pub fn search_fuzzy(&self, query: &str) -> Array {
+        self.table
+            .borrow()
+            .search_fuzzy(query)
+            .into_iter()
+            .map(|(index, score)| Array::of2(&JsValue::from(index), &JsValue::from(score)))
+            .collect()
+    }
+
+    /// Keep rows matching an fzf-style query, best match first
+    #[wasm_bindgen(js_name = filterFuzzy)]
+    pub fn filter_fuzzy(&self, query: &str) {
+        self.table.borrow_mut().filter_fuzzy(query);
+    }
+
+    /// Rows in their current order as arrays of strings, without the header
+    #[wasm_bindgen(js_name = toArray)]
+    pub fn to_array(&self) -> Array {
+        let table = self.table.borrow();
+        table
+            .rows()
+            .iter()
+            .map(|row| {
+                row_fields(row)
+                    .into_iter()
+                    .map(JsValue::from)
+                    .collect::<Array>()
+            })
+            .collect()
+    }
+
+    /// Rows in their current order as objects keyed by header, or by column index
+    /// for columns without one
+    #[wasm_bindgen(js_name = toObjects)]
+    pub fn to_objects(&self) -> Array {
+        let table = self.table.borrow();
+        let headers = table.headers().map(row_fields).unwrap_or_default();
+        let keys: Vec<JsValue> = (0..table.cols())
+            .map(|col| {
+                headers
+                    .get(col)
+                    .map_or_else(|| JsValue::from(col.to_string()), |key| JsValue::from(*key))
+            })
+            .collect();
+        table
+            .rows()
+            .iter()
+            .map(|row| {
+                let object = Object::new();
+                for (key, value) in keys.iter().zip(row_fields(row)) {
+                    // Setting a property on a fresh plain object cannot fail
+                    let _ = Reflect::set(&object, key, &JsValue::from(value));
+                }
+                object
+            })
+            .collect()
+    }
+
+    /// Number of pages of `pageSize` rows; 0 for a table without rows
+    #[wasm_bindgen(js_name = pageCount)]
+    pub fn page_count(&self, page_size: usize) -> usize {
+        self.table.borrow().paginate(page_size).page_count()
+    }
+
+    /// Render page `page` (1-based) of `pageSize` rows, with the headers repeated and
+    /// column widths measured over the whole table; `undefined` past the last page
+    #[wasm_bindgen(js_name = renderPage)]
+    pub fn render_page(&self, page: usize, page_size: usize) -> Option<String> {
+        let index = page.checked_sub(1)?;
+        self.table
+            .borrow()
+            .paginate(page_size)
+            .nth(index)
+            .map(|page| page.render())
+    }
+
     /// Render the table to a string
     #[wasm_bindgen]
     pub fn render(&self) -> String {
@@ -204,6 +575,59 @@ impl JsTableObject {
     pub fn to_string_js(&self) -> String {
         self.table.borrow().render()
     }
+
+    /// Render to an HTML `<table>` with escaped text, for inserting into the DOM
+    #[wasm_bindgen(js_name = renderHtml)]
+    pub fn render_html(&self) -> String {
+        self.table.borrow().to_html()
+    }
+}
+
+impl JsTable {
+    /// Parses a style or alignment name with `lenient`, which falls back to a default,
+    /// or strictly in strict mode.
+    fn parse<T: FromStr>(
+        &self,
+        kind: &str,
+        value: &str,
+        lenient: fn(&str) -> T,
+    ) -> Result<T, JsError> {
+        if self.strict.get() {
+            value
+                .parse()
+                .map_err(|_| JsError::new(&format!("unknown {kind} `{value}`")))
+        } else {
+            Ok(lenient(value))
+        }
+    }
+
+    /// Records a warning if a new row's cell count differs from the expected one.
+    fn check_row(&self, len: usize) {
+        let index = self.rows.get();
+        self.rows.set(index + 1);
+        let expected = self.columns.get();
+        if expected.is_none() {
+            self.columns.set(Some(len));
+        }
+        if let Some(warning) = row_length_warning(index, len, expected) {
+            self.warnings.borrow_mut().push(warning);
+        }
+    }
+
+    fn constrain(&self, column: usize, constraint: WidthConstraint) {
+        let builder = self.builder.take();
+        let new_builder = builder.constrain(column, constraint);
+        self.builder.replace(new_builder);
+    }
+}
+
+impl JsTableObject {
+    fn column_named(&self, name: &str) -> Result<usize, JsError> {
+        self.table
+            .borrow()
+            .column_index(name)
+            .ok_or_else(|| JsError::new(&format!("unknown column `{name}`")))
+    }
 }
 
 /// Convenience function to create and render a table in one call
@@ -232,6 +656,42 @@ pub fn create_table(data: &Array, style: Option<String>) -> String {
     builder.render()
 }
 
+/// Create and render a table from an array of objects. Headers are the keys of the
+/// first object; missing values and `null` become empty cells, and nested values are
+/// shown as JSON.
+#[wasm_bindgen(js_name = createTableFromObjects)]
+#[allow(clippy::needless_pass_by_value)]
+pub fn create_table_from_objects(data: &Array, style: Option<String>) -> String {
+    let table_style = style.as_deref().map_or(TableStyle::Classic, parse_style);
+
+    let mut builder = TableBuilder::new().style(table_style);
+
+    let objects: Vec<Object> = data
+        .iter()
+        .filter_map(|value| value.dyn_into::<Object>().ok())
+        .collect();
+    let Some(first) = objects.first() else {
+        return builder.render();
+    };
+    let keys: Vec<JsValue> = Object::keys(first).iter().collect();
+    let header: Vec<String> = keys.iter().filter_map(JsValue::as_string).collect();
+    builder = builder.header(header);
+
+    for object in &objects {
+        let row: Vec<String> = keys
+            .iter()
+            .map(|key| {
+                Reflect::get(object, key)
+                    .map(|value| cell_text(&value))
+                    .unwrap_or_default()
+            })
+            .collect();
+        builder = builder.row(row);
+    }
+
+    builder.render()
+}
+
 /// Render a simple table from rows
 #[wasm_bindgen(js_name = renderRows)]
 #[allow(clippy::needless_pass_by_value)]
@@ -250,6 +710,25 @@ pub fn render_rows(rows: &Array, style: Option<String>) -> String {
     builder.render()
 }
 
+/// Describes a row whose cell count differs from the expected one; `index` is 0-based.
+fn row_length_warning(index: usize, len: usize, expected: Option<usize>) -> Option<String> {
+    let expected = expected.filter(|&expected| expected != len)?;
+    Some(format!("row {index} has {len} cells, expected {expected}"))
+}
+
+/// Reads `key` from an options object; `undefined` if it or the object is missing.
+fn option(options: &JsValue, key: &str) -> JsValue {
+    if options.is_object() {
+        Reflect::get(options, &JsValue::from(key)).unwrap_or(JsValue::UNDEFINED)
+    } else {
+        JsValue::UNDEFINED
+    }
+}
+
+fn parse_color_mode(mode: &str) -> ColorMode {
+    mode.parse().unwrap_or_default()
+}
+
 fn parse_style(style: &str) -> TableStyle {
     style.parse().unwrap_or(TableStyle::Classic)
 }
@@ -262,23 +741,88 @@ fn parse_vertical_alignment(align: &str) -> VerticalAlignment {
     align.parse().unwrap_or(VerticalAlignment::Top)
 }
 
-fn array_to_vec(arr: &Array) -> Vec<&str> {
-    arr.iter()
-        .filter_map(|val| val.as_string())
-        .map(|s| {
-            // Leak the string to get a &'static str
-            // This is safe for wasm as the strings are short-lived during conversion
-            Box::leak(s.into_boxed_str()) as &str
-        })
-        .collect()
+/// Returns the cell text for a JavaScript value: strings as they are, `null` and
+/// `undefined` as empty, and anything else as JSON.
+fn cell_text(value: &JsValue) -> String {
+    if let Some(text) = value.as_string() {
+        return text;
+    }
+    if value.is_null() || value.is_undefined() {
+        return String::new();
+    }
+    JSON::stringify(value)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_default()
+}
+
+/// Returns one field per column of `row`, with an empty field for each column a
+/// spanning cell covers beyond its first.
+fn row_fields(row: &Row) -> Vec<&str> {
+    let mut fields = Vec::with_capacity(row.len());
+    for cell in row.cells() {
+        fields.push(cell.content());
+        fields.extend(core::iter::repeat_n("", cell.span().saturating_sub(1)));
+    }
+    fields
+}
+
+/// Reads a constraint written as a string (`"max:30"`) or as a one-key object
+/// (`{max: 30}`), using the core `WidthConstraint` syntax for both.
+fn parse_constraint(value: &JsValue) -> Result<WidthConstraint, JsError> {
+    let text = if let Some(text) = value.as_string() {
+        text
+    } else {
+        if !value.is_object() {
+            return Err(JsError::new("expected a constraint like {max: 30}"));
+        }
+        let entries = Object::entries(value.unchecked_ref());
+        let entry: Array = entries.get(0).unchecked_into();
+        match (
+            entries.length(),
+            entry.get(0).as_string(),
+            entry.get(1).as_f64(),
+        ) {
+            (1, Some(kind), Some(amount)) => format!("{kind}:{amount}"),
+            _ => return Err(JsError::new("expected a constraint like {max: 30}")),
+        }
+    };
+    text.parse()
+        .map_err(|()| JsError::new(&format!("invalid width constraint `{text}`")))
+}
+
+/// Reads a comparator result the way `Array.sort` does: negative, zero or positive,
+/// with `NaN` and non-numbers treated as equal.
+fn ordering(result: Option<f64>) -> Ordering {
+    result
+        .and_then(|value| value.partial_cmp(&0.0))
+        .unwrap_or(Ordering::Equal)
+}
+
+fn array_to_vec(arr: &Array) -> Vec<String> {
+    arr.iter().filter_map(|val| val.as_string()).collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::ordering;
     use crate::parse_alignment;
+    use crate::parse_color_mode;
     use crate::parse_style;
     use crate::parse_vertical_alignment;
-    use crabular::{Alignment, TableStyle, VerticalAlignment};
+    use crate::row_fields;
+    use crate::row_length_warning;
+    use core::cmp::Ordering;
+    use crabular::{Alignment, Cell, ColorMode, Row, TableStyle, VerticalAlignment};
+
+    #[test]
+    fn test_ordering() {
+        assert_eq!(ordering(Some(-2.5)), Ordering::Less);
+        assert_eq!(ordering(Some(0.0)), Ordering::Equal);
+        assert_eq!(ordering(Some(1.0)), Ordering::Greater);
+        assert_eq!(ordering(Some(f64::NAN)), Ordering::Equal);
+        assert_eq!(ordering(None), Ordering::Equal);
+    }
 
     #[test]
     fn test_parse_style() {
@@ -309,4 +853,31 @@ mod tests {
         );
         assert_eq!(parse_vertical_alignment("unknown"), VerticalAlignment::Top);
     }
+
+    #[test]
+    fn test_parse_color_mode() {
+        assert_eq!(parse_color_mode("html"), ColorMode::Html);
+        assert_eq!(parse_color_mode("none"), ColorMode::None);
+        assert_eq!(parse_color_mode("unknown"), ColorMode::Ansi);
+    }
+
+    #[test]
+    fn test_row_length_warning() {
+        assert_eq!(row_length_warning(0, 3, None), None);
+        assert_eq!(row_length_warning(1, 3, Some(3)), None);
+        assert_eq!(
+            row_length_warning(2, 2, Some(3)).as_deref(),
+            Some("row 2 has 2 cells, expected 3")
+        );
+    }
+
+    #[test]
+    fn test_row_fields() {
+        let mut spanning = Cell::new("wide", Alignment::Left);
+        spanning.set_span(2);
+        let mut row = Row::from(["a"]);
+        row.push(spanning);
+        row.push(Cell::new("b", Alignment::Left));
+        assert_eq!(row_fields(&row), ["a", "wide", "", "b"]);
+    }
 }
@@ -0,0 +1,204 @@
+//! Renders every table style with a set of scenes into the golden files in
+//! `examples/gallery`, which double as a visual reference.
+//!
+//! ```text
+//! cargo run --example gallery            # rewrite the golden files
+//! cargo run --example gallery -- --check # fail if a render differs from its file
+//! ```
+//!
+//! `cargo test` runs the check too, so any change to borders, junctions or padding
+//! shows up as a diff of these files.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crabular::{Aggregate, Alignment, Cell, Row, Table, TableBuilder, TableStyle, WidthConstraint};
+
+const STYLES: [(&str, TableStyle); 9] = [
+    ("classic", TableStyle::Classic),
+    ("modern", TableStyle::Modern),
+    ("minimal", TableStyle::Minimal),
+    ("compact", TableStyle::Compact),
+    ("markdown", TableStyle::Markdown),
+    ("rest", TableStyle::Rest),
+    ("asciidoc", TableStyle::AsciiDoc),
+    ("org", TableStyle::Org),
+    ("plain", TableStyle::Plain),
+];
+
+/// Builds a scene's table in the given style.
+type Scene = fn(TableStyle) -> Table;
+
+/// Scenes rendered in every style, each exercising different borders and constraints.
+const SCENES: [(&str, Scene); 7] = [
+    ("basic", basic),
+    ("colspan", colspan),
+    ("wrap", wrap),
+    ("fixed-max", fixed_max),
+    ("weight", weight),
+    ("column-groups", column_groups),
+    ("totals", totals),
+];
+
+fn basic(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["Name", "Age", "City"])
+        .align(1, Alignment::Right)
+        .rows([
+            ["Kelana", "30", "Berlin"],
+            ["Kata", "25", "Yogyakarta"],
+            ["Cherry Blossom", "35", "Bikini Bottom"],
+        ])
+        .build()
+}
+
+fn colspan(style: TableStyle) -> Table {
+    let mut table = Table::new();
+    table.set_style(style);
+    table.set_headers(["Quarter", "Jan", "Feb", "Mar"]);
+    table.add_row(["Q1", "10", "12", "9"]);
+    let mut row = Row::new();
+    row.push(Cell::new("Closed", Alignment::Left));
+    let mut merged = Cell::new("Spans all three months", Alignment::Center);
+    merged.set_span(3);
+    row.push(merged);
+    table.add_row(row);
+    table
+}
+
+fn wrap(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["Key", "Description"])
+        .constrain(1, WidthConstraint::Wrap(16))
+        .rows([
+            ["a", "A short note"],
+            ["b", "A description long enough to wrap over several lines"],
+            ["c", "First line\nSecond line"],
+        ])
+        .build()
+}
+
+fn fixed_max(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["ID", "Name", "Comment"])
+        .constrain(0, WidthConstraint::Fixed(4))
+        .constrain(1, WidthConstraint::Min(12))
+        .constrain(2, WidthConstraint::Max(12))
+        .rows([
+            ["1", "Kelana", "Fits"],
+            ["22", "Kata", "Much too long to fit the column"],
+        ])
+        .build()
+}
+
+fn weight(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["ID", "Name", "Notes"])
+        .constrain(0, WidthConstraint::Fixed(4))
+        .constrain(1, WidthConstraint::Weight(1))
+        .constrain(2, WidthConstraint::Weight(2))
+        .row(["1", "Kelana", "Weighted 1:2"])
+        .build()
+}
+
+fn column_groups(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["Region", "Q1", "Q2", "Q3", "Q4"])
+        .column_group(1..3, "First half")
+        .column_group(3..5, "Second half")
+        .rows([
+            ["North", "10", "12", "9", "14"],
+            ["South", "7", "8", "11", "6"],
+        ])
+        .build()
+}
+
+fn totals(style: TableStyle) -> Table {
+    TableBuilder::new()
+        .style(style)
+        .header(["City", "Name", "Sales"])
+        .rows([
+            ["Berlin", "Kata", "10.5"],
+            ["Paris", "Kelana", "3"],
+            ["Berlin", "Squidward", "4"],
+        ])
+        .align(2, Alignment::Right)
+        .group_by(0)
+        .aggregate(2, Aggregate::Sum)
+        .subtotals()
+        .grand_total()
+        .build()
+}
+
+/// Returns every golden file name with its expected content, index included.
+fn renders() -> Vec<(String, String)> {
+    let mut renders = Vec::new();
+    let mut index = String::from(
+        "# Gallery\n\nEvery table style rendering the same scenes. Generated by \
+         `cargo run --example gallery`; do not edit.\n",
+    );
+    for (scene, build) in SCENES {
+        let _ = write!(index, "\n## {scene}\n");
+        for (style, table_style) in STYLES {
+            let output = build(table_style).render();
+            let _ = write!(index, "\n### {style}\n\n```text\n{output}```\n");
+            renders.push((format!("{style}-{scene}.txt"), output));
+        }
+    }
+    renders.push(("README.md".to_string(), index));
+    renders
+}
+
+fn gallery_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/gallery")
+}
+
+/// Returns the names of the golden files that are missing or differ from the renders.
+fn stale() -> Vec<String> {
+    let dir = gallery_dir();
+    renders()
+        .into_iter()
+        .filter(|(name, output)| fs::read_to_string(dir.join(name)).ok().as_ref() != Some(output))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+fn main() -> Result<ExitCode, std::io::Error> {
+    if std::env::args().any(|arg| arg == "--check") {
+        let stale = stale();
+        if stale.is_empty() {
+            return Ok(ExitCode::SUCCESS);
+        }
+        for name in stale {
+            eprintln!("examples/gallery/{name} is out of date");
+        }
+        eprintln!("run `cargo run --example gallery` to update it");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let dir = gallery_dir();
+    fs::create_dir_all(&dir)?;
+    for (name, output) in renders() {
+        fs::write(dir.join(name), output)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn golden_files_are_up_to_date() {
+        let stale = super::stale();
+        assert!(
+            stale.is_empty(),
+            "out of date, run `cargo run --example gallery`: {stale:?}"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::alloc_prelude::*;
+
+/// Aggregate function used for group subtotals and the grand-total footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Sum of the numeric values in the column
+    Sum,
+    /// Arithmetic mean of the numeric values in the column
+    Avg,
+    /// Smallest numeric value in the column
+    Min,
+    /// Largest numeric value in the column
+    Max,
+    /// Number of non-empty cells in the column
+    Count,
+}
+
+impl Aggregate {
+    /// Computes the aggregate over the given cell contents and formats the result.
+    ///
+    /// Non-numeric values, including `NaN` and infinities, are ignored by every
+    /// aggregate except `Count`.
+    /// The result keeps as many decimal places as the most precise input
+    /// (`Avg` always keeps at least two).
+    ///
+    /// # Examples
+    /// ```
+    /// use crabular::Aggregate;
+    ///
+    /// assert_eq!(Aggregate::Sum.compute(["1.5", "2.25"]), "3.75");
+    /// assert_eq!(Aggregate::Max.compute(["3", "x", "10"]), "10");
+    /// assert_eq!(Aggregate::Count.compute(["a", "", "c"]), "2");
+    /// ```
+    #[must_use]
+    pub fn compute<'a, I>(self, values: I) -> String
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut count = 0usize;
+        let mut numbers: Vec<f64> = Vec::new();
+        let mut precision = 0;
+
+        for value in values {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            count += 1;
+            if let Ok(number) = value.parse::<f64>()
+                && number.is_finite()
+            {
+                numbers.push(number);
+                precision = precision.max(decimals(value));
+            }
+        }
+
+        let result = match self {
+            Aggregate::Count => return count.to_string(),
+            _ if numbers.is_empty() => return String::new(),
+            Aggregate::Sum => numbers.iter().sum(),
+            Aggregate::Avg => {
+                precision = precision.max(2);
+                #[allow(clippy::cast_precision_loss)]
+                let len = numbers.len() as f64;
+                numbers.iter().sum::<f64>() / len
+            }
+            Aggregate::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregate::Max => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        };
+
+        format!("{result:.precision$}")
+    }
+}
+
+/// Decimal places written by a number such as `2.50` (two) or `1.5e-3` (four), at
+/// most the 17 an `f64` can tell apart.
+fn decimals(value: &str) -> usize {
+    let (mantissa, exponent) = value.split_once(['e', 'E']).unwrap_or((value, "0"));
+    let fraction = mantissa
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len());
+    let exponent: i64 = exponent.parse().unwrap_or(0);
+    let decimals = i64::try_from(fraction)
+        .unwrap_or(i64::MAX)
+        .saturating_sub(exponent);
+    usize::try_from(decimals.clamp(0, 17)).unwrap_or(0)
+}
+
+impl core::str::FromStr for Aggregate {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sum" | "total" => Ok(Aggregate::Sum),
+            "avg" | "mean" | "average" => Ok(Aggregate::Avg),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "count" => Ok(Aggregate::Count),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Aggregate;
+
+    #[test]
+    fn sum() {
+        let cases = [
+            (vec!["1", "2", "3"], "6"),
+            (vec!["95.5", "87.2"], "182.7"),
+            (vec!["1.5", "2"], "3.5"),
+            (vec!["-1", "1"], "0"),
+        ];
+        for (values, expected) in cases {
+            assert_eq!(Aggregate::Sum.compute(values), expected);
+        }
+    }
+
+    #[test]
+    fn avg() {
+        assert_eq!(Aggregate::Avg.compute(["1", "2"]), "1.50");
+        assert_eq!(Aggregate::Avg.compute(["1.1", "2"]), "1.55");
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(Aggregate::Min.compute(["5", "-2", "3"]), "-2");
+        assert_eq!(Aggregate::Max.compute(["5", "-2", "3"]), "5");
+    }
+
+    #[test]
+    fn count_ignores_empty() {
+        assert_eq!(Aggregate::Count.compute(["a", "", " ", "b"]), "2");
+        assert_eq!(Aggregate::Count.compute([]), "0");
+    }
+
+    #[test]
+    fn non_numeric_values_are_skipped() {
+        assert_eq!(Aggregate::Sum.compute(["n/a", "4", "6"]), "10");
+        assert_eq!(Aggregate::Sum.compute(["n/a"]), "");
+    }
+
+    #[test]
+    fn non_finite_values_are_skipped() {
+        let values = ["NaN", "inf", "-infinity", "Infinity", "2", "4"];
+        let cases = [
+            (Aggregate::Sum, "6"),
+            (Aggregate::Avg, "3.00"),
+            (Aggregate::Min, "2"),
+            (Aggregate::Max, "4"),
+            (Aggregate::Count, "6"),
+        ];
+        for (aggregate, expected) in cases {
+            assert_eq!(aggregate.compute(values), expected, "{aggregate:?}");
+        }
+        assert_eq!(Aggregate::Max.compute(["nan"]), "");
+    }
+
+    #[test]
+    fn exponent_notation_precision() {
+        let cases = [
+            (vec!["1.5e3"], "1500"),
+            (vec!["1e-7"], "0.0000001"),
+            (vec!["2.5E-2", "1"], "1.025"),
+            (vec!["1.25e+1", "0.5"], "13.0"),
+            (vec!["1e-400"], "0.00000000000000000"),
+        ];
+        for (values, expected) in cases {
+            assert_eq!(
+                Aggregate::Sum.compute(values.clone()),
+                expected,
+                "{values:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("sum".parse(), Ok(Aggregate::Sum));
+        assert_eq!("AVG".parse(), Ok(Aggregate::Avg));
+        assert_eq!("mean".parse(), Ok(Aggregate::Avg));
+        assert_eq!("min".parse(), Ok(Aggregate::Min));
+        assert_eq!("max".parse(), Ok(Aggregate::Max));
+        assert_eq!("count".parse(), Ok(Aggregate::Count));
+        assert_eq!("median".parse::<Aggregate>(), Err(()));
+    }
+}
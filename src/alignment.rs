@@ -4,6 +4,9 @@ pub enum Alignment {
     Left,
     Center,
     Right,
+    /// Right-aligns numbers so their decimal points line up down the column, padding
+    /// shorter fractions on the right. Content without digits is right-aligned.
+    Decimal,
 }
 
 impl core::str::FromStr for Alignment {
@@ -14,6 +17,7 @@ impl core::str::FromStr for Alignment {
             "left" | "l" => Ok(Alignment::Left),
             "center" | "c" | "middle" => Ok(Alignment::Center),
             "right" | "r" => Ok(Alignment::Right),
+            "decimal" | "d" => Ok(Alignment::Decimal),
             _ => Err(()),
         }
     }
@@ -69,6 +73,7 @@ mod tests {
         assert_eq!("middle".parse(), Ok(Alignment::Center));
         assert_eq!("right".parse(), Ok(Alignment::Right));
         assert_eq!("r".parse(), Ok(Alignment::Right));
+        assert_eq!("decimal".parse(), Ok(Alignment::Decimal));
         assert_eq!("invalid".parse::<Alignment>(), Err(()));
     }
 }
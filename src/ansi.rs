@@ -0,0 +1,349 @@
+use crate::alloc_prelude::*;
+use crate::color::Color;
+use crate::html;
+
+/// Removes ANSI escape sequences (SGR colors, cursor movement, OSC hyperlinks)
+/// from `text`, keeping only the visible characters.
+pub(crate) fn strip(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameter and intermediate bytes, ended by a final byte in `@..=~`
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ended by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                }
+            }
+            // Two-character sequences such as ESC c
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+/// The SGR sequence that turns all attributes off.
+pub(crate) const RESET: &str = "\x1b[0m";
+
+/// Returns the byte length of the escape sequences at the start of `text`.
+pub(crate) fn escape_len(text: &str) -> usize {
+    let mut len = 0;
+    while let Some(rest) = text[len..].strip_prefix('\x1b') {
+        len += 1 + sequence_len(rest);
+    }
+    len
+}
+
+/// Returns the byte length of an escape sequence after its ESC, ending where
+/// [`strip`] would.
+fn sequence_len(rest: &str) -> usize {
+    let mut chars = rest.char_indices();
+    match chars.next() {
+        Some((_, '[')) => chars
+            .find(|(_, c)| ('@'..='~').contains(c))
+            .map_or(rest.len(), |(index, c)| index + c.len_utf8()),
+        Some((_, ']')) => {
+            for (index, c) in chars {
+                if c == '\x07' {
+                    return index + 1;
+                }
+                if c == '\x1b' {
+                    return index + 1 + usize::from(rest[index + 1..].starts_with('\\'));
+                }
+            }
+            rest.len()
+        }
+        Some((_, c)) => c.len_utf8(),
+        None => 0,
+    }
+}
+
+/// Starts every line after the first with the SGR attributes the lines before it left
+/// on, so colors continue on the lines of a wrapped cell.
+pub(crate) fn carry_styles(lines: Vec<String>) -> Vec<String> {
+    let mut active = String::new();
+    let mut carried = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut index = 0;
+        let mut next = active.clone();
+        while let Some(start) = line[index..].find('\x1b').map(|offset| index + offset) {
+            let end = start + 1 + sequence_len(&line[start + 1..]);
+            let sequence = &line[start..end];
+            if let Some(params) = sequence
+                .strip_prefix("\x1b[")
+                .and_then(|sequence| sequence.strip_suffix('m'))
+            {
+                if params
+                    .split(';')
+                    .next()
+                    .is_none_or(|first| first.is_empty() || first == "0")
+                {
+                    next.clear();
+                }
+                if !params.is_empty() && params != "0" {
+                    next.push_str(sequence);
+                }
+            }
+            index = end;
+        }
+        if active.is_empty() {
+            carried.push(line);
+        } else {
+            carried.push(format!("{active}{line}"));
+        }
+        active = next;
+    }
+    carried
+}
+
+/// Converts text with SGR colors and OSC 8 hyperlinks into HTML: colors and bold become
+/// `<span style>`, hyperlinks become `<a href>`, other escape sequences are dropped and
+/// the text is escaped.
+pub(crate) fn to_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut style = Style::default();
+    let mut span_open = false;
+    let mut link_open = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            let mut buffer = [0; 4];
+            html::write_escaped(&mut output, c.encode_utf8(&mut buffer));
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut last = None;
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        last = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if last != Some('m') {
+                    continue;
+                }
+                style.apply(&params);
+                if span_open {
+                    output.push_str("</span>");
+                    span_open = false;
+                }
+                if let Some(css) = style.css() {
+                    output.push_str("<span style=\"");
+                    output.push_str(&css);
+                    output.push_str("\">");
+                    span_open = true;
+                }
+            }
+            Some(']') => {
+                let mut command = String::new();
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                    command.push(c);
+                }
+                // OSC 8 is `8;params;url`, with an empty url closing the link
+                let Some(target) = command.strip_prefix("8;") else {
+                    continue;
+                };
+                let url = target.split_once(';').map_or("", |(_, url)| url);
+                if link_open {
+                    output.push_str("</a>");
+                    link_open = false;
+                }
+                if !url.is_empty() {
+                    output.push_str("<a href=\"");
+                    html::write_escaped(&mut output, url);
+                    output.push_str("\">");
+                    link_open = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if link_open {
+        output.push_str("</a>");
+    }
+    if span_open {
+        output.push_str("</span>");
+    }
+    output
+}
+
+/// The SGR attributes in effect while converting to HTML.
+#[derive(Default)]
+struct Style {
+    bold: bool,
+    color: Option<Color>,
+    background: Option<Color>,
+}
+
+impl Style {
+    /// Applies the `;`-separated parameters of an SGR sequence.
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';').map(|param| param.parse::<u8>().ok());
+        while let Some(param) = params.next() {
+            match param {
+                None | Some(0) => *self = Style::default(),
+                Some(1) => self.bold = true,
+                Some(22) => self.bold = false,
+                Some(code @ 30..=37) => self.color = Some(Color::Fixed(code - 30)),
+                Some(code @ 90..=97) => self.color = Some(Color::Fixed(code - 90 + 8)),
+                Some(39) => self.color = None,
+                Some(38) => self.color = Self::extended_color(&mut params),
+                Some(code @ 40..=47) => self.background = Some(Color::Fixed(code - 40)),
+                Some(code @ 100..=107) => self.background = Some(Color::Fixed(code - 100 + 8)),
+                Some(49) => self.background = None,
+                Some(48) => self.background = Self::extended_color(&mut params),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Reads the `5;N` or `2;R;G;B` parameters that follow a `38` or `48`.
+    fn extended_color(params: &mut impl Iterator<Item = Option<u8>>) -> Option<Color> {
+        match params.next().flatten() {
+            Some(5) => params.next().flatten().map(Color::Fixed),
+            Some(2) => match (params.next(), params.next(), params.next()) {
+                (Some(Some(r)), Some(Some(g)), Some(Some(b))) => Some(Color::Rgb(r, g, b)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn css(&self) -> Option<String> {
+        let mut css = Vec::new();
+        if let Some(color) = self.color {
+            css.push(format!("color: {}", color.css()));
+        }
+        if let Some(background) = self.background {
+            css.push(format!("background-color: {}", background.css()));
+        }
+        if self.bold {
+            css.push("font-weight: bold".to_string());
+        }
+        (!css.is_empty()).then(|| css.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ansi::{carry_styles, escape_len, strip, to_html};
+
+    #[test]
+    fn strips_escape_sequences() {
+        let cases = [
+            ("plain", "plain"),
+            ("\x1b[31mred\x1b[0m", "red"),
+            ("\x1b[1;38;5;208mbold\x1b[m text", "bold text"),
+            ("\x1b[2K\x1b[1Gprogress", "progress"),
+            ("\x1b]8;;https://example.com\x07link\x1b]8;;\x07", "link"),
+            ("\x1b]0;title\x1b\\after", "after"),
+            ("\x1bcreset", "reset"),
+            ("日本\x1b[32m語\x1b[0m", "日本語"),
+            ("dangling\x1b[", "dangling"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(strip(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn escape_lengths() {
+        let cases = [
+            ("plain", 0),
+            ("\x1b[31mred", 5),
+            ("\x1b[1m\x1b[32mgreen", 9),
+            ("\x1b]8;;https://example.com\x1b\\link", 26),
+            ("\x1bcreset", 2),
+            ("\x1b[", 2),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(escape_len(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn carries_styles_across_lines() {
+        let lines = ["\x1b[31mred and", "still red\x1b[0m then", "plain \x1b[1m"]
+            .map(str::to_string)
+            .to_vec();
+        assert_eq!(
+            carry_styles(lines),
+            [
+                "\x1b[31mred and",
+                "\x1b[31mstill red\x1b[0m then",
+                "plain \x1b[1m"
+            ]
+        );
+        let lines = ["\x1b[1m\x1b[0;32mx", "y"].map(str::to_string).to_vec();
+        assert_eq!(carry_styles(lines)[1], "\x1b[0;32my");
+    }
+
+    #[test]
+    fn converts_to_html() {
+        let cases = [
+            ("a < b", "a &lt; b"),
+            (
+                "\x1b[31mred\x1b[0m plain",
+                "<span style=\"color: #cd0000\">red</span> plain",
+            ),
+            (
+                "\x1b[1;38;5;208mbold\x1b[m",
+                "<span style=\"color: #ff8700; font-weight: bold\">bold</span>",
+            ),
+            (
+                "\x1b[38;2;1;2;3mrgb\x1b[1mboth\x1b[0m",
+                "<span style=\"color: #010203\">rgb</span>\
+                 <span style=\"color: #010203; font-weight: bold\">both</span>",
+            ),
+            (
+                "\x1b]8;;https://x/?a=1&b=2\x1b\\link\x1b]8;;\x1b\\",
+                "<a href=\"https://x/?a=1&amp;b=2\">link</a>",
+            ),
+            (
+                "\x1b[2Kunclosed \x1b[32mgreen",
+                "unclosed <span style=\"color: #00cd00\">green</span>",
+            ),
+            ("\x1b]0;title\x07after", "after"),
+            (
+                "\x1b[48;5;236;33mstripe\x1b[49mfg\x1b[0m",
+                "<span style=\"color: #cdcd00; background-color: #303030\">stripe</span>\
+                 <span style=\"color: #cdcd00\">fg</span>",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(to_html(input), expected, "{input:?}");
+        }
+    }
+}
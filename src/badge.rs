@@ -0,0 +1,138 @@
+use alloc::collections::BTreeMap;
+
+use crate::alloc_prelude::*;
+use crate::color::Color;
+
+/// Maps enum-like values such as `ok`, `warn` and `err` to colored badges like
+/// `[ OK ]`, padded to a common width so a status column lines up.
+///
+/// Badges are registered as a named formatter with
+/// [`FormatterRegistry::register_badges`](crate::FormatterRegistry::register_badges).
+/// The built-in `badge` formatter uses [`Badges::status`]. Values are matched ignoring
+/// case and surrounding whitespace; other values are shown unchanged.
+///
+/// # Example
+/// ```
+/// use crabular::{Badges, Color, FormatterRegistry, Table};
+///
+/// let mut formatters = FormatterRegistry::new();
+/// formatters.register_badges(
+///     "state",
+///     Badges::new()
+///         .badge("up", "UP", Color::Green)
+///         .badge("degraded", "SLOW", Color::Yellow),
+/// );
+///
+/// let mut table = Table::new().header(["Host", "State"]).row(["db1", "up"]);
+/// table.set_formatters(formatters);
+/// table.format_column(1, "state");
+/// assert!(table.render().contains("\x1b[32m[  UP  ]\x1b[0m"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Badges {
+    badges: BTreeMap<String, (String, Option<Color>)>,
+}
+
+impl Badges {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The badges of the built-in `badge` formatter: `ok`, `warn` and `err` along with
+    /// common spellings such as `success`, `warning` and `failed`.
+    #[must_use]
+    pub fn status() -> Self {
+        let mut badges = Self::new();
+        for value in ["ok", "pass", "passed", "success", "up"] {
+            badges = badges.badge(value, "OK", Color::Green);
+        }
+        for value in ["warn", "warning"] {
+            badges = badges.badge(value, "WARN", Color::Yellow);
+        }
+        for value in ["err", "error", "fail", "failed", "down"] {
+            badges = badges.badge(value, "ERR", Color::Red);
+        }
+        badges
+    }
+
+    /// Shows `value` as a `label` badge in `color`.
+    #[must_use]
+    pub fn badge(mut self, value: &str, label: &str, color: Color) -> Self {
+        self.badges
+            .insert(Self::key(value), (label.to_string(), Some(color)));
+        self
+    }
+
+    /// Drops the colors, leaving plain-text badges for output that is not a terminal.
+    #[must_use]
+    pub fn without_colors(mut self) -> Self {
+        for (_, color) in self.badges.values_mut() {
+            *color = None;
+        }
+        self
+    }
+
+    /// Returns the width of the widest label, which every badge is padded to.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.badges
+            .values()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the badge text for `value`, or `None` if no badge matches.
+    #[must_use]
+    pub fn label(&self, value: &str) -> Option<String> {
+        let (label, _) = self.badges.get(&Self::key(value))?;
+        let padding = self.width() - label.chars().count();
+        let left = padding / 2;
+        Some(format!(
+            "[ {}{label}{} ]",
+            " ".repeat(left),
+            " ".repeat(padding - left)
+        ))
+    }
+
+    /// Returns the color of the badge for `value`.
+    #[must_use]
+    pub fn color(&self, value: &str) -> Option<Color> {
+        self.badges.get(&Self::key(value))?.1
+    }
+
+    fn key(value: &str) -> String {
+        value.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Badges, Color};
+
+    #[test]
+    fn status_badges_share_a_width() {
+        let badges = Badges::status();
+        assert_eq!(badges.width(), 4);
+        let cases = [
+            ("ok", Some("[  OK  ]"), Some(Color::Green)),
+            (" Warning ", Some("[ WARN ]"), Some(Color::Yellow)),
+            ("ERR", Some("[ ERR  ]"), Some(Color::Red)),
+            ("pending", None, None),
+        ];
+        for (value, label, color) in cases {
+            assert_eq!(badges.label(value).as_deref(), label, "{value}");
+            assert_eq!(badges.color(value), color, "{value}");
+        }
+    }
+
+    #[test]
+    fn custom_badges() {
+        let badges = Badges::new().badge("ok", "OK", Color::Green);
+        assert_eq!(badges.label("ok").as_deref(), Some("[ OK ]"));
+        assert_eq!(badges.clone().without_colors().color("ok"), None);
+        assert_eq!(badges.color("ok"), Some(Color::Green));
+        assert_eq!(Badges::new().label("ok"), None);
+    }
+}
@@ -0,0 +1,112 @@
+/// Which lines of a table's border style are drawn, so any style can drop its outer
+/// frame, its column separators or its row rules. Hidden column separators leave a
+/// space, so columns stay apart; a hidden frame takes no room.
+///
+/// Markdown tables follow it too, the delimiter row included, although Markdown
+/// renderers need the header separator. Compact Markdown
+/// ([`MarkdownMode::Compact`](crate::MarkdownMode::Compact)) and `AsciiDoc` ignore it.
+///
+/// # Example
+/// ```
+/// use crabular::{BorderVisibility, Table, TableStyle};
+///
+/// let mut table = Table::new().header(["Name", "Age"]).row(["Kata", "30"]);
+/// table.set_style(TableStyle::Modern);
+/// table.set_border_visibility(BorderVisibility::all().without_frame());
+/// assert_eq!(
+///     table.render(),
+///     " Name  │ Age \n───────┼─────\n Kata  │ 30  \n"
+/// );
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderVisibility {
+    /// The line above the first row
+    pub top: bool,
+    /// The line below the last row
+    pub bottom: bool,
+    /// The left edge of every row and rule
+    pub left: bool,
+    /// The right edge of every row and rule
+    pub right: bool,
+    /// Rules between body rows, such as group separators and the rule above a total
+    pub inner_horizontal: bool,
+    /// Separators between columns
+    pub inner_vertical: bool,
+    /// The rule between the header and the body
+    pub header_separator: bool,
+}
+
+impl BorderVisibility {
+    /// Every line the style has.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            top: true,
+            bottom: true,
+            left: true,
+            right: true,
+            inner_horizontal: true,
+            inner_vertical: true,
+            header_separator: true,
+        }
+    }
+
+    /// No lines at all, leaving columns separated by spaces.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            top: false,
+            bottom: false,
+            left: false,
+            right: false,
+            inner_horizontal: false,
+            inner_vertical: false,
+            header_separator: false,
+        }
+    }
+
+    /// Hides the top, bottom, left and right edges.
+    #[must_use]
+    pub const fn without_frame(mut self) -> Self {
+        self.top = false;
+        self.bottom = false;
+        self.left = false;
+        self.right = false;
+        self
+    }
+
+    /// Keeps the rule under the header as the only horizontal line inside the table.
+    #[must_use]
+    pub const fn header_rule_only(mut self) -> Self {
+        self.inner_horizontal = false;
+        self.header_separator = true;
+        self
+    }
+}
+
+impl Default for BorderVisibility {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BorderVisibility;
+
+    #[test]
+    fn presets() {
+        assert_eq!(BorderVisibility::default(), BorderVisibility::all());
+        let frameless = BorderVisibility::all().without_frame();
+        assert!(!frameless.top && !frameless.left && frameless.inner_vertical);
+        let header_only = BorderVisibility::none().header_rule_only();
+        assert_eq!(
+            header_only,
+            BorderVisibility {
+                header_separator: true,
+                ..BorderVisibility::none()
+            }
+        );
+    }
+}
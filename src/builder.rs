@@ -1,10 +1,25 @@
+use crate::aggregate::Aggregate;
 use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::border_visibility::BorderVisibility;
 use crate::constraint::WidthConstraint;
-use crate::padding::Padding;
+use crate::formatter::FormatterRegistry;
+use crate::header_style::HeaderStyle;
+use crate::margin::Margin;
+use crate::messages::Messages;
+#[cfg(feature = "unicode-normalization")]
+use crate::normalization::Normalization;
+use crate::padding::{Padding, VerticalPadding};
+use crate::render_options::RenderOptions;
 use crate::row::Row;
 use crate::style::TableStyle;
 use crate::table::Table;
+use crate::text_transform::TextTransform;
+use crate::theme::Theme;
+use crate::validation::TableError;
 use crate::vertical_alignment::VerticalAlignment;
+use crate::wrap_policy::WrapPolicy;
+use core::ops::Range;
 
 /// A builder for creating tables with a fluent API.
 ///
@@ -51,6 +66,59 @@ impl TableBuilder {
         self
     }
 
+    /// Sets the tab stop interval used to expand tabs in rows added afterwards.
+    #[must_use]
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.table.set_tab_width(width);
+        self
+    }
+
+    /// Sets the marker that ends truncated content, or `None` to cut hard.
+    /// Call before adding rows for it to apply to the [`truncate`](Self::truncate) limit.
+    #[must_use]
+    pub fn truncation_marker<'a>(mut self, marker: impl Into<Option<&'a str>>) -> Self {
+        self.table.set_truncation_marker(marker);
+        self
+    }
+
+    /// Strips ANSI escape codes from the headers and rows added afterwards.
+    #[must_use]
+    pub fn strip_ansi(mut self) -> Self {
+        self.table.set_strip_ansi(true);
+        self
+    }
+
+    /// Stores equal cell contents of the rows added afterwards once, see
+    /// [`Table::set_interning`].
+    #[must_use]
+    pub fn interning(mut self) -> Self {
+        self.table.set_interning(true);
+        self
+    }
+
+    /// Sets how the header row is presented (case, emphasis, alignment).
+    #[must_use]
+    pub fn header_style(mut self, header_style: HeaderStyle) -> Self {
+        self.table.set_header_style(header_style);
+        self
+    }
+
+    /// Sets which lines of the border style are drawn, see
+    /// [`Table::set_border_visibility`].
+    #[must_use]
+    pub fn border_visibility(mut self, visibility: BorderVisibility) -> Self {
+        self.table.set_border_visibility(visibility);
+        self
+    }
+
+    /// Applies a theme's border style, header style, zebra striping and column type
+    /// colors, see [`Table::set_theme`].
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.table.set_theme(theme);
+        self
+    }
+
     /// Sets the table headers.
     #[must_use]
     pub fn header<R: Into<Row>>(mut self, headers: R) -> Self {
@@ -58,6 +126,36 @@ impl TableBuilder {
         self
     }
 
+    /// Refuses rows whose length differs from the headers', see
+    /// [`Table::set_strict_columns`]. [`try_build`](Self::try_build) reports them.
+    #[must_use]
+    pub fn strict_columns(mut self) -> Self {
+        self.table.set_strict_columns(true);
+        self
+    }
+
+    /// Refuses rows that do not cover exactly `cols` columns, see
+    /// [`Table::set_expected_cols`]. [`try_build`](Self::try_build) reports them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{TableBuilder, TableError};
+    /// let result = TableBuilder::new()
+    ///     .expect_cols(2)
+    ///     .row(["a", "b"])
+    ///     .row(["c"])
+    ///     .try_build();
+    /// assert_eq!(
+    ///     result.err(),
+    ///     Some(TableError::RowLength { row: 1, expected: 2, found: 1 })
+    /// );
+    /// ```
+    #[must_use]
+    pub fn expect_cols(mut self, cols: usize) -> Self {
+        self.table.set_expected_cols(cols);
+        self
+    }
+
     /// Adds a row to the table.
     #[must_use]
     pub fn row<R: Into<Row>>(mut self, cells: R) -> Self {
@@ -96,6 +194,72 @@ impl TableBuilder {
         self
     }
 
+    /// Sets how a width-limited column handles content longer than its limit.
+    #[must_use]
+    pub fn wrap_policy(mut self, column: usize, policy: WrapPolicy) -> Self {
+        self.table.set_wrap_policy(column, policy);
+        self
+    }
+
+    /// Displays a column through a named formatter, such as `bytes`, `mask:4` or a
+    /// [`NumberFormat`](crate::NumberFormat).
+    #[must_use]
+    pub fn format_column(mut self, column: usize, spec: impl Into<String>) -> Self {
+        self.table.format_column(column, spec);
+        self
+    }
+
+    /// Changes the case or whitespace of a column's cells when rendered.
+    #[must_use]
+    pub fn transform_column(mut self, column: usize, transform: TextTransform) -> Self {
+        self.table.transform_column(column, transform);
+        self
+    }
+
+    /// Links a column's cells to a URL built from `template`, e.g.
+    /// `https://example.com/issues/{value}`.
+    #[must_use]
+    pub fn link_column(mut self, column: usize, template: &str) -> Self {
+        self.table.link_column(column, template);
+        self
+    }
+
+    /// Sets the table's own named formatters.
+    #[must_use]
+    pub fn formatters(mut self, formatters: FormatterRegistry) -> Self {
+        self.table.set_formatters(formatters);
+        self
+    }
+
+    /// Shows `placeholder` in empty body cells.
+    #[must_use]
+    pub fn empty_placeholder(mut self, placeholder: &str) -> Self {
+        self.table.set_empty_placeholder(placeholder);
+        self
+    }
+
+    /// Replaces the text the renderer adds, such as the "… (N more rows)" line.
+    #[must_use]
+    pub fn messages(mut self, messages: Messages) -> Self {
+        self.table.set_messages(messages);
+        self
+    }
+
+    /// Normalizes content before it is compared by sorting and filtering.
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.table.set_normalization(normalization);
+        self
+    }
+
+    /// Hyphenates words that word wrapping breaks apart.
+    #[must_use]
+    pub fn hyphenate(mut self) -> Self {
+        self.table.set_hyphenate(true);
+        self
+    }
+
     /// Sets the vertical alignment for multi-line cells.
     #[must_use]
     pub fn valign(mut self, alignment: VerticalAlignment) -> Self {
@@ -110,6 +274,13 @@ impl TableBuilder {
         self
     }
 
+    /// Sets the blank lines above and below every row's content.
+    #[must_use]
+    pub fn vertical_padding(mut self, padding: VerticalPadding) -> Self {
+        self.table.set_vertical_padding(padding);
+        self
+    }
+
     /// Sets the spacing between columns.
     #[must_use]
     pub fn spacing(mut self, spacing: usize) -> Self {
@@ -117,12 +288,109 @@ impl TableBuilder {
         self
     }
 
+    /// Renders only the first `max_rows` rows, followed by a line telling how many
+    /// rows were left out.
+    #[must_use]
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.table.set_max_rows(Some(max_rows));
+        self
+    }
+
+    /// Sets output options such as the Markdown layout.
+    #[must_use]
+    pub fn render_options(mut self, options: RenderOptions) -> Self {
+        self.table.set_render_options(options);
+        self
+    }
+
+    /// Indents every rendered line by `width` spaces.
+    #[must_use]
+    pub fn indent(self, width: usize) -> Self {
+        let right = self.table.render_options().get_margin().right;
+        self.margin(Margin::new(width, right))
+    }
+
+    /// Sets the blank space around every rendered line, outside the borders.
+    #[must_use]
+    pub fn margin(mut self, margin: Margin) -> Self {
+        let options = self.table.render_options().clone().margin(margin);
+        self.table.set_render_options(options);
+        self
+    }
+
+    /// Caches the rendered output until the table changes.
+    #[must_use]
+    pub fn memoize(mut self) -> Self {
+        self.table.set_memoize(true);
+        self
+    }
+
+    /// Groups rows by the content of a column when rendering.
+    #[must_use]
+    pub fn group_by(mut self, column: usize) -> Self {
+        self.table.group_by(column);
+        self
+    }
+
+    /// Registers an aggregate for a column, used by subtotal and grand-total rows.
+    #[must_use]
+    pub fn aggregate(mut self, column: usize, aggregate: Aggregate) -> Self {
+        self.table.aggregate(column, aggregate);
+        self
+    }
+
+    /// Adds a subtotal row after each group.
+    #[must_use]
+    pub fn subtotals(mut self) -> Self {
+        self.table.set_subtotals(true);
+        self
+    }
+
+    /// Adds a grand-total footer row.
+    #[must_use]
+    pub fn grand_total(mut self) -> Self {
+        self.table.set_grand_total(true);
+        self
+    }
+
+    /// Sets `columns` apart as a section, with an optional label above it.
+    #[must_use]
+    pub fn column_group<'a>(
+        mut self,
+        columns: Range<usize>,
+        label: impl Into<Option<&'a str>>,
+    ) -> Self {
+        self.table.column_group(columns, label);
+        self
+    }
+
     /// Builds and returns the table.
     #[must_use]
     pub fn build(self) -> Table {
         self.table
     }
 
+    /// Builds the table after checking its rows and width constraints with
+    /// [`Table::validate`], instead of rendering whatever was given.
+    ///
+    /// # Errors
+    /// Returns the first [`TableError`] found.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{TableBuilder, TableError};
+    /// let result = TableBuilder::new()
+    ///     .header(["Name", "Age"])
+    ///     .row(["Kata", "30"])
+    ///     .row(["Kelana"])
+    ///     .try_build();
+    /// assert!(matches!(result, Err(TableError::RowLength { row: 1, .. })));
+    /// ```
+    pub fn try_build(self) -> Result<Table, TableError> {
+        self.table.validate()?;
+        Ok(self.table)
+    }
+
     /// Builds the table and renders it to a string.
     #[must_use]
     pub fn render(self) -> String {
@@ -130,6 +398,7 @@ impl TableBuilder {
     }
 
     /// Builds the table and prints it to stdout.
+    #[cfg(feature = "std")]
     pub fn print(self) {
         self.table.print();
     }
@@ -137,7 +406,10 @@ impl TableBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Alignment, Padding, TableBuilder, TableStyle, VerticalAlignment, WidthConstraint};
+    use crate::{
+        Aggregate, Alignment, FormatterRegistry, HeaderStyle, Margin, MarkdownMode, Padding,
+        RenderOptions, TableBuilder, TableStyle, VerticalAlignment, WidthConstraint, WrapPolicy,
+    };
 
     #[test]
     fn new_is_empty() {
@@ -167,6 +439,13 @@ mod tests {
         assert_eq!(table.headers().unwrap().len(), 3);
     }
 
+    #[test]
+    fn with_header_style() {
+        let style = HeaderStyle::new().uppercase().bold();
+        let table = TableBuilder::new().header_style(style).build();
+        assert_eq!(table.header_style(), style);
+    }
+
     #[test]
     fn with_rows() {
         let table = TableBuilder::new()
@@ -203,6 +482,34 @@ mod tests {
         assert_eq!(table.get_valign(), VerticalAlignment::Middle);
     }
 
+    #[test]
+    fn with_wrap_policy() {
+        let table = TableBuilder::new()
+            .constrain(0, WidthConstraint::Wrap(4))
+            .wrap_policy(0, WrapPolicy::Character)
+            .build();
+        assert_eq!(table.wrap_policy(0), WrapPolicy::Character);
+    }
+
+    #[test]
+    fn with_format_column() {
+        let mut formatters = FormatterRegistry::new();
+        formatters.register("upper", str::to_uppercase);
+        let table = TableBuilder::new()
+            .formatters(formatters)
+            .format_column(1, "upper")
+            .build();
+        assert_eq!(table.column_format(1), Some("upper"));
+        assert_eq!(table.column_format(0), None);
+        assert_eq!(table.formatters().names().collect::<Vec<_>>(), ["upper"]);
+    }
+
+    #[test]
+    fn with_hyphenate() {
+        let table = TableBuilder::new().hyphenate().build();
+        assert!(table.hyphenate());
+    }
+
     #[test]
     fn with_padding() {
         let table = TableBuilder::new().padding(Padding::new(2, 3)).build();
@@ -225,6 +532,99 @@ mod tests {
         assert_eq!(table.constraints().len(), 2);
     }
 
+    #[test]
+    fn with_render_options() {
+        let output = TableBuilder::new()
+            .style(TableStyle::Markdown)
+            .render_options(RenderOptions::new().markdown(MarkdownMode::Compact))
+            .header(["A", "B"])
+            .row(["1", "2"])
+            .render();
+        assert_eq!(output, "|A|B|\n|---|---|\n|1|2|\n");
+    }
+
+    #[test]
+    fn with_indent() {
+        let output = TableBuilder::new()
+            .style(TableStyle::Compact)
+            .indent(4)
+            .row(["a", "b"])
+            .render();
+        assert_eq!(output, "    │a│b│\n");
+    }
+
+    #[test]
+    fn with_margin_keeps_other_options() {
+        let table = TableBuilder::new()
+            .render_options(RenderOptions::new().line_prefix("> "))
+            .margin(Margin::new(1, 2))
+            .indent(3)
+            .build();
+        assert_eq!(table.render_options().get_line_prefix(), "> ");
+        assert_eq!(table.render_options().get_margin(), Margin::new(3, 2));
+    }
+
+    #[test]
+    fn with_truncation_marker() {
+        let table = TableBuilder::new()
+            .truncate(5)
+            .truncation_marker("…")
+            .row(["Kelana"])
+            .build();
+        assert_eq!(table.truncation_marker(), Some("…"));
+        assert_eq!(table.rows()[0].cells()[0].content(), "Kela…");
+    }
+
+    #[test]
+    fn with_strip_ansi() {
+        let table = TableBuilder::new()
+            .strip_ansi()
+            .row(["\x1b[33mwarn\x1b[0m"])
+            .build();
+        assert!(table.strip_ansi());
+        assert_eq!(table.rows()[0].cells()[0].content(), "warn");
+    }
+
+    #[test]
+    fn with_tab_width() {
+        let table = TableBuilder::new().tab_width(8).row(["a\tb"]).build();
+        assert_eq!(table.tab_width(), 8);
+        assert_eq!(table.rows()[0].cells()[0].content(), "a       b");
+    }
+
+    #[test]
+    fn with_max_rows() {
+        let table = TableBuilder::new()
+            .header(["N"])
+            .rows((0..5).map(|n| [n.to_string()]))
+            .max_rows(2)
+            .build();
+        assert_eq!(table.max_rows(), Some(2));
+        assert_eq!(table.len(), 5);
+        assert!(table.render().contains("… (3 more rows)"));
+    }
+
+    #[test]
+    fn with_memoize() {
+        let table = TableBuilder::new().memoize().row(["1"]).build();
+        assert!(table.memoize());
+        assert_eq!(table.render(), table.render());
+    }
+
+    #[test]
+    fn with_grouping() {
+        let table = TableBuilder::new()
+            .group_by(0)
+            .aggregate(1, Aggregate::Sum)
+            .subtotals()
+            .grand_total()
+            .build();
+        assert_eq!(table.group_column(), Some(0));
+        assert_eq!(table.aggregates(), &[(1, Aggregate::Sum)]);
+        assert!(table.has_subtotals());
+        assert!(table.has_grand_total());
+    }
+
     #[test]
     fn render() {
         let output = TableBuilder::new()
@@ -1,25 +1,99 @@
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+
 use crate::Alignment;
+use crate::alloc_prelude::*;
 
 #[derive(Clone)]
 pub struct Cell {
-    content: String,
+    content: Content,
     alignment: Alignment,
     span: usize,
+    note: Option<String>,
+}
+
+/// The text of a cell, either owned by the cell or shared with other cells so that
+/// equal values are stored once.
+#[derive(Clone)]
+enum Content {
+    Owned(String),
+    Shared(Arc<str>),
 }
 
 impl Cell {
+    /// Creates a cell showing `content`, which can be any `Display` value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Alignment, Cell};
+    /// assert_eq!(Cell::new("text", Alignment::Left).content(), "text");
+    /// assert_eq!(Cell::new(9.5, Alignment::Right).content(), "9.5");
+    /// ```
+    #[must_use]
+    pub fn new(content: impl core::fmt::Display, alignment: Alignment) -> Self {
+        Self {
+            content: Content::Owned(content.to_string()),
+            alignment,
+            span: 1,
+            note: None,
+        }
+    }
+
+    /// Creates a cell showing a shared string without copying it. Cells made from
+    /// clones of the same `Arc` store their content once, for large tables built from
+    /// an external string pool; see also [`Table::set_interning`](crate::Table::set_interning).
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use crabular::{Alignment, Cell};
+    /// let status: Arc<str> = Arc::from("active");
+    /// let cells: Vec<Cell> = (0..3)
+    ///     .map(|_| Cell::from_shared(Arc::clone(&status), Alignment::Left))
+    ///     .collect();
+    /// assert_eq!(cells[2].content(), "active");
+    /// assert_eq!(Arc::strong_count(&status), 4);
+    /// ```
     #[must_use]
-    pub fn new(content: &str, alignment: Alignment) -> Self {
+    pub fn from_shared(content: Arc<str>, alignment: Alignment) -> Self {
         Self {
-            content: content.to_string(),
+            content: Content::Shared(content),
             alignment,
             span: 1,
+            note: None,
         }
     }
 
     #[must_use]
     pub fn content(&self) -> &str {
-        &self.content
+        match &self.content {
+            Content::Owned(content) => content,
+            Content::Shared(content) => content,
+        }
+    }
+
+    pub fn set_content(&mut self, content: &str) {
+        match &mut self.content {
+            Content::Owned(owned) => content.clone_into(owned),
+            Content::Shared(_) => self.content = Content::Owned(content.to_string()),
+        }
+    }
+
+    /// Replaces owned content with the equal string from `strings`, adding it there
+    /// first if it is new, so equal cells share one allocation.
+    pub(crate) fn intern(&mut self, strings: &mut BTreeSet<Arc<str>>) {
+        let Content::Owned(content) = &self.content else {
+            return;
+        };
+        let shared = if let Some(shared) = strings.get(content.as_str()) {
+            Arc::clone(shared)
+        } else {
+            let shared: Arc<str> = Arc::from(content.as_str());
+            strings.insert(Arc::clone(&shared));
+            shared
+        };
+        self.content = Content::Shared(shared);
     }
 
     #[must_use]
@@ -39,17 +113,102 @@ impl Cell {
     pub fn set_alignment(&mut self, alignment: Alignment) {
         self.alignment = alignment;
     }
+
+    /// Returns the note attached to the cell, if any.
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Attaches a note, e.g. to explain an anomaly in a report, or removes it with `None`.
+    ///
+    /// HTML output shows the note as a `title` tooltip. Text output marks the cell with
+    /// a footnote number like `[1]` and lists the notes below the table, unless
+    /// [`NoteMode::Ignore`](crate::NoteMode::Ignore) is set in the render options.
+    pub fn set_note<'a>(&mut self, note: impl Into<Option<&'a str>>) {
+        self.note = note.into().map(str::to_string);
+    }
 }
 
 impl core::fmt::Display for Cell {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "{}", self.content)
+        f.write_str(self.content())
+    }
+}
+
+/// Converts a value into a left-aligned [`Cell`], so rows can be built from tuples of
+/// mixed types without calling `to_string` on every number:
+///
+/// ```
+/// # use crabular::Table;
+/// let table = Table::new()
+///     .header(["Name", "Age", "Score", "Active"])
+///     .row(("Kelana", 30, 9.5, true))
+///     .row(("Kata", 25, None::<f64>, false));
+/// assert_eq!(table.rows()[0].cells()[2].content(), "9.5");
+/// assert_eq!(table.rows()[1].cells()[2].content(), "");
+/// ```
+///
+/// Implemented for strings, numbers, `bool`, `char`, [`Cell`] itself (kept as it is) and
+/// `Option` of any of these, where `None` is an empty cell. Other `Display` types go
+/// through [`Cell::new`].
+pub trait IntoCell {
+    fn into_cell(self) -> Cell;
+}
+
+impl IntoCell for Cell {
+    fn into_cell(self) -> Cell {
+        self
     }
 }
 
+impl<T: IntoCell> IntoCell for Option<T> {
+    fn into_cell(self) -> Cell {
+        self.map_or_else(|| Cell::new("", Alignment::default()), IntoCell::into_cell)
+    }
+}
+
+macro_rules! into_cell_via_display {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl IntoCell for $ty {
+                fn into_cell(self) -> Cell {
+                    Cell::new(self, Alignment::default())
+                }
+            }
+        )*
+    };
+}
+
+into_cell_via_display!(
+    &str,
+    String,
+    &String,
+    Cow<'_, str>,
+    char,
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
 #[cfg(test)]
 mod tests {
-    use crate::{Alignment, Cell};
+    use alloc::collections::BTreeSet;
+    use alloc::sync::Arc;
+
+    use crate::{Alignment, Cell, IntoCell};
 
     #[test]
     fn creation() {
@@ -67,6 +226,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn display_content() {
+        assert_eq!(Cell::new(42, Alignment::Right).content(), "42");
+        assert_eq!(Cell::new(-1.25, Alignment::Right).content(), "-1.25");
+        assert_eq!(Cell::new('x', Alignment::Left).content(), "x");
+        assert_eq!(
+            Cell::new(String::from("owned"), Alignment::Left).content(),
+            "owned"
+        );
+    }
+
+    #[test]
+    fn into_cell() {
+        assert_eq!(7u8.into_cell().content(), "7");
+        assert_eq!(false.into_cell().content(), "false");
+        assert_eq!(Some("x").into_cell().content(), "x");
+        assert_eq!(None::<i32>.into_cell().content(), "");
+
+        let mut wide = Cell::new("wide", Alignment::Right);
+        wide.set_span(2);
+        let cell = wide.into_cell();
+        assert_eq!(cell.span(), 2);
+        assert_eq!(cell.alignment(), Alignment::Right);
+    }
+
     #[test]
     fn set_span() {
         let cases = [1, 2, 3, 5, 10];
@@ -77,6 +261,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_content() {
+        let mut cell = Cell::new("before", Alignment::Right);
+        cell.set_span(2);
+        cell.set_content("after");
+        assert_eq!(cell.content(), "after");
+        assert_eq!(cell.alignment(), Alignment::Right);
+        assert_eq!(cell.span(), 2);
+    }
+
+    #[test]
+    fn shared_content() {
+        let mut strings = BTreeSet::new();
+        let mut first = Cell::new("ok", Alignment::Left);
+        let mut second = Cell::from_shared(Arc::from("ok"), Alignment::Left);
+        first.intern(&mut strings);
+        second.intern(&mut strings);
+        let mut third = Cell::new("ok", Alignment::Left);
+        third.intern(&mut strings);
+        assert_eq!(strings.len(), 1);
+        assert_eq!(first.content().as_ptr(), third.content().as_ptr());
+        // Content that is already shared keeps its own string
+        assert_ne!(first.content().as_ptr(), second.content().as_ptr());
+
+        third.set_content("changed");
+        assert_eq!(third.content(), "changed");
+        assert_eq!(first.content(), "ok");
+    }
+
     #[test]
     fn set_alignment() {
         let mut cell = Cell::new("test", Alignment::Left);
@@ -88,6 +301,17 @@ mod tests {
         assert_eq!(cell.alignment(), Alignment::Center);
     }
 
+    #[test]
+    fn set_note() {
+        let mut cell = Cell::new("42", Alignment::Right);
+        assert_eq!(cell.note(), None);
+        cell.set_note("estimated");
+        assert_eq!(cell.note(), Some("estimated"));
+        assert_eq!(cell.clone().note(), Some("estimated"));
+        cell.set_note(None);
+        assert_eq!(cell.note(), None);
+    }
+
     #[test]
     fn clone_trait() {
         let cell = Cell::new("test", Alignment::Center);
@@ -0,0 +1,210 @@
+use crate::alloc_prelude::*;
+
+/// Terminal color, emitted as an ANSI SGR escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// A color from the 256-color palette
+    Fixed(u8),
+    /// A 24-bit true color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Returns the SGR parameters selecting this color as the foreground.
+    ///
+    /// # Examples
+    /// ```
+    /// use crabular::Color;
+    ///
+    /// assert_eq!(Color::Red.fg_code(), "31");
+    /// assert_eq!(Color::Fixed(208).fg_code(), "38;5;208");
+    /// assert_eq!(Color::Rgb(1, 2, 3).fg_code(), "38;2;1;2;3");
+    /// ```
+    #[must_use]
+    pub fn fg_code(self) -> String {
+        match self {
+            Color::Black => "30".to_string(),
+            Color::Red => "31".to_string(),
+            Color::Green => "32".to_string(),
+            Color::Yellow => "33".to_string(),
+            Color::Blue => "34".to_string(),
+            Color::Magenta => "35".to_string(),
+            Color::Cyan => "36".to_string(),
+            Color::White => "37".to_string(),
+            Color::Fixed(n) => format!("38;5;{n}"),
+            Color::Rgb(r, g, b) => format!("38;2;{r};{g};{b}"),
+        }
+    }
+
+    /// Returns the SGR parameters selecting this color as the background.
+    ///
+    /// # Examples
+    /// ```
+    /// use crabular::Color;
+    ///
+    /// assert_eq!(Color::Red.bg_code(), "41");
+    /// assert_eq!(Color::Fixed(236).bg_code(), "48;5;236");
+    /// assert_eq!(Color::Rgb(1, 2, 3).bg_code(), "48;2;1;2;3");
+    /// ```
+    #[must_use]
+    pub fn bg_code(self) -> String {
+        match self {
+            Color::Black => "40".to_string(),
+            Color::Red => "41".to_string(),
+            Color::Green => "42".to_string(),
+            Color::Yellow => "43".to_string(),
+            Color::Blue => "44".to_string(),
+            Color::Magenta => "45".to_string(),
+            Color::Cyan => "46".to_string(),
+            Color::White => "47".to_string(),
+            Color::Fixed(n) => format!("48;5;{n}"),
+            Color::Rgb(r, g, b) => format!("48;2;{r};{g};{b}"),
+        }
+    }
+
+    /// Returns the color as a CSS hex value, taking named and 256-palette colors from
+    /// the xterm defaults.
+    ///
+    /// # Examples
+    /// ```
+    /// use crabular::Color;
+    ///
+    /// assert_eq!(Color::Red.css(), "#cd0000");
+    /// assert_eq!(Color::Fixed(208).css(), "#ff8700");
+    /// assert_eq!(Color::Rgb(1, 2, 3).css(), "#010203");
+    /// ```
+    #[must_use]
+    pub fn css(self) -> String {
+        const BASIC: [(u8, u8, u8); 16] = [
+            (0x00, 0x00, 0x00),
+            (0xcd, 0x00, 0x00),
+            (0x00, 0xcd, 0x00),
+            (0xcd, 0xcd, 0x00),
+            (0x00, 0x00, 0xee),
+            (0xcd, 0x00, 0xcd),
+            (0x00, 0xcd, 0xcd),
+            (0xe5, 0xe5, 0xe5),
+            (0x7f, 0x7f, 0x7f),
+            (0xff, 0x00, 0x00),
+            (0x00, 0xff, 0x00),
+            (0xff, 0xff, 0x00),
+            (0x5c, 0x5c, 0xff),
+            (0xff, 0x00, 0xff),
+            (0x00, 0xff, 0xff),
+            (0xff, 0xff, 0xff),
+        ];
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        let (r, g, b) = match self {
+            Color::Black => BASIC[0],
+            Color::Red => BASIC[1],
+            Color::Green => BASIC[2],
+            Color::Yellow => BASIC[3],
+            Color::Blue => BASIC[4],
+            Color::Magenta => BASIC[5],
+            Color::Cyan => BASIC[6],
+            Color::White => BASIC[7],
+            Color::Fixed(n @ 0..16) => BASIC[usize::from(n)],
+            Color::Fixed(n @ 16..232) => {
+                let n = usize::from(n - 16);
+                (CUBE[n / 36], CUBE[n / 6 % 6], CUBE[n % 6])
+            }
+            Color::Fixed(n) => {
+                let level = 8 + (n - 232) * 10;
+                (level, level, level)
+            }
+            Color::Rgb(r, g, b) => (r, g, b),
+        };
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+impl core::str::FromStr for Color {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "black" => Ok(Color::Black),
+            "red" => Ok(Color::Red),
+            "green" => Ok(Color::Green),
+            "yellow" => Ok(Color::Yellow),
+            "blue" => Ok(Color::Blue),
+            "magenta" | "purple" => Ok(Color::Magenta),
+            "cyan" => Ok(Color::Cyan),
+            "white" => Ok(Color::White),
+            other => {
+                if let Some(hex) = other.strip_prefix('#')
+                    && hex.len() == 6
+                {
+                    let channel = |range: core::ops::Range<usize>| {
+                        hex.get(range).and_then(|h| u8::from_str_radix(h, 16).ok())
+                    };
+                    if let (Some(r), Some(g), Some(b)) =
+                        (channel(0..2), channel(2..4), channel(4..6))
+                    {
+                        return Ok(Color::Rgb(r, g, b));
+                    }
+                }
+                other.parse::<u8>().map(Color::Fixed).map_err(|_| ())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    #[test]
+    fn fg_code() {
+        let cases = [
+            (Color::Black, "30"),
+            (Color::Red, "31"),
+            (Color::Green, "32"),
+            (Color::Yellow, "33"),
+            (Color::Blue, "34"),
+            (Color::Magenta, "35"),
+            (Color::Cyan, "36"),
+            (Color::White, "37"),
+            (Color::Fixed(42), "38;5;42"),
+            (Color::Rgb(255, 0, 16), "38;2;255;0;16"),
+        ];
+        for (color, expected) in cases {
+            assert_eq!(color.fg_code(), expected);
+        }
+    }
+
+    #[test]
+    fn css() {
+        let cases = [
+            (Color::Blue, "#0000ee"),
+            (Color::Fixed(9), "#ff0000"),
+            (Color::Fixed(16), "#000000"),
+            (Color::Fixed(231), "#ffffff"),
+            (Color::Fixed(232), "#080808"),
+            (Color::Fixed(255), "#eeeeee"),
+            (Color::Rgb(255, 0, 16), "#ff0010"),
+        ];
+        for (color, expected) in cases {
+            assert_eq!(color.css(), expected, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("red".parse(), Ok(Color::Red));
+        assert_eq!("Purple".parse(), Ok(Color::Magenta));
+        assert_eq!("208".parse(), Ok(Color::Fixed(208)));
+        assert_eq!("#ff0010".parse(), Ok(Color::Rgb(255, 0, 16)));
+        assert_eq!("#ff00".parse::<Color>(), Err(()));
+        assert_eq!("chartreuse".parse::<Color>(), Err(()));
+    }
+}
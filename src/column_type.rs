@@ -0,0 +1,199 @@
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::cell::Cell;
+use crate::table::Table;
+
+/// What the non-empty cells of a column hold, as detected by [`Table::column_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// No non-empty cells
+    Empty,
+    /// Whole numbers such as `42` or `-7`
+    Integer,
+    /// Numbers with a fraction or exponent such as `3.5` or `1e-3`, possibly mixed with
+    /// whole numbers
+    Decimal,
+    /// `true`/`false` or `yes`/`no`, in any case
+    Boolean,
+    /// ISO 8601 dates such as `2024-03-01`
+    Date,
+    /// Anything else
+    Text,
+}
+
+impl ColumnType {
+    /// Detects the type shared by every non-empty value, ignoring surrounding whitespace.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::ColumnType;
+    /// assert_eq!(ColumnType::detect(["1", "", "-7"]), ColumnType::Integer);
+    /// assert_eq!(ColumnType::detect(["1", "2.5"]), ColumnType::Decimal);
+    /// assert_eq!(ColumnType::detect(["Yes", "no"]), ColumnType::Boolean);
+    /// assert_eq!(ColumnType::detect(["1", "n/a"]), ColumnType::Text);
+    /// ```
+    pub fn detect<'a>(values: impl IntoIterator<Item = &'a str>) -> Self {
+        values
+            .into_iter()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(Self::of_value)
+            .reduce(Self::combine)
+            .unwrap_or(ColumnType::Empty)
+    }
+
+    /// Returns the alignment that reads best for the type: right for numbers, center
+    /// for booleans, and `None` where left-aligned text is already right.
+    #[must_use]
+    pub fn alignment(self) -> Option<Alignment> {
+        match self {
+            ColumnType::Integer | ColumnType::Decimal => Some(Alignment::Right),
+            ColumnType::Boolean => Some(Alignment::Center),
+            ColumnType::Empty | ColumnType::Date | ColumnType::Text => None,
+        }
+    }
+
+    /// Returns whether the type is [`Integer`](Self::Integer) or [`Decimal`](Self::Decimal).
+    #[must_use]
+    pub fn is_numeric(self) -> bool {
+        matches!(self, ColumnType::Integer | ColumnType::Decimal)
+    }
+
+    fn of_value(value: &str) -> Self {
+        if value.parse::<i64>().is_ok() {
+            ColumnType::Integer
+        } else if value.parse::<f64>().is_ok() && value.bytes().any(|b| b.is_ascii_digit()) {
+            // The digit check keeps words like `inf` and `NaN` out
+            ColumnType::Decimal
+        } else if ["true", "false", "yes", "no"]
+            .iter()
+            .any(|word| value.eq_ignore_ascii_case(word))
+        {
+            ColumnType::Boolean
+        } else if is_date(value) {
+            ColumnType::Date
+        } else {
+            ColumnType::Text
+        }
+    }
+
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (
+                ColumnType::Integer | ColumnType::Decimal,
+                ColumnType::Integer | ColumnType::Decimal,
+            ) => ColumnType::Decimal,
+            _ => ColumnType::Text,
+        }
+    }
+}
+
+/// Returns whether `value` is a `YYYY-MM-DD` date.
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let number =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    number(year, 4)
+        && number(month, 2)
+        && number(day, 2)
+        && (1..=12).contains(&month.parse::<u8>().unwrap_or(0))
+        && (1..=31).contains(&day.parse::<u8>().unwrap_or(0))
+}
+
+impl Table {
+    /// Detects what the body cells of `column` hold. Headers are not inspected.
+    #[must_use]
+    pub fn column_type(&self, column: usize) -> ColumnType {
+        ColumnType::detect(
+            self.rows()
+                .iter()
+                .filter_map(|row| row.cells().get(column))
+                .map(Cell::content),
+        )
+    }
+
+    /// Returns the detected type of every column.
+    #[must_use]
+    pub fn column_types(&self) -> Vec<ColumnType> {
+        (0..self.cols())
+            .map(|column| self.column_type(column))
+            .collect()
+    }
+
+    /// Aligns each column by its detected type: numbers right and booleans centered.
+    /// Columns of other types keep their alignment.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Alignment, Table};
+    /// let mut table = Table::new()
+    ///     .header(["Name", "Score", "Passed"])
+    ///     .row(["Kata", "9.5", "yes"])
+    ///     .row(["Kelana", "10", "no"]);
+    /// table.auto_align();
+    /// assert_eq!(table.get_align(1), Some(Alignment::Right));
+    /// assert_eq!(table.get_align(2), Some(Alignment::Center));
+    /// assert!(table.render().contains("| Kata    |   9.5  |  yes   |"));
+    /// ```
+    pub fn auto_align(&mut self) {
+        for (column, column_type) in self.column_types().into_iter().enumerate() {
+            if let Some(alignment) = column_type.alignment() {
+                self.align(column, alignment);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, ColumnType, Table};
+
+    #[test]
+    fn detect_values() {
+        let cases: [(&[&str], ColumnType); 9] = [
+            (&[], ColumnType::Empty),
+            (&["", "  "], ColumnType::Empty),
+            (&[" 42 ", "-7", "+3"], ColumnType::Integer),
+            (&["1.5", "1e3", "2"], ColumnType::Decimal),
+            (&["TRUE", "false"], ColumnType::Boolean),
+            (&["2024-03-01", "1999-12-31"], ColumnType::Date),
+            (&["2024-13-01"], ColumnType::Text),
+            (&["inf", "NaN"], ColumnType::Text),
+            (&["1", "yes"], ColumnType::Text),
+        ];
+        for (values, expected) in cases {
+            assert_eq!(
+                ColumnType::detect(values.iter().copied()),
+                expected,
+                "{values:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn auto_align_keeps_text_columns() {
+        let mut table = Table::new()
+            .header(["id", "name", "active", "joined"])
+            .row(["1", "Kata", "true", "2024-01-02"])
+            .row(["2", "Kelana", "false", "2024-02-03"]);
+        table.align(1, Alignment::Center);
+        assert_eq!(
+            table.column_types(),
+            [
+                ColumnType::Integer,
+                ColumnType::Text,
+                ColumnType::Boolean,
+                ColumnType::Date
+            ]
+        );
+        table.auto_align();
+        assert_eq!(table.get_align(0), Some(Alignment::Right));
+        assert_eq!(table.get_align(1), Some(Alignment::Center));
+        assert_eq!(table.get_align(2), Some(Alignment::Center));
+        assert_eq!(table.get_align(3), None);
+    }
+}
@@ -7,6 +7,43 @@ pub enum WidthConstraint {
     Max(usize),
     Proportional(u8),
     Wrap(usize),
+    /// A share of the width left over by the other columns, relative to the weights of
+    /// the other `Weight` columns. Content wider than its share is never cut.
+    Weight(u32),
+}
+
+/// Parses `auto`, `fixed:N`, `min:N`, `max:N`, `wrap:N`, `weight:N` and `proportional:N`
+/// (or `N%`).
+impl core::str::FromStr for WidthConstraint {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s == "auto" {
+            return Ok(WidthConstraint::Auto);
+        }
+        if let Some(percent) = s.strip_suffix('%') {
+            return percent
+                .parse()
+                .map(WidthConstraint::Proportional)
+                .map_err(|_| ());
+        }
+        let (kind, value) = s.split_once(':').ok_or(())?;
+        let value: usize = value.trim().parse().map_err(|_| ())?;
+        match kind {
+            "fixed" => Ok(WidthConstraint::Fixed(value)),
+            "min" => Ok(WidthConstraint::Min(value)),
+            "max" => Ok(WidthConstraint::Max(value)),
+            "wrap" => Ok(WidthConstraint::Wrap(value)),
+            "weight" => u32::try_from(value)
+                .map(WidthConstraint::Weight)
+                .map_err(|_| ()),
+            "proportional" => u8::try_from(value)
+                .map(WidthConstraint::Proportional)
+                .map_err(|_| ()),
+            _ => Err(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -38,6 +75,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_str() {
+        let cases = [
+            ("auto", Ok(WidthConstraint::Auto)),
+            ("Fixed:10", Ok(WidthConstraint::Fixed(10))),
+            ("min:5", Ok(WidthConstraint::Min(5))),
+            ("max: 20", Ok(WidthConstraint::Max(20))),
+            ("wrap:30", Ok(WidthConstraint::Wrap(30))),
+            ("proportional:40", Ok(WidthConstraint::Proportional(40))),
+            ("25%", Ok(WidthConstraint::Proportional(25))),
+            ("weight:3", Ok(WidthConstraint::Weight(3))),
+            ("proportional:300", Err(())),
+            ("fixed", Err(())),
+            ("wide:3", Err(())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<WidthConstraint>(), expected, "{input}");
+        }
+    }
+
     #[test]
     fn debug_trait() {
         assert_eq!(format!("{:?}", WidthConstraint::Auto), "Auto");
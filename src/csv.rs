@@ -0,0 +1,370 @@
+use core::iter::Peekable;
+use core::str::Chars;
+
+use crate::alloc_prelude::*;
+use crate::render_options::Newline;
+use crate::table::Table;
+
+/// When fields are wrapped in double quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quoting {
+    /// Only fields containing the delimiter, a quote or a line break (default)
+    #[default]
+    Minimal,
+    /// Every field
+    Always,
+}
+
+impl core::str::FromStr for Quoting {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "minimal" | "min" => Ok(Quoting::Minimal),
+            "always" | "all" => Ok(Quoting::Always),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Dialect options for [`Table::to_csv`](crate::Table::to_csv) and
+/// [`Table::from_delimited_str`].
+///
+/// Quotes inside fields are doubled, and fields containing line breaks are always
+/// quoted, so multi-line cells survive a round trip.
+///
+/// # Example
+/// ```
+/// use crabular::{CsvOptions, Newline, Quoting, Table};
+///
+/// let table = Table::new().header(["Name", "Note"]).row(["Kata", "says \"hi\""]);
+///
+/// assert_eq!(
+///     table.to_csv(&CsvOptions::new()),
+///     "Name,Note\nKata,\"says \"\"hi\"\"\"\n"
+/// );
+///
+/// let options = CsvOptions::new()
+///     .delimiter(';')
+///     .quoting(Quoting::Always)
+///     .newline(Newline::CrLf);
+/// assert!(table.to_csv(&options).starts_with("\"Name\";\"Note\"\r\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    delimiter: char,
+    quoting: Quoting,
+    newline: Newline,
+    header: bool,
+    wrapped: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quoting: Quoting::default(),
+            newline: Newline::default(),
+            header: true,
+            wrapped: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the field delimiter, `,` by default.
+    #[must_use]
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    #[must_use]
+    pub fn quoting(mut self, quoting: Quoting) -> Self {
+        self.quoting = quoting;
+        self
+    }
+
+    /// Sets the record terminator. Line breaks inside fields are written as-is.
+    #[must_use]
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Leaves the header row out of the output.
+    #[must_use]
+    pub fn no_header(mut self) -> Self {
+        self.header = false;
+        self
+    }
+
+    /// Exports body cells in `Wrap` columns as their wrapped lines joined by `\n`,
+    /// matching the rendered table. Headers are never wrapped.
+    #[must_use]
+    pub fn wrapped(mut self) -> Self {
+        self.wrapped = true;
+        self
+    }
+
+    #[must_use]
+    pub fn get_delimiter(&self) -> char {
+        self.delimiter
+    }
+
+    #[must_use]
+    pub fn get_quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    #[must_use]
+    pub fn get_newline(&self) -> Newline {
+        self.newline
+    }
+
+    #[must_use]
+    pub fn has_header(&self) -> bool {
+        self.header
+    }
+
+    #[must_use]
+    pub fn is_wrapped(&self) -> bool {
+        self.wrapped
+    }
+
+    /// Writes `fields` as one record, including the record terminator.
+    pub(crate) fn write_record<'a, I>(&self, output: &mut String, fields: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        for (index, field) in fields.into_iter().enumerate() {
+            if index > 0 {
+                output.push(self.delimiter);
+            }
+            self.write_field(output, field);
+        }
+        output.push_str(self.newline.as_str());
+    }
+
+    fn write_field(&self, output: &mut String, field: &str) {
+        let quote = match self.quoting {
+            Quoting::Always => true,
+            Quoting::Minimal => field
+                .chars()
+                .any(|c| c == self.delimiter || matches!(c, '"' | '\n' | '\r')),
+        };
+        if !quote {
+            output.push_str(field);
+            return;
+        }
+        output.push('"');
+        for c in field.chars() {
+            if c == '"' {
+                output.push('"');
+            }
+            output.push(c);
+        }
+        output.push('"');
+    }
+
+    /// Splits `text` into records, undoing the quoting of
+    /// [`write_record`](Self::write_record). Records end at `\n` or `\r\n` outside
+    /// quotes, blank lines are skipped, and a quote that is never closed runs to the end
+    /// of the text.
+    pub(crate) fn read_records(&self, text: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut chars = text.chars().peekable();
+        while chars.peek().is_some() {
+            let record = self.read_record(&mut chars);
+            if record.len() > 1 || record.first().is_some_and(|field| !field.is_empty()) {
+                records.push(record);
+            }
+        }
+        records
+    }
+
+    fn read_record(&self, chars: &mut Peekable<Chars<'_>>) -> Vec<String> {
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' if quoted => quoted = false,
+                '"' if field.is_empty() => quoted = true,
+                _ if quoted => field.push(c),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => break,
+                _ if c == self.delimiter => record.push(core::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        record.push(field);
+        record
+    }
+}
+
+impl Table {
+    /// Builds a table from CSV text, with the first record as headers. See
+    /// [`from_delimited_str`](Self::from_delimited_str) for the accepted syntax.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::from_csv_str("City,Note\nBerlin,\"big, busy\"\n");
+    /// assert_eq!(table.headers().unwrap().cells()[1].content(), "Note");
+    /// assert_eq!(table.rows()[0].cells()[1].content(), "big, busy");
+    /// ```
+    #[must_use]
+    pub fn from_csv_str(text: &str) -> Self {
+        Self::from_delimited_str(text, &CsvOptions::new())
+    }
+
+    /// Builds a table from tab-separated text, with the first record as headers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::from_tsv_str("Name\tAge\nKata\t30\n");
+    /// assert_eq!(table.rows()[0].cells()[1].content(), "30");
+    /// ```
+    #[must_use]
+    pub fn from_tsv_str(text: &str) -> Self {
+        Self::from_delimited_str(text, &CsvOptions::new().delimiter('\t'))
+    }
+
+    /// Builds a table from delimited text, reading the delimiter and whether the first
+    /// record holds the headers from `options`; the other options only apply to export.
+    ///
+    /// Fields may be wrapped in double quotes to contain the delimiter, line breaks or
+    /// doubled `""` quotes, as written by [`to_csv`](Self::to_csv). Records end at `\n`
+    /// or `\r\n` and blank lines are skipped. Parsing never fails: a quote that is
+    /// never closed runs to the end of the text, and records may have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{CsvOptions, Table};
+    /// let options = CsvOptions::new().delimiter(';').no_header();
+    /// let table = Table::from_delimited_str("1;\"two\nlines\"\r\n3;4\r\n", &options);
+    /// assert!(table.headers().is_none());
+    /// assert_eq!(table.rows()[0].cells()[1].content(), "two\nlines");
+    /// assert_eq!(table.to_csv(&options), "1;\"two\nlines\"\n3;4\n");
+    /// ```
+    #[must_use]
+    pub fn from_delimited_str(text: &str, options: &CsvOptions) -> Self {
+        let mut records = options.read_records(text).into_iter();
+        let mut table = Self::new();
+        if options.has_header()
+            && let Some(headers) = records.next()
+        {
+            table.set_headers(headers);
+        }
+        for record in records {
+            table.add_row(record);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CsvOptions, Newline, Quoting, Table};
+
+    fn record(options: &CsvOptions, fields: &[&str]) -> String {
+        let mut output = String::new();
+        options.write_record(&mut output, fields.iter().copied());
+        output
+    }
+
+    #[test]
+    fn defaults() {
+        let options = CsvOptions::new();
+        assert_eq!(options.get_delimiter(), ',');
+        assert_eq!(options.get_quoting(), Quoting::Minimal);
+        assert_eq!(options.get_newline(), Newline::Lf);
+        assert!(options.has_header());
+        assert!(!options.is_wrapped());
+    }
+
+    #[test]
+    fn quote_minimal() {
+        let options = CsvOptions::new();
+        let cases = [
+            (vec!["a", "b"], "a,b\n"),
+            (vec!["a,b", "c"], "\"a,b\",c\n"),
+            (vec!["say \"hi\""], "\"say \"\"hi\"\"\"\n"),
+            (vec!["two\nlines", ""], "\"two\nlines\",\n"),
+            (vec!["cr\r"], "\"cr\r\"\n"),
+        ];
+        for (fields, expected) in cases {
+            assert_eq!(record(&options, &fields), expected);
+        }
+    }
+
+    #[test]
+    fn quote_always() {
+        let options = CsvOptions::new().quoting(Quoting::Always);
+        assert_eq!(record(&options, &["a", ""]), "\"a\",\"\"\n");
+    }
+
+    #[test]
+    fn custom_delimiter_and_newline() {
+        let options = CsvOptions::new().delimiter('\t').newline(Newline::CrLf);
+        assert_eq!(record(&options, &["a,b", "c\td"]), "a,b\t\"c\td\"\r\n");
+    }
+
+    #[test]
+    fn read_records() {
+        let options = CsvOptions::new();
+        let cases: [(&str, &[&[&str]]); 8] = [
+            ("", &[]),
+            ("a,b\nc,d", &[&["a", "b"], &["c", "d"]]),
+            ("a,b\r\n\r\nc,\n", &[&["a", "b"], &["c", ""]]),
+            (",\n", &[&["", ""]]),
+            ("\"a,b\",\"say \"\"hi\"\"\"\n", &[&["a,b", "say \"hi\""]]),
+            ("\"two\nlines\",x", &[&["two\nlines", "x"]]),
+            ("5\" disk,\"\"", &[&["5\" disk", ""]]),
+            ("\"open,end\nx", &[&["open,end\nx"]]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(options.read_records(text), expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kata", "says \"hi\", twice"])
+            .row(["Kelana", "two\nlines"]);
+        for options in [
+            CsvOptions::new(),
+            CsvOptions::new().delimiter('\t').newline(Newline::CrLf),
+            CsvOptions::new().quoting(Quoting::Always),
+        ] {
+            let text = table.to_csv(&options);
+            assert_eq!(
+                Table::from_delimited_str(&text, &options).to_csv(&options),
+                text
+            );
+        }
+        let tsv = Table::from_tsv_str("a\tb,c\n1\t2,3\n");
+        assert_eq!(tsv.headers().unwrap().cells()[1].content(), "b,c");
+        assert_eq!(tsv.len(), 1);
+    }
+
+    #[test]
+    fn quoting_from_str() {
+        assert_eq!("always".parse(), Ok(Quoting::Always));
+        assert_eq!("Minimal".parse(), Ok(Quoting::Minimal));
+        assert_eq!("never".parse::<Quoting>(), Err(()));
+    }
+}
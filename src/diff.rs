@@ -0,0 +1,378 @@
+use alloc::collections::VecDeque;
+
+use crate::HashMap;
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::cell::Cell;
+use crate::row::Row;
+use crate::table::Table;
+
+/// How a row differs between the two tables of a [`DiffTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowChange {
+    Unchanged,
+    /// Only in the new table
+    Added,
+    /// Only in the old table
+    Removed,
+    /// In both tables, with at least one cell changed
+    Changed,
+}
+
+impl RowChange {
+    /// Returns the marker shown in the diff: `+`, `-`, `~`, or a space for unchanged rows.
+    #[must_use]
+    pub fn marker(self) -> &'static str {
+        match self {
+            RowChange::Unchanged => " ",
+            RowChange::Added => "+",
+            RowChange::Removed => "-",
+            RowChange::Changed => "~",
+        }
+    }
+}
+
+/// Layout of a rendered [`DiffTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiffLayout {
+    /// One row per diffed row, led by a marker column, with changed cells shown as
+    /// `old → new` (default)
+    #[default]
+    Annotated,
+    /// The old columns, the marker column and the new columns next to each other
+    SideBySide,
+}
+
+/// Comparison of two tables, created by [`Table::diff`](crate::Table::diff).
+///
+/// Rows are paired by position unless a [key column](Self::key_column) is set, in
+/// which case rows with the same key are compared and rows whose key appears in only
+/// one table are added or removed. Cells are compared by their position in the row.
+/// The diff renders as a table in the old table's style, with rows marked `+` (added),
+/// `-` (removed) or `~` (changed).
+///
+/// # Example
+/// ```
+/// use crabular::{RowChange, Table};
+///
+/// let old = Table::new()
+///     .header(["id", "name", "score"])
+///     .row(["1", "Kata", "90"])
+///     .row(["2", "Kelana", "75"]);
+/// let new = Table::new()
+///     .header(["id", "name", "score"])
+///     .row(["1", "Kata", "95"])
+///     .row(["3", "Squidward", "60"]);
+///
+/// let diff = old.diff(&new).key_column(0);
+/// assert_eq!(
+///     diff.changes(),
+///     [RowChange::Changed, RowChange::Added, RowChange::Removed]
+/// );
+/// assert!(diff.render().contains("| ~  | 1   | Kata       | 90 → 95 |"));
+/// ```
+#[derive(Clone)]
+pub struct DiffTable<'a> {
+    old: &'a Table,
+    new: &'a Table,
+    key: Option<usize>,
+    layout: DiffLayout,
+    only_changes: bool,
+}
+
+impl<'a> DiffTable<'a> {
+    pub(crate) fn new(old: &'a Table, new: &'a Table) -> Self {
+        Self {
+            old,
+            new,
+            key: None,
+            layout: DiffLayout::default(),
+            only_changes: false,
+        }
+    }
+
+    /// Pairs rows by the content of `column` instead of by position.
+    #[must_use]
+    pub fn key_column(mut self, column: usize) -> Self {
+        self.key = Some(column);
+        self
+    }
+
+    /// Sets the rendered layout.
+    #[must_use]
+    pub fn layout(mut self, layout: DiffLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Leaves unchanged rows out of the rendered diff.
+    #[must_use]
+    pub fn only_changes(mut self) -> Self {
+        self.only_changes = true;
+        self
+    }
+
+    /// Returns the change of every diffed row, in display order.
+    #[must_use]
+    pub fn changes(&self) -> Vec<RowChange> {
+        self.pairs().iter().map(|(change, _, _)| *change).collect()
+    }
+
+    /// Returns whether any row was added, removed or changed.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        self.changes()
+            .iter()
+            .any(|change| *change != RowChange::Unchanged)
+    }
+
+    /// Builds the diff as a table, which can be styled further before rendering.
+    #[must_use]
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table.set_style(self.old.style());
+        let headers = self.new.headers().or(self.old.headers());
+        match self.layout {
+            DiffLayout::Annotated => {
+                if let Some(headers) = headers {
+                    table.set_headers(Self::marked("", contents(headers)));
+                }
+                for (change, old, new) in self.pairs() {
+                    if self.only_changes && change == RowChange::Unchanged {
+                        continue;
+                    }
+                    let cells = match (old, new) {
+                        (Some(old), Some(new)) => annotate(old, new),
+                        (Some(row), None) | (None, Some(row)) => contents(row),
+                        (None, None) => Vec::new(),
+                    };
+                    table.add_row(Self::marked(change.marker(), cells));
+                }
+            }
+            DiffLayout::SideBySide => {
+                let width = self.old.cols().max(self.new.cols());
+                if let Some(headers) = headers {
+                    let headers = padded(Some(headers), width);
+                    table.set_headers(Self::side_by_side(&headers, "", &headers));
+                }
+                for (change, old, new) in self.pairs() {
+                    if self.only_changes && change == RowChange::Unchanged {
+                        continue;
+                    }
+                    table.add_row(Self::side_by_side(
+                        &padded(old, width),
+                        change.marker(),
+                        &padded(new, width),
+                    ));
+                }
+            }
+        }
+        table
+    }
+
+    /// Renders the diff.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.to_table().render()
+    }
+
+    /// Pairs old and new rows. Unmatched old rows are placed after the old row they
+    /// followed, so removals show up where they happened.
+    fn pairs(&self) -> Vec<(RowChange, Option<&'a Row>, Option<&'a Row>)> {
+        let (old, new) = (self.old.rows(), self.new.rows());
+        let Some(key) = self.key else {
+            return (0..old.len().max(new.len()))
+                .map(|index| pair(old.get(index), new.get(index)))
+                .collect();
+        };
+
+        let key_of = |row: &'a Row| row.cells().get(key).map_or("", Cell::content);
+        let mut by_key: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (index, row) in old.iter().enumerate() {
+            by_key.entry(key_of(row)).or_default().push_back(index);
+        }
+        let mut matched = vec![false; old.len()];
+        let new_matches: Vec<Option<usize>> = new
+            .iter()
+            .map(|row| {
+                let index = by_key.get_mut(key_of(row))?.pop_front()?;
+                matched[index] = true;
+                Some(index)
+            })
+            .collect();
+
+        let mut pairs = Vec::with_capacity(old.len().max(new.len()));
+        let mut next_old = 0;
+        for (row, old_index) in new.iter().zip(new_matches) {
+            let Some(old_index) = old_index else {
+                pairs.push((RowChange::Added, None, Some(row)));
+                continue;
+            };
+            while next_old < old_index {
+                if !matched[next_old] {
+                    pairs.push((RowChange::Removed, Some(&old[next_old]), None));
+                }
+                next_old += 1;
+            }
+            next_old = next_old.max(old_index + 1);
+            pairs.push(pair(Some(&old[old_index]), Some(row)));
+        }
+        for (index, row) in old.iter().enumerate().skip(next_old) {
+            if !matched[index] {
+                pairs.push((RowChange::Removed, Some(row), None));
+            }
+        }
+        pairs
+    }
+
+    fn marked(marker: &str, cells: Vec<String>) -> Row {
+        let mut row = Row::new();
+        row.push(Cell::new(marker, Alignment::Center));
+        for cell in cells {
+            row.push(Cell::new(&cell, Alignment::Left));
+        }
+        row
+    }
+
+    fn side_by_side(old: &[String], marker: &str, new: &[String]) -> Row {
+        let mut row = Row::with_alignment(old, Alignment::Left);
+        row.push(Cell::new(marker, Alignment::Center));
+        for cell in new {
+            row.push(Cell::new(cell, Alignment::Left));
+        }
+        row
+    }
+}
+
+impl core::fmt::Display for DiffTable<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+fn pair<'a>(
+    old: Option<&'a Row>,
+    new: Option<&'a Row>,
+) -> (RowChange, Option<&'a Row>, Option<&'a Row>) {
+    let change = match (old, new) {
+        (Some(old), Some(new)) if contents(old) == contents(new) => RowChange::Unchanged,
+        (Some(_), Some(_)) => RowChange::Changed,
+        (Some(_), None) => RowChange::Removed,
+        (None, _) => RowChange::Added,
+    };
+    (change, old, new)
+}
+
+fn contents(row: &Row) -> Vec<String> {
+    row.cells()
+        .iter()
+        .map(|cell| cell.content().to_string())
+        .collect()
+}
+
+/// Returns the cells of `new`, with changed cells shown as `old → new`.
+fn annotate(old: &Row, new: &Row) -> Vec<String> {
+    let (old, new) = (contents(old), contents(new));
+    (0..old.len().max(new.len()))
+        .map(|index| {
+            let before = old.get(index).map_or("", String::as_str);
+            let after = new.get(index).map_or("", String::as_str);
+            if before == after {
+                after.to_string()
+            } else {
+                format!("{before} → {after}")
+            }
+        })
+        .collect()
+}
+
+/// Returns the cells of `row`, or blanks, padded to `width` cells.
+fn padded(row: Option<&Row>, width: usize) -> Vec<String> {
+    let mut cells = row.map(contents).unwrap_or_default();
+    cells.resize(width.max(cells.len()), String::new());
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DiffLayout, RowChange, Table};
+
+    fn snapshots() -> (Table, Table) {
+        let old = Table::new()
+            .header(["id", "name"])
+            .row(["1", "Kata"])
+            .row(["2", "Kelana"])
+            .row(["3", "Squidward"]);
+        let new = Table::new()
+            .header(["id", "name"])
+            .row(["1", "Kata"])
+            .row(["3", "Squid"])
+            .row(["4", "Patrick"]);
+        (old, new)
+    }
+
+    #[test]
+    fn pairs_rows_by_position() {
+        let (old, new) = snapshots();
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.changes(),
+            [RowChange::Unchanged, RowChange::Changed, RowChange::Changed]
+        );
+        assert!(diff.has_changes());
+        assert!(!old.diff(&old).has_changes());
+    }
+
+    #[test]
+    fn pairs_rows_by_key() {
+        let (old, new) = snapshots();
+        let diff = old.diff(&new).key_column(0);
+        assert_eq!(
+            diff.changes(),
+            [
+                RowChange::Unchanged,
+                RowChange::Removed,
+                RowChange::Changed,
+                RowChange::Added
+            ]
+        );
+        assert_eq!(
+            diff.only_changes().render(),
+            "+----+-----+-------------------+\n\
+             |    | id  | name              |\n\
+             +----+-----+-------------------+\n\
+             | -  | 2   | Kelana            |\n\
+             | ~  | 3   | Squidward → Squid |\n\
+             | +  | 4   | Patrick           |\n\
+             +----+-----+-------------------+\n"
+        );
+    }
+
+    #[test]
+    fn side_by_side_layout() {
+        let (old, new) = snapshots();
+        let output = old
+            .diff(&new)
+            .key_column(0)
+            .layout(DiffLayout::SideBySide)
+            .to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "| id  | name       |    | id  | name    |");
+        assert_eq!(lines[4], "| 2   | Kelana     | -  |     |         |");
+        assert_eq!(lines[6], "|     |            | +  | 4   | Patrick |");
+    }
+
+    #[test]
+    fn markers() {
+        let markers: Vec<&str> = [
+            RowChange::Unchanged,
+            RowChange::Added,
+            RowChange::Removed,
+            RowChange::Changed,
+        ]
+        .iter()
+        .map(|change| change.marker())
+        .collect();
+        assert_eq!(markers, [" ", "+", "-", "~"]);
+    }
+}
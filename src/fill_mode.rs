@@ -0,0 +1,64 @@
+/// How [`Table::normalize_rows`](crate::Table::normalize_rows) evens out rows whose
+/// length differs from the header's.
+///
+/// # Example
+/// ```
+/// use crabular::{FillMode, Table};
+///
+/// let mut table = Table::new()
+///     .header(["Name", "Age"])
+///     .row(["Kata"])
+///     .row(["Kelana", "28", "Jakarta"]);
+/// assert_eq!(table.normalize_rows(FillMode::Both), 2);
+/// assert!(table.rows().iter().all(|row| row.len() == 2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillMode {
+    /// Pad short rows with empty cells
+    PadEmpty,
+    /// Drop the cells of long rows past the last column, narrowing a cell that spans it
+    TruncateExtra,
+    /// Pad short rows and cut long ones
+    Both,
+}
+
+impl FillMode {
+    pub(crate) fn pads(self) -> bool {
+        matches!(self, FillMode::PadEmpty | FillMode::Both)
+    }
+
+    pub(crate) fn truncates(self) -> bool {
+        matches!(self, FillMode::TruncateExtra | FillMode::Both)
+    }
+}
+
+impl core::str::FromStr for FillMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pad" | "pad-empty" => Ok(FillMode::PadEmpty),
+            "truncate" | "truncate-extra" => Ok(FillMode::TruncateExtra),
+            "both" => Ok(FillMode::Both),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FillMode;
+
+    #[test]
+    fn from_str() {
+        let cases = [
+            ("pad", Ok(FillMode::PadEmpty)),
+            ("Truncate-Extra", Ok(FillMode::TruncateExtra)),
+            ("both", Ok(FillMode::Both)),
+            ("fill", Err(())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<FillMode>(), expected);
+        }
+    }
+}
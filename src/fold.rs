@@ -0,0 +1,88 @@
+use crate::alloc_prelude::*;
+
+/// Lowercases `text` and strips diacritics from Latin letters, turning `Ł`, `ó` and
+/// `e` followed by a combining accent into `l`, `o` and `e`. Ligatures and `ß` are
+/// spelled out (`æ` → `ae`, `ß` → `ss`). With the `unicode-normalization` feature,
+/// every precomposed letter is decomposed first, so accents on any script are removed.
+pub(crate) fn fold(text: &str) -> String {
+    #[cfg(feature = "unicode-normalization")]
+    let decomposed: String = unicode_normalization::UnicodeNormalization::nfd(text).collect();
+    #[cfg(feature = "unicode-normalization")]
+    let text = decomposed.as_str();
+
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if is_combining_mark(c) {
+            continue;
+        }
+        match base_letters(c) {
+            Some(letters) => folded.push_str(letters),
+            None => folded.push(c),
+        }
+    }
+    folded
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036f}'
+            | '\u{1ab0}'..='\u{1aff}'
+            | '\u{1dc0}'..='\u{1dff}'
+            | '\u{20d0}'..='\u{20ff}'
+            | '\u{fe20}'..='\u{fe2f}'
+    )
+}
+
+/// Returns the unaccented spelling of a lowercase Latin-1 or Latin Extended-A letter.
+fn base_letters(c: char) -> Option<&'static str> {
+    let letters = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ð' | 'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' | 'ĸ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fold::fold;
+
+    #[test]
+    fn folds_case_and_accents() {
+        let cases = [
+            ("Zoë", "zoe"),
+            ("ŁÓDŹ", "lodz"),
+            ("Ærøskøbing", "aeroskobing"),
+            ("Straße", "strasse"),
+            ("Cafe\u{301}", "cafe"),
+            ("São Paulo", "sao paulo"),
+            ("東京", "東京"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(fold(text), expected, "{text}");
+        }
+    }
+}
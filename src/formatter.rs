@@ -0,0 +1,332 @@
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+use crate::alloc_prelude::*;
+use crate::badge::Badges;
+use crate::number_format::NumberFormat;
+
+/// A function that turns stored cell content into the text that is displayed.
+pub type Formatter = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+#[cfg(feature = "std")]
+static GLOBAL: RwLock<FormatterRegistry> = RwLock::new(FormatterRegistry::new());
+
+/// Named cell formatters, so that configuration files and command-line flags can refer
+/// to a formatter by name instead of by code.
+///
+/// A formatter spec is a name, optionally followed by `:` and an argument. Specs are
+/// resolved against the table's registry first, then the global registry, then the
+/// built-in formatters:
+///
+/// | Spec | Output |
+/// |------|--------|
+/// | `bytes` | `1536` → `1.5 KiB` |
+/// | `duration` | seconds, `3725` → `1h 2m 5s` |
+/// | `mask`, `mask:N` | all but the last N (default 4) characters replaced by `*` |
+/// | `template:TEXT` | `TEXT` with every `{}` replaced by the content |
+/// | `badge` | `ok` → `[  OK  ]` in green, see [`Badges::status`] |
+/// | `number:OPTIONS` | `1234.5` → `$1,234.50` with `thousands,decimals=2,currency=$`, see [`NumberFormat`] |
+///
+/// Built-in formatters leave content they cannot parse unchanged.
+///
+/// # Example
+/// ```
+/// use crabular::{FormatterRegistry, Table, TableStyle};
+///
+/// let mut formatters = FormatterRegistry::new();
+/// formatters.register("yes_no", |value| {
+///     if value == "true" { "yes" } else { "no" }.to_string()
+/// });
+///
+/// let mut table = Table::new()
+///     .header(["File", "Size", "Cached"])
+///     .row(["a.bin", "2048", "true"]);
+/// table.set_style(TableStyle::Markdown);
+/// table.set_formatters(formatters);
+/// table.format_column(1, "bytes");
+/// table.format_column(2, "yes_no");
+///
+/// assert!(table.render().contains("| a.bin  | 2.0 KiB  | yes    |"));
+/// assert_eq!(table.rows()[0].cells()[1].content(), "2048");
+/// ```
+#[derive(Clone, Default)]
+pub struct FormatterRegistry {
+    formatters: BTreeMap<String, Formatter>,
+    badges: BTreeMap<String, Badges>,
+}
+
+impl FormatterRegistry {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            formatters: BTreeMap::new(),
+            badges: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `formatter` under `name`, replacing any formatter of that name.
+    pub fn register<F>(&mut self, name: &str, formatter: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.formatters
+            .insert(name.to_string(), Arc::new(formatter));
+        self.badges.remove(name);
+    }
+
+    /// Registers `badges` under `name`. The formatter shows the badge text, and the
+    /// renderer draws it in the badge's color.
+    pub fn register_badges(&mut self, name: &str, badges: Badges) {
+        let labels = badges.clone();
+        self.register(name, move |value| {
+            labels.label(value).unwrap_or_else(|| value.to_string())
+        });
+        self.badges.insert(name.to_string(), badges);
+    }
+
+    /// Registers `formatter` under `name` for every table in the process.
+    #[cfg(feature = "std")]
+    pub fn register_global<F>(name: &str, formatter: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        GLOBAL
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .register(name, formatter);
+    }
+
+    /// Returns the names registered in this registry, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.formatters.keys().map(String::as_str)
+    }
+
+    /// Resolves a formatter spec against this registry, the global registry and the
+    /// built-in formatters, in that order.
+    #[must_use]
+    pub fn resolve(&self, spec: &str) -> Option<Formatter> {
+        if let Some(formatter) = self.formatters.get(spec) {
+            return Some(Arc::clone(formatter));
+        }
+        #[cfg(feature = "std")]
+        {
+            let global = GLOBAL.read().unwrap_or_else(PoisonError::into_inner);
+            if let Some(formatter) = global.formatters.get(spec) {
+                return Some(Arc::clone(formatter));
+            }
+        }
+        builtin(spec)
+    }
+
+    /// Resolves the badges behind a formatter spec, in the same order as
+    /// [`resolve`](Self::resolve). Returns `None` for formatters that are not badges.
+    #[must_use]
+    pub fn resolve_badges(&self, spec: &str) -> Option<Badges> {
+        if self.formatters.contains_key(spec) {
+            return self.badges.get(spec).cloned();
+        }
+        #[cfg(feature = "std")]
+        {
+            let global = GLOBAL.read().unwrap_or_else(PoisonError::into_inner);
+            if global.formatters.contains_key(spec) {
+                return global.badges.get(spec).cloned();
+            }
+        }
+        (spec == "badge").then(Badges::status)
+    }
+}
+
+impl core::fmt::Debug for FormatterRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+fn builtin(spec: &str) -> Option<Formatter> {
+    let (name, argument) = match spec.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (spec, None),
+    };
+    match (name, argument) {
+        ("bytes", None) => Some(Arc::new(bytes)),
+        ("duration", None) => Some(Arc::new(duration)),
+        ("badge", None) => {
+            let badges = Badges::status();
+            Some(Arc::new(move |value: &str| {
+                badges.label(value).unwrap_or_else(|| value.to_string())
+            }))
+        }
+        ("mask", None) => Some(Arc::new(|value: &str| mask(value, 4))),
+        ("mask", Some(visible)) => {
+            let visible: usize = visible.parse().ok()?;
+            Some(Arc::new(move |value: &str| mask(value, visible)))
+        }
+        ("number", _) => {
+            let format: NumberFormat = spec.parse().ok()?;
+            Some(Arc::new(move |value: &str| format.apply(value)))
+        }
+        ("template", Some(template)) => {
+            let template = template.to_string();
+            Some(Arc::new(move |value: &str| template.replace("{}", value)))
+        }
+        _ => None,
+    }
+}
+
+fn bytes(value: &str) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let Ok(count) = value.trim().parse::<u64>() else {
+        return value.to_string();
+    };
+    if count < 1024 {
+        return format!("{count} B");
+    }
+    let mut divisor: u128 = 1024;
+    let mut unit = 0;
+    while unit + 1 < UNITS.len() && u128::from(count) >= divisor * 1024 {
+        divisor *= 1024;
+        unit += 1;
+    }
+    let tenths = (u128::from(count) * 10 + divisor / 2) / divisor;
+    format!("{}.{} {}", tenths / 10, tenths % 10, UNITS[unit])
+}
+
+fn duration(value: &str) -> String {
+    let Ok(seconds) = value.trim().parse::<u64>() else {
+        return value.to_string();
+    };
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds / 3600 % 24, "h"),
+        (seconds / 60 % 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let output: Vec<String> = parts
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{amount}{unit}"))
+        .collect();
+    if output.is_empty() {
+        "0s".to_string()
+    } else {
+        output.join(" ")
+    }
+}
+
+fn mask(value: &str, visible: usize) -> String {
+    let hidden = value.chars().count().saturating_sub(visible);
+    value
+        .chars()
+        .enumerate()
+        .map(|(index, c)| if index < hidden { '*' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Badges, Color, FormatterRegistry};
+
+    fn apply(spec: &str, value: &str) -> String {
+        FormatterRegistry::new().resolve(spec).unwrap()(value)
+    }
+
+    #[test]
+    fn bytes() {
+        let cases = [
+            ("0", "0 B"),
+            ("1023", "1023 B"),
+            ("1024", "1.0 KiB"),
+            ("1536", "1.5 KiB"),
+            ("1048576", "1.0 MiB"),
+            ("5368709120", "5.0 GiB"),
+            ("n/a", "n/a"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(apply("bytes", value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn duration() {
+        let cases = [
+            ("0", "0s"),
+            ("59", "59s"),
+            ("3725", "1h 2m 5s"),
+            ("90000", "1d 1h"),
+            ("soon", "soon"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(apply("duration", value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn mask_and_template() {
+        assert_eq!(apply("mask", "4111111111111111"), "************1111");
+        assert_eq!(apply("mask:2", "secret"), "****et");
+        assert_eq!(apply("mask:0", "abc"), "***");
+        assert_eq!(apply("template:{} ms", "12"), "12 ms");
+    }
+
+    #[test]
+    fn badges() {
+        assert_eq!(apply("badge", "warn"), "[ WARN ]");
+        assert_eq!(apply("badge", "pending"), "pending");
+
+        let mut registry = FormatterRegistry::new();
+        assert_eq!(
+            registry.resolve_badges("badge").unwrap().color("ok"),
+            Some(Color::Green)
+        );
+        assert!(registry.resolve_badges("bytes").is_none());
+
+        registry.register_badges("state", Badges::new().badge("up", "UP", Color::Cyan));
+        assert_eq!(registry.resolve("state").unwrap()("up"), "[ UP ]");
+        assert!(registry.resolve_badges("state").is_some());
+
+        // A plain formatter under the same name replaces the badges
+        registry.register("state", str::to_uppercase);
+        assert!(registry.resolve_badges("state").is_none());
+    }
+
+    #[test]
+    fn number() {
+        assert_eq!(apply("number:thousands", "1234567"), "1,234,567");
+        assert_eq!(apply("number:currency=$,decimals=2", "-12.5"), "-$12.50");
+        assert_eq!(apply("number:percent", "0.5"), "50%");
+        assert_eq!(apply("number", "n/a"), "n/a");
+    }
+
+    #[test]
+    fn unknown_specs() {
+        let registry = FormatterRegistry::new();
+        for spec in [
+            "nope",
+            "bytes:1",
+            "mask:x",
+            "template",
+            "number:decimals=-1",
+        ] {
+            assert!(registry.resolve(spec).is_none(), "{spec}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn local_registry_overrides_global_and_builtins() {
+        FormatterRegistry::register_global("formatter_test_global", |value| format!("<{value}>"));
+        let mut registry = FormatterRegistry::new();
+        registry.register("bytes", str::to_uppercase);
+
+        assert_eq!(registry.resolve("bytes").unwrap()("kb"), "KB");
+        assert_eq!(
+            registry.resolve("formatter_test_global").unwrap()("x"),
+            "<x>"
+        );
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["bytes"]);
+        assert_eq!(format!("{registry:?}"), "{\"bytes\"}");
+    }
+}
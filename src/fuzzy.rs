@@ -0,0 +1,68 @@
+use core::cmp::Reverse;
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+
+use crate::row::Row;
+
+/// Scores each row against `query`, returning the indices and scores of the rows that
+/// match, best first. Rows with equal scores keep their order.
+///
+/// The query uses fzf syntax: space-separated terms that must all match, with `'exact`,
+/// `^prefix`, `suffix$` and `!negated` terms. Terms match case-insensitively unless they
+/// contain an uppercase letter. Each row is matched as its cells joined by spaces.
+pub(crate) fn rank(rows: &[Row], query: &str) -> Vec<(usize, u32)> {
+    let pattern = Pattern::parse(query, CaseMatching::Smart, Normalization::Smart);
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buffer = Vec::new();
+    let mut text = String::new();
+    let mut ranked: Vec<(usize, u32)> = rows
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row)| {
+            text.clear();
+            for (position, cell) in row.cells().iter().enumerate() {
+                if position > 0 {
+                    text.push(' ');
+                }
+                text.push_str(cell.content());
+            }
+            pattern
+                .score(Utf32Str::new(&text, &mut buffer), &mut matcher)
+                .map(|score| (index, score))
+        })
+        .collect();
+    ranked.sort_by_key(|&(_, score)| Reverse(score));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Row;
+    use crate::fuzzy::rank;
+
+    #[test]
+    fn ranks_best_match_first() {
+        let rows: Vec<Row> = [
+            ["kubernetes", "cluster"],
+            ["kube-proxy", "daemon"],
+            ["karma", "tests"],
+            ["postgres", "database"],
+        ]
+        .into_iter()
+        .map(Row::from)
+        .collect();
+
+        let matches = rank(&rows, "kube");
+        let indices: Vec<usize> = matches.iter().map(|&(index, _)| index).collect();
+        assert_eq!(indices.len(), 2);
+        assert!(indices.contains(&0) && indices.contains(&1));
+        assert!(matches[0].1 >= matches[1].1);
+
+        // Terms may match across cells
+        assert_eq!(rank(&rows, "pgdb")[0].0, 3);
+        assert_eq!(rank(&rows, "!kube").len(), 2);
+        assert!(rank(&rows, "zzz").is_empty());
+        assert_eq!(rank(&rows, "").len(), 4);
+    }
+}
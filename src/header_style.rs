@@ -0,0 +1,155 @@
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::color::Color;
+
+/// Presentation applied to the header row at render time, leaving the stored
+/// header text untouched.
+///
+/// # Example
+/// ```
+/// use crabular::{Alignment, HeaderStyle, Table};
+///
+/// let mut table = Table::new().header(["name", "age"]).row(["Kata", "30"]);
+/// table.set_header_style(HeaderStyle::new().uppercase().align(Alignment::Center));
+/// assert!(table.render().contains("NAME"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HeaderStyle {
+    uppercase: bool,
+    bold: bool,
+    color: Option<Color>,
+    alignment: Option<Alignment>,
+    double_rule: bool,
+}
+
+impl HeaderStyle {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders header text in uppercase.
+    #[must_use]
+    pub fn uppercase(mut self) -> Self {
+        self.uppercase = true;
+        self
+    }
+
+    /// Renders header text in bold using ANSI escape codes.
+    #[must_use]
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Renders header text in the given color using ANSI escape codes.
+    #[must_use]
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Aligns header cells independently of the data column alignments.
+    #[must_use]
+    pub fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Draws the rule below the header with the style's double characters, such as
+    /// `╞═══╪═══╡` in the Modern style.
+    #[must_use]
+    pub fn double_rule(mut self) -> Self {
+        self.double_rule = true;
+        self
+    }
+
+    #[must_use]
+    pub fn is_uppercase(&self) -> bool {
+        self.uppercase
+    }
+
+    #[must_use]
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    #[must_use]
+    pub fn get_color(&self) -> Option<Color> {
+        self.color
+    }
+
+    #[must_use]
+    pub fn get_alignment(&self) -> Option<Alignment> {
+        self.alignment
+    }
+
+    #[must_use]
+    pub fn is_double_rule(&self) -> bool {
+        self.double_rule
+    }
+
+    /// Returns the ANSI escape sequence that starts the header emphasis,
+    /// or `None` when neither bold nor color is set.
+    pub(crate) fn sgr(self) -> Option<String> {
+        let mut params: Vec<String> = Vec::new();
+        if self.bold {
+            params.push("1".to_string());
+        }
+        if let Some(color) = self.color {
+            params.push(color.fg_code());
+        }
+        if params.is_empty() {
+            None
+        } else {
+            Some(format!("\x1b[{}m", params.join(";")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, Color, HeaderStyle};
+
+    #[test]
+    fn default_is_plain() {
+        let style = HeaderStyle::default();
+        assert!(!style.is_uppercase());
+        assert!(!style.is_bold());
+        assert_eq!(style.get_color(), None);
+        assert_eq!(style.get_alignment(), None);
+        assert!(!style.is_double_rule());
+        assert_eq!(style.sgr(), None);
+    }
+
+    #[test]
+    fn builder_methods() {
+        let style = HeaderStyle::new()
+            .uppercase()
+            .bold()
+            .color(Color::Cyan)
+            .align(Alignment::Center)
+            .double_rule();
+        assert!(style.is_uppercase());
+        assert!(style.is_bold());
+        assert_eq!(style.get_color(), Some(Color::Cyan));
+        assert_eq!(style.get_alignment(), Some(Alignment::Center));
+        assert!(style.is_double_rule());
+    }
+
+    #[test]
+    fn sgr() {
+        let cases = [
+            (HeaderStyle::new().bold(), Some("\x1b[1m")),
+            (HeaderStyle::new().color(Color::Red), Some("\x1b[31m")),
+            (
+                HeaderStyle::new().bold().color(Color::Fixed(8)),
+                Some("\x1b[1;38;5;8m"),
+            ),
+            (HeaderStyle::new().uppercase(), None),
+        ];
+        for (style, expected) in cases {
+            assert_eq!(style.sgr().as_deref(), expected);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::alloc_prelude::*;
+
+/// Appends `text` to `output` with the HTML special characters escaped.
+pub(crate) fn write_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::html::write_escaped;
+
+    #[test]
+    fn escapes() {
+        let cases = [
+            ("plain", "plain"),
+            ("a < b && c > d", "a &lt; b &amp;&amp; c &gt; d"),
+            ("say \"hi\" it's", "say &quot;hi&quot; it&#39;s"),
+            ("日本語", "日本語"),
+        ];
+        for (text, expected) in cases {
+            let mut output = String::new();
+            write_escaped(&mut output, text);
+            assert_eq!(output, expected);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::HashMap;
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::cell::Cell;
+use crate::row::Row;
+use crate::schema::SchemaError;
+use crate::table::Table;
+
+/// Which rows [`Table::join`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinKind {
+    /// Only rows with a match in both tables (default)
+    #[default]
+    Inner,
+    /// Every row of the left table, with blank cells where the right table has no match
+    Left,
+}
+
+impl core::str::FromStr for JoinKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inner" => Ok(JoinKind::Inner),
+            "left" | "left-outer" => Ok(JoinKind::Left),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Table {
+    /// Combines this table with `other` on equal keys in `left_col` and `right_col`.
+    ///
+    /// Each row is followed by the cells of every matching row of `other`, without its
+    /// key column, so a key matching several rows repeats the left row. The result has
+    /// this table's headers followed by the other table's, and keeps this table's style
+    /// and column settings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{JoinKind, Table};
+    /// let users = Table::new()
+    ///     .header(["id", "name"])
+    ///     .row(["1", "Kata"])
+    ///     .row(["2", "Kelana"]);
+    /// let scores = Table::new().header(["user", "score"]).row(["1", "95"]);
+    ///
+    /// let joined = users.join(&scores, 0, 0, JoinKind::Left);
+    /// assert_eq!(joined.headers().map(|headers| headers.len()), Some(3));
+    /// assert_eq!(joined.rows()[0].cells()[2].content(), "95");
+    /// assert_eq!(joined.rows()[1].cells()[2].content(), "");
+    /// assert_eq!(users.join(&scores, 0, 0, JoinKind::Inner).len(), 1);
+    /// ```
+    #[must_use]
+    pub fn join(&self, other: &Table, left_col: usize, right_col: usize, kind: JoinKind) -> Table {
+        let left_width = self.cols();
+        let right_width = other.cols().saturating_sub(1);
+
+        let mut matches: HashMap<&str, Vec<&Row>> = HashMap::new();
+        for row in other.rows() {
+            matches.entry(key(row, right_col)).or_default().push(row);
+        }
+
+        let mut joined = self.filtered(|_| false);
+        if self.headers().is_some() || other.headers().is_some() {
+            joined.set_headers(combine(
+                self.headers(),
+                left_width,
+                other.headers(),
+                right_col,
+                right_width,
+            ));
+        }
+        for row in self.rows() {
+            match matches.get(key(row, left_col)) {
+                Some(rows) => {
+                    for other_row in rows {
+                        joined.add_row(combine(
+                            Some(row),
+                            left_width,
+                            Some(other_row),
+                            right_col,
+                            right_width,
+                        ));
+                    }
+                }
+                None if kind == JoinKind::Left => {
+                    joined.add_row(combine(Some(row), left_width, None, right_col, right_width));
+                }
+                None => {}
+            }
+        }
+        joined
+    }
+
+    /// Appends the rows of `other` below this table's rows, returning the result with
+    /// this table's headers and settings.
+    ///
+    /// # Errors
+    /// Returns a [`SchemaError`] if both tables have headers and they differ, so
+    /// snapshots of differently shaped inputs are not silently mixed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let january = Table::new().header(["day", "sales"]).row(["1", "10"]);
+    /// let february = Table::new().header(["day", "sales"]).row(["1", "12"]);
+    /// let both = january.concat(&february)?;
+    /// assert_eq!(both.len(), 2);
+    ///
+    /// let other = Table::new().header(["day", "visits"]);
+    /// assert!(january.concat(&other).is_err());
+    /// # Ok::<(), crabular::SchemaError>(())
+    /// ```
+    pub fn concat(&self, other: &Table) -> Result<Table, SchemaError> {
+        if let (Some(headers), Some(_)) = (self.headers(), other.headers()) {
+            let names: Vec<&str> = headers.cells().iter().map(Cell::content).collect();
+            other.expect_schema(&names)?;
+        }
+        let mut combined = self.filtered(|_| true);
+        for row in other.rows() {
+            combined.add_row(row.clone());
+        }
+        Ok(combined)
+    }
+}
+
+fn key(row: &Row, column: usize) -> &str {
+    row.cells().get(column).map_or("", Cell::content)
+}
+
+/// Returns `left` padded to `left_width` cells followed by `right` without its key
+/// column, padded to `right_width` cells.
+fn combine(
+    left: Option<&Row>,
+    left_width: usize,
+    right: Option<&Row>,
+    right_col: usize,
+    right_width: usize,
+) -> Row {
+    let mut row = left.cloned().unwrap_or_default();
+    while row.len() < left_width {
+        row.push(Cell::new("", Alignment::Left));
+    }
+    let mut added = 0;
+    if let Some(right) = right {
+        for (index, cell) in right.cells().iter().enumerate() {
+            if index != right_col {
+                row.push(cell.clone());
+                added += 1;
+            }
+        }
+    }
+    for _ in added..right_width {
+        row.push(Cell::new("", Alignment::Left));
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cell, JoinKind, Row, Table};
+
+    fn contents(table: &Table) -> Vec<Vec<&str>> {
+        table
+            .rows()
+            .iter()
+            .map(|row| row.cells().iter().map(Cell::content).collect())
+            .collect()
+    }
+
+    #[test]
+    fn inner_and_left_joins() {
+        let orders = Table::new()
+            .header(["order", "customer"])
+            .row(["A1", "c1"])
+            .row(["A2", "c2"])
+            .row(["A3", "c1"]);
+        let customers = Table::new()
+            .header(["city", "id", "name"])
+            .row(["Berlin", "c1", "Kata"])
+            .row(["Paris", "c3", "Kelana"]);
+
+        let inner = orders.join(&customers, 1, 1, JoinKind::Inner);
+        assert_eq!(
+            inner.headers().map(Row::len),
+            Some(4),
+            "the right key column is dropped"
+        );
+        assert_eq!(
+            contents(&inner),
+            [
+                ["A1", "c1", "Berlin", "Kata"],
+                ["A3", "c1", "Berlin", "Kata"]
+            ]
+        );
+
+        let left = orders.join(&customers, 1, 1, JoinKind::Left);
+        assert_eq!(contents(&left)[1], ["A2", "c2", "", ""]);
+        assert_eq!(left.len(), 3);
+    }
+
+    #[test]
+    fn join_repeats_rows_for_every_match() {
+        let left = Table::new().row(["k", "left"]);
+        let right = Table::new().row(["k", "1"]).row(["k", "2"]).row(["x", "3"]);
+        assert_eq!(
+            contents(&left.join(&right, 0, 0, JoinKind::Inner)),
+            [["k", "left", "1"], ["k", "left", "2"]]
+        );
+    }
+
+    #[test]
+    fn join_kind_from_str() {
+        assert_eq!("Inner".parse(), Ok(JoinKind::Inner));
+        assert_eq!("left".parse(), Ok(JoinKind::Left));
+        assert_eq!("outer".parse::<JoinKind>(), Err(()));
+    }
+
+    #[test]
+    fn concat_checks_headers() {
+        let a = Table::new().header(["x"]).row(["1"]);
+        let b = Table::new().header(["x"]).row(["2"]);
+        let headerless = Table::new().row(["3"]);
+        let combined = a.concat(&b).unwrap().concat(&headerless).unwrap();
+        assert_eq!(contents(&combined), [["1"], ["2"], ["3"]]);
+
+        let Err(error) = a.concat(&Table::new().header(["y"])) else {
+            panic!("headers differ");
+        };
+        assert_eq!(error.missing(), ["x"]);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::alloc_prelude::*;
+
+/// Appends `value` to `output` as a quoted JSON string.
+pub(crate) fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                use core::fmt::Write;
+                let _ = write!(output, "\\u{:04x}", u32::from(c));
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json::write_string;
+
+    #[test]
+    fn escapes() {
+        let cases = [
+            ("plain", r#""plain""#),
+            ("say \"hi\"", r#""say \"hi\"""#),
+            ("back\\slash", r#""back\\slash""#),
+            ("two\nlines\r\t", r#""two\nlines\r\t""#),
+            ("\u{1b}[1m", r#""\u001b[1m""#),
+            ("日本語", "\"日本語\""),
+        ];
+        for (value, expected) in cases {
+            let mut output = String::new();
+            write_string(&mut output, value);
+            assert_eq!(output, expected);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use alloc::boxed::Box;
+
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::ansi;
+use crate::split::SplitOptions;
+use crate::table::Table;
+use crate::text;
+
+/// Width of a grid whose [`Grid::width`] is not set, the same 120 characters that
+/// proportional column widths are laid out in.
+const DEFAULT_WIDTH: usize = 120;
+
+/// One cell of a [`Grid`]: a table or a block of text, with an optional title line.
+///
+/// Tables are rendered when the grid is, at the width of their cell: a table that is
+/// too wide is stacked into chunks of columns with [`Table::render_split`]. Lines that
+/// still do not fit are cut.
+pub struct Panel {
+    title: Option<String>,
+    content: Content,
+}
+
+enum Content {
+    Table(Box<Table>),
+    Text(String),
+}
+
+impl Panel {
+    /// A panel showing `text`, which may span several lines and contain colors.
+    #[must_use]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            title: None,
+            content: Content::Text(text.into()),
+        }
+    }
+
+    /// A panel showing `table`.
+    #[must_use]
+    pub fn table(table: Table) -> Self {
+        Self {
+            title: None,
+            content: Content::Table(Box::new(table)),
+        }
+    }
+
+    /// Shows `title` on the line above the content.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Returns the panel's lines, each exactly `width` columns wide.
+    fn lines(&self, width: usize) -> Vec<String> {
+        let rendered = match &self.content {
+            Content::Text(text) => text.clone(),
+            Content::Table(table) => {
+                let output = table.render();
+                if text::ansi_width(&output) > width {
+                    table.render_split(&SplitOptions::new(width))
+                } else {
+                    output
+                }
+            }
+        };
+        self.title
+            .iter()
+            .map(String::as_str)
+            .chain(rendered.lines())
+            .map(|line| {
+                let mut line = text::ansi_fit(line, width, Alignment::Left, None);
+                if line.contains('\x1b') {
+                    line.push_str(ansi::RESET);
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+impl From<Table> for Panel {
+    fn from(table: Table) -> Self {
+        Panel::table(table)
+    }
+}
+
+impl From<&str> for Panel {
+    fn from(text: &str) -> Self {
+        Panel::text(text)
+    }
+}
+
+impl From<String> for Panel {
+    fn from(text: String) -> Self {
+        Panel::text(text)
+    }
+}
+
+/// Arranges tables and text panels into a grid of rows and columns, rendering a whole
+/// terminal dashboard as one string.
+///
+/// The grid's width is shared out evenly between its columns after the gaps, the
+/// leftmost columns taking any remainder. Panels are placed left to right, then top to
+/// bottom; panels beyond `rows * cols` start new rows. Each row is as tall as its
+/// tallest panel.
+///
+/// # Example
+/// ```
+/// use crabular::{Grid, Panel, Table, TableStyle};
+///
+/// let mut users = Table::new().header(["User", "Role"]).row(["Kata", "admin"]);
+/// users.set_style(TableStyle::Modern);
+/// let dashboard = Grid::new(1, 2)
+///     .width(40)
+///     .gap(2)
+///     .place(users)
+///     .place(Panel::text("3 jobs running\n1 failed").title("Jobs"))
+///     .render();
+/// assert_eq!(
+///     dashboard,
+///     "\
+/// ┌───────┬───────┐    Jobs
+/// │ User  │ Role  │    3 jobs running
+/// ├───────┼───────┤    1 failed
+/// │ Kata  │ admin │
+/// └───────┴───────┘
+/// "
+/// );
+/// ```
+pub struct Grid {
+    rows: usize,
+    cols: usize,
+    width: usize,
+    gap: usize,
+    row_gap: usize,
+    panels: Vec<Panel>,
+}
+
+impl Grid {
+    /// Creates an empty grid of `rows` by `cols` cells, 120 characters wide with two
+    /// spaces between columns and a blank line between rows.
+    #[must_use]
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols: cols.max(1),
+            width: DEFAULT_WIDTH,
+            gap: 2,
+            row_gap: 1,
+            panels: Vec::new(),
+        }
+    }
+
+    /// Sets the total width of the rendered grid.
+    #[must_use]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the number of spaces between columns.
+    #[must_use]
+    pub fn gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets the number of blank lines between rows.
+    #[must_use]
+    pub fn row_gap(mut self, row_gap: usize) -> Self {
+        self.row_gap = row_gap;
+        self
+    }
+
+    /// Places a table or panel in the next free cell.
+    #[must_use]
+    pub fn place(mut self, panel: impl Into<Panel>) -> Self {
+        self.panels.push(panel.into());
+        self
+    }
+
+    /// Returns the width of each column: the width after the gaps, shared out evenly.
+    fn column_widths(&self) -> Vec<usize> {
+        let gaps = self.gap * (self.cols - 1);
+        let available = self.width.saturating_sub(gaps);
+        let share = available / self.cols;
+        let remainder = available % self.cols;
+        (0..self.cols)
+            .map(|column| share + usize::from(column < remainder))
+            .collect()
+    }
+
+    /// Renders the grid. Trailing spaces are removed from every line, and empty cells
+    /// of the last rows are left blank.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let widths = self.column_widths();
+        let rows = self.rows.max(self.panels.len().div_ceil(self.cols));
+        let gap = " ".repeat(self.gap);
+        let mut output = String::new();
+        for row in 0..rows {
+            if row > 0 {
+                output.push_str(&"\n".repeat(self.row_gap));
+            }
+            let cells: Vec<Vec<String>> = widths
+                .iter()
+                .enumerate()
+                .map(|(column, &width)| {
+                    self.panels
+                        .get(row * self.cols + column)
+                        .map_or_else(Vec::new, |panel| panel.lines(width))
+                })
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or(0);
+            for line in 0..height {
+                let start = output.len();
+                for (column, cell) in cells.iter().enumerate() {
+                    if column > 0 {
+                        output.push_str(&gap);
+                    }
+                    match cell.get(line) {
+                        Some(text) => output.push_str(text),
+                        None => output.push_str(&" ".repeat(widths[column])),
+                    }
+                }
+                let end = start + output[start..].trim_end_matches(' ').len();
+                output.truncate(end);
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Panel, Table, TableStyle};
+
+    #[test]
+    fn shares_width_between_columns() {
+        let grid = Grid::new(1, 3).width(20).gap(1);
+        assert_eq!(grid.column_widths(), [6, 6, 6]);
+        let grid = Grid::new(1, 3).width(21).gap(1);
+        assert_eq!(grid.column_widths(), [7, 6, 6]);
+        assert_eq!(Grid::new(1, 2).width(1).column_widths(), [0, 0]);
+    }
+
+    #[test]
+    fn places_panels_in_rows() {
+        let output = Grid::new(1, 2)
+            .width(9)
+            .gap(1)
+            .place("a\nb")
+            .place("c")
+            .place(Panel::text("too long").title("T"))
+            .render();
+        assert_eq!(output, "a    c\nb\n\nT\ntoo\n");
+    }
+
+    #[test]
+    fn splits_wide_tables() {
+        let mut table = Table::new()
+            .header(["Name", "Q1", "Q2"])
+            .row(["Kata", "10", "20"]);
+        table.set_style(TableStyle::Compact);
+        let output = Grid::new(1, 1).width(10).place(table).render();
+        assert!(output.lines().all(|line| line.chars().count() <= 10));
+        assert!(output.contains("Q1"));
+        assert!(output.contains("Q2"));
+    }
+}
@@ -1,21 +1,118 @@
 #![doc = include_str!("../README.md")]
+// Unit tests link `std` for the test harness and the standard prelude; the library
+// itself is still checked against `alloc` alone by the plain `no_std` build.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+/// The `alloc` items that the standard prelude provides, so modules build the same
+/// with and without `std`.
+mod alloc_prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
+
+// Hash maps need `std` for their random state, so `no_std` builds use ordered maps.
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+pub mod aggregate;
 pub mod alignment;
+mod ansi;
+pub mod badge;
+pub mod border_visibility;
 pub mod builder;
 pub mod cell;
+pub mod color;
+pub mod column_type;
 pub mod constraint;
+pub mod csv;
+pub mod diff;
+pub mod fill_mode;
+mod fold;
+pub mod formatter;
+#[cfg(feature = "fuzzy")]
+mod fuzzy;
+pub mod header_style;
+mod html;
+pub mod join;
+mod json;
+pub mod layout;
+mod link;
+#[cfg(feature = "terminal")]
+pub mod live;
+pub mod margin;
+pub mod messages;
+#[cfg(feature = "unicode-normalization")]
+pub mod normalization;
+pub mod number_format;
 pub mod padding;
+pub mod page;
+mod parallel;
+pub mod perf;
+pub mod prelude;
+pub mod provider;
+pub mod render_options;
+pub mod render_report;
 pub mod row;
+pub mod schema;
+mod search;
+pub mod spec;
+pub mod split;
+pub mod stream;
 pub mod style;
 pub mod table;
+pub mod text;
+pub mod text_transform;
+pub mod theme;
+pub mod validation;
 pub mod vertical_alignment;
+pub mod wrap_policy;
 
+pub use aggregate::Aggregate;
 pub use alignment::Alignment;
+pub use badge::Badges;
+pub use border_visibility::BorderVisibility;
 pub use builder::TableBuilder;
-pub use cell::Cell;
+pub use cell::{Cell, IntoCell};
+pub use color::Color;
+pub use column_type::ColumnType;
 pub use constraint::WidthConstraint;
-pub use padding::Padding;
+pub use csv::{CsvOptions, Quoting};
+pub use diff::{DiffLayout, DiffTable, RowChange};
+pub use fill_mode::FillMode;
+pub use formatter::{Formatter, FormatterRegistry};
+pub use header_style::HeaderStyle;
+pub use join::JoinKind;
+pub use layout::{Grid, Panel};
+#[cfg(feature = "terminal")]
+pub use live::LiveTable;
+pub use margin::Margin;
+pub use messages::Messages;
+#[cfg(feature = "unicode-normalization")]
+pub use normalization::Normalization;
+pub use number_format::NumberFormat;
+pub use padding::{Padding, VerticalPadding};
+pub use page::{Page, Pages};
+#[cfg(feature = "instrumentation")]
+pub use perf::RenderStats;
+pub use provider::RowProvider;
+pub use render_options::{ColorMode, MarkdownMode, Newline, NoteMode, RenderOptions};
+pub use render_report::RenderReport;
 pub use row::Row;
+pub use schema::SchemaError;
+pub use spec::{AggregateSpec, ColumnRef, ColumnSpec, FilterSpec, SortSpec, SpecError, TableSpec};
+pub use split::SplitOptions;
+pub use stream::TableStream;
 pub use style::TableStyle;
 pub use table::Table;
+pub use text_transform::TextTransform;
+pub use theme::Theme;
+pub use validation::TableError;
 pub use vertical_alignment::VerticalAlignment;
+pub use wrap_policy::WrapPolicy;
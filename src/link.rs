@@ -0,0 +1,55 @@
+use crate::alloc_prelude::*;
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Fills a link template such as `https://example.com/issues/{value}`, replacing each
+/// `{value}` with `value` percent-encoded so spaces and reserved characters stay inside
+/// the URL.
+pub(crate) fn expand(template: &str, value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push('%');
+            encoded.push(char::from(HEX[usize::from(byte >> 4)]));
+            encoded.push(char::from(HEX[usize::from(byte & 0xf)]));
+        }
+    }
+    template.replace("{value}", &encoded)
+}
+
+/// Wraps `text` in an OSC 8 hyperlink to `url`, which terminals that support it show
+/// as clickable and others ignore.
+pub(crate) fn osc8(output: &mut String, url: &str, text: &str) {
+    output.push_str("\x1b]8;;");
+    output.push_str(url);
+    output.push_str("\x1b\\");
+    output.push_str(text);
+    output.push_str("\x1b]8;;\x1b\\");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::link::{expand, osc8};
+
+    #[test]
+    fn expands_and_encodes() {
+        let cases = [
+            ("https://issues/{value}", "GH-12", "https://issues/GH-12"),
+            ("https://x/?q={value}", "a b&c", "https://x/?q=a%20b%26c"),
+            ("https://x/{value}/{value}", "ü", "https://x/%C3%BC/%C3%BC"),
+            ("https://x/static", "1", "https://x/static"),
+        ];
+        for (template, value, expected) in cases {
+            assert_eq!(expand(template, value), expected);
+        }
+    }
+
+    #[test]
+    fn wraps_in_osc8() {
+        let mut output = String::new();
+        osc8(&mut output, "https://x/1", "1");
+        assert_eq!(output, "\x1b]8;;https://x/1\x1b\\1\x1b]8;;\x1b\\");
+    }
+}
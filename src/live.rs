@@ -0,0 +1,167 @@
+use core::fmt::Write as _;
+use std::io::{self, Stdout, Write};
+
+use crate::table::Table;
+
+/// Redraws a table in place in the terminal, writing only the lines that changed since
+/// the last frame, so a table refreshed several times a second does not flicker.
+///
+/// Each update moves the cursor up to the changed lines with ANSI escape sequences,
+/// rewrites them and clears what is left of the old frame, leaving the cursor on the
+/// line below the table. Output written between updates would be overwritten; call
+/// [`forget`](Self::forget) after it to start the next frame below.
+///
+/// # Example
+/// ```
+/// use crabular::{LiveTable, Table};
+///
+/// let progress = |percent: &str| Table::new().header(["Job", "Progress"]).row(["backup", percent]);
+/// let mut live = LiveTable::with_writer(Vec::new());
+/// live.update(&progress("10%"))?;
+/// live.update(&progress("95%"))?;
+/// let output = String::from_utf8(live.into_inner()).unwrap();
+/// // The second frame only rewrites the row, two lines above the cursor
+/// assert!(output.ends_with("\x1b[2A\r| backup  | 95%      |\x1b[K\n\x1b[1B"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct LiveTable<W: Write = Stdout> {
+    output: W,
+    /// Lines of the last frame written.
+    lines: Vec<String>,
+}
+
+impl LiveTable {
+    /// Creates a live table drawing to stdout.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_writer(io::stdout())
+    }
+}
+
+impl Default for LiveTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> LiveTable<W> {
+    /// Creates a live table drawing to `output`.
+    pub fn with_writer(output: W) -> Self {
+        Self {
+            output,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Renders `table` and draws it over the last frame.
+    ///
+    /// # Errors
+    /// Returns the error of writing to or flushing the output.
+    pub fn update(&mut self, table: &Table) -> io::Result<()> {
+        self.update_text(&table.render())
+    }
+
+    /// Draws `frame`, any already rendered text, over the last frame.
+    ///
+    /// # Errors
+    /// Returns the error of writing to or flushing the output.
+    pub fn update_text(&mut self, frame: &str) -> io::Result<()> {
+        let next: Vec<&str> = frame.lines().collect();
+        let update = frame_update(&self.lines, &next);
+        self.output.write_all(update.as_bytes())?;
+        self.output.flush()?;
+        self.lines = next.into_iter().map(str::to_string).collect();
+        Ok(())
+    }
+
+    /// Forgets the last frame, so the next update is drawn in full below the cursor.
+    pub fn forget(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Returns the output, such as the buffer given to [`with_writer`](Self::with_writer).
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+/// Returns the escape sequences and text that turn the `previous` frame on screen into
+/// `next`, with the cursor starting and ending at the start of the line below the frame.
+fn frame_update(previous: &[String], next: &[&str]) -> String {
+    let mut output = String::new();
+    let mut row = previous.len();
+    let move_to = |output: &mut String, row: &mut usize, target: usize| {
+        if target < *row {
+            let _ = write!(output, "\x1b[{}A", *row - target);
+        } else if target > *row {
+            let _ = write!(output, "\x1b[{}B", target - *row);
+        }
+        *row = target;
+    };
+
+    for (index, line) in next.iter().enumerate() {
+        if previous.get(index).is_some_and(|old| old == line) {
+            continue;
+        }
+        move_to(&mut output, &mut row, index);
+        output.push('\r');
+        output.push_str(line);
+        // Erases what is left of a longer old line
+        output.push_str("\x1b[K\n");
+        row = index + 1;
+    }
+    if next.len() < previous.len() {
+        move_to(&mut output, &mut row, next.len());
+        // Erases the old lines below the shorter frame
+        output.push_str("\x1b[J");
+    }
+    move_to(&mut output, &mut row, next.len());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::live::frame_update;
+    use crate::{LiveTable, Table};
+
+    fn lines(frame: &[&str]) -> Vec<String> {
+        frame.iter().map(|line| (*line).to_string()).collect()
+    }
+
+    #[test]
+    fn rewrites_changed_lines() {
+        let cases: [(&[&str], &[&str], &str); 5] = [
+            (&[], &["a", "b"], "\ra\x1b[K\n\rb\x1b[K\n"),
+            (&["a", "b"], &["a", "b"], ""),
+            (
+                &["a", "b", "c"],
+                &["a", "x", "c"],
+                "\x1b[2A\rx\x1b[K\n\x1b[1B",
+            ),
+            (&["a", "b"], &["a", "b", "c"], "\rc\x1b[K\n"),
+            (&["a", "b", "c"], &["x"], "\x1b[3A\rx\x1b[K\n\x1b[J"),
+        ];
+        for (previous, next, expected) in cases {
+            assert_eq!(
+                frame_update(&lines(previous), next),
+                expected,
+                "{previous:?} -> {next:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn live_table_remembers_frames() {
+        let mut live = LiveTable::with_writer(Vec::new());
+        let table = Table::new().header(["A"]).row(["1"]);
+        live.update(&table).unwrap();
+        let first = live.output.len();
+        live.update(&table).unwrap();
+        assert_eq!(live.output.len(), first);
+
+        live.forget();
+        live.update_text("done\n").unwrap();
+        let output = String::from_utf8(live.into_inner()).unwrap();
+        assert!(output.ends_with("\rdone\x1b[K\n"));
+    }
+}
@@ -0,0 +1,43 @@
+/// Blank space around every rendered line of a table, outside its borders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Margin {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Margin {
+    #[must_use]
+    pub const fn new(left: usize, right: usize) -> Self {
+        Self { left, right }
+    }
+
+    #[must_use]
+    pub const fn uniform(margin: usize) -> Self {
+        Self {
+            left: margin,
+            right: margin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Margin;
+
+    #[test]
+    fn new() {
+        let margin = Margin::new(2, 1);
+        assert_eq!(margin.left, 2);
+        assert_eq!(margin.right, 1);
+    }
+
+    #[test]
+    fn uniform() {
+        assert_eq!(Margin::uniform(3), Margin::new(3, 3));
+    }
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(Margin::default(), Margin::new(0, 0));
+    }
+}
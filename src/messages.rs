@@ -0,0 +1,191 @@
+use crate::alloc_prelude::*;
+
+/// Text the renderer adds to a table, so tools can show it in the user's language.
+///
+/// Templates use `{}` for the number of hidden or collapsed rows. The one/other pair covers
+/// languages with two plural forms; for others, pass the same neutral phrasing twice.
+///
+/// # Example
+/// ```
+/// use crabular::{Messages, Table};
+///
+/// let mut table = Table::new().header(["Nama"]).row(["Kelana"]).row(["Kata"]);
+/// table.set_max_rows(Some(1));
+/// table.set_messages(
+///     Messages::new()
+///         .more_rows("… ({} baris lagi)", "… ({} baris lagi)")
+///         .no_data("tidak ada data"),
+/// );
+/// assert!(table.render().contains("… (1 baris lagi)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    more_rows_one: String,
+    more_rows_other: String,
+    subtotal: String,
+    total: String,
+    no_data: Option<String>,
+    record_field: String,
+    record_value: String,
+    collapsed_group_one: String,
+    collapsed_group_other: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            more_rows_one: "… ({} more row)".to_string(),
+            more_rows_other: "… ({} more rows)".to_string(),
+            subtotal: "Subtotal".to_string(),
+            total: "Total".to_string(),
+            no_data: None,
+            record_field: "Field".to_string(),
+            record_value: "Value".to_string(),
+            collapsed_group_one: "▶ {group} ({} row)".to_string(),
+            collapsed_group_other: "▶ {group} ({} rows)".to_string(),
+        }
+    }
+}
+
+impl Messages {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the line shown below rows cut off by `max_rows`, for one hidden row and
+    /// for any other number.
+    #[must_use]
+    pub fn more_rows(mut self, one: &str, other: &str) -> Self {
+        self.more_rows_one = one.to_string();
+        self.more_rows_other = other.to_string();
+        self
+    }
+
+    /// Sets the label of subtotal rows, `Subtotal` by default.
+    #[must_use]
+    pub fn subtotal(mut self, label: &str) -> Self {
+        self.subtotal = label.to_string();
+        self
+    }
+
+    /// Sets the label of the grand-total row, `Total` by default.
+    #[must_use]
+    pub fn total(mut self, label: &str) -> Self {
+        self.total = label.to_string();
+        self
+    }
+
+    /// Shows `text` in a row spanning all columns when a table with headers has no
+    /// rows. By default such a table renders only its headers.
+    #[must_use]
+    pub fn no_data(mut self, text: &str) -> Self {
+        self.no_data = Some(text.to_string());
+        self
+    }
+
+    /// Sets the headers of [`Table::render_record`](crate::Table::render_record),
+    /// `Field` and `Value` by default. Two empty headers leave the header row out.
+    #[must_use]
+    pub fn record(mut self, field: &str, value: &str) -> Self {
+        self.record_field = field.to_string();
+        self.record_value = value.to_string();
+        self
+    }
+
+    /// Sets the line shown in place of a collapsed group, for one row and for any other
+    /// number. `{group}` is replaced by the group's key and `{}` by its row count.
+    #[must_use]
+    pub fn collapsed_group(mut self, one: &str, other: &str) -> Self {
+        self.collapsed_group_one = one.to_string();
+        self.collapsed_group_other = other.to_string();
+        self
+    }
+
+    /// Returns the line announcing `hidden` rows cut off by `max_rows`.
+    #[must_use]
+    pub fn format_more_rows(&self, hidden: usize) -> String {
+        let template = if hidden == 1 {
+            &self.more_rows_one
+        } else {
+            &self.more_rows_other
+        };
+        template.replace("{}", &hidden.to_string())
+    }
+
+    /// Returns the line shown in place of the collapsed group `group` of `rows` rows.
+    #[must_use]
+    pub fn format_collapsed_group(&self, group: &str, rows: usize) -> String {
+        let template = if rows == 1 {
+            &self.collapsed_group_one
+        } else {
+            &self.collapsed_group_other
+        };
+        template
+            .replace("{}", &rows.to_string())
+            .replace("{group}", group)
+    }
+
+    #[must_use]
+    pub fn get_subtotal(&self) -> &str {
+        &self.subtotal
+    }
+
+    #[must_use]
+    pub fn get_total(&self) -> &str {
+        &self.total
+    }
+
+    #[must_use]
+    pub fn get_no_data(&self) -> Option<&str> {
+        self.no_data.as_deref()
+    }
+
+    #[must_use]
+    pub fn get_record_field(&self) -> &str {
+        &self.record_field
+    }
+
+    #[must_use]
+    pub fn get_record_value(&self) -> &str {
+        &self.record_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Messages;
+
+    #[test]
+    fn defaults_are_english() {
+        let messages = Messages::new();
+        assert_eq!(messages.format_more_rows(1), "… (1 more row)");
+        assert_eq!(messages.format_more_rows(1234), "… (1234 more rows)");
+        assert_eq!(messages.format_collapsed_group("EU", 1), "▶ EU (1 row)");
+        assert_eq!(messages.format_collapsed_group("{}", 12), "▶ {} (12 rows)");
+        assert_eq!(messages.get_subtotal(), "Subtotal");
+        assert_eq!(messages.get_total(), "Total");
+        assert_eq!(messages.get_no_data(), None);
+        assert_eq!(messages.get_record_field(), "Field");
+        assert_eq!(messages.get_record_value(), "Value");
+    }
+
+    #[test]
+    fn overrides() {
+        let messages = Messages::new()
+            .more_rows("+{} Zeile", "+{} Zeilen")
+            .subtotal("Zwischensumme")
+            .total("Summe")
+            .no_data("keine Daten")
+            .record("Feld", "Wert")
+            .collapsed_group("▸ {group}: {} Zeile", "▸ {group}: {} Zeilen");
+        assert_eq!(messages.format_more_rows(1), "+1 Zeile");
+        assert_eq!(messages.format_more_rows(0), "+0 Zeilen");
+        assert_eq!(messages.get_subtotal(), "Zwischensumme");
+        assert_eq!(messages.get_total(), "Summe");
+        assert_eq!(messages.get_no_data(), Some("keine Daten"));
+        assert_eq!(messages.get_record_field(), "Feld");
+        assert_eq!(messages.get_record_value(), "Wert");
+        assert_eq!(messages.format_collapsed_group("EU", 3), "▸ EU: 3 Zeilen");
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::borrow::Cow;
+
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick, is_nfkc_quick};
+
+/// Unicode normalization applied to cell content before it is compared by
+/// [`sort`](crate::Table::sort), [`sort_desc`](crate::Table::sort_desc),
+/// [`filter_eq`](crate::Table::filter_eq) and [`filter_has`](crate::Table::filter_has).
+///
+/// Stored and rendered content is never changed.
+///
+/// # Example
+/// ```
+/// use crabular::{Normalization, Table};
+///
+/// // "café" with a precomposed é, and with e followed by a combining accent
+/// let mut table = Table::new().row(["caf\u{e9}"]).row(["cafe\u{301}"]);
+/// table.set_normalization(Normalization::Nfc);
+/// table.filter_eq(0, "caf\u{e9}");
+/// assert_eq!(table.len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalization {
+    /// Compare content as stored (default)
+    #[default]
+    None,
+    /// Canonical composition: composed and decomposed accents compare equal
+    Nfc,
+    /// Compatibility composition: also folds ligatures, full-width forms and the like,
+    /// so `ﬁ` equals `fi`
+    Nfkc,
+}
+
+impl Normalization {
+    /// Returns `text` in this normalization form, borrowing it when it already is.
+    #[must_use]
+    pub fn apply(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Normalization::None => Cow::Borrowed(text),
+            Normalization::Nfc if is_nfc_quick(text.chars()) == IsNormalized::Yes => {
+                Cow::Borrowed(text)
+            }
+            Normalization::Nfc => Cow::Owned(text.nfc().collect()),
+            Normalization::Nfkc if is_nfkc_quick(text.chars()) == IsNormalized::Yes => {
+                Cow::Borrowed(text)
+            }
+            Normalization::Nfkc => Cow::Owned(text.nfkc().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Normalization;
+
+    #[test]
+    fn apply() {
+        let decomposed = "cafe\u{301}";
+        assert_eq!(Normalization::None.apply(decomposed), decomposed);
+        assert_eq!(Normalization::Nfc.apply(decomposed), "caf\u{e9}");
+        assert_eq!(Normalization::Nfc.apply("\u{fb01}le"), "\u{fb01}le");
+        assert_eq!(Normalization::Nfkc.apply("\u{fb01}le"), "file");
+    }
+}
@@ -0,0 +1,221 @@
+use core::fmt::Write;
+
+use crate::alloc_prelude::*;
+
+/// How a numeric column is displayed: thousands separators, fixed decimals, percentages
+/// and currency symbols, applied at render time so stored content stays raw.
+///
+/// A `NumberFormat` is a [formatter spec](crate::FormatterRegistry) of the form
+/// `number:OPTIONS`, where the comma-separated options are `thousands`, `decimals=N`,
+/// `percent` and `currency=SYMBOL`, so it can also be given in table specs and on the
+/// command line. Content that is not a number is shown unchanged.
+///
+/// # Example
+/// ```
+/// use crabular::{NumberFormat, Table};
+///
+/// let mut table = Table::new()
+///     .header(["Item", "Price", "Share"])
+///     .row(["Laptop", "1234.5", "0.125"]);
+/// table.format_column(1, NumberFormat::currency("$").with_thousands().decimals(2));
+/// table.format_column(2, "number:percent,decimals=1");
+///
+/// assert!(table.render().contains("| Laptop  | $1,234.50  | 12.5% |"));
+/// assert_eq!(NumberFormat::thousands().decimals(2).apply("-9876543.21"), "-9,876,543.21");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NumberFormat {
+    thousands: bool,
+    decimals: Option<usize>,
+    percent: bool,
+    currency: Option<String>,
+}
+
+impl NumberFormat {
+    /// A format that only normalizes the number, such as `1.50` to `1.5`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Groups the integer digits in thousands with `,`.
+    #[must_use]
+    pub fn thousands() -> Self {
+        Self::new().with_thousands()
+    }
+
+    /// Shows the value times 100 followed by `%`.
+    #[must_use]
+    pub fn percent() -> Self {
+        Self {
+            percent: true,
+            ..Self::default()
+        }
+    }
+
+    /// Puts `symbol` in front of the number, after any minus sign.
+    #[must_use]
+    pub fn currency(symbol: &str) -> Self {
+        Self {
+            currency: Some(symbol.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Groups the integer digits in thousands with `,`.
+    #[must_use]
+    pub fn with_thousands(mut self) -> Self {
+        self.thousands = true;
+        self
+    }
+
+    /// Rounds to exactly `decimals` digits after the decimal point.
+    #[must_use]
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Formats `value`, or returns it unchanged if it is not a finite number.
+    #[must_use]
+    pub fn apply(&self, value: &str) -> String {
+        let Ok(mut number) = value.trim().parse::<f64>() else {
+            return value.to_string();
+        };
+        if !number.is_finite() {
+            return value.to_string();
+        }
+        if self.percent {
+            number *= 100.0;
+        }
+        let digits = match self.decimals {
+            Some(decimals) => format!("{:.decimals$}", number.abs()),
+            None => number.abs().to_string(),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut output = String::with_capacity(digits.len() + 8);
+        // Rounding may leave nothing but zeros, which should not read as negative
+        if number.is_sign_negative() && digits.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            output.push('-');
+        }
+        if let Some(currency) = &self.currency {
+            output.push_str(currency);
+        }
+        for (index, digit) in integer.chars().enumerate() {
+            if self.thousands && index > 0 && (integer.len() - index) % 3 == 0 {
+                output.push(',');
+            }
+            output.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            output.push('.');
+            output.push_str(fraction);
+        }
+        if self.percent {
+            output.push('%');
+        }
+        output
+    }
+}
+
+/// Writes the formatter spec, such as `number:thousands,decimals=2`.
+impl core::fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut options = Vec::new();
+        if self.thousands {
+            options.push("thousands".to_string());
+        }
+        if let Some(decimals) = self.decimals {
+            options.push(format!("decimals={decimals}"));
+        }
+        if self.percent {
+            options.push("percent".to_string());
+        }
+        if let Some(currency) = &self.currency {
+            options.push(format!("currency={currency}"));
+        }
+        f.write_str("number")?;
+        if !options.is_empty() {
+            f.write_char(':')?;
+            f.write_str(&options.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `number` or `number:OPTIONS` formatter spec.
+impl core::str::FromStr for NumberFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, options) = s.split_once(':').unwrap_or((s, ""));
+        if name.trim() != "number" {
+            return Err(());
+        }
+        let mut format = NumberFormat::new();
+        for option in options.split(',').map(str::trim).filter(|o| !o.is_empty()) {
+            match option.split_once('=') {
+                None if option == "thousands" => format.thousands = true,
+                None if option == "percent" => format.percent = true,
+                Some(("decimals", decimals)) => {
+                    format.decimals = Some(decimals.trim().parse().map_err(|_| ())?);
+                }
+                Some(("currency", symbol)) => format.currency = Some(symbol.to_string()),
+                _ => return Err(()),
+            }
+        }
+        Ok(format)
+    }
+}
+
+impl From<NumberFormat> for String {
+    fn from(format: NumberFormat) -> Self {
+        format.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::NumberFormat;
+
+    #[test]
+    fn apply() {
+        let cases = [
+            (NumberFormat::new(), "1.50", "1.5"),
+            (NumberFormat::thousands(), "1234567", "1,234,567"),
+            (NumberFormat::thousands(), "999", "999"),
+            (
+                NumberFormat::thousands().decimals(2),
+                "-1234.567",
+                "-1,234.57",
+            ),
+            (NumberFormat::new().decimals(0), "2.5", "2"),
+            (NumberFormat::new().decimals(1), "-0.01", "0.0"),
+            (NumberFormat::percent(), "0.25", "25%"),
+            (NumberFormat::percent().decimals(1), "0.1234", "12.3%"),
+            (NumberFormat::currency("€").decimals(2), "-3", "-€3.00"),
+            (NumberFormat::thousands(), "n/a", "n/a"),
+            (NumberFormat::thousands(), "inf", "inf"),
+            (NumberFormat::thousands(), "", ""),
+        ];
+        for (format, value, expected) in cases {
+            assert_eq!(format.apply(value), expected, "{format} {value}");
+        }
+    }
+
+    #[test]
+    fn spec_round_trip() {
+        let format = NumberFormat::currency("$").with_thousands().decimals(2);
+        assert_eq!(format.to_string(), "number:thousands,decimals=2,currency=$");
+        assert_eq!(format.to_string().parse(), Ok(format));
+        assert_eq!("number".parse(), Ok(NumberFormat::new()));
+        assert_eq!("number:percent".parse(), Ok(NumberFormat::percent()));
+        assert_eq!("number:decimals=x".parse::<NumberFormat>(), Err(()));
+        assert_eq!("number:wide".parse::<NumberFormat>(), Err(()));
+        assert_eq!("bytes".parse::<NumberFormat>(), Err(()));
+    }
+}
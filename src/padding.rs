@@ -25,9 +25,33 @@ impl Default for Padding {
     }
 }
 
+/// Blank lines above and below every row's content, set with
+/// [`Table::set_vertical_padding`](crate::Table::set_vertical_padding). Ignored by the
+/// Markdown style. None by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VerticalPadding {
+    pub top: usize,
+    pub bottom: usize,
+}
+
+impl VerticalPadding {
+    #[must_use]
+    pub const fn new(top: usize, bottom: usize) -> Self {
+        Self { top, bottom }
+    }
+
+    #[must_use]
+    pub const fn uniform(lines: usize) -> Self {
+        Self {
+            top: lines,
+            bottom: lines,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Padding;
+    use crate::{Padding, VerticalPadding};
 
     #[test]
     fn new() {
@@ -70,4 +94,13 @@ mod tests {
         assert_eq!(Padding::new(1, 2), Padding::new(1, 2));
         assert_ne!(Padding::new(1, 2), Padding::new(2, 1));
     }
+
+    #[test]
+    fn vertical() {
+        assert_eq!(VerticalPadding::default(), VerticalPadding::new(0, 0));
+        assert_eq!(
+            VerticalPadding::uniform(2),
+            VerticalPadding { top: 2, bottom: 2 }
+        );
+    }
 }
@@ -0,0 +1,237 @@
+use crate::alloc_prelude::*;
+use crate::table::Table;
+use core::ops::Range;
+
+/// Iterator over the pages of a table, created by [`Table::paginate`](crate::Table::paginate).
+///
+/// Column widths are measured once over the whole table, so columns line up
+/// from one page to the next.
+///
+/// # Example
+/// ```
+/// use crabular::Table;
+///
+/// let mut table = Table::new().header(["ID", "Name"]);
+/// for i in 0..5 {
+///     table.add_row([i.to_string(), format!("Item {i}")]);
+/// }
+///
+/// let pages: Vec<String> = table.paginate(2).map(|page| page.render()).collect();
+/// assert_eq!(pages.len(), 3);
+/// assert!(pages.iter().all(|page| page.contains("Name")));
+/// ```
+#[derive(Clone)]
+pub struct Pages<'a> {
+    table: &'a Table,
+    column_widths: Vec<usize>,
+    total_rows: usize,
+    page_size: usize,
+    page_count: usize,
+    next: usize,
+}
+
+impl<'a> Pages<'a> {
+    pub(crate) fn new(
+        table: &'a Table,
+        column_widths: Vec<usize>,
+        total_rows: usize,
+        page_size: usize,
+    ) -> Self {
+        let page_size = page_size.max(1);
+        let page_count = if table.is_empty() {
+            0
+        } else {
+            total_rows.div_ceil(page_size).max(1)
+        };
+        Self {
+            table,
+            column_widths,
+            total_rows,
+            page_size,
+            page_count,
+            next: 0,
+        }
+    }
+
+    /// Returns the total number of pages, including those already yielded.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.page_count
+    }
+}
+
+impl<'a> Iterator for Pages<'a> {
+    type Item = Page<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.page_count {
+            return None;
+        }
+        let start = self.next * self.page_size;
+        let end = (start + self.page_size).min(self.total_rows);
+        self.next += 1;
+        Some(Page {
+            table: self.table,
+            column_widths: self.column_widths.clone(),
+            rows: start..end,
+            number: self.next,
+            total: self.page_count,
+        })
+    }
+
+    /// Skips to the page without building the ones before it.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.next = self.next.saturating_add(n).min(self.page_count);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.page_count - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Pages<'_> {}
+
+/// A view of one page of a table. The headers are repeated on every page.
+#[derive(Clone)]
+pub struct Page<'a> {
+    table: &'a Table,
+    column_widths: Vec<usize>,
+    rows: Range<usize>,
+    number: usize,
+    total: usize,
+}
+
+impl Page<'_> {
+    /// Returns the 1-based page number.
+    #[must_use]
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.total
+    }
+
+    #[must_use]
+    pub fn is_last(&self) -> bool {
+        self.number == self.total
+    }
+
+    /// Returns the range of rendered body rows on this page. Subtotal and
+    /// grand-total rows count as body rows.
+    #[must_use]
+    pub fn rows(&self) -> Range<usize> {
+        self.rows.clone()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Renders the page as a complete table with its own borders and headers.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.table
+            .render_page(&self.column_widths, self.rows.clone())
+    }
+}
+
+impl core::fmt::Display for Page<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Table, TableStyle};
+
+    fn numbered(rows: usize) -> Table {
+        let mut table = Table::new().header(["ID", "Name"]);
+        for i in 0..rows {
+            table.add_row([i.to_string(), format!("Item {i}")]);
+        }
+        table
+    }
+
+    #[test]
+    fn page_count() {
+        let cases = [(0, 1), (1, 1), (4, 2), (5, 3), (6, 3)];
+        for (rows, expected) in cases {
+            let table = numbered(rows);
+            let pages = table.paginate(2);
+            assert_eq!(pages.page_count(), expected, "{rows} rows");
+            assert_eq!(pages.len(), expected);
+        }
+    }
+
+    #[test]
+    fn nth_skips_pages() {
+        let table = numbered(5);
+        let mut pages = table.paginate(2);
+        let page = pages.nth(2).unwrap();
+        assert_eq!((page.number(), page.rows()), (3, 4..5));
+        assert!(pages.next().is_none());
+        assert!(table.paginate(2).nth(3).is_none());
+        assert!(table.paginate(2).nth(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn empty_table_has_no_pages() {
+        assert_eq!(Table::new().paginate(10).count(), 0);
+    }
+
+    #[test]
+    fn zero_page_size_is_one_row_per_page() {
+        assert_eq!(numbered(3).paginate(0).count(), 3);
+    }
+
+    #[test]
+    fn pages_repeat_headers() {
+        let table = numbered(5);
+        for page in table.paginate(2) {
+            let output = page.render();
+            assert!(output.lines().nth(1).unwrap().contains("Name"));
+            assert_eq!(output.lines().count(), 4 + page.len());
+        }
+    }
+
+    #[test]
+    fn page_metadata() {
+        let table = numbered(5);
+        let pages: Vec<_> = table.paginate(2).collect();
+        assert_eq!(pages[0].number(), 1);
+        assert_eq!(pages[0].rows(), 0..2);
+        assert!(!pages[0].is_last());
+        assert_eq!(pages[2].rows(), 4..5);
+        assert_eq!(pages[2].page_count(), 3);
+        assert!(pages[2].is_last());
+    }
+
+    #[test]
+    fn widths_are_shared_across_pages() {
+        let mut table = numbered(1);
+        table.add_row(["1", "A much longer name"]);
+        let pages: Vec<String> = table.paginate(1).map(|page| page.render()).collect();
+        let first_line = |page: &String| page.lines().next().unwrap().to_string();
+        assert_eq!(first_line(&pages[0]), first_line(&pages[1]));
+    }
+
+    #[test]
+    fn single_page_matches_render() {
+        let mut table = numbered(3);
+        table.set_style(TableStyle::Modern);
+        let page = table.paginate(10).next().unwrap();
+        assert_eq!(page.render(), table.render());
+        assert_eq!(page.to_string(), table.render());
+    }
+}
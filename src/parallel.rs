@@ -0,0 +1,81 @@
+use core::cmp::Ordering;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::alloc_prelude::*;
+
+/// The number of items from which work is spread over the rayon thread pool. Below it
+/// the pool's overhead outweighs the gain, so smaller tables stay on the calling thread.
+#[cfg(feature = "parallel")]
+pub(crate) const THRESHOLD: usize = 10_000;
+
+#[cfg(feature = "parallel")]
+fn is_large<T>(items: &[T]) -> bool {
+    items.len() >= THRESHOLD
+}
+
+/// Maps every item to a value, keeping the order.
+pub(crate) fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if is_large(items) {
+        return items.par_iter().map(f).collect();
+    }
+    items.iter().map(f).collect()
+}
+
+/// Sorts the items with a stable sort.
+pub(crate) fn sort_by<T, F>(items: &mut [T], compare: F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    #[cfg(feature = "parallel")]
+    if is_large(items) {
+        items.par_sort_by(compare);
+        return;
+    }
+    items.sort_by(compare);
+}
+
+/// Keeps the items for which `keep` returns true, in their order.
+#[cfg(feature = "parallel")]
+pub(crate) fn retain<T, F>(items: &mut Vec<T>, keep: F)
+where
+    T: Sync,
+    F: Fn(&T) -> bool + Sync + Send,
+{
+    if is_large(items) {
+        let kept = map(items, keep);
+        let mut kept = kept.into_iter();
+        items.retain(|_| kept.next().unwrap_or(false));
+        return;
+    }
+    items.retain(keep);
+}
+
+/// Folds the items into an accumulator. In parallel each thread folds its share from
+/// `A::default()` and the partial results are combined with `merge`.
+pub(crate) fn fold<T, A, F, M>(items: &[T], fold: F, merge: M) -> A
+where
+    T: Sync,
+    A: Default + Send,
+    F: Fn(A, &T) -> A + Sync + Send,
+    M: Fn(A, A) -> A + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if is_large(items) {
+        return items
+            .par_iter()
+            .fold(A::default, fold)
+            .reduce(A::default, merge);
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = merge;
+    items.iter().fold(A::default(), fold)
+}
@@ -0,0 +1,169 @@
+//! Performance guide.
+//!
+//! Rendering cost grows with the number of cells, and most of it is spent measuring
+//! and formatting them. In rough order of impact:
+//!
+//! - Render only what is shown. [`Table::set_max_rows`] and [`Table::paginate`]
+//!   format the visible rows only; column widths are still measured once over the
+//!   whole table so pages line up.
+//! - Do not re-render an unchanged table. [`Table::set_memoize`] returns the previous
+//!   output until the table changes, and [`Table::generation`] lets callers skip work
+//!   on their side too.
+//! - Prefer adding and sorting rows to rebuilding the table. The widest cell of each
+//!   column is kept up to date as rows are added or reordered, so the next render only
+//!   measures rows whose displayed text differs from their content, such as formatted
+//!   columns; removing or filtering rows measures them all again.
+//! - Reuse widths when only the content changes within the same widths.
+//!   [`Table::render_cached`] keeps the measured widths until the table is modified.
+//! - Reuse buffers. [`Table::render_into`] writes into a caller-owned `Vec<u8>`.
+//! - Load large inputs on several threads with [`Table::from_parallel_chunks`].
+//! - Enable the `parallel` feature to measure column widths, sort numerically and
+//!   filter large tables on the rayon thread pool.
+//!
+//! # Measuring
+//!
+//! Wall-clock benchmarks are noisy. With the `instrumentation` feature enabled, every
+//! table counts the work its renders do in a `RenderStats`, so a regression such as
+//! an extra measuring pass or a cell formatted twice shows up as a changed number in a
+//! test rather than a slower benchmark:
+//!
+//! ```
+//! # #[cfg(feature = "instrumentation")]
+//! # {
+//! use crabular::Table;
+//!
+//! let mut table = Table::new().header(["A", "B"]).row(["1", "2"]);
+//! table.set_memoize(true);
+//! let _ = table.render();
+//! let _ = table.render();
+//!
+//! let stats = table.render_stats();
+//! assert_eq!(stats.renders, 1);
+//! assert_eq!(stats.memo_hits, 1);
+//! assert_eq!(stats.width_passes, 1);
+//! assert_eq!(stats.cells_formatted, 4);
+//! # }
+//! ```
+//!
+//! The counters describe work, not heap traffic. Allocations are counted by the
+//! unpublished `alloc-count` workspace crate, whose counting global allocator needs
+//! the `unsafe` code this crate forbids. Its `measure` returns the number of
+//! allocations and bytes a closure made, and its tests check them for renders, such
+//! as a memoized render allocating only the returned copy:
+//!
+//! ```ignore
+//! let (output, stats) = alloc_count::measure(|| table.render());
+//! assert_eq!(stats.allocations, 1);
+//! assert_eq!(stats.bytes, output.len());
+//! ```
+
+#[cfg(doc)]
+use crate::Table;
+
+/// Counters of the work done by a table's renders, available with the
+/// `instrumentation` feature through [`Table::render_stats`](crate::Table::render_stats).
+#[cfg(feature = "instrumentation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenderStats {
+    /// Renders that built their output, including pages and split renders
+    pub renders: usize,
+    /// Renders answered from the memoized output
+    pub memo_hits: usize,
+    /// Passes measuring the column widths
+    pub width_passes: usize,
+    /// Body cells measured by renders. The widths of stored rows are kept up to date
+    /// as rows are added, so rows are measured again only after rows are removed or
+    /// changed.
+    pub cells_measured: usize,
+    /// Cell lines padded and aligned into a column
+    pub cells_formatted: usize,
+    /// Lines written
+    pub lines: usize,
+    /// Bytes written
+    pub bytes: usize,
+}
+
+#[cfg(all(test, feature = "instrumentation"))]
+mod tests {
+    use crate::{RenderStats, Table, WidthConstraint};
+
+    fn table() -> Table {
+        Table::new()
+            .header(["ID", "Name"])
+            .row(["1", "Kelana"])
+            .row(["2", "Kata"])
+    }
+
+    #[test]
+    fn counts_render_work() {
+        let table = table();
+        let output = table.render();
+        assert_eq!(
+            table.render_stats(),
+            RenderStats {
+                renders: 1,
+                memo_hits: 0,
+                width_passes: 1,
+                cells_measured: 4,
+                cells_formatted: 6,
+                lines: 6,
+                bytes: output.len(),
+            }
+        );
+    }
+
+    #[test]
+    fn counts_wrapped_lines() {
+        let mut table = table();
+        table.set_constraint(1, WidthConstraint::Wrap(3));
+        let _ = table.render();
+        // Header plus "Kel|ana" and "Kat|a": three rows of two lines each.
+        assert_eq!(table.render_stats().cells_formatted, 12);
+    }
+
+    #[test]
+    fn render_cached_skips_width_passes() {
+        let table = table();
+        let _ = table.render_cached();
+        let _ = table.render_cached();
+        let stats = table.render_stats();
+        assert_eq!((stats.renders, stats.width_passes), (2, 1));
+    }
+
+    #[test]
+    fn tracks_row_widths_between_renders() {
+        let mut table = table();
+        let _ = table.render();
+        table.add_row(["3", "Cherry Blossom"]);
+        table.sort(1);
+        let _ = table.render();
+        assert_eq!(table.render_stats().cells_measured, 4);
+
+        // Removing the widest cell of a column measures the rows again
+        table.remove_row(0);
+        let _ = table.render();
+        assert_eq!(table.render_stats().cells_measured, 8);
+
+        // Formatted rows are measured as displayed on every render
+        table.format_column(0, "template:#{}");
+        let _ = table.render();
+        let _ = table.render();
+        assert_eq!(table.render_stats().cells_measured, 16);
+    }
+
+    #[test]
+    fn pages_measure_once() {
+        let table = table();
+        let pages = table.paginate(1).count();
+        assert_eq!(pages, 2);
+        assert_eq!(table.render_stats().width_passes, 1);
+    }
+
+    #[test]
+    fn reset() {
+        let table = table();
+        let _ = table.render();
+        table.reset_render_stats();
+        assert_eq!(table.render_stats(), RenderStats::default());
+    }
+}
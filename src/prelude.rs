@@ -0,0 +1,35 @@
+//! The types most tables need, for a single glob import.
+//!
+//! ```
+//! use crabular::prelude::*;
+//!
+//! let table = TableBuilder::new()
+//!     .style(TableStyle::Modern)
+//!     .header(["Name", "Age"])
+//!     .constrain(0, WidthConstraint::Min(8))
+//!     .align(1, Alignment::Right)
+//!     .row(["Kata", "30"])
+//!     .build();
+//! assert!(table.render().contains("Kata"));
+//! ```
+//!
+//! The prelude only grows within a minor version, and none of its names clash with
+//! the standard prelude, so `use crabular::prelude::*` keeps compiling across
+//! upgrades. Everything else is exported from the crate root.
+
+pub use crate::alignment::Alignment;
+pub use crate::border_visibility::BorderVisibility;
+pub use crate::builder::TableBuilder;
+pub use crate::cell::{Cell, IntoCell};
+pub use crate::color::Color;
+pub use crate::constraint::WidthConstraint;
+pub use crate::header_style::HeaderStyle;
+pub use crate::padding::{Padding, VerticalPadding};
+pub use crate::render_options::RenderOptions;
+pub use crate::row::Row;
+pub use crate::style::TableStyle;
+pub use crate::table::Table;
+pub use crate::theme::Theme;
+pub use crate::validation::TableError;
+pub use crate::vertical_alignment::VerticalAlignment;
+pub use crate::wrap_policy::WrapPolicy;
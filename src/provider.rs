@@ -0,0 +1,93 @@
+use crate::alloc_prelude::*;
+use crate::row::Row;
+use crate::table::Table;
+
+/// A source of rows that can live outside the table, such as a database cursor, a
+/// memory-mapped file or a columnar store.
+///
+/// [`Table::render_viewport`] asks a provider only for the rows it displays, so the
+/// full data set never has to be materialized as [`Row`]s.
+///
+/// # Example
+/// ```
+/// use crabular::{Row, RowProvider, Table};
+///
+/// /// A billion rows, computed on demand.
+/// struct Squares;
+///
+/// impl RowProvider for Squares {
+///     fn len(&self) -> usize {
+///         1_000_000_000
+///     }
+///
+///     fn get(&self, index: usize) -> Row {
+///         Row::from([index.to_string(), (index * index).to_string()])
+///     }
+/// }
+///
+/// let template = Table::new().header(["n", "n²"]);
+/// let output = template.render_viewport(&Squares, 999_999_998..1_000_000_000);
+/// assert!(output.contains("999999998000000001"));
+/// ```
+pub trait RowProvider {
+    /// Returns the total number of rows.
+    fn len(&self) -> usize;
+
+    /// Returns the row at `index`, which is always less than [`len`](Self::len).
+    fn get(&self, index: usize) -> Row;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl RowProvider for [Row] {
+    fn len(&self) -> usize {
+        <[Row]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Row {
+        self[index].clone()
+    }
+}
+
+impl RowProvider for Vec<Row> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, index: usize) -> Row {
+        self[index].clone()
+    }
+}
+
+impl RowProvider for Table {
+    fn len(&self) -> usize {
+        self.rows().len()
+    }
+
+    fn get(&self, index: usize) -> Row {
+        self.rows()[index].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Row, RowProvider, Table};
+
+    #[test]
+    fn slice_and_vec_providers() {
+        let rows = vec![Row::from(["a"]), Row::from(["b"])];
+        assert_eq!(RowProvider::len(&rows), 2);
+        assert_eq!(RowProvider::get(&rows, 1).cells()[0].content(), "b");
+        assert_eq!(RowProvider::len(&rows[..1]), 1);
+        assert!(RowProvider::is_empty(&Vec::<Row>::new()));
+    }
+
+    #[test]
+    fn table_provider() {
+        let table = Table::new().header(["h"]).row(["1"]).row(["2"]);
+        assert_eq!(RowProvider::len(&table), 2);
+        assert_eq!(RowProvider::get(&table, 0).cells()[0].content(), "1");
+    }
+}
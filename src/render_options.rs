@@ -0,0 +1,437 @@
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::ansi;
+use crate::margin::Margin;
+use crate::text;
+
+/// Layout of Markdown tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkdownMode {
+    /// Pipes aligned into columns using padding (default)
+    #[default]
+    Pretty,
+    /// Minimal `|a|b|` rows with no padding, which keeps diffs small
+    Compact,
+}
+
+impl core::str::FromStr for MarkdownMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pretty" | "aligned" => Ok(MarkdownMode::Pretty),
+            "compact" | "minified" | "min" => Ok(MarkdownMode::Compact),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Line terminator written after every rendered line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Newline {
+    /// `\n` (default)
+    #[default]
+    Lf,
+    /// `\r\n`, for files targeting Windows tools
+    CrLf,
+}
+
+impl Newline {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
+impl core::str::FromStr for Newline {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lf" | "unix" => Ok(Newline::Lf),
+            "crlf" | "windows" => Ok(Newline::CrLf),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How colors and hyperlinks, from header styles, badges and linked columns, are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// ANSI escape sequences, for terminals (default)
+    #[default]
+    Ansi,
+    /// `<span style>` and `<a href>` tags with the text HTML-escaped, for showing the
+    /// table in a `<pre>` element
+    Html,
+    /// Plain text, with every escape sequence removed
+    None,
+}
+
+impl core::str::FromStr for ColorMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ansi" | "terminal" => Ok(ColorMode::Ansi),
+            "html" => Ok(ColorMode::Html),
+            "none" | "plain" | "off" => Ok(ColorMode::None),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How cell notes, see [`Cell::set_note`](crate::Cell::set_note), are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteMode {
+    /// Footnote markers like `[1]` with the notes listed below the table in text output,
+    /// and `title` tooltips in HTML (default)
+    #[default]
+    Footnotes,
+    /// Notes are left out of the output
+    Ignore,
+}
+
+impl core::str::FromStr for NoteMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "footnotes" | "footnote" => Ok(NoteMode::Footnotes),
+            "ignore" | "none" | "off" => Ok(NoteMode::Ignore),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Output options that change how a table is written out rather than what it contains.
+///
+/// # Example
+/// ```
+/// use crabular::{MarkdownMode, RenderOptions, Table, TableStyle};
+///
+/// let mut table = Table::new().header(["a", "b"]).row(["1", "2"]);
+/// table.set_style(TableStyle::Markdown);
+/// table.set_render_options(RenderOptions::new().markdown(MarkdownMode::Compact));
+/// assert_eq!(table.render(), "|a|b|\n|---|---|\n|1|2|\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenderOptions {
+    markdown: MarkdownMode,
+    line_prefix: String,
+    margin: Margin,
+    newline: Newline,
+    hyperlinks: bool,
+    color_mode: ColorMode,
+    notes: NoteMode,
+    ansi_passthrough: bool,
+    placement: Option<(Alignment, usize)>,
+}
+
+impl RenderOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the layout used when the table style is `Markdown`.
+    #[must_use]
+    pub fn markdown(mut self, mode: MarkdownMode) -> Self {
+        self.markdown = mode;
+        self
+    }
+
+    /// Prepends `prefix` to every rendered line, e.g. to indent a table inside a
+    /// code comment or YAML literal. Blank lines get the prefix without trailing
+    /// whitespace.
+    #[must_use]
+    pub fn line_prefix(mut self, prefix: &str) -> Self {
+        self.line_prefix = prefix.to_string();
+        self
+    }
+
+    /// Surrounds every rendered line with spaces. The left margin goes after the
+    /// line prefix, so `line_prefix("// ")` with a margin of 2 gives `//   +---`.
+    #[must_use]
+    pub fn margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Places the table within `total_width` columns, such as a terminal or dashboard
+    /// panel, by indenting every line: `Center` centers it and `Right` (or `Decimal`)
+    /// moves it to the right edge. A table as wide as `total_width` or wider is left
+    /// where it is. The margin counts as part of the table; the line prefix does not.
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::{Alignment, RenderOptions, Table};
+    ///
+    /// let mut table = Table::new().row(["42"]);
+    /// table.set_render_options(RenderOptions::new().horizontal_placement(Alignment::Right, 12));
+    /// assert_eq!(table.render().lines().nth(1), Some("      | 42 |"));
+    /// ```
+    #[must_use]
+    pub fn horizontal_placement(mut self, alignment: Alignment, total_width: usize) -> Self {
+        self.placement = Some((alignment, total_width));
+        self
+    }
+
+    /// Sets the line terminator.
+    #[must_use]
+    pub fn newline(mut self, newline: Newline) -> Self {
+        self.newline = newline;
+        self
+    }
+
+    /// Turns cells of linked columns into OSC 8 hyperlinks, see
+    /// [`Table::link_column`](crate::Table::link_column). Only enable this for terminals,
+    /// since the escape sequences end up in files and pipes as-is.
+    #[must_use]
+    pub fn hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = enabled;
+        self
+    }
+
+    /// Sets how colors and hyperlinks are written.
+    #[must_use]
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Keeps ANSI escape sequences already in cell content, such as colors from
+    /// another tool, without counting them towards column widths. Every cell line that
+    /// has one ends with a reset, so colors do not bleed into the next column, and
+    /// wrapped lines continue the colors of the line before.
+    ///
+    /// Without it escape sequences are measured as text; see
+    /// [`Table::set_strip_ansi`](crate::Table::set_strip_ansi) to remove them instead.
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::{RenderOptions, Table};
+    ///
+    /// let mut table = Table::new()
+    ///     .header(["Status", "Host"])
+    ///     .row(["\x1b[31mdown", "db-1"]);
+    /// table.set_render_options(RenderOptions::new().ansi_passthrough(true));
+    /// assert_eq!(
+    ///     table.render().lines().nth(3),
+    ///     Some("| \x1b[31mdown  \x1b[0m  | db-1 |")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn ansi_passthrough(mut self, enabled: bool) -> Self {
+        self.ansi_passthrough = enabled;
+        self
+    }
+
+    /// Sets how cell notes are shown.
+    #[must_use]
+    pub fn notes(mut self, mode: NoteMode) -> Self {
+        self.notes = mode;
+        self
+    }
+
+    #[must_use]
+    pub fn get_markdown(&self) -> MarkdownMode {
+        self.markdown
+    }
+
+    #[must_use]
+    pub fn get_line_prefix(&self) -> &str {
+        &self.line_prefix
+    }
+
+    #[must_use]
+    pub fn get_margin(&self) -> Margin {
+        self.margin
+    }
+
+    #[must_use]
+    pub fn get_newline(&self) -> Newline {
+        self.newline
+    }
+
+    #[must_use]
+    pub fn get_hyperlinks(&self) -> bool {
+        self.hyperlinks
+    }
+
+    #[must_use]
+    pub fn get_color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    #[must_use]
+    pub fn get_notes(&self) -> NoteMode {
+        self.notes
+    }
+
+    #[must_use]
+    pub fn get_ansi_passthrough(&self) -> bool {
+        self.ansi_passthrough
+    }
+
+    #[must_use]
+    pub fn get_horizontal_placement(&self) -> Option<(Alignment, usize)> {
+        self.placement
+    }
+
+    /// Applies the line prefix, margin, newline and color mode to `output`, which uses
+    /// `\n` line endings.
+    pub(crate) fn apply_to_lines(&self, output: String) -> String {
+        let output = self.apply_layout(output);
+        match self.color_mode {
+            ColorMode::Ansi => output,
+            ColorMode::Html => ansi::to_html(&output),
+            ColorMode::None => ansi::strip(&output),
+        }
+    }
+
+    fn apply_layout(&self, output: String) -> String {
+        let indent = self.placement_indent(&output);
+        if self.line_prefix.is_empty()
+            && self.margin == Margin::default()
+            && self.newline == Newline::Lf
+            && indent == 0
+        {
+            return output;
+        }
+        let newline = self.newline.as_str();
+        let mut result = String::with_capacity(output.len());
+        for line in output.split_terminator('\n') {
+            if line.is_empty() {
+                result.push_str(self.line_prefix.trim_end());
+            } else {
+                result.push_str(&self.line_prefix);
+                result.push_str(&" ".repeat(indent + self.margin.left));
+                result.push_str(line);
+                result.push_str(&" ".repeat(self.margin.right));
+            }
+            result.push_str(newline);
+        }
+        result
+    }
+
+    /// Returns the spaces put before every line to place the table, whose widest line
+    /// sets its width.
+    fn placement_indent(&self, output: &str) -> usize {
+        let Some((alignment, total_width)) = self.placement else {
+            return 0;
+        };
+        let width = output.lines().map(text::ansi_width).max().unwrap_or(0)
+            + self.margin.left
+            + self.margin.right;
+        let free = total_width.saturating_sub(width);
+        match alignment {
+            Alignment::Left => 0,
+            Alignment::Center => free / 2,
+            Alignment::Right | Alignment::Decimal => free,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, ColorMode, Margin, MarkdownMode, Newline, NoteMode, RenderOptions};
+
+    #[test]
+    fn defaults() {
+        let options = RenderOptions::new();
+        assert_eq!(MarkdownMode::default(), MarkdownMode::Pretty);
+        assert_eq!(options.get_markdown(), MarkdownMode::Pretty);
+        assert_eq!(options.get_line_prefix(), "");
+        assert_eq!(options.get_margin(), Margin::default());
+        assert_eq!(options.get_newline(), Newline::Lf);
+        assert!(!options.get_hyperlinks());
+        assert_eq!(options.get_color_mode(), ColorMode::Ansi);
+        assert_eq!(options.get_notes(), NoteMode::Footnotes);
+    }
+
+    #[test]
+    fn note_mode_from_str() {
+        assert_eq!("Footnotes".parse(), Ok(NoteMode::Footnotes));
+        assert_eq!("off".parse(), Ok(NoteMode::Ignore));
+        assert_eq!("tooltip".parse::<NoteMode>(), Err(()));
+    }
+
+    #[test]
+    fn color_mode_from_str() {
+        assert_eq!("HTML".parse(), Ok(ColorMode::Html));
+        assert_eq!("off".parse(), Ok(ColorMode::None));
+        assert_eq!("ansi".parse(), Ok(ColorMode::Ansi));
+        assert_eq!("css".parse::<ColorMode>(), Err(()));
+    }
+
+    #[test]
+    fn markdown_mode() {
+        let options = RenderOptions::new().markdown(MarkdownMode::Compact);
+        assert_eq!(options.get_markdown(), MarkdownMode::Compact);
+    }
+
+    #[test]
+    fn markdown_mode_from_str() {
+        assert_eq!("pretty".parse(), Ok(MarkdownMode::Pretty));
+        assert_eq!("Compact".parse(), Ok(MarkdownMode::Compact));
+        assert_eq!("min".parse(), Ok(MarkdownMode::Compact));
+        assert_eq!("fancy".parse::<MarkdownMode>(), Err(()));
+    }
+
+    #[test]
+    fn newline_from_str() {
+        assert_eq!("CRLF".parse(), Ok(Newline::CrLf));
+        assert_eq!("unix".parse(), Ok(Newline::Lf));
+        assert_eq!("cr".parse::<Newline>(), Err(()));
+    }
+
+    #[test]
+    fn apply_to_lines() {
+        let cases = [
+            (RenderOptions::new(), "a\n\nb\n"),
+            (RenderOptions::new().line_prefix("// "), "// a\n//\n// b\n"),
+            (
+                RenderOptions::new().newline(Newline::CrLf),
+                "a\r\n\r\nb\r\n",
+            ),
+            (
+                RenderOptions::new()
+                    .line_prefix("  ")
+                    .newline(Newline::CrLf),
+                "  a\r\n\r\n  b\r\n",
+            ),
+            (
+                RenderOptions::new().margin(Margin::new(2, 1)),
+                "  a \n\n  b \n",
+            ),
+            (
+                RenderOptions::new()
+                    .line_prefix("- ")
+                    .margin(Margin::new(1, 0)),
+                "-  a\n-\n-  b\n",
+            ),
+            (
+                RenderOptions::new().horizontal_placement(Alignment::Center, 6),
+                "  a\n\n  b\n",
+            ),
+            (
+                RenderOptions::new()
+                    .line_prefix("> ")
+                    .margin(Margin::new(1, 1))
+                    .horizontal_placement(Alignment::Right, 4),
+                ">   a \n>\n>   b \n",
+            ),
+            (
+                RenderOptions::new().horizontal_placement(Alignment::Left, 80),
+                "a\n\nb\n",
+            ),
+        ];
+        for (options, expected) in cases {
+            assert_eq!(options.apply_to_lines("a\n\nb\n".to_string()), expected);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::alloc_prelude::*;
+
+/// Issues of the last [`render`](crate::Table::render) that did not stop it, returned by
+/// [`Table::last_render_report`](crate::Table::last_render_report), so tools can tell
+/// their users that values were cut and how to avoid it.
+///
+/// Its `Display` sums the issues up in one line, empty when there are none.
+///
+/// # Example
+/// ```
+/// use crabular::{Table, WidthConstraint};
+///
+/// let mut table = Table::new()
+///     .header(["Name", "Path"])
+///     .row(["a", "/usr/local/share"])
+///     .row(["b", "/tmp"]);
+/// table.set_constraint(1, WidthConstraint::Fixed(8));
+/// let _ = table.render();
+/// let report = table.last_render_report();
+/// assert_eq!(report.truncated_cells, 1);
+/// assert_eq!(report.clipped_columns, [1]);
+/// assert_eq!(
+///     report.to_string(),
+///     "1 value was truncated; column 1 is narrower than its content"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenderReport {
+    /// Header and body cells cut to fit their column
+    pub truncated_cells: usize,
+    /// Columns drawn narrower than their widest content, by a `Fixed`, `Max` or `Wrap`
+    /// constraint. Their content is wrapped or cut.
+    pub clipped_columns: Vec<usize>,
+    /// Columns whose width constraint was left out: constraints for columns the table
+    /// does not have, and `Proportional` constraints adding up to more than 100 percent
+    pub normalized_constraints: Vec<usize>,
+}
+
+impl RenderReport {
+    /// Returns true when the render had no issues.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.truncated_cells == 0
+            && self.clipped_columns.is_empty()
+            && self.normalized_constraints.is_empty()
+    }
+
+    /// Returns the report of a view with a column added in front, such as the
+    /// selection column, in the columns of the table it was made from.
+    pub(crate) fn without_first_column(self) -> Self {
+        let shift = |columns: Vec<usize>| -> Vec<usize> {
+            columns
+                .into_iter()
+                .filter_map(|column| column.checked_sub(1))
+                .collect()
+        };
+        Self {
+            truncated_cells: self.truncated_cells,
+            clipped_columns: shift(self.clipped_columns),
+            normalized_constraints: shift(self.normalized_constraints),
+        }
+    }
+}
+
+impl core::fmt::Display for RenderReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut issues = Vec::new();
+        match self.truncated_cells {
+            0 => {}
+            1 => issues.push("1 value was truncated".to_string()),
+            count => issues.push(format!("{count} values were truncated")),
+        }
+        match self.clipped_columns.as_slice() {
+            [] => {}
+            [column] => issues.push(format!("column {column} is narrower than its content")),
+            columns => issues.push(format!(
+                "columns {} are narrower than their content",
+                list(columns)
+            )),
+        }
+        match self.normalized_constraints.as_slice() {
+            [] => {}
+            [column] => issues.push(format!(
+                "the width constraint of column {column} was ignored"
+            )),
+            columns => issues.push(format!(
+                "the width constraints of columns {} were ignored",
+                list(columns)
+            )),
+        }
+        f.write_str(&issues.join("; "))
+    }
+}
+
+/// Returns `columns` separated by commas.
+fn list(columns: &[usize]) -> String {
+    columns
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RenderReport;
+
+    #[test]
+    fn display() {
+        assert_eq!(RenderReport::default().to_string(), "");
+        assert!(RenderReport::default().is_empty());
+        let report = RenderReport {
+            truncated_cells: 3,
+            clipped_columns: vec![0, 2],
+            normalized_constraints: vec![4],
+        };
+        assert!(!report.is_empty());
+        assert_eq!(
+            report.to_string(),
+            "3 values were truncated; columns 0, 2 are narrower than their content; \
+             the width constraint of column 4 was ignored"
+        );
+    }
+}
@@ -1,15 +1,24 @@
 use crate::Alignment;
-use crate::cell::Cell;
+use crate::alloc_prelude::*;
+use crate::cell::{Cell, IntoCell};
 
 #[derive(Clone)]
 pub struct Row {
     cells: Vec<Cell>,
+    /// Position among the rows added to the table, see [`Table::current_order`](crate::Table::current_order).
+    origin: usize,
+    /// Origin of the parent row, see [`Table::add_child_row`](crate::Table::add_child_row).
+    parent: Option<usize>,
 }
 
 impl Row {
     #[must_use]
     pub fn new() -> Self {
-        Self { cells: Vec::new() }
+        Self {
+            cells: Vec::new(),
+            origin: 0,
+            parent: None,
+        }
     }
 
     #[must_use]
@@ -22,7 +31,11 @@ impl Row {
             .into_iter()
             .map(|s| Cell::new(s.as_ref(), alignment))
             .collect();
-        Self { cells }
+        Self {
+            cells,
+            origin: 0,
+            parent: None,
+        }
     }
 
     pub fn push(&mut self, cell: Cell) {
@@ -52,6 +65,22 @@ impl Row {
         self.cells.get_mut(index)
     }
 
+    pub(crate) fn origin(&self) -> usize {
+        self.origin
+    }
+
+    pub(crate) fn set_origin(&mut self, origin: usize) {
+        self.origin = origin;
+    }
+
+    pub(crate) fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    pub(crate) fn set_parent(&mut self, parent: Option<usize>) {
+        self.parent = parent;
+    }
+
     #[must_use]
     pub fn cells(&self) -> &[Cell] {
         &self.cells
@@ -137,6 +166,33 @@ impl<S: AsRef<str>, const N: usize> From<&[S; N]> for Row {
     }
 }
 
+/// Builds a row from a tuple of mixed types, such as `("Kelana", 30, 9.5)`.
+macro_rules! row_from_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: IntoCell),+> From<($($name,)+)> for Row {
+            #[allow(non_snake_case)]
+            fn from(($($name,)+): ($($name,)+)) -> Self {
+                let mut row = Row::new();
+                $(row.push($name.into_cell());)+
+                row
+            }
+        }
+    };
+}
+
+row_from_tuple!(A);
+row_from_tuple!(A, B);
+row_from_tuple!(A, B, C);
+row_from_tuple!(A, B, C, D);
+row_from_tuple!(A, B, C, D, E);
+row_from_tuple!(A, B, C, D, E, F);
+row_from_tuple!(A, B, C, D, E, F, G);
+row_from_tuple!(A, B, C, D, E, F, G, H);
+row_from_tuple!(A, B, C, D, E, F, G, H, I);
+row_from_tuple!(A, B, C, D, E, F, G, H, I, J);
+row_from_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+row_from_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 #[cfg(test)]
 mod tests {
     use crate::{Alignment, Cell, Row};
@@ -154,6 +210,17 @@ mod tests {
         assert!(row.is_empty());
     }
 
+    #[test]
+    fn from_mixed_tuple() {
+        let mut total = Cell::new("Total", Alignment::Right);
+        total.set_span(2);
+        let row = Row::from((total, 12.5, Some(3u32), None::<&str>, String::from("ok")));
+        let contents: Vec<&str> = row.cells().iter().map(Cell::content).collect();
+        assert_eq!(contents, ["Total", "12.5", "3", "", "ok"]);
+        assert_eq!(row.cells()[0].span(), 2);
+        assert_eq!(Row::from(("only",)).len(), 1);
+    }
+
     #[test]
     fn from_strings() {
         let row1: Row = ["a"].into();
@@ -0,0 +1,189 @@
+use crate::alloc_prelude::*;
+use crate::cell::Cell;
+use crate::table::Table;
+
+/// Why a table's headers do not match the expected columns, returned by
+/// [`Table::expect_schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    expected: Vec<String>,
+    found: Vec<String>,
+    missing: Vec<String>,
+    extra: Vec<String>,
+    misordered: Vec<String>,
+}
+
+impl SchemaError {
+    /// The expected column names.
+    #[must_use]
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    /// The table's header names, empty for a table without headers.
+    #[must_use]
+    pub fn found(&self) -> &[String] {
+        &self.found
+    }
+
+    /// Expected columns the table does not have.
+    #[must_use]
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Columns of the table that are not expected, including repeats of expected ones.
+    #[must_use]
+    pub fn extra(&self) -> &[String] {
+        &self.extra
+    }
+
+    /// Expected columns that are present but in a different order, in expected order.
+    #[must_use]
+    pub fn misordered(&self) -> &[String] {
+        &self.misordered
+    }
+}
+
+impl core::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "expected columns [{}], found [{}]",
+            self.expected.join(", "),
+            self.found.join(", ")
+        )?;
+        for (kind, names) in [
+            ("missing", &self.missing),
+            ("extra", &self.extra),
+            ("misordered", &self.misordered),
+        ] {
+            if !names.is_empty() {
+                let names: Vec<String> = names.iter().map(|name| format!("`{name}`")).collect();
+                write!(f, "; {kind} {}", names.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for SchemaError {}
+
+impl Table {
+    /// Checks that the headers are exactly `expected`, in order, so pipelines ingesting
+    /// CSV files can fail loudly when an input changes shape. Names are compared after
+    /// trimming whitespace and are case-sensitive.
+    ///
+    /// # Errors
+    /// Returns a [`SchemaError`] listing the missing, extra and misordered columns.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new().header(["name", "id", "notes"]);
+    /// let error = table.expect_schema(&["id", "name", "score"]).unwrap_err();
+    /// assert_eq!(error.missing(), ["score"]);
+    /// assert_eq!(error.extra(), ["notes"]);
+    /// assert_eq!(error.misordered(), ["id", "name"]);
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "expected columns [id, name, score], found [name, id, notes]; missing `score`; \
+    ///      extra `notes`; misordered `id`, `name`"
+    /// );
+    /// ```
+    pub fn expect_schema(&self, expected: &[&str]) -> Result<(), SchemaError> {
+        let found: Vec<&str> = self
+            .headers()
+            .map(|headers| {
+                headers
+                    .cells()
+                    .iter()
+                    .map(Cell::content)
+                    .map(str::trim)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let expected: Vec<&str> = expected.iter().map(|name| name.trim()).collect();
+        if found == expected {
+            return Ok(());
+        }
+
+        // Each expected name is matched by at most one header
+        let mut unmatched = found.clone();
+        let mut missing = Vec::new();
+        let mut common = Vec::new();
+        for name in &expected {
+            if let Some(index) = unmatched.iter().position(|header| header == name) {
+                unmatched.remove(index);
+                common.push(*name);
+            } else {
+                missing.push(*name);
+            }
+        }
+        let mut remaining = common.clone();
+        let found_order: Vec<&str> = found
+            .iter()
+            .copied()
+            .filter(|header| {
+                let index = remaining.iter().position(|name| name == header);
+                index.map(|index| remaining.remove(index)).is_some()
+            })
+            .collect();
+        let misordered = common
+            .iter()
+            .zip(&found_order)
+            .filter(|(expected, found)| expected != found)
+            .map(|(expected, _)| *expected);
+
+        let owned = |names: &[&str]| names.iter().map(ToString::to_string).collect();
+        Err(SchemaError {
+            expected: owned(&expected),
+            found: owned(&found),
+            missing: owned(&missing),
+            extra: owned(&unmatched),
+            misordered: misordered.map(ToString::to_string).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Table;
+
+    #[test]
+    fn matching_schema() {
+        let table = Table::new().header(["id", " name "]).row(["1", "Kata"]);
+        assert_eq!(table.expect_schema(&["id", "name"]), Ok(()));
+        assert!(table.expect_schema(&["id", "Name"]).is_err());
+    }
+
+    #[test]
+    fn missing_and_extra_columns() {
+        let table = Table::new().header(["id", "name", "name"]);
+        let error = table.expect_schema(&["id", "name", "score"]).unwrap_err();
+        assert_eq!(error.missing(), ["score"]);
+        assert_eq!(error.extra(), ["name"]);
+        assert!(error.misordered().is_empty());
+        assert_eq!(error.found(), ["id", "name", "name"]);
+    }
+
+    #[test]
+    fn misordered_columns() {
+        let table = Table::new().header(["score", "name", "id"]);
+        let error = table.expect_schema(&["id", "name", "score"]).unwrap_err();
+        assert!(error.missing().is_empty());
+        assert!(error.extra().is_empty());
+        assert_eq!(error.misordered(), ["id", "score"]);
+    }
+
+    #[test]
+    fn table_without_headers() {
+        let table = Table::new().row(["1", "Kata"]);
+        let error = table.expect_schema(&["id"]).unwrap_err();
+        assert_eq!(error.missing(), ["id"]);
+        assert_eq!(
+            error.to_string(),
+            "expected columns [id], found []; missing `id`"
+        );
+    }
+}
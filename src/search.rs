@@ -0,0 +1,110 @@
+use crate::alloc_prelude::*;
+use crate::table::Table;
+
+impl Table {
+    /// Returns the `(row, cell)` index of every body cell containing `needle`, row by
+    /// row, so callers can highlight matches or jump to them before rendering.
+    ///
+    /// Rows are indices into [`rows`](Self::rows) and cells into
+    /// [`Row::cells`](crate::Row::cells). Stored content is searched, compared like
+    /// [`filter_has`](Self::filter_has); headers are not searched.
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::Table;
+    ///
+    /// let table = Table::new()
+    ///     .header(["Service", "Owner"])
+    ///     .row(["billing-api", "payments"])
+    ///     .row(["auth-api", "identity"]);
+    /// assert_eq!(table.find("api"), [(0, 0), (1, 0)]);
+    /// assert_eq!(table.find("pay"), [(0, 1)]);
+    /// assert_eq!(table.find_in_column(1, "i"), [1]);
+    /// ```
+    #[must_use]
+    pub fn find(&self, needle: &str) -> Vec<(usize, usize)> {
+        let needle = self.comparable(needle);
+        self.find_cells(|content| self.comparable(content).contains(needle.as_ref()))
+    }
+
+    /// Returns the index of every row whose cell in `column` contains `needle`.
+    #[must_use]
+    pub fn find_in_column(&self, column: usize, needle: &str) -> Vec<usize> {
+        let needle = self.comparable(needle);
+        self.find_rows(column, |content| {
+            self.comparable(content).contains(needle.as_ref())
+        })
+    }
+
+    /// Returns the `(row, cell)` index of every body cell that `pattern` matches, like
+    /// [`find`](Self::find).
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::Table;
+    /// use regex::Regex;
+    ///
+    /// let table = Table::new().row(["v1.2.0", "stable"]).row(["v2.0.0-rc1", "beta"]);
+    /// let prerelease = Regex::new(r"-rc\d+$").unwrap();
+    /// assert_eq!(table.find_regex(&prerelease), [(1, 0)]);
+    /// assert_eq!(table.find_regex_in_column(1, &Regex::new("^b").unwrap()), [1]);
+    /// ```
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn find_regex(&self, pattern: &regex::Regex) -> Vec<(usize, usize)> {
+        self.find_cells(|content| pattern.is_match(content))
+    }
+
+    /// Returns the index of every row whose cell in `column` `pattern` matches.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn find_regex_in_column(&self, column: usize, pattern: &regex::Regex) -> Vec<usize> {
+        self.find_rows(column, |content| pattern.is_match(content))
+    }
+
+    fn find_cells(&self, matches: impl Fn(&str) -> bool) -> Vec<(usize, usize)> {
+        self.rows()
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .cells()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| matches(cell.content()))
+                    .map(move |(cell, _)| (row, cell))
+            })
+            .collect()
+    }
+
+    fn find_rows(&self, column: usize, matches: impl Fn(&str) -> bool) -> Vec<usize> {
+        self.rows()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.cells()
+                    .get(column)
+                    .is_some_and(|cell| matches(cell.content()))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, Cell, Row, Table};
+
+    #[test]
+    fn find_skips_headers_and_missing_cells() {
+        let mut table = Table::new().header(["Name", "Note"]).row(["Name", "x"]);
+        let mut short = Row::new();
+        short.push(Cell::new("xx", Alignment::Left));
+        table.add_row(short);
+        assert_eq!(table.find("Name"), [(0, 0)]);
+        assert_eq!(table.find("x"), [(0, 1), (1, 0)]);
+        assert_eq!(table.find_in_column(1, "x"), [0]);
+        assert!(table.find_in_column(5, "x").is_empty());
+        assert!(table.find("missing").is_empty());
+    }
+}
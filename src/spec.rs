@@ -0,0 +1,544 @@
+use crate::aggregate::Aggregate;
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::constraint::WidthConstraint;
+#[cfg(feature = "serde")]
+use crate::join::JoinKind;
+use crate::style::TableStyle;
+use crate::table::Table;
+use crate::wrap_policy::WrapPolicy;
+
+/// A declarative description of how to present a table, applied with
+/// [`Table::apply_spec`].
+///
+/// With the `serde` feature, a spec can be loaded from TOML, JSON, YAML or any other
+/// serde format, so reports can be defined outside the code. Every field is optional.
+/// Enums are written the way their `FromStr` impls parse them, for example
+/// `"modern"`, `"right"` or `"max:30"`.
+///
+/// ```toml
+/// style = "markdown"
+/// max_rows = 50
+///
+/// [[columns]]
+/// column = "Size"
+/// align = "right"
+/// format = "bytes"
+///
+/// [[columns]]
+/// column = "Issue"
+/// link = "https://github.com/org/repo/issues/{value}"
+///
+/// [[columns]]
+/// column = 0
+/// width = "max:30"
+///
+/// [[filters]]
+/// column = "Owner"
+/// contains = "root"
+///
+/// [[sort]]
+/// column = "Size"
+/// numeric = true
+/// descending = true
+///
+/// group_by = "Owner"
+/// subtotals = true
+/// grand_total = true
+///
+/// [[aggregates]]
+/// column = "Size"
+/// function = "sum"
+/// ```
+///
+/// # Example
+/// ```
+/// use crabular::{Alignment, ColumnRef, ColumnSpec, SortSpec, Table, TableSpec, TableStyle};
+///
+/// let spec = TableSpec {
+///     style: Some(TableStyle::Markdown),
+///     columns: vec![ColumnSpec {
+///         align: Some(Alignment::Right),
+///         ..ColumnSpec::new(ColumnRef::Name("Size".to_string()))
+///     }],
+///     sort: vec![SortSpec {
+///         numeric: true,
+///         ..SortSpec::new(ColumnRef::Index(1))
+///     }],
+///     ..TableSpec::default()
+/// };
+///
+/// let mut table = Table::new()
+///     .header(["File", "Size"])
+///     .row(["b.txt", "20"])
+///     .row(["a.txt", "3"]);
+/// table.apply_spec(&spec).unwrap();
+/// assert!(table.render().starts_with("| File   | Size |\n|:------ |-----:|\n| a.txt  |    3 |"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct TableSpec {
+    pub style: Option<TableStyle>,
+    /// Renders only this many rows, see [`Table::set_max_rows`].
+    pub max_rows: Option<usize>,
+    pub columns: Vec<ColumnSpec>,
+    /// Filters that every row must pass.
+    pub filters: Vec<FilterSpec>,
+    /// Sort keys, most significant first.
+    pub sort: Vec<SortSpec>,
+    /// Groups rows by this column, see [`Table::group_by`].
+    pub group_by: Option<ColumnRef>,
+    /// Aggregates shown in subtotal and grand-total rows.
+    pub aggregates: Vec<AggregateSpec>,
+    /// Adds a subtotal row after each group, see [`Table::set_subtotals`].
+    pub subtotals: bool,
+    /// Adds a grand-total row, see [`Table::set_grand_total`].
+    pub grand_total: bool,
+}
+
+/// A column given by header name or 0-based index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(untagged))]
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+/// Presentation settings for one column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct ColumnSpec {
+    pub column: ColumnRef,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub align: Option<Alignment>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub width: Option<WidthConstraint>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wrap: Option<WrapPolicy>,
+    /// Formatter spec, see [`FormatterRegistry`](crate::FormatterRegistry).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub format: Option<String>,
+    /// URL template where `{value}` stands for the content, see [`Table::link_column`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub link: Option<String>,
+}
+
+impl ColumnSpec {
+    #[must_use]
+    pub fn new(column: ColumnRef) -> Self {
+        Self {
+            column,
+            align: None,
+            width: None,
+            wrap: None,
+            format: None,
+            link: None,
+        }
+    }
+}
+
+/// Keeps rows whose column equals `equals` and/or contains `contains`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct FilterSpec {
+    pub column: ColumnRef,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub equals: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contains: Option<String>,
+}
+
+impl FilterSpec {
+    #[must_use]
+    pub fn new(column: ColumnRef) -> Self {
+        Self {
+            column,
+            equals: None,
+            contains: None,
+        }
+    }
+}
+
+/// One sort key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct SortSpec {
+    pub column: ColumnRef,
+    /// Compares the content as numbers, see [`Table::sort_num`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub numeric: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub descending: bool,
+}
+
+impl SortSpec {
+    #[must_use]
+    pub fn new(column: ColumnRef) -> Self {
+        Self {
+            column,
+            numeric: false,
+            descending: false,
+        }
+    }
+}
+
+/// An aggregate computed for one column, see [`Table::aggregate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct AggregateSpec {
+    pub column: ColumnRef,
+    pub function: Aggregate,
+}
+
+/// Why a [`TableSpec`] could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecError {
+    /// No header has this name
+    UnknownColumn(String),
+    /// The formatter spec does not resolve
+    UnknownFormatter(String),
+}
+
+impl core::fmt::Display for SpecError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            SpecError::UnknownColumn(name) => write!(f, "unknown column `{name}`"),
+            SpecError::UnknownFormatter(spec) => write!(f, "unknown formatter `{spec}`"),
+        }
+    }
+}
+
+impl core::error::Error for SpecError {}
+
+impl Table {
+    /// Applies a [`TableSpec`]: style and row limit, per-column settings, then filters,
+    /// then sorts, then grouping and aggregates.
+    ///
+    /// Every column name and formatter is checked before anything changes, so on
+    /// error the table is left as it was.
+    ///
+    /// # Errors
+    /// Returns [`SpecError`] if a column name matches no header or a formatter spec
+    /// does not resolve.
+    pub fn apply_spec(&mut self, spec: &TableSpec) -> Result<(), SpecError> {
+        let columns = spec
+            .columns
+            .iter()
+            .map(|column| self.resolve_column(&column.column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let filters = spec
+            .filters
+            .iter()
+            .map(|filter| self.resolve_column(&filter.column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let sorts = spec
+            .sort
+            .iter()
+            .map(|sort| self.resolve_column(&sort.column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let group = spec
+            .group_by
+            .as_ref()
+            .map(|column| self.resolve_column(column))
+            .transpose()?;
+        let aggregates = spec
+            .aggregates
+            .iter()
+            .map(|aggregate| self.resolve_column(&aggregate.column))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(format) = spec
+            .columns
+            .iter()
+            .filter_map(|column| column.format.as_deref())
+            .find(|format| self.formatters().resolve(format).is_none())
+        {
+            return Err(SpecError::UnknownFormatter(format.to_string()));
+        }
+
+        if let Some(style) = spec.style {
+            self.set_style(style);
+        }
+        if spec.max_rows.is_some() {
+            self.set_max_rows(spec.max_rows);
+        }
+        for (column, settings) in columns.into_iter().zip(&spec.columns) {
+            if let Some(alignment) = settings.align {
+                self.align(column, alignment);
+            }
+            if let Some(width) = settings.width {
+                self.set_constraint(column, width);
+            }
+            if let Some(policy) = settings.wrap {
+                self.set_wrap_policy(column, policy);
+            }
+            if let Some(format) = &settings.format {
+                self.format_column(column, format);
+            }
+            if let Some(template) = &settings.link {
+                self.link_column(column, template);
+            }
+        }
+        for (column, filter) in filters.into_iter().zip(&spec.filters) {
+            if let Some(value) = &filter.equals {
+                self.filter_eq(column, value);
+            }
+            if let Some(substring) = &filter.contains {
+                self.filter_has(column, substring);
+            }
+        }
+        // Sorts are stable, so applying the least significant key first leaves the
+        // rows ordered by all keys.
+        for (column, sort) in sorts.into_iter().zip(&spec.sort).rev() {
+            match (sort.numeric, sort.descending) {
+                (false, false) => self.sort(column),
+                (false, true) => self.sort_desc(column),
+                (true, false) => self.sort_num(column),
+                (true, true) => self.sort_num_desc(column),
+            }
+        }
+        if let Some(column) = group {
+            self.group_by(column);
+        }
+        for (column, aggregate) in aggregates.into_iter().zip(&spec.aggregates) {
+            self.aggregate(column, aggregate.function);
+        }
+        if spec.subtotals {
+            self.set_subtotals(true);
+        }
+        if spec.grand_total {
+            self.set_grand_total(true);
+        }
+        Ok(())
+    }
+
+    fn resolve_column(&self, column: &ColumnRef) -> Result<usize, SpecError> {
+        match column {
+            ColumnRef::Index(index) => Ok(*index),
+            ColumnRef::Name(name) => self
+                .column_index(name)
+                .ok_or_else(|| SpecError::UnknownColumn(name.clone())),
+        }
+    }
+}
+
+/// Deserializes enums through their `FromStr` impls, so specs use the same names as
+/// the rest of the API.
+#[cfg(feature = "serde")]
+macro_rules! deserialize_from_str {
+    ($($ty:ty => $expected:literal),* $(,)?) => {
+        $(
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <alloc::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                    value.parse().map_err(|()| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Str(&value), &$expected)
+                    })
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+deserialize_from_str! {
+    TableStyle => "a table style such as `modern` or `markdown`",
+    Aggregate => "`sum`, `avg`, `min`, `max` or `count`",
+    JoinKind => "`inner` or `left`",
+    Alignment => "`left`, `center`, `right` or `decimal`",
+    WidthConstraint => "`auto`, `fixed:N`, `min:N`, `max:N`, `wrap:N`, `weight:N` or `N%`",
+    WrapPolicy => "`word`, `character`, `truncate` or `never`",
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Aggregate, AggregateSpec, Alignment, ColumnRef, ColumnSpec, FilterSpec, SortSpec,
+        SpecError, Table, TableSpec, TableStyle, WidthConstraint, WrapPolicy,
+    };
+
+    fn people() -> Table {
+        Table::new()
+            .header(["Name", "Team", "Score"])
+            .row(["Kelana", "red", "7"])
+            .row(["Kata", "blue", "12"])
+            .row(["Cherry", "red", "12"])
+            .row(["Blossom", "green", "3"])
+    }
+
+    fn names(table: &Table) -> Vec<&str> {
+        table
+            .rows()
+            .iter()
+            .map(|row| row.cells()[0].content())
+            .collect()
+    }
+
+    #[test]
+    fn empty_spec_changes_nothing() {
+        let mut table = people();
+        let before = table.render();
+        table.apply_spec(&TableSpec::default()).unwrap();
+        assert_eq!(table.render(), before);
+    }
+
+    #[test]
+    fn columns_by_name_and_index() {
+        let mut table = people();
+        let spec = TableSpec {
+            style: Some(TableStyle::Modern),
+            max_rows: Some(2),
+            columns: vec![
+                ColumnSpec {
+                    align: Some(Alignment::Right),
+                    format: Some("template:{} pts".to_string()),
+                    link: Some("https://scores/{value}".to_string()),
+                    ..ColumnSpec::new(ColumnRef::Name("Score".to_string()))
+                },
+                ColumnSpec {
+                    width: Some(WidthConstraint::Wrap(4)),
+                    wrap: Some(WrapPolicy::Character),
+                    ..ColumnSpec::new(ColumnRef::Index(0))
+                },
+            ],
+            ..TableSpec::default()
+        };
+        table.apply_spec(&spec).unwrap();
+        assert_eq!(table.style(), TableStyle::Modern);
+        assert_eq!(table.column_format(2), Some("template:{} pts"));
+        assert_eq!(table.column_link(2), Some("https://scores/{value}"));
+        assert_eq!(table.constraints()[0], WidthConstraint::Wrap(4));
+        assert_eq!(table.wrap_policy(0), WrapPolicy::Character);
+        assert!(table.render().contains("7 pts"));
+    }
+
+    #[test]
+    fn filters_then_sorts_by_all_keys() {
+        let mut table = people();
+        let spec = TableSpec {
+            filters: vec![FilterSpec {
+                contains: Some("r".to_string()),
+                ..FilterSpec::new(ColumnRef::Name("Team".to_string()))
+            }],
+            sort: vec![
+                SortSpec {
+                    numeric: true,
+                    descending: true,
+                    ..SortSpec::new(ColumnRef::Index(2))
+                },
+                SortSpec::new(ColumnRef::Index(0)),
+            ],
+            ..TableSpec::default()
+        };
+        table.apply_spec(&spec).unwrap();
+        assert_eq!(names(&table), ["Cherry", "Kelana", "Blossom"]);
+    }
+
+    #[test]
+    fn groups_and_aggregates() {
+        let mut table = people();
+        let spec = TableSpec {
+            style: Some(TableStyle::Markdown),
+            group_by: Some(ColumnRef::Name("Team".to_string())),
+            aggregates: vec![AggregateSpec {
+                column: ColumnRef::Name("Score".to_string()),
+                function: Aggregate::Sum,
+            }],
+            subtotals: true,
+            grand_total: true,
+            ..TableSpec::default()
+        };
+        table.apply_spec(&spec).unwrap();
+        assert_eq!(table.group_column(), Some(1));
+        assert_eq!(table.aggregates(), [(2, Aggregate::Sum)]);
+        assert!(table.has_subtotals() && table.has_grand_total());
+        assert!(table.render().contains("| 34 "));
+    }
+
+    #[test]
+    fn errors_leave_table_unchanged() {
+        let mut table = people();
+        let spec = TableSpec {
+            style: Some(TableStyle::Markdown),
+            sort: vec![SortSpec::new(ColumnRef::Name("Age".to_string()))],
+            ..TableSpec::default()
+        };
+        assert_eq!(
+            table.apply_spec(&spec),
+            Err(SpecError::UnknownColumn("Age".to_string()))
+        );
+
+        let spec = TableSpec {
+            style: Some(TableStyle::Markdown),
+            columns: vec![ColumnSpec {
+                format: Some("nope".to_string()),
+                ..ColumnSpec::new(ColumnRef::Index(0))
+            }],
+            ..TableSpec::default()
+        };
+        let error = table.apply_spec(&spec).unwrap_err();
+        assert_eq!(error.to_string(), "unknown formatter `nope`");
+        assert_eq!(table.style(), TableStyle::Classic);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::{Aggregate, ColumnRef, TableSpec, TableStyle, WidthConstraint};
+
+    #[test]
+    fn deserialize_json() {
+        let spec: TableSpec = serde_json::from_str(
+            r#"{
+                "style": "markdown",
+                "columns": [{"column": "Size", "width": "max:30", "align": "r"}],
+                "sort": [{"column": 1, "numeric": true}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(spec.style, Some(TableStyle::Markdown));
+        assert_eq!(spec.columns[0].column, ColumnRef::Name("Size".to_string()));
+        assert_eq!(spec.columns[0].width, Some(WidthConstraint::Max(30)));
+        assert_eq!(spec.sort[0].column, ColumnRef::Index(1));
+        assert!(spec.sort[0].numeric && !spec.sort[0].descending);
+        assert!(spec.filters.is_empty());
+
+        let spec: TableSpec = serde_json::from_str(
+            r#"{"group_by": "Team", "aggregates": [{"column": 2, "function": "avg"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(spec.group_by, Some(ColumnRef::Name("Team".to_string())));
+        assert_eq!(spec.aggregates[0].function, Aggregate::Avg);
+    }
+
+    #[test]
+    fn deserialize_rejects_bad_values() {
+        for json in [
+            r#"{"style": "fancy"}"#,
+            r#"{"columns": [{"column": 0, "width": "huge"}]}"#,
+            r#"{"colour": "red"}"#,
+            r#"{"aggregates": [{"column": 0, "function": "median"}]}"#,
+        ] {
+            assert!(serde_json::from_str::<TableSpec>(json).is_err(), "{json}");
+        }
+    }
+}
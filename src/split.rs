@@ -0,0 +1,137 @@
+use crate::alloc_prelude::*;
+
+/// Options for [`Table::render_split`](crate::Table::render_split), which stacks a
+/// table that is too wide into several narrower chunks of columns.
+///
+/// # Example
+/// ```
+/// use crabular::{SplitOptions, Table};
+///
+/// let table = Table::new()
+///     .header(["ID", "Name", "Q1", "Q2", "Q3", "Q4"])
+///     .row(["1", "Kata", "10", "20", "30", "40"]);
+///
+/// let options = SplitOptions::new(30)
+///     .label_column(1)
+///     .continued_caption("(continued)");
+/// let output = table.render_split(&options);
+/// assert!(output.contains("(continued)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitOptions {
+    max_width: usize,
+    frozen_columns: usize,
+    label_column: Option<usize>,
+    continued_caption: Option<String>,
+}
+
+impl SplitOptions {
+    /// Creates options that split the table so every line fits in `max_width` characters.
+    /// A chunk always holds at least one column, even if that column alone is wider.
+    #[must_use]
+    pub fn new(max_width: usize) -> Self {
+        Self {
+            max_width,
+            frozen_columns: 0,
+            label_column: None,
+            continued_caption: None,
+        }
+    }
+
+    /// Repeats the first `count` columns at the start of every chunk.
+    #[must_use]
+    pub fn frozen_columns(mut self, count: usize) -> Self {
+        self.frozen_columns = count;
+        self
+    }
+
+    /// Repeats the given column at the start of every chunk, after any frozen columns.
+    #[must_use]
+    pub fn label_column(mut self, column: usize) -> Self {
+        self.label_column = Some(column);
+        self
+    }
+
+    /// Prints `caption` on its own line above every chunk after the first.
+    #[must_use]
+    pub fn continued_caption(mut self, caption: &str) -> Self {
+        self.continued_caption = Some(caption.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    #[must_use]
+    pub fn get_frozen_columns(&self) -> usize {
+        self.frozen_columns
+    }
+
+    #[must_use]
+    pub fn get_label_column(&self) -> Option<usize> {
+        self.label_column
+    }
+
+    #[must_use]
+    pub fn get_continued_caption(&self) -> Option<&str> {
+        self.continued_caption.as_deref()
+    }
+
+    /// Returns the columns repeated in every chunk, in display order.
+    pub(crate) fn repeated_columns(&self, num_columns: usize) -> Vec<usize> {
+        let mut repeated: Vec<usize> = (0..self.frozen_columns.min(num_columns)).collect();
+        if let Some(label) = self.label_column
+            && label < num_columns
+            && !repeated.contains(&label)
+        {
+            repeated.push(label);
+        }
+        repeated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SplitOptions;
+
+    #[test]
+    fn new_has_no_repeated_columns() {
+        let options = SplitOptions::new(80);
+        assert_eq!(options.max_width(), 80);
+        assert_eq!(options.get_frozen_columns(), 0);
+        assert_eq!(options.get_label_column(), None);
+        assert_eq!(options.get_continued_caption(), None);
+        assert!(options.repeated_columns(5).is_empty());
+    }
+
+    #[test]
+    fn repeated_columns() {
+        let cases = [
+            (SplitOptions::new(80).frozen_columns(2), 5, vec![0, 1]),
+            (SplitOptions::new(80).label_column(3), 5, vec![3]),
+            (
+                SplitOptions::new(80).frozen_columns(1).label_column(3),
+                5,
+                vec![0, 3],
+            ),
+            (
+                SplitOptions::new(80).frozen_columns(2).label_column(1),
+                5,
+                vec![0, 1],
+            ),
+            (SplitOptions::new(80).label_column(9), 5, vec![]),
+            (SplitOptions::new(80).frozen_columns(9), 2, vec![0, 1]),
+        ];
+        for (options, num_columns, expected) in cases {
+            assert_eq!(options.repeated_columns(num_columns), expected);
+        }
+    }
+
+    #[test]
+    fn continued_caption() {
+        let options = SplitOptions::new(40).continued_caption("(cont.)");
+        assert_eq!(options.get_continued_caption(), Some("(cont.)"));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::alloc_prelude::*;
+use crate::row::Row;
+use crate::table::Table;
+
+/// Renders a table in pieces, created by [`Table::stream`](crate::Table::stream).
+///
+/// Write [`header`](Self::header) once, then [`rows`](Self::rows) for each batch of
+/// rows as it arrives, then [`footer`](Self::footer). The pieces concatenate to the
+/// same output as rendering all rows at once with the same column widths, so only
+/// one batch has to be held in memory.
+///
+/// # Example
+/// ```
+/// use crabular::{Table, TableStyle};
+///
+/// let mut template = Table::new().header(["n", "square"]);
+/// template.set_style(TableStyle::Markdown);
+///
+/// let mut stream = template.stream_with_widths(vec![4, 8]);
+/// print!("{}", stream.header());
+/// for batch in 0..3 {
+///     let rows: Vec<[String; 2]> = (batch * 100..(batch + 1) * 100)
+///         .map(|n| [n.to_string(), (n * n).to_string()])
+///         .collect();
+///     print!("{}", stream.rows(rows));
+/// }
+/// print!("{}", stream.footer());
+/// assert_eq!(stream.rows_written(), 300);
+/// ```
+pub struct TableStream<'a> {
+    table: &'a Table,
+    column_widths: Vec<usize>,
+    last_row: Option<Row>,
+    rows_written: usize,
+}
+
+impl<'a> TableStream<'a> {
+    pub(crate) fn new(table: &'a Table, column_widths: Vec<usize>) -> Self {
+        Self {
+            table,
+            column_widths,
+            last_row: None,
+            rows_written: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn column_widths(&self) -> &[usize] {
+        &self.column_widths
+    }
+
+    /// Returns the number of rows streamed so far.
+    #[must_use]
+    pub fn rows_written(&self) -> usize {
+        self.rows_written
+    }
+
+    /// Renders the top border and the header row.
+    #[must_use]
+    pub fn header(&self) -> String {
+        let output = self.table.render_head(&self.column_widths, None);
+        self.table.render_options().apply_to_lines(output)
+    }
+
+    /// Renders the next batch of rows. Rows are prepared as on insertion, so
+    /// truncation, ANSI stripping, tab expansion and column formatters apply.
+    pub fn rows<I, R>(&mut self, rows: I) -> String
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<Row>,
+    {
+        let rows: Vec<Row> = rows.into_iter().map(Into::into).collect();
+        let Some(last) = rows.last().cloned() else {
+            return String::new();
+        };
+        let count = rows.len();
+        let output = self.table.render_stream_rows(
+            &self.column_widths,
+            rows,
+            self.rows_written,
+            self.last_row.as_ref(),
+        );
+        self.rows_written += count;
+        self.last_row = Some(last);
+        self.table.render_options().apply_to_lines(output)
+    }
+
+    /// Renders the bottom border.
+    #[must_use]
+    pub fn footer(&self) -> String {
+        let last_row = self.last_row.as_ref().or(self.table.headers());
+        let output = self.table.render_tail(&self.column_widths, last_row);
+        self.table.render_options().apply_to_lines(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MarkdownMode, RenderOptions, Table, TableStyle};
+
+    fn people() -> Table {
+        Table::new()
+            .header(["Name", "Age"])
+            .row(["Kelana", "30"])
+            .row(["Kata", "25"])
+            .row(["Cherry Blossom", "35"])
+    }
+
+    fn streamed(table: &Table, batch: usize) -> String {
+        let mut stream = table.stream();
+        let mut output = stream.header();
+        for rows in table.rows().chunks(batch) {
+            output.push_str(&stream.rows(rows.to_vec()));
+        }
+        output.push_str(&stream.footer());
+        output
+    }
+
+    #[test]
+    fn matches_render_in_every_style() {
+        let styles = [
+            TableStyle::Classic,
+            TableStyle::Modern,
+            TableStyle::Minimal,
+            TableStyle::Compact,
+            TableStyle::Markdown,
+            TableStyle::Rest,
+            TableStyle::AsciiDoc,
+            TableStyle::Org,
+        ];
+        for style in styles {
+            let mut table = people();
+            table.set_style(style);
+            for batch in 1..=3 {
+                assert_eq!(
+                    streamed(&table, batch),
+                    table.render(),
+                    "{style:?} by {batch}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_compact_markdown_and_line_options() {
+        let mut table = people();
+        table.set_style(TableStyle::Markdown);
+        table.set_render_options(
+            RenderOptions::new()
+                .markdown(MarkdownMode::Compact)
+                .line_prefix("// "),
+        );
+        assert_eq!(streamed(&table, 2), table.render());
+    }
+
+    #[test]
+    fn later_rows_keep_the_sample_widths() {
+        let sample = Table::new().header(["ID", "Name"]).row(["1", "Kata"]);
+        let mut stream = sample.stream();
+        assert_eq!(stream.column_widths(), [2, 4]);
+        let rows = stream.rows([["22", "Cherry Blossom", "extra"]]);
+        assert_eq!(rows, "| 22  | C... |\n");
+        assert_eq!(stream.rows(Vec::<[&str; 2]>::new()), "");
+        assert_eq!(stream.rows_written(), 1);
+    }
+
+    #[test]
+    fn empty_stream_has_header_and_footer() {
+        let table = Table::new().header(["A"]);
+        let stream = table.stream();
+        assert_eq!(stream.header() + &stream.footer(), table.render());
+    }
+}
@@ -1,5 +1,7 @@
 use core::str::FromStr;
 
+use crate::padding::Padding;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TableStyle {
     #[default]
@@ -8,6 +10,15 @@ pub enum TableStyle {
     Minimal,
     Compact,
     Markdown,
+    /// reStructuredText grid table, with `=` under the header and a rule between rows
+    Rest,
+    /// `AsciiDoc` `|===` table
+    AsciiDoc,
+    /// Emacs org-mode table, with `|---+---|` rules
+    Org,
+    /// Columns separated by two spaces, with no borders or rules and no trailing
+    /// whitespace, like `column -t`, so output stays easy to split with `awk` or `cut`
+    Plain,
 }
 
 impl FromStr for TableStyle {
@@ -20,6 +31,10 @@ impl FromStr for TableStyle {
             "minimal" => Ok(TableStyle::Minimal),
             "compact" => Ok(TableStyle::Compact),
             "markdown" => Ok(TableStyle::Markdown),
+            "rest" | "rst" | "restructuredtext" => Ok(TableStyle::Rest),
+            "asciidoc" | "adoc" => Ok(TableStyle::AsciiDoc),
+            "org" | "orgmode" | "org-mode" => Ok(TableStyle::Org),
+            "plain" | "plainspace" => Ok(TableStyle::Plain),
             _ => Err(()),
         }
     }
@@ -38,10 +53,47 @@ pub struct BorderChars {
     pub right_cross: &'static str,
     pub bottom_cross: &'static str,
     pub cross: &'static str,
+    /// Heavier rule used above summary rows such as the grand total.
+    pub double_horizontal: &'static str,
+    pub double_left_cross: &'static str,
+    pub double_right_cross: &'static str,
+    pub double_top_cross: &'static str,
+    pub double_bottom_cross: &'static str,
+    pub double_cross: &'static str,
+    /// Rule between the header row and the body. Most styles use the regular rule;
+    /// reStructuredText uses its `=` rule.
+    pub header_horizontal: &'static str,
+    pub header_left_cross: &'static str,
+    pub header_right_cross: &'static str,
+    pub header_top_cross: &'static str,
+    pub header_bottom_cross: &'static str,
+    pub header_cross: &'static str,
 }
 
 impl TableStyle {
+    /// Cell padding used unless the table sets its own: none for `Compact` and
+    /// `Plain`, one space on each side for the other styles.
+    #[must_use]
+    pub const fn default_padding(self) -> Padding {
+        match self {
+            TableStyle::Compact | TableStyle::Plain => Padding::uniform(0),
+            _ => Padding::uniform(1),
+        }
+    }
+
+    /// Extra space after each column but the last, used unless the table sets its own:
+    /// none for `Compact`, two spaces for `Plain` and one for the other styles.
+    #[must_use]
+    pub const fn default_spacing(self) -> usize {
+        match self {
+            TableStyle::Compact => 0,
+            TableStyle::Plain => 2,
+            _ => 1,
+        }
+    }
+
     #[must_use]
+    #[allow(clippy::too_many_lines)]
     pub fn border_chars(self) -> BorderChars {
         match self {
             TableStyle::Classic => BorderChars {
@@ -56,6 +108,27 @@ impl TableStyle {
                 right_cross: "+",
                 bottom_cross: "+",
                 cross: "+",
+                double_horizontal: "=",
+                double_left_cross: "+",
+                double_right_cross: "+",
+                double_top_cross: "+",
+                double_bottom_cross: "+",
+                double_cross: "+",
+                header_horizontal: "-",
+                header_left_cross: "+",
+                header_right_cross: "+",
+                header_top_cross: "+",
+                header_bottom_cross: "+",
+                header_cross: "+",
+            },
+            TableStyle::Rest => BorderChars {
+                header_horizontal: "=",
+                header_left_cross: "+",
+                header_right_cross: "+",
+                header_top_cross: "+",
+                header_bottom_cross: "+",
+                header_cross: "+",
+                ..TableStyle::Classic.border_chars()
             },
             TableStyle::Modern => BorderChars {
                 vertical: "│",
@@ -69,6 +142,18 @@ impl TableStyle {
                 right_cross: "┤",
                 bottom_cross: "┴",
                 cross: "┼",
+                double_horizontal: "═",
+                double_left_cross: "╞",
+                double_right_cross: "╡",
+                double_top_cross: "╤",
+                double_bottom_cross: "╧",
+                double_cross: "╪",
+                header_horizontal: "─",
+                header_left_cross: "├",
+                header_right_cross: "┤",
+                header_top_cross: "┬",
+                header_bottom_cross: "┴",
+                header_cross: "┼",
             },
             TableStyle::Minimal => BorderChars {
                 vertical: " ",
@@ -82,21 +167,64 @@ impl TableStyle {
                 right_cross: "─",
                 bottom_cross: " ",
                 cross: "─",
+                double_horizontal: "═",
+                double_left_cross: "═",
+                double_right_cross: "═",
+                double_top_cross: " ",
+                double_bottom_cross: " ",
+                double_cross: "═",
+                header_horizontal: "─",
+                header_left_cross: "─",
+                header_right_cross: "─",
+                header_top_cross: " ",
+                header_bottom_cross: " ",
+                header_cross: "─",
             },
             TableStyle::Compact => BorderChars {
                 vertical: "│",
-                horizontal: "─",
-                top_left: " ",
-                top_right: " ",
-                bottom_left: " ",
-                bottom_right: " ",
-                top_cross: " ",
-                left_cross: "─",
-                right_cross: "─",
-                bottom_cross: " ",
                 cross: "┼",
+                double_cross: "╪",
+                header_cross: "┼",
+                ..TableStyle::Minimal.border_chars()
             },
-            TableStyle::Markdown => BorderChars {
+            TableStyle::Org => BorderChars {
+                top_cross: "+",
+                bottom_cross: "+",
+                cross: "+",
+                double_top_cross: "+",
+                double_bottom_cross: "+",
+                double_cross: "+",
+                header_top_cross: "+",
+                header_bottom_cross: "+",
+                header_cross: "+",
+                ..TableStyle::Markdown.border_chars()
+            },
+            TableStyle::Plain => BorderChars {
+                vertical: "",
+                horizontal: "",
+                top_left: "",
+                top_right: "",
+                bottom_left: "",
+                bottom_right: "",
+                top_cross: "",
+                left_cross: "",
+                right_cross: "",
+                bottom_cross: "",
+                cross: "",
+                double_horizontal: "",
+                double_left_cross: "",
+                double_right_cross: "",
+                double_top_cross: "",
+                double_bottom_cross: "",
+                double_cross: "",
+                header_horizontal: "",
+                header_left_cross: "",
+                header_right_cross: "",
+                header_top_cross: "",
+                header_bottom_cross: "",
+                header_cross: "",
+            },
+            TableStyle::Markdown | TableStyle::AsciiDoc => BorderChars {
                 vertical: "|",
                 horizontal: "-",
                 top_left: "|",
@@ -108,6 +236,18 @@ impl TableStyle {
                 right_cross: "|",
                 bottom_cross: "|",
                 cross: "|",
+                double_horizontal: "-",
+                double_left_cross: "|",
+                double_right_cross: "|",
+                double_top_cross: "|",
+                double_bottom_cross: "|",
+                double_cross: "|",
+                header_horizontal: "-",
+                header_left_cross: "|",
+                header_right_cross: "|",
+                header_top_cross: "|",
+                header_bottom_cross: "|",
+                header_cross: "|",
             },
         }
     }
@@ -115,7 +255,7 @@ impl TableStyle {
 
 #[cfg(test)]
 mod tests {
-    use crate::TableStyle;
+    use crate::{Padding, TableStyle};
 
     #[test]
     fn variants_equality() {
@@ -149,6 +289,7 @@ mod tests {
         assert_eq!(chars.horizontal, "-");
         assert_eq!(chars.top_left, "+");
         assert_eq!(chars.cross, "+");
+        assert_eq!(chars.double_horizontal, "=");
     }
 
     #[test]
@@ -158,6 +299,8 @@ mod tests {
         assert_eq!(chars.horizontal, "─");
         assert_eq!(chars.top_left, "┌");
         assert_eq!(chars.cross, "┼");
+        assert_eq!(chars.double_horizontal, "═");
+        assert_eq!(chars.double_cross, "╪");
     }
 
     #[test]
@@ -180,6 +323,45 @@ mod tests {
         assert_eq!(chars.right_cross, "─");
     }
 
+    #[test]
+    fn default_padding_and_spacing() {
+        assert_eq!(TableStyle::Compact.default_padding(), Padding::uniform(0));
+        assert_eq!(TableStyle::Compact.default_spacing(), 0);
+        assert_eq!(TableStyle::Classic.default_padding(), Padding::default());
+        assert_eq!(TableStyle::Markdown.default_spacing(), 1);
+        assert_eq!(TableStyle::Plain.default_padding(), Padding::uniform(0));
+        assert_eq!(TableStyle::Plain.default_spacing(), 2);
+    }
+
+    #[test]
+    fn border_chars_rest() {
+        let chars = TableStyle::Rest.border_chars();
+        assert_eq!(chars.vertical, "|");
+        assert_eq!(chars.cross, "+");
+        assert_eq!(chars.double_horizontal, "=");
+        assert_eq!(chars.header_horizontal, "=");
+        assert_eq!(TableStyle::Classic.border_chars().header_horizontal, "-");
+    }
+
+    #[test]
+    fn border_chars_org() {
+        let chars = TableStyle::Org.border_chars();
+        assert_eq!(chars.vertical, "|");
+        assert_eq!(chars.left_cross, "|");
+        assert_eq!(chars.cross, "+");
+        assert_eq!(chars.double_horizontal, "-");
+    }
+
+    #[test]
+    fn from_str_documentation_styles() {
+        assert_eq!("rst".parse(), Ok(TableStyle::Rest));
+        assert_eq!("reST".parse(), Ok(TableStyle::Rest));
+        assert_eq!("AsciiDoc".parse(), Ok(TableStyle::AsciiDoc));
+        assert_eq!("adoc".parse(), Ok(TableStyle::AsciiDoc));
+        assert_eq!("org-mode".parse(), Ok(TableStyle::Org));
+        assert_eq!("plainspace".parse(), Ok(TableStyle::Plain));
+    }
+
     #[test]
     fn border_chars_markdown() {
         let chars = TableStyle::Markdown.border_chars();
@@ -1,25 +1,163 @@
+use crate::HashMap;
+use crate::aggregate::Aggregate;
 use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::ansi;
+use crate::badge::Badges;
+use crate::border_visibility::BorderVisibility;
 use crate::cell::Cell;
+use crate::color::Color;
+use crate::column_type::ColumnType;
 use crate::constraint::WidthConstraint;
-use crate::padding::Padding;
+use crate::csv::CsvOptions;
+use crate::diff::DiffTable;
+use crate::fill_mode::FillMode;
+use crate::fold::fold;
+use crate::formatter::{Formatter, FormatterRegistry};
+#[cfg(feature = "fuzzy")]
+use crate::fuzzy;
+use crate::header_style::HeaderStyle;
+use crate::html;
+use crate::json;
+use crate::link;
+use crate::messages::Messages;
+#[cfg(feature = "unicode-normalization")]
+use crate::normalization::Normalization;
+use crate::padding::{Padding, VerticalPadding};
+use crate::page::Pages;
+use crate::parallel;
+#[cfg(feature = "instrumentation")]
+use crate::perf::RenderStats;
+use crate::provider::RowProvider;
+use crate::render_options::{MarkdownMode, NoteMode, RenderOptions};
+use crate::render_report::RenderReport;
 use crate::row::Row;
+use crate::split::SplitOptions;
+use crate::stream::TableStream;
 use crate::style::{BorderChars, TableStyle};
+use crate::text;
+use crate::text_transform::TextTransform;
+use crate::theme::Theme;
+use crate::validation::{TableError, span_width};
 use crate::vertical_alignment::VerticalAlignment;
+use crate::wrap_policy::WrapPolicy;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
 use core::cell::RefCell;
+use core::ops::Range;
+
+/// Marker appended to truncated content unless configured otherwise.
+const DEFAULT_TRUNCATION_MARKER: &str = "...";
+const DEFAULT_TAB_WIDTH: usize = 4;
+const SELECTED_MARKER: &str = "[x]";
+const UNSELECTED_MARKER: &str = "[ ]";
+
+/// Horizontal rule drawn between two body rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    /// Regular separator, used between groups and above subtotals.
+    Single,
+    /// Heavier separator, used above the grand total.
+    Double,
+    /// Separator below the header row, drawn with the style's header characters.
+    Header,
+}
+
+/// A body row in render order, along with the rule drawn above it.
+struct BodyRow<'a> {
+    rule: Option<Rule>,
+    row: Cow<'a, Row>,
+    /// False for rows spanning the whole table, such as the elision line, which
+    /// only widen the last column when they would not fit otherwise.
+    measured: bool,
+    /// Subtotal or grand-total row, whose blank cells stay blank.
+    summary: bool,
+    /// Table rows the line stands for: one for a row, the group's size for a collapsed
+    /// group and none for summaries and messages. `max_rows` counts these.
+    rows: usize,
+    /// Colors and hyperlinks by starting column, filled in by `format_body`.
+    decorations: Vec<Decoration>,
+}
+
+/// How a body cell's text is drawn beyond its content.
+#[derive(Debug, Clone, Default)]
+struct Decoration {
+    /// OSC 8 hyperlink target, see [`Table::link_column`].
+    url: Option<String>,
+    /// Foreground color, e.g. of a badge.
+    color: Option<Color>,
+}
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct Table {
     rows: Vec<Row>,
+    /// Number of rows ever added, which numbers the next row's origin.
+    added_rows: usize,
     headers: Option<Row>,
     style: TableStyle,
     constraints: Vec<WidthConstraint>,
-    padding: Padding,
-    column_spacing: usize,
+    /// `None` uses the style's default padding.
+    padding: Option<Padding>,
+    vertical_padding: VerticalPadding,
+    /// `None` uses the style's default spacing.
+    column_spacing: Option<usize>,
     column_alignments: Vec<Alignment>,
+    wrap_policies: Vec<Option<WrapPolicy>>,
+    column_formats: Vec<Option<String>>,
+    column_transforms: Vec<Option<TextTransform>>,
+    column_links: Vec<Option<String>>,
+    formatters: FormatterRegistry,
+    empty_placeholder: String,
+    hyphenate: bool,
     vertical_alignment: VerticalAlignment,
     truncate: Option<usize>,
+    truncation_marker: Option<String>,
+    strip_ansi: bool,
+    tab_width: usize,
+    max_rows: Option<usize>,
+    header_style: HeaderStyle,
+    border_visibility: BorderVisibility,
+    /// Background of every other body row.
+    zebra: Option<Color>,
+    /// Foreground of body cells by the detected type of their column.
+    type_colors: Vec<(ColumnType, Color)>,
+    render_options: RenderOptions,
+    messages: Messages,
+    group_column: Option<usize>,
+    /// Keys of the groups shown as a single line.
+    collapsed_groups: BTreeSet<String>,
+    aggregates: Vec<(usize, Aggregate)>,
+    subtotals: bool,
+    grand_total: bool,
+    column_groups: Vec<(Range<usize>, Option<String>)>,
+    strict_columns: bool,
+    /// Column count set with `set_expected_cols`, instead of the headers'.
+    expected_cols: Option<usize>,
+    /// Rows refused by strict column mode since the last `take_rejected_rows`.
+    rejected_rows: Vec<TableError>,
+    /// Origins of the selected rows. Keys past the table's rows select rows of a
+    /// [`RowProvider`] by index in `render_viewport`.
+    selected: BTreeSet<usize>,
+    selection_column: bool,
     /// Cached column widths for repeated renders.
     /// Uses interior mutability to allow caching in `&self` methods.
     cached_widths: RefCell<Option<Vec<usize>>>,
+    /// Widest cell of each column over `rows`, widened as rows are added and measured
+    /// again only after rows are removed or changed. `None` until the next render.
+    row_widths: RefCell<Option<Vec<usize>>>,
+    generation: u64,
+    memoize: bool,
+    /// Contents shared by the cells of added rows while interning is on.
+    interned: Option<BTreeSet<Arc<str>>>,
+    /// Output of the last `render` while memoization is on.
+    cached_output: RefCell<Option<String>>,
+    /// Issues of the last `render`.
+    last_report: RefCell<RenderReport>,
+    #[cfg(feature = "instrumentation")]
+    stats: RefCell<RenderStats>,
+    #[cfg(feature = "unicode-normalization")]
+    normalization: Normalization,
 }
 
 impl Table {
@@ -27,142 +165,516 @@ impl Table {
     pub fn new() -> Self {
         Self {
             rows: Vec::new(),
+            added_rows: 0,
             headers: None,
             style: TableStyle::Classic,
             constraints: Vec::new(),
-            padding: Padding::default(),
-            column_spacing: 1,
+            padding: None,
+            vertical_padding: VerticalPadding::default(),
+            column_spacing: None,
             column_alignments: Vec::new(),
+            wrap_policies: Vec::new(),
+            column_formats: Vec::new(),
+            column_transforms: Vec::new(),
+            column_links: Vec::new(),
+            formatters: FormatterRegistry::new(),
+            empty_placeholder: String::new(),
+            hyphenate: false,
             vertical_alignment: VerticalAlignment::Top,
             truncate: None,
+            truncation_marker: Some(DEFAULT_TRUNCATION_MARKER.to_string()),
+            strip_ansi: false,
+            tab_width: DEFAULT_TAB_WIDTH,
+            max_rows: None,
+            header_style: HeaderStyle::default(),
+            border_visibility: BorderVisibility::all(),
+            zebra: None,
+            type_colors: Vec::new(),
+            render_options: RenderOptions::default(),
+            messages: Messages::default(),
+            group_column: None,
+            collapsed_groups: BTreeSet::new(),
+            aggregates: Vec::new(),
+            subtotals: false,
+            grand_total: false,
+            column_groups: Vec::new(),
+            strict_columns: false,
+            expected_cols: None,
+            rejected_rows: Vec::new(),
+            selected: BTreeSet::new(),
+            selection_column: false,
             cached_widths: RefCell::new(None),
+            row_widths: RefCell::new(None),
+            generation: 0,
+            memoize: false,
+            interned: None,
+            cached_output: RefCell::new(None),
+            last_report: RefCell::new(RenderReport::default()),
+            #[cfg(feature = "instrumentation")]
+            stats: RefCell::new(RenderStats::default()),
+            #[cfg(feature = "unicode-normalization")]
+            normalization: Normalization::None,
+        }
+    }
+
+    /// Creates an empty table with room for `rows` rows and the column settings of
+    /// `cols` columns, so loading a table of known size does not reallocate as it grows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::with_capacity(10_000, 3);
+    /// for n in 0..10_000 {
+    ///     table.add_row((n, n * 2, n * 3));
+    /// }
+    /// assert_eq!(table.len(), 10_000);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(rows: usize, cols: usize) -> Self {
+        let mut table = Self::new();
+        table.rows.reserve(rows);
+        table.column_alignments.reserve(cols);
+        table.constraints.reserve(cols);
+        table
+    }
+
+    /// Builds a table from row chunks produced independently, for example by worker
+    /// threads each parsing one slice of a large input. Chunks are appended in the
+    /// order given, so the result is the same as loading the rows sequentially.
+    ///
+    /// Rows are `Send`, so chunks can be built on any thread; only the merge runs on
+    /// the calling thread, and it moves rows rather than copying their content.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let lines = ["1,a", "2,b", "3,c", "4,d", "5,e"];
+    /// let chunks: Vec<Vec<Vec<&str>>> = std::thread::scope(|scope| {
+    ///     let workers: Vec<_> = lines
+    ///         .chunks(2)
+    ///         .map(|chunk| {
+    ///             scope.spawn(move || chunk.iter().map(|line| line.split(',').collect()).collect())
+    ///         })
+    ///         .collect();
+    ///     workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+    /// });
+    ///
+    /// let table = Table::from_parallel_chunks(chunks);
+    /// assert_eq!(table.len(), 5);
+    /// assert_eq!(table.rows()[2].cells()[1].content(), "c");
+    /// ```
+    #[must_use]
+    pub fn from_parallel_chunks<I, R>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<R>>,
+        R: Into<Row>,
+    {
+        let chunks: Vec<Vec<R>> = chunks.into_iter().collect();
+        let mut table = Self::new();
+        table.rows.reserve(chunks.iter().map(Vec::len).sum());
+        for chunk in chunks {
+            for row in chunk {
+                let row = table.prepare_new_row(row.into());
+                table.rows.push(row);
+            }
+        }
+        table.invalidate_cache();
+        table
+    }
+
+    /// Records a change to the table: bumps the generation and invalidates the
+    /// cached column widths, the tracked row widths and the rendered output.
+    fn invalidate_cache(&mut self) {
+        *self.row_widths.get_mut() = None;
+        self.invalidate_output();
+    }
+
+    /// Records a change that leaves the tracked row widths valid, such as adding or
+    /// reordering rows, so the next render does not measure every row again.
+    fn invalidate_output(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.clear_caches();
+    }
+
+    /// Widens the tracked row widths to fit a row being added.
+    fn track_row_widths(&mut self, row: &Row) {
+        let width = self.text_width();
+        if let Some(widths) = self.row_widths.get_mut().take() {
+            *self.row_widths.get_mut() = Some(Self::widen(width, widths, row));
         }
     }
 
-    /// Invalidates the cached column widths.
-    fn invalidate_cache(&self) {
+    fn clear_caches(&self) {
         *self.cached_widths.borrow_mut() = None;
+        *self.cached_output.borrow_mut() = None;
+    }
+
+    /// Returns a counter that changes whenever the table is modified, including its
+    /// options. Embedders can compare it with the value seen at their last render to
+    /// skip unnecessary work, without hashing the contents.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new().row(["1"]);
+    /// let seen = table.generation();
+    /// assert_eq!(table.generation(), seen);
+    ///
+    /// table.add_row(["2"]);
+    /// assert_ne!(table.generation(), seen);
+    /// ```
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
     }
 
     pub fn set_headers<R: Into<Row>>(&mut self, headers: R) {
-        let row = headers.into();
-        let row = if let Some(limit) = self.truncate {
-            Self::truncate_row(&row, limit)
-        } else {
-            row
-        };
+        let row = self.prepare_row(headers.into());
         self.headers = Some(row);
         self.invalidate_cache();
     }
 
+    /// Adds a row at the end. In [strict column mode](Self::set_strict_columns) a row
+    /// of the wrong length is not added; the error is kept in
+    /// [`rejected_rows`](Self::rejected_rows) instead.
     pub fn add_row<R: Into<Row>>(&mut self, row: R) {
+        if let Err(error) = self.try_add_row(row) {
+            self.rejected_rows.push(error);
+        }
+    }
+
+    /// Adds a row at the end, or returns why strict column mode refuses it.
+    ///
+    /// # Errors
+    /// Returns [`TableError::RowLength`] if strict column mode is on and the row covers
+    /// a different number of columns than expected.
+    pub fn try_add_row<R: Into<Row>>(&mut self, row: R) -> Result<(), TableError> {
         let row = row.into();
-        let row = if let Some(limit) = self.truncate {
-            Self::truncate_row(&row, limit)
-        } else {
-            row
-        };
+        self.check_columns(&row, self.rows.len())?;
+        let row = self.prepare_new_row(row);
+        self.track_row_widths(&row);
         self.rows.push(row);
-        self.invalidate_cache();
+        self.invalidate_output();
+        Ok(())
     }
 
     pub fn insert_row<R: Into<Row>>(&mut self, index: usize, row: R) {
         let row = row.into();
-        let row = if let Some(limit) = self.truncate {
-            Self::truncate_row(&row, limit)
-        } else {
-            row
+        if let Err(error) = self.check_columns(&row, index) {
+            self.rejected_rows.push(error);
+            return;
+        }
+        let row = self.prepare_new_row(row);
+        self.track_row_widths(&row);
+        self.rows.insert(index, row);
+        self.invalidate_output();
+    }
+
+    /// Refuses rows that cover a different number of columns than the headers, or
+    /// than the first row without headers, so mistakes surface instead of rendering
+    /// as a ragged table. Spanned cells count every column they cover.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, TableError};
+    /// let mut table = Table::new().header(["Name", "Age"]);
+    /// table.set_strict_columns(true);
+    /// table.add_row(["Kata", "30"]);
+    /// table.add_row(["Kelana"]);
+    /// assert_eq!(table.len(), 1);
+    /// assert_eq!(
+    ///     table.take_rejected_rows(),
+    ///     [TableError::RowLength { row: 1, expected: 2, found: 1 }]
+    /// );
+    ///
+    /// assert!(table.try_add_row(["Kelana", "28", "Jakarta"]).is_err());
+    /// assert!(table.try_add_row(["Kelana", "28"]).is_ok());
+    /// ```
+    pub fn set_strict_columns(&mut self, strict: bool) {
+        self.strict_columns = strict;
+        self.invalidate_output();
+    }
+
+    #[must_use]
+    pub fn strict_columns(&self) -> bool {
+        self.strict_columns
+    }
+
+    /// Turns strict column mode on, expecting every row to cover `cols` columns
+    /// whatever the headers.
+    pub fn set_expected_cols(&mut self, cols: usize) {
+        self.strict_columns = true;
+        self.expected_cols = Some(cols);
+        self.invalidate_output();
+    }
+
+    /// Returns the number of columns rows must cover in strict column mode, or `None`
+    /// when the mode is off or nothing sets the count yet.
+    #[must_use]
+    pub fn expected_cols(&self) -> Option<usize> {
+        if !self.strict_columns {
+            return None;
+        }
+        self.expected_cols.or_else(|| {
+            self.headers
+                .as_ref()
+                .or_else(|| self.rows.first())
+                .map(span_width)
+        })
+    }
+
+    /// Returns the errors of the rows strict column mode refused, oldest first.
+    #[must_use]
+    pub fn rejected_rows(&self) -> &[TableError] {
+        &self.rejected_rows
+    }
+
+    /// Returns and forgets the errors of the rows strict column mode refused.
+    pub fn take_rejected_rows(&mut self) -> Vec<TableError> {
+        core::mem::take(&mut self.rejected_rows)
+    }
+
+    /// Evens out ragged rows to the header width, or to the expected column count set
+    /// with [`set_expected_cols`](Self::set_expected_cols), or to the widest row without
+    /// either. Rendering pads short rows with blank cells anyway; this makes the rows
+    /// themselves regular once, for exports and later column operations. Returns the
+    /// number of rows changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{FillMode, Table};
+    /// let mut table = Table::new()
+    ///     .header(["Name", "Age"])
+    ///     .row(["Kata"])
+    ///     .row(["Kelana", "28", "Jakarta"]);
+    /// assert_eq!(table.normalize_rows(FillMode::PadEmpty), 1);
+    /// assert_eq!(table.rows()[0].len(), 2);
+    /// assert_eq!(table.rows()[1].len(), 3);
+    ///
+    /// table.normalize_rows(FillMode::TruncateExtra);
+    /// assert_eq!(table.to_jsonl().lines().nth(1), Some(r#"{"Name":"Kelana","Age":"28"}"#));
+    /// ```
+    pub fn normalize_rows(&mut self, mode: FillMode) -> usize {
+        let Some(width) = self
+            .expected_cols
+            .or_else(|| self.headers.as_ref().map(span_width))
+            .or_else(|| self.rows.iter().map(span_width).max())
+        else {
+            return 0;
+        };
+        let mut changed = 0;
+        for row in &mut self.rows {
+            let found = span_width(row);
+            if found < width && mode.pads() {
+                for _ in found..width {
+                    row.push(Cell::new("", Alignment::Left));
+                }
+            } else if found > width && mode.truncates() {
+                let mut found = found;
+                while found > width
+                    && let Some(last) = row.len().checked_sub(1)
+                {
+                    let span = row.cells()[last].span();
+                    let start = found - span;
+                    if start >= width {
+                        row.remove(last);
+                        found = start;
+                    } else if let Some(cell) = row.cell_mut(last) {
+                        cell.set_span(width - start);
+                        found = width;
+                    }
+                }
+            } else {
+                continue;
+            }
+            changed += 1;
+        }
+        if changed > 0 {
+            self.invalidate_cache();
+        }
+        changed
+    }
+
+    /// Checks a row about to be added at `index` against strict column mode.
+    fn check_columns(&self, row: &Row, index: usize) -> Result<(), TableError> {
+        let Some(expected) = self.expected_cols() else {
+            return Ok(());
         };
+        let found = span_width(row);
+        if found == expected {
+            Ok(())
+        } else {
+            Err(TableError::RowLength {
+                row: index,
+                expected,
+                found,
+            })
+        }
+    }
+
+    /// Prepares a row being added and numbers it for [`current_order`](Self::current_order).
+    fn prepare_new_row(&mut self, row: Row) -> Row {
+        let mut row = self.prepare_row(row);
+        if let Some(strings) = &mut self.interned {
+            for index in 0..row.len() {
+                if let Some(cell) = row.cell_mut(index) {
+                    cell.intern(strings);
+                }
+            }
+        }
+        row.set_origin(self.added_rows);
+        row.set_parent(None);
+        self.added_rows += 1;
+        row
+    }
+
+    /// Adds `row` as a child of the row at index `parent`, after the parent's existing
+    /// children and their descendants, and returns its index. Returns `None` without
+    /// adding the row if `parent` is out of range or strict column mode refuses it.
+    ///
+    /// Once a table has child rows, the first column renders as a tree with `├─` and
+    /// `└─` branches. The tree follows the current row order, so sorting may scatter it;
+    /// a row whose parent was removed or filtered out is drawn as a root.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, TableStyle};
+    /// let mut table = Table::new();
+    /// table.set_style(TableStyle::Minimal);
+    /// table.set_headers(["Path", "Size"]);
+    /// table.add_row(["src", ""]);
+    /// let text = table.add_child_row(0, ["text.rs", "12K"]);
+    /// table.add_child_row(0, ["table", ""]);
+    /// table.add_child_row(2, ["row.rs", "4K"]);
+    /// assert_eq!(text, Some(1));
+    ///
+    /// let output = table.render();
+    /// let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    /// assert_eq!(
+    ///     lines[2..],
+    ///     [
+    ///         "  src",
+    ///         "  ├─ text.rs      12K",
+    ///         "  └─ table",
+    ///         "     └─ row.rs    4K",
+    ///     ]
+    /// );
+    /// ```
+    pub fn add_child_row<R: Into<Row>>(&mut self, parent: usize, row: R) -> Option<usize> {
+        let parent_origin = self.rows.as_slice().get(parent)?.origin();
+        let row = row.into();
+        // The parent's subtree is the run of rows after it whose parents are in it
+        let mut subtree = vec![parent_origin];
+        let mut index = parent + 1;
+        while let Some(next) = self.rows.as_slice().get(index)
+            && next
+                .parent()
+                .is_some_and(|origin| subtree.contains(&origin))
+        {
+            subtree.push(next.origin());
+            index += 1;
+        }
+        if let Err(error) = self.check_columns(&row, index) {
+            self.rejected_rows.push(error);
+            return None;
+        }
+        let mut row = self.prepare_new_row(row);
+        row.set_parent(Some(parent_origin));
+        self.track_row_widths(&row);
         self.rows.insert(index, row);
-        self.invalidate_cache();
+        self.invalidate_output();
+        Some(index)
     }
 
     pub fn remove_row(&mut self, index: usize) -> Option<Row> {
-        if index < self.rows.len() {
+        if index >= self.rows.len() {
+            return None;
+        }
+        let row = self.rows.remove(index);
+        // Only removing the widest cell of a column changes the tracked widths
+        let width = self.text_width();
+        let widest = self.row_widths.get_mut().as_ref().is_none_or(|widths| {
+            Self::widen(width, Vec::new(), &row)
+                .iter()
+                .zip(widths)
+                .any(|(width, widest)| width == widest)
+        });
+        if widest {
             self.invalidate_cache();
-            Some(self.rows.remove(index))
         } else {
-            None
+            self.invalidate_output();
         }
+        Some(row)
     }
 
     /// Sorts the rows by the content of the specified column in ascending order.
     /// Uses lexicographic (string) comparison.
     pub fn sort(&mut self, column: usize) {
-        self.rows.sort_by(|a, b| {
-            let a_content = a.cells().get(column).map_or("", Cell::content);
-            let b_content = b.cells().get(column).map_or("", Cell::content);
-            a_content.cmp(b_content)
-        });
+        self.sort_text(column, false);
     }
 
     /// Sorts the rows by the content of the specified column in descending order.
     /// Uses lexicographic (string) comparison.
     pub fn sort_desc(&mut self, column: usize) {
-        self.rows.sort_by(|a, b| {
-            let a_content = a.cells().get(column).map_or("", Cell::content);
-            let b_content = b.cells().get(column).map_or("", Cell::content);
-            b_content.cmp(a_content)
-        });
+        self.sort_text(column, true);
+    }
+
+    fn sort_text(&mut self, column: usize, descending: bool) {
+        let mut rows = core::mem::take(&mut self.rows);
+        // Compute keys once, since normalizing may allocate
+        let keys: Vec<Cow<'_, str>> = rows
+            .iter()
+            .map(|row| self.comparable(row.cells().get(column).map_or("", Cell::content)))
+            .collect();
+        let mut indices: Vec<usize> = (0..rows.len()).collect();
+        if descending {
+            indices.sort_by(|&a, &b| keys[b].cmp(&keys[a]));
+        } else {
+            indices.sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+        }
+
+        self.rows = indices
+            .into_iter()
+            .map(|idx| core::mem::take(&mut rows[idx]))
+            .collect();
+        self.invalidate_output();
     }
 
     /// Sorts the rows by the specified column, treating cell content as numbers.
     /// Non-numeric values are treated as 0.0.
     ///
     /// This method pre-parses numeric values before sorting for better performance
-    /// on large tables.
+    /// on large tables. With the `parallel` feature, tables of many thousands of rows
+    /// are parsed and sorted on the rayon thread pool.
     pub fn sort_num(&mut self, column: usize) {
-        // Pre-parse numeric values to avoid repeated parsing during sort
-        let parsed: Vec<f64> = self
-            .rows
-            .iter()
-            .map(|row| {
-                row.cells()
-                    .get(column)
-                    .and_then(|c| c.content().parse().ok())
-                    .unwrap_or(0.0)
-            })
-            .collect();
-
-        // Create indices and sort by parsed values
-        let mut indices: Vec<usize> = (0..self.rows.len()).collect();
-        indices.sort_by(|&a, &b| {
-            parsed[a]
-                .partial_cmp(&parsed[b])
-                .unwrap_or(core::cmp::Ordering::Equal)
-        });
-
-        // Reorder rows using the sorted indices
-        let mut sorted_rows = Vec::with_capacity(self.rows.len());
-        for idx in indices {
-            sorted_rows.push(core::mem::take(&mut self.rows[idx]));
-        }
-        self.rows = sorted_rows;
+        self.sort_numeric(column, false);
     }
 
     /// Sorts the rows by the specified column in descending order, treating content as numbers.
     /// Non-numeric values are treated as 0.0.
     ///
     /// This method pre-parses numeric values before sorting for better performance
-    /// on large tables.
+    /// on large tables. With the `parallel` feature, tables of many thousands of rows
+    /// are parsed and sorted on the rayon thread pool.
     pub fn sort_num_desc(&mut self, column: usize) {
+        self.sort_numeric(column, true);
+    }
+
+    fn sort_numeric(&mut self, column: usize, descending: bool) {
         // Pre-parse numeric values to avoid repeated parsing during sort
-        let parsed: Vec<f64> = self
-            .rows
-            .iter()
-            .map(|row| {
-                row.cells()
-                    .get(column)
-                    .and_then(|c| c.content().parse().ok())
-                    .unwrap_or(0.0)
-            })
-            .collect();
+        let parsed: Vec<f64> = parallel::map(&self.rows, |row| {
+            row.cells()
+                .get(column)
+                .and_then(|c| c.content().parse().ok())
+                .unwrap_or(0.0)
+        });
 
-        // Create indices and sort by parsed values (descending)
+        // Create indices and sort by parsed values
         let mut indices: Vec<usize> = (0..self.rows.len()).collect();
-        indices.sort_by(|&a, &b| {
-            parsed[b]
-                .partial_cmp(&parsed[a])
+        parallel::sort_by(&mut indices, |&a, &b| {
+            let (a, b) = if descending { (b, a) } else { (a, b) };
+            parsed[a]
+                .partial_cmp(&parsed[b])
                 .unwrap_or(core::cmp::Ordering::Equal)
         });
 
@@ -172,6 +684,7 @@ impl Table {
             sorted_rows.push(core::mem::take(&mut self.rows[idx]));
         }
         self.rows = sorted_rows;
+        self.invalidate_output();
     }
 
     /// Sorts the rows using a custom comparison function.
@@ -180,6 +693,48 @@ impl Table {
         F: FnMut(&Row, &Row) -> core::cmp::Ordering,
     {
         self.rows.sort_by(compare);
+        self.invalidate_output();
+    }
+
+    /// Returns the rows in their current order as origin indices: the position of
+    /// each row among all rows added to the table, counting from 0. Together with
+    /// [`apply_order`](Self::apply_order) this captures the result of sorts and
+    /// filters so it can be persisted and reapplied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new().row(["b"]).row(["c"]).row(["a"]);
+    /// table.sort(0);
+    /// table.filter_has(0, "a");
+    /// assert_eq!(table.current_order(), [2]);
+    ///
+    /// let mut reloaded = Table::new().row(["b"]).row(["c"]).row(["a"]);
+    /// reloaded.apply_order(&[2, 0]);
+    /// assert_eq!(reloaded.rows()[1].cells()[0].content(), "b");
+    /// ```
+    #[must_use]
+    pub fn current_order(&self) -> Vec<usize> {
+        self.rows.iter().map(Row::origin).collect()
+    }
+
+    /// Keeps only the rows whose origin index is in `order`, arranged in that order.
+    /// Indices of rows that are not in the table are ignored, as are repeats.
+    pub fn apply_order(&mut self, order: &[usize]) {
+        let mut rows: Vec<Option<Row>> = core::mem::take(&mut self.rows)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let positions: HashMap<usize, usize> = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| Some((row.as_ref()?.origin(), index)))
+            .collect();
+        self.rows = order
+            .iter()
+            .filter_map(|origin| rows.get_mut(*positions.get(origin)?)?.take())
+            .collect();
+        self.invalidate_cache();
     }
 
     /// Filters rows in place, keeping only those for which the predicate returns true.
@@ -189,16 +744,44 @@ impl Table {
         F: FnMut(&Row) -> bool,
     {
         self.rows.retain(predicate);
+        self.invalidate_cache();
+    }
+
+    /// Filters rows in place like [`filter`](Self::filter), but tests the rows of large
+    /// tables on the rayon thread pool, so the predicate must be shareable between
+    /// threads.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new().header(["n"]);
+    /// for n in 0..50_000 {
+    ///     table.add_row([n.to_string()]);
+    /// }
+    /// table.par_filter(|row| row.cells()[0].content().ends_with("999"));
+    /// assert_eq!(table.rows().len(), 50);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn par_filter<F>(&mut self, predicate: F)
+    where
+        F: Fn(&Row) -> bool + Sync + Send,
+    {
+        parallel::retain(&mut self.rows, predicate);
+        self.invalidate_cache();
     }
 
     /// Filters rows by the content of a specific column.
     /// Keeps rows where the column content equals the given value.
     pub fn filter_eq(&mut self, column: usize, value: &str) {
-        self.rows.retain(|row| {
+        let value = self.comparable(value);
+        let mut rows = core::mem::take(&mut self.rows);
+        rows.retain(|row| {
             row.cells()
                 .get(column)
-                .is_some_and(|cell| cell.content() == value)
+                .is_some_and(|cell| self.comparable(cell.content()) == value)
         });
+        self.rows = rows;
+        self.invalidate_cache();
     }
 
     /// Filters rows by the content of a specific column using a predicate.
@@ -212,15 +795,156 @@ impl Table {
                 .get(column)
                 .is_some_and(|cell| predicate(cell.content()))
         });
+        self.invalidate_cache();
     }
 
     /// Filters rows where the specified column content contains the given substring.
     pub fn filter_has(&mut self, column: usize, substring: &str) {
+        let substring = self.comparable(substring);
+        let mut rows = core::mem::take(&mut self.rows);
+        rows.retain(|row| {
+            row.cells()
+                .get(column)
+                .is_some_and(|cell| self.comparable(cell.content()).contains(substring.as_ref()))
+        });
+        self.rows = rows;
+        self.invalidate_cache();
+    }
+
+    /// Keeps rows where the column contains `needle`, ignoring case and diacritics, so
+    /// `"zoe"` finds `Zoë` and `"lodz"` finds `Łódź`.
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::Table;
+    ///
+    /// let mut table = Table::new()
+    ///     .row(["Zoë Saldaña"])
+    ///     .row(["François Truffaut"])
+    ///     .row(["Zoltan Kodaly"]);
+    /// table.filter_has_fold(0, "zoe saldana");
+    /// assert_eq!(table.len(), 1);
+    /// ```
+    pub fn filter_has_fold(&mut self, column: usize, needle: &str) {
+        let needle = fold(needle);
         self.rows.retain(|row| {
             row.cells()
                 .get(column)
-                .is_some_and(|cell| cell.content().contains(substring))
+                .is_some_and(|cell| fold(cell.content()).contains(&needle))
         });
+        self.invalidate_cache();
+    }
+
+    /// Ranks the rows against `query` like fzf, returning the index and score of each
+    /// matching row, best match first.
+    ///
+    /// The query is a list of space-separated terms that must all match: plain terms
+    /// match fuzzily, `'term` exactly, `^term` at the start, `term$` at the end, and
+    /// `!term` excludes rows. Matching ignores case unless a term has an uppercase letter.
+    ///
+    /// # Example
+    /// ```
+    /// use crabular::Table;
+    ///
+    /// let table = Table::new()
+    ///     .header(["Service", "Owner"])
+    ///     .row(["billing-api", "payments"])
+    ///     .row(["auth-gateway", "identity"])
+    ///     .row(["batch-import", "data"]);
+    /// let matches = table.search_fuzzy("bapi");
+    /// assert_eq!(matches[0].0, 0);
+    /// ```
+    #[cfg(feature = "fuzzy")]
+    #[must_use]
+    pub fn search_fuzzy(&self, query: &str) -> Vec<(usize, u32)> {
+        fuzzy::rank(&self.rows, query)
+    }
+
+    /// Keeps the rows matching `query` (see [`search_fuzzy`](Self::search_fuzzy)),
+    /// ordered best match first. An empty query keeps every row in place.
+    #[cfg(feature = "fuzzy")]
+    pub fn filter_fuzzy(&mut self, query: &str) {
+        let matches = fuzzy::rank(&self.rows, query);
+        let mut rows = core::mem::take(&mut self.rows);
+        self.rows = matches
+            .into_iter()
+            .map(|(index, _)| core::mem::take(&mut rows[index]))
+            .collect();
+        self.invalidate_cache();
+    }
+
+    /// Sets the Unicode normalization applied to content compared by `sort`,
+    /// `sort_desc`, `filter_eq` and `filter_has`.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+        self.invalidate_output();
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Returns `content` as compared by sorting and filtering.
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) fn comparable<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        self.normalization.apply(content)
+    }
+
+    #[cfg(not(feature = "unicode-normalization"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn comparable<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(content)
+    }
+
+    /// Copies how `other` looks onto this table, keeping this table's headers and rows:
+    /// the style, border visibility, padding, spacing, column alignments, width
+    /// constraints, wrap policies, column formats and links, formatters, header style,
+    /// theme colors, placeholder, vertical alignment, truncation marker, row limit,
+    /// messages and render options.
+    ///
+    /// Settings applied as rows are added (truncation, tab width, ANSI stripping),
+    /// settings tied to the data (grouping, totals, column groups, selection, strict
+    /// columns) and caching are not copied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Alignment, Table, TableStyle};
+    /// let mut template = Table::new();
+    /// template.set_style(TableStyle::Markdown);
+    /// template.align(1, Alignment::Right);
+    ///
+    /// let mut table = Table::new().header(["Name", "Age"]).row(["Kata", "30"]);
+    /// table.copy_config_from(&template);
+    /// assert_eq!(table.style(), TableStyle::Markdown);
+    /// assert!(table.render().contains("| Kata  |  30 |"));
+    /// ```
+    pub fn copy_config_from(&mut self, other: &Table) {
+        self.style = other.style;
+        self.border_visibility = other.border_visibility;
+        self.padding = other.padding;
+        self.vertical_padding = other.vertical_padding;
+        self.column_spacing = other.column_spacing;
+        self.column_alignments.clone_from(&other.column_alignments);
+        self.constraints.clone_from(&other.constraints);
+        self.wrap_policies.clone_from(&other.wrap_policies);
+        self.column_formats.clone_from(&other.column_formats);
+        self.column_transforms.clone_from(&other.column_transforms);
+        self.column_links.clone_from(&other.column_links);
+        self.formatters.clone_from(&other.formatters);
+        self.header_style = other.header_style;
+        self.zebra = other.zebra;
+        self.type_colors.clone_from(&other.type_colors);
+        self.empty_placeholder.clone_from(&other.empty_placeholder);
+        self.hyphenate = other.hyphenate;
+        self.vertical_alignment = other.vertical_alignment;
+        self.truncation_marker.clone_from(&other.truncation_marker);
+        self.max_rows = other.max_rows;
+        self.messages.clone_from(&other.messages);
+        self.render_options.clone_from(&other.render_options);
+        self.invalidate_cache();
     }
 
     /// Returns a new table containing only rows that match the predicate.
@@ -232,18 +956,65 @@ impl Table {
     {
         Self {
             rows: self.rows.iter().filter(|r| predicate(r)).cloned().collect(),
+            added_rows: self.added_rows,
             headers: self.headers.clone(),
             style: self.style,
             constraints: self.constraints.clone(),
             padding: self.padding,
+            vertical_padding: self.vertical_padding,
             column_spacing: self.column_spacing,
             column_alignments: self.column_alignments.clone(),
+            wrap_policies: self.wrap_policies.clone(),
+            column_formats: self.column_formats.clone(),
+            column_transforms: self.column_transforms.clone(),
+            column_links: self.column_links.clone(),
+            formatters: self.formatters.clone(),
+            empty_placeholder: self.empty_placeholder.clone(),
+            hyphenate: self.hyphenate,
             vertical_alignment: self.vertical_alignment,
             truncate: self.truncate,
+            truncation_marker: self.truncation_marker.clone(),
+            strip_ansi: self.strip_ansi,
+            tab_width: self.tab_width,
+            max_rows: self.max_rows,
+            header_style: self.header_style,
+            border_visibility: self.border_visibility,
+            zebra: self.zebra,
+            type_colors: self.type_colors.clone(),
+            render_options: self.render_options.clone(),
+            messages: self.messages.clone(),
+            group_column: self.group_column,
+            collapsed_groups: self.collapsed_groups.clone(),
+            aggregates: self.aggregates.clone(),
+            subtotals: self.subtotals,
+            grand_total: self.grand_total,
+            column_groups: self.column_groups.clone(),
+            strict_columns: self.strict_columns,
+            expected_cols: self.expected_cols,
+            rejected_rows: Vec::new(),
+            selected: self.selected.clone(),
+            selection_column: self.selection_column,
             cached_widths: RefCell::new(None),
+            row_widths: RefCell::new(None),
+            generation: 0,
+            memoize: self.memoize,
+            interned: self.interned.clone(),
+            cached_output: RefCell::new(None),
+            last_report: RefCell::new(RenderReport::default()),
+            #[cfg(feature = "instrumentation")]
+            stats: RefCell::new(RenderStats::default()),
+            #[cfg(feature = "unicode-normalization")]
+            normalization: self.normalization,
         }
     }
 
+    /// Compares this table, as the old snapshot, with `other`, highlighting added,
+    /// removed and changed rows and cells. See [`DiffTable`].
+    #[must_use]
+    pub fn diff<'a>(&'a self, other: &'a Table) -> DiffTable<'a> {
+        DiffTable::new(self, other)
+    }
+
     /// Adds a new column to the table with the given values.
     /// The first value becomes the header (if headers exist), and the rest become row values.
     /// If there are more rows than values, empty cells are added.
@@ -265,6 +1036,7 @@ impl Table {
 
         // Extend column alignments to include the new column
         self.column_alignments.push(alignment);
+        self.invalidate_cache();
     }
 
     /// Inserts a new column at the specified index.
@@ -293,6 +1065,11 @@ impl Table {
         if index < self.column_alignments.len() {
             self.column_alignments.insert(index, alignment);
         }
+
+        if index < self.wrap_policies.len() {
+            self.wrap_policies.insert(index, None);
+        }
+        self.invalidate_cache();
     }
 
     /// Removes a column at the specified index from all rows and headers.
@@ -324,6 +1101,11 @@ impl Table {
             self.column_alignments.remove(index);
         }
 
+        if index < self.wrap_policies.len() {
+            self.wrap_policies.remove(index);
+        }
+
+        self.invalidate_cache();
         removed
     }
 
@@ -336,16 +1118,46 @@ impl Table {
         header_cols.max(row_cols)
     }
 
-    pub fn set_style(&mut self, style: TableStyle) {
-        self.style = style;
-    }
-
-    pub fn set_padding(&mut self, padding: Padding) {
-        self.padding = padding;
-    }
-
+    /// Returns the index of the first column whose header is exactly `name`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new().header(["Name", "Score"]);
+    /// assert_eq!(table.column_index("Score"), Some(1));
+    /// assert_eq!(table.column_index("score"), None);
+    /// ```
+    #[must_use]
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        let mut column = 0;
+        for cell in self.headers.as_ref()?.cells() {
+            if cell.content() == name {
+                return Some(column);
+            }
+            column += cell.span().max(1);
+        }
+        None
+    }
+
+    pub fn set_style(&mut self, style: TableStyle) {
+        self.style = style;
+        self.invalidate_cache();
+    }
+
+    pub fn set_padding(&mut self, padding: Padding) {
+        self.padding = Some(padding);
+        self.invalidate_cache();
+    }
+
+    /// Adds blank lines above and below every row's content, for a roomier layout.
+    pub fn set_vertical_padding(&mut self, padding: VerticalPadding) {
+        self.vertical_padding = padding;
+        self.invalidate_cache();
+    }
+
     pub fn spacing(&mut self, spacing: usize) {
-        self.column_spacing = spacing;
+        self.column_spacing = Some(spacing);
+        self.invalidate_cache();
     }
 
     pub fn align(&mut self, column: usize, alignment: Alignment) {
@@ -353,14 +1165,139 @@ impl Table {
             self.column_alignments.resize(column + 1, Alignment::Left);
         }
         self.column_alignments[column] = alignment;
+        self.invalidate_cache();
+    }
+
+    /// Sets how a `Wrap`, `Fixed` or `Max` column handles content longer than its limit.
+    pub fn set_wrap_policy(&mut self, column: usize, policy: WrapPolicy) {
+        if column >= self.wrap_policies.len() {
+            self.wrap_policies.resize(column + 1, None);
+        }
+        self.wrap_policies[column] = Some(policy);
+        self.invalidate_cache();
+    }
+
+    /// Returns the wrap policy of a column: the one set with
+    /// [`set_wrap_policy`](Self::set_wrap_policy), otherwise `Word` for `Wrap`
+    /// columns and `Truncate` for all others.
+    #[must_use]
+    pub fn wrap_policy(&self, column: usize) -> WrapPolicy {
+        if let Some(Some(policy)) = self.wrap_policies.get(column) {
+            return *policy;
+        }
+        match self.constraints.get(column) {
+            Some(WidthConstraint::Wrap(_)) => WrapPolicy::Word,
+            _ => WrapPolicy::Truncate,
+        }
+    }
+
+    /// Displays a column's body cells through the named formatter, such as `bytes`,
+    /// `mask:4` or a [`NumberFormat`](crate::NumberFormat). See [`FormatterRegistry`]
+    /// for the spec syntax and built-in formatters.
+    ///
+    /// Only the rendered text changes: sorting, filtering and exports still see the
+    /// stored values. A spec that does not resolve leaves the column unformatted.
+    pub fn format_column(&mut self, column: usize, spec: impl Into<String>) {
+        if column >= self.column_formats.len() {
+            self.column_formats.resize(column + 1, None);
+        }
+        self.column_formats[column] = Some(spec.into());
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn column_format(&self, column: usize) -> Option<&str> {
+        self.column_formats.get(column)?.as_deref()
+    }
+
+    /// Changes the case or whitespace of a column's body cells when rendered, after the
+    /// column's formatter. Stored content is unchanged. See [`TextTransform`].
+    pub fn transform_column(&mut self, column: usize, transform: TextTransform) {
+        if column >= self.column_transforms.len() {
+            self.column_transforms.resize(column + 1, None);
+        }
+        self.column_transforms[column] = Some(transform);
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn column_transform(&self, column: usize) -> Option<TextTransform> {
+        self.column_transforms.get(column).copied().flatten()
+    }
+
+    /// Links a column's body cells to a URL built from `template`, where `{value}`
+    /// stands for the stored content, percent-encoded.
+    ///
+    /// The displayed text is unchanged. [`to_html`](Self::to_html) wraps the cells in
+    /// `<a href>`, and rendered output does the same with OSC 8 escape sequences when
+    /// [`RenderOptions::hyperlinks`] is enabled. Empty cells and summary rows get no link.
+    pub fn link_column(&mut self, column: usize, template: &str) {
+        if column >= self.column_links.len() {
+            self.column_links.resize(column + 1, None);
+        }
+        self.column_links[column] = Some(template.to_string());
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn column_link(&self, column: usize) -> Option<&str> {
+        self.column_links.get(column)?.as_deref()
+    }
+
+    /// Returns the URL for `content` in `column`, if the column has a link template.
+    fn link_for(&self, column: usize, content: &str) -> Option<String> {
+        if content.is_empty() {
+            return None;
+        }
+        let template = self.column_link(column)?;
+        Some(link::expand(template, content))
+    }
+
+    /// Sets the table's own formatters, which take precedence over the global and
+    /// built-in ones.
+    pub fn set_formatters(&mut self, formatters: FormatterRegistry) {
+        self.formatters = formatters;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn formatters(&self) -> &FormatterRegistry {
+        &self.formatters
+    }
+
+    /// Shows `placeholder`, such as `-` or `n/a`, in body cells that are empty, so
+    /// missing values stand out from cells that were cut off or wrapped. Stored content
+    /// and exports are unchanged, and subtotal and total rows stay blank.
+    pub fn set_empty_placeholder(&mut self, placeholder: &str) {
+        self.empty_placeholder = placeholder.to_string();
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn empty_placeholder(&self) -> &str {
+        &self.empty_placeholder
+    }
+
+    /// Ends each piece of a word that word wrapping breaks apart with a hyphen, so
+    /// readers can tell the word continues on the next line.
+    pub fn set_hyphenate(&mut self, hyphenate: bool) {
+        self.hyphenate = hyphenate;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn hyphenate(&self) -> bool {
+        self.hyphenate
     }
 
     pub fn valign(&mut self, alignment: VerticalAlignment) {
         self.vertical_alignment = alignment;
+        self.invalidate_cache();
     }
 
     pub fn constrain(&mut self, constraint: WidthConstraint) {
         self.constraints.push(constraint);
+        self.invalidate_cache();
     }
 
     pub fn set_constraint(&mut self, column: usize, constraint: WidthConstraint) {
@@ -368,6 +1305,7 @@ impl Table {
             self.constraints.resize(column + 1, WidthConstraint::Auto);
         }
         self.constraints[column] = constraint;
+        self.invalidate_cache();
     }
 
     #[must_use]
@@ -390,14 +1328,22 @@ impl Table {
         self.style
     }
 
+    /// Returns the padding set with `set_padding`, or else the style's default.
     #[must_use]
     pub fn padding(&self) -> Padding {
-        self.padding
+        self.padding.unwrap_or_else(|| self.style.default_padding())
     }
 
+    #[must_use]
+    pub fn vertical_padding(&self) -> VerticalPadding {
+        self.vertical_padding
+    }
+
+    /// Returns the spacing set with `spacing`, or else the style's default.
     #[must_use]
     pub fn get_spacing(&self) -> usize {
         self.column_spacing
+            .unwrap_or_else(|| self.style.default_spacing())
     }
 
     #[must_use]
@@ -410,1346 +1356,5930 @@ impl Table {
         self.vertical_alignment
     }
 
-    #[must_use]
-    pub fn len(&self) -> usize {
-        self.rows.len()
+    /// Sets how the header row is presented (case, emphasis, alignment).
+    pub fn set_header_style(&mut self, header_style: HeaderStyle) {
+        self.header_style = header_style;
+        self.invalidate_cache();
     }
 
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.rows.is_empty() && self.headers.is_none()
+    pub fn header_style(&self) -> HeaderStyle {
+        self.header_style
     }
 
-    #[must_use]
-    pub fn row<R: Into<Row>>(mut self, cells: R) -> Self {
-        self.add_row(cells.into());
-        self
+    /// Sets which lines of the border style are drawn, such as no outer frame or no
+    /// column separators.
+    pub fn set_border_visibility(&mut self, visibility: BorderVisibility) {
+        self.border_visibility = visibility;
+        self.invalidate_cache();
     }
 
     #[must_use]
-    pub fn header<R: Into<Row>>(mut self, headers: R) -> Self {
-        self.set_headers(headers);
-        self
+    pub fn border_visibility(&self) -> BorderVisibility {
+        self.border_visibility
     }
 
-    #[must_use]
-    pub fn truncate(mut self, limit: usize) -> Self {
-        self.truncate = Some(limit);
-        self
+    /// Applies a theme's border style, header style, zebra striping and column type
+    /// colors, replacing the ones set before.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Color, ColumnType, Table, TableStyle, Theme};
+    /// let mut table = Table::new().header(["Name", "Age"]).row(["Kata", "30"]);
+    /// table.set_theme(Theme::new(TableStyle::Compact).type_color(ColumnType::Integer, Color::Blue));
+    /// assert!(table.render().contains("\x1b[34m30\x1b[0m"));
+    /// ```
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.style = theme.get_style();
+        self.header_style = theme.get_header_style();
+        self.zebra = theme.get_zebra();
+        self.type_colors = theme.into_type_colors();
+        self.invalidate_cache();
     }
 
-    fn truncate_row(row: &Row, limit: usize) -> Row {
-        let mut new_row = Row::new();
-        for cell in row.cells() {
-            let content = cell.content();
-            let truncated = if content.len() > limit {
-                if limit > 3 {
-                    format!("{}...", &content[..limit - 3])
-                } else {
-                    content[..limit].to_string()
-                }
-            } else {
-                content.to_string()
-            };
-            new_row.push(Cell::new(&truncated, cell.alignment()));
-        }
-        new_row
+    /// Replaces the text the renderer adds, such as the "… (N more rows)" line and
+    /// the subtotal and total labels.
+    pub fn set_messages(&mut self, messages: Messages) {
+        self.messages = messages;
+        self.invalidate_cache();
     }
 
-    pub fn print(&self) {
-        print!("{}", self.render());
+    #[must_use]
+    pub fn messages(&self) -> &Messages {
+        &self.messages
     }
 
-    /// Renders the table into a provided byte buffer, reusing the allocation.
-    ///
-    /// This method allows for zero-allocation rendering when the buffer is reused
-    /// across multiple renders, making it ideal for repeated rendering scenarios
-    /// like pagination or filtering UI.
-    ///
-    /// # Arguments
-    /// * `buf` - A buffer to render into. Will be cleared and reused.
-    ///
-    /// # Returns
-    /// * `Ok(())` if rendering succeeded
-    ///
-    /// # Errors
-    /// This function currently never returns an error, but returns `Result` for
-    /// future compatibility with potential I/O operations.
+    /// Renders only the first `max_rows` body rows, followed by a line such as
+    /// "… (1234 more rows)" spanning all columns. `None` renders every row.
     ///
-    /// # Examples
-    /// ```ignore
-    /// let mut buffer = Vec::with_capacity(4096);
-    /// for item in items {
-    ///     buffer.clear();
-    ///     table.render_into(&mut buffer)?;
-    ///     stdout.write_all(&buffer)?;
-    /// }
-    /// ```
-    pub fn render_into(&self, buf: &mut Vec<u8>) -> core::fmt::Result {
-        buf.clear();
-        let rendered = self.render();
-        buf.extend_from_slice(rendered.as_bytes());
-        Ok(())
+    /// A collapsed group counts as one row, and its rows are counted in the hidden
+    /// ones. Subtotals are not counted and are cut with the rest of their group; the
+    /// grand total is always rendered, below the elided line.
+    pub fn set_max_rows(&mut self, max_rows: Option<usize>) {
+        self.max_rows = max_rows;
+        self.invalidate_cache();
     }
 
-    /// Formats a cell's content with the given width and alignment.
-    ///
-    /// This is a lower-level function that can be useful for custom formatting needs.
-    ///
-    /// # Arguments
-    /// * `content` - The cell content to format
-    /// * `width` - The target width for the formatted output
-    /// * `alignment` - The alignment to use
-    ///
-    /// # Returns
-    /// The formatted string with appropriate padding
-    ///
-    /// # Examples
-    /// ```
-    /// # use crabular::{Table, Alignment};
-    /// let formatted = Table::format_cell("test", 10, Alignment::Left);
-    /// assert_eq!(formatted, "test      ");
-    /// ```
     #[must_use]
-    pub fn format_cell(content: &str, width: usize, alignment: Alignment) -> String {
-        let content_len = content.chars().count();
+    pub fn max_rows(&self) -> Option<usize> {
+        self.max_rows
+    }
+
+    /// Sets output options such as the Markdown layout.
+    pub fn set_render_options(&mut self, options: RenderOptions) {
+        self.render_options = options;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn render_options(&self) -> &RenderOptions {
+        &self.render_options
+    }
 
-        if content_len > width {
-            return if width > 3 {
-                let truncated: String = content.chars().take(width - 3).collect();
-                format!("{truncated}...")
+    /// Returns the headers as displayed, with the header style's text transform and
+    /// footnote markers applied.
+    fn display_headers(&self) -> Option<Cow<'_, Row>> {
+        let headers = self.headers.as_ref()?;
+        let notes = self.notes();
+        if !self.header_style.is_uppercase() && notes.is_empty() {
+            return Some(Cow::Borrowed(headers));
+        }
+
+        let mut row = Row::new();
+        for cell in headers.cells() {
+            let mut content = if self.header_style.is_uppercase() {
+                cell.content().to_uppercase()
             } else {
-                ".".repeat(width)
+                cell.content().to_string()
             };
+            if let Some(marker) = Self::note_marker(&notes, cell) {
+                content.push_str(&marker);
+            }
+            let mut display = Cell::new(&content, cell.alignment());
+            display.set_span(cell.span());
+            row.push(display);
         }
+        Some(Cow::Owned(row))
+    }
 
-        if content_len == width {
-            return content.to_string();
+    /// Returns the distinct cell notes, headers first, in the order they are numbered
+    /// as footnotes. Empty when notes are ignored.
+    fn notes(&self) -> Vec<&str> {
+        let mut notes: Vec<&str> = Vec::new();
+        if self.render_options.get_notes() == NoteMode::Ignore {
+            return notes;
         }
-
-        // Optimized version: pre-allocate and use push_str() instead of format!
-        let padding = width - content_len;
-        let mut result = String::with_capacity(width);
-
-        match alignment {
-            Alignment::Left => {
-                result.push_str(content);
-                for _ in 0..padding {
-                    result.push(' ');
-                }
-            }
-            Alignment::Right => {
-                for _ in 0..padding {
-                    result.push(' ');
-                }
-                result.push_str(content);
-            }
-            Alignment::Center => {
-                let left = padding / 2;
-                let right = padding - left;
-                for _ in 0..left {
-                    result.push(' ');
-                }
-                result.push_str(content);
-                for _ in 0..right {
-                    result.push(' ');
+        for row in self.headers.iter().chain(&self.rows) {
+            for note in row.cells().iter().filter_map(Cell::note) {
+                if !notes.contains(&note) {
+                    notes.push(note);
                 }
             }
         }
+        notes
+    }
 
-        result
+    /// Returns the `[n]` footnote marker of a cell with a note.
+    fn note_marker(notes: &[&str], cell: &Cell) -> Option<String> {
+        let note = cell.note()?;
+        let index = notes.iter().position(|known| *known == note)?;
+        Some(format!("[{}]", index + 1))
     }
 
-    pub(crate) fn wrap_text(text: &str, width: usize) -> Vec<String> {
-        if text.is_empty() || width == 0 {
-            return vec![String::new()];
+    /// Returns the tree branches drawn before the first cell of each child row, by row
+    /// origin. Empty unless some row has a parent.
+    fn tree_prefixes(&self) -> HashMap<usize, String> {
+        if self.rows.iter().all(|row| row.parent().is_none()) {
+            return HashMap::new();
         }
-
-        if text.chars().count() <= width {
-            return vec![text.to_string()];
+        let positions: HashMap<usize, usize> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| (row.origin(), index))
+            .collect();
+        let parents: Vec<Option<usize>> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let parent = *positions.get(&row.parent()?)?;
+                (parent < index).then_some(parent)
+            })
+            .collect();
+        let mut has_later_sibling = vec![false; parents.len()];
+        let mut seen = Vec::new();
+        for (index, parent) in parents.iter().enumerate().rev() {
+            has_later_sibling[index] = seen.contains(parent);
+            seen.push(*parent);
         }
 
-        let mut lines = Vec::new();
-        let mut current_line = String::with_capacity(width);
-        let mut current_char_count = 0;
-
-        // Iterate directly over split_whitespace() without collecting into Vec
-        for word in text.split_whitespace() {
-            let word_char_count = word.chars().count();
-
-            if current_char_count == 0 {
-                // Starting a new line
-                if word_char_count > width {
-                    Self::wrap_long_word(word, width, &mut lines);
-                } else {
-                    current_line.push_str(word);
-                    current_char_count = word_char_count;
-                }
+        let mut prefixes = HashMap::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            let Some(mut ancestor) = parents[index] else {
+                continue;
+            };
+            let mut prefix = String::from(if has_later_sibling[index] {
+                "├─ "
             } else {
-                // Continuing an existing line
-                let potential_len = current_char_count + 1 + word_char_count;
-                if potential_len <= width {
-                    current_line.push(' ');
-                    current_line.push_str(word);
-                    current_char_count = potential_len;
+                "└─ "
+            });
+            while parents[ancestor].is_some() {
+                let rail = if has_later_sibling[ancestor] {
+                    "│  "
                 } else {
-                    // Line is full, start a new one
-                    lines.push(core::mem::take(&mut current_line));
-                    current_char_count = 0;
-
-                    if word_char_count > width {
-                        Self::wrap_long_word(word, width, &mut lines);
-                    } else {
-                        current_line.push_str(word);
-                        current_char_count = word_char_count;
-                    }
-                }
+                    "   "
+                };
+                prefix.insert_str(0, rail);
+                ancestor = parents[ancestor].unwrap_or_default();
             }
+            prefixes.insert(row.origin(), prefix);
         }
+        prefixes
+    }
 
-        if !current_line.is_empty() {
-            lines.push(current_line);
+    /// Returns the footnote list written below the table, one `[n] note` line each.
+    fn footnotes(&self) -> String {
+        let mut output = String::new();
+        for (index, note) in self.notes().iter().enumerate() {
+            output.push('[');
+            output.push_str(&(index + 1).to_string());
+            output.push_str("] ");
+            output.push_str(note);
+            output.push('\n');
         }
+        output
+    }
 
-        if lines.is_empty() {
-            lines.push(String::new());
+    /// Groups rows by the content of the specified column when rendering.
+    ///
+    /// Groups appear in the order their key is first seen, and rows keep their
+    /// relative order within a group. A separator is drawn between groups.
+    /// The stored row order is not changed. Grouping by another column expands all
+    /// groups.
+    pub fn group_by(&mut self, column: usize) {
+        if self.group_column != Some(column) {
+            self.collapsed_groups.clear();
         }
-
-        lines
+        self.group_column = Some(column);
+        self.invalidate_cache();
     }
 
-    /// Helper to wrap a word that exceeds the column width.
-    /// Breaks the word into chunks of `width` characters and appends to `lines`.
-    fn wrap_long_word(word: &str, width: usize, lines: &mut Vec<String>) {
-        let mut chars = word.chars().peekable();
+    /// Returns the keys of the groups in the order they are rendered, or nothing when
+    /// rows are not grouped.
+    #[must_use]
+    pub fn group_keys(&self) -> Vec<&str> {
+        let Some(column) = self.group_column else {
+            return Vec::new();
+        };
+        self.group_rows(column)
+            .iter()
+            .filter_map(|group| group.first())
+            .map(|row| row.cells().get(column).map_or("", Cell::content))
+            .collect()
+    }
 
-        while chars.peek().is_some() {
-            let chunk: String = chars.by_ref().take(width).collect();
-            lines.push(chunk);
-        }
+    /// Shows the group `key` as one line, such as `▶ EU (12 rows)`, instead of its
+    /// rows. Its subtotal row is still drawn. Together with
+    /// [`group_keys`](Self::group_keys) this is the model of an expandable tree
+    /// table; the line's text is set with [`Messages::collapsed_group`].
+    ///
+    /// Column widths are measured over the rows shown, so they may change as groups
+    /// collapse and expand.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, TableStyle};
+    /// let mut table = Table::new()
+    ///     .header(["Region", "City"])
+    ///     .row(["EU", "Berlin"])
+    ///     .row(["US", "Boston"])
+    ///     .row(["EU", "Paris"]);
+    /// table.set_style(TableStyle::Minimal);
+    /// table.group_by(0);
+    /// table.collapse_group("EU");
+    /// assert!(table.is_group_collapsed("EU"));
+    ///
+    /// let output = table.render();
+    /// assert!(output.contains("▶ EU (2 rows)"));
+    /// assert!(!output.contains("Paris"));
+    ///
+    /// assert!(!table.toggle_group("EU"));
+    /// assert!(table.render().contains("Paris"));
+    /// ```
+    pub fn collapse_group(&mut self, key: &str) {
+        self.collapsed_groups.insert(key.to_string());
+        self.invalidate_cache();
     }
 
-    fn calculate_column_widths(&self) -> Vec<usize> {
-        let mut max_widths: Vec<usize> = Vec::new();
+    /// Shows the rows of the group `key` again.
+    pub fn expand_group(&mut self, key: &str) {
+        self.collapsed_groups.remove(key);
+        self.invalidate_cache();
+    }
 
-        if let Some(headers) = self.headers() {
-            for (idx, cell) in headers.cells().iter().enumerate() {
-                let width = cell.content().chars().count();
-                if max_widths.len() < idx + 1 {
-                    max_widths.resize(idx + 1, 0);
-                }
-                if width > max_widths[idx] {
-                    max_widths[idx] = width;
-                }
-            }
+    /// Collapses the group `key` if it is expanded and expands it otherwise. Returns
+    /// whether it is now collapsed.
+    pub fn toggle_group(&mut self, key: &str) -> bool {
+        if self.is_group_collapsed(key) {
+            self.expand_group(key);
+            false
+        } else {
+            self.collapse_group(key);
+            true
         }
+    }
 
-        for row in &self.rows {
-            for (idx, cell) in row.cells().iter().enumerate() {
-                let width = cell.content().chars().count();
-                if max_widths.len() < idx + 1 {
-                    max_widths.resize(idx + 1, 0);
-                }
-                if width > max_widths[idx] {
-                    max_widths[idx] = width;
-                }
-            }
-        }
+    /// Collapses every current group.
+    pub fn collapse_all_groups(&mut self) {
+        let keys: Vec<String> = self.group_keys().into_iter().map(str::to_string).collect();
+        self.collapsed_groups.extend(keys);
+        self.invalidate_cache();
+    }
 
-        self.apply_width_constraints(&mut max_widths);
-        self.apply_proportional_constraints(&mut max_widths);
-        max_widths
+    /// Expands every group.
+    pub fn expand_all_groups(&mut self) {
+        self.collapsed_groups.clear();
+        self.invalidate_cache();
     }
 
-    fn apply_width_constraints(&self, widths: &mut [usize]) {
-        for (i, constraint) in self.constraints.iter().enumerate() {
-            if i < widths.len() {
-                match constraint {
-                    WidthConstraint::Fixed(w) => {
-                        widths[i] = *w;
-                    }
-                    WidthConstraint::Min(m) => {
-                        if widths[i] < *m {
-                            widths[i] = *m;
-                        }
-                    }
-                    WidthConstraint::Max(m) => {
-                        if widths[i] > *m {
-                            widths[i] = *m;
-                        }
-                    }
-                    WidthConstraint::Wrap(w) => {
-                        if widths[i] > *w {
-                            widths[i] = *w;
-                        }
-                    }
-                    WidthConstraint::Auto | WidthConstraint::Proportional(_) => {}
-                }
-            }
-        }
+    #[must_use]
+    pub fn is_group_collapsed(&self, key: &str) -> bool {
+        self.collapsed_groups.contains(key)
     }
 
-    fn apply_proportional_constraints(&self, widths: &mut [usize]) {
-        let total_percentage: u8 = self
-            .constraints
-            .iter()
-            .filter_map(|c| {
-                if let WidthConstraint::Proportional(p) = c {
-                    Some(*p)
-                } else {
-                    None
-                }
-            })
-            .sum();
+    /// Removes grouping, rendering rows in their stored order.
+    pub fn ungroup(&mut self) {
+        self.group_column = None;
+        self.invalidate_cache();
+    }
 
-        if total_percentage == 0 || total_percentage > 100 {
-            return;
+    #[must_use]
+    pub fn group_column(&self) -> Option<usize> {
+        self.group_column
+    }
+
+    /// Registers an aggregate computed for `column` in subtotal and grand-total rows.
+    /// Registering a second aggregate for the same column replaces the first.
+    pub fn aggregate(&mut self, column: usize, aggregate: Aggregate) {
+        if let Some(entry) = self.aggregates.iter_mut().find(|(col, _)| *col == column) {
+            entry.1 = aggregate;
+        } else {
+            self.aggregates.push((column, aggregate));
         }
+        self.invalidate_cache();
+    }
 
-        let padding = self.padding.left + self.padding.right;
-        let spacing = self
-            .column_spacing
-            .saturating_mul(widths.len().saturating_sub(1));
-        let max_width: usize = 120;
-        let available_width = max_width.saturating_sub(padding * widths.len() + spacing);
+    #[must_use]
+    pub fn aggregates(&self) -> &[(usize, Aggregate)] {
+        &self.aggregates
+    }
 
-        let proportional_width = available_width;
-        for (i, constraint) in self.constraints.iter().enumerate() {
-            if i < widths.len()
-                && let WidthConstraint::Proportional(percentage) = constraint
-            {
-                let calculated_width = (proportional_width * *percentage as usize) / 100;
-                widths[i] = widths[i].max(calculated_width);
-            }
-        }
+    /// Enables a subtotal row after each group.
+    /// Has no effect unless rows are grouped and at least one aggregate is registered.
+    pub fn set_subtotals(&mut self, enabled: bool) {
+        self.subtotals = enabled;
+        self.invalidate_cache();
     }
 
     #[must_use]
-    pub fn render(&self) -> String {
-        if self.is_empty() {
-            return String::new();
-        }
+    pub fn has_subtotals(&self) -> bool {
+        self.subtotals
+    }
+
+    /// Enables a grand-total footer row computed over all rows.
+    /// Has no effect unless at least one aggregate is registered.
+    pub fn set_grand_total(&mut self, enabled: bool) {
+        self.grand_total = enabled;
+        self.invalidate_cache();
+    }
 
-        let column_widths = self.calculate_column_widths();
-        self.render_with_widths(&column_widths)
+    #[must_use]
+    pub fn has_grand_total(&self) -> bool {
+        self.grand_total
     }
 
-    /// Renders the table using cached column widths if available.
+    /// Sets `columns` apart as a section, e.g. identity | metrics | metadata, drawn with
+    /// doubled separators at its edges. A `label` is centered above the section in a
+    /// group label row. Groups should not overlap.
     ///
-    /// This method provides improved performance for repeated renders of the same table.
-    /// The first call calculates and caches column widths. Subsequent calls reuse the cache
-    /// until the table is modified.
+    /// Only the Classic, Modern, Minimal and Compact styles draw groups; the document
+    /// styles have no syntax for them. Groups are not kept by [`render_split`](Self::render_split).
+    pub fn column_group<'a>(&mut self, columns: Range<usize>, label: impl Into<Option<&'a str>>) {
+        self.column_groups
+            .push((columns, label.into().map(str::to_string)));
+        self.invalidate_cache();
+    }
+
+    /// Removes all column groups.
+    pub fn clear_column_groups(&mut self) {
+        self.column_groups.clear();
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn column_groups(&self) -> &[(Range<usize>, Option<String>)] {
+        &self.column_groups
+    }
+
+    /// Returns the column groups drawn by the current style.
+    fn drawn_column_groups(&self) -> &[(Range<usize>, Option<String>)] {
+        match self.style {
+            TableStyle::Classic
+            | TableStyle::Modern
+            | TableStyle::Minimal
+            | TableStyle::Compact => &self.column_groups,
+            _ => &[],
+        }
+    }
+
+    /// Returns whether a doubled separator is drawn before `column`.
+    fn is_group_boundary(&self, column: usize, num_columns: usize) -> bool {
+        column > 0
+            && column < num_columns
+            && self.drawn_column_groups().iter().any(|(columns, _)| {
+                !columns.is_empty() && (columns.start == column || columns.end == column)
+            })
+    }
+
+    fn group_boundaries(&self, num_columns: usize) -> Vec<bool> {
+        (0..=num_columns)
+            .map(|column| self.is_group_boundary(column, num_columns))
+            .collect()
+    }
+
+    /// Returns the row of centered group labels drawn above the headers, with a blank
+    /// cell over each run of ungrouped columns, or `None` if no group has a label.
+    fn group_label_row(&self, num_columns: usize) -> Option<Row> {
+        let groups = self.drawn_column_groups();
+        if groups.iter().all(|(_, label)| label.is_none()) {
+            return None;
+        }
+
+        let mut row = Row::new();
+        let mut column = 0;
+        let mut blank = 0;
+        while column < num_columns {
+            let group = groups
+                .iter()
+                .find(|(columns, _)| columns.start == column && columns.end > column);
+            let Some((columns, label)) = group else {
+                blank += 1;
+                column += 1;
+                continue;
+            };
+            if blank > 0 {
+                let mut cell = Cell::new("", Alignment::Center);
+                cell.set_span(blank);
+                row.push(cell);
+                blank = 0;
+            }
+            let mut cell = Cell::new(label.as_deref().unwrap_or_default(), Alignment::Center);
+            cell.set_span(columns.end.min(num_columns) - column);
+            column += cell.span();
+            row.push(cell);
+        }
+        if blank > 0 {
+            let mut cell = Cell::new("", Alignment::Center);
+            cell.set_span(blank);
+            row.push(cell);
+        }
+        Some(row)
+    }
+
+    /// Selects or deselects the row at index `row`, the state behind interactive
+    /// pickers. Rows are remembered by identity, so the selection follows them through
+    /// sorts and filters; an index past the table's rows is kept as is and selects the
+    /// provider row of that index in [`render_viewport`](Self::render_viewport).
     ///
-    /// # Returns
-    /// The rendered table as a `String`
+    /// The selection is drawn only once [`set_selection_column`](Self::set_selection_column)
+    /// is on.
     ///
     /// # Examples
     /// ```
-    /// # use crabular::{Table, Alignment};
-    /// let table = Table::new().header(&["A", "B"]).row(&["1", "2"]);
-    /// let _first = table.render_cached(); // Calculates and caches widths
-    /// let _second = table.render_cached(); // Uses cached widths (faster)
+    /// # use crabular::{Table, TableStyle};
+    /// let mut table = Table::new()
+    ///     .header(["Package", "Version"])
+    ///     .row(["serde", "1.0"])
+    ///     .row(["rayon", "1.10"]);
+    /// table.set_style(TableStyle::Minimal);
+    /// table.set_selection_column(true);
+    /// table.set_selected(1, true);
+    ///
+    /// table.sort(0);
+    /// assert_eq!(table.selected_rows(), [0]);
+    ///
+    /// let output = table.render();
+    /// let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    /// assert_eq!(lines[2..], ["  [x]    rayon      1.10", "  [ ]    serde      1.0"]);
     /// ```
-    #[must_use]
-    pub fn render_cached(&self) -> String {
-        if self.is_empty() {
-            return String::new();
+    pub fn set_selected(&mut self, row: usize, selected: bool) {
+        let key = self.selection_key(row);
+        if selected {
+            self.selected.insert(key);
+        } else {
+            self.selected.remove(&key);
         }
-
-        // Use cached widths or calculate and cache them
-        let column_widths = {
-            let mut cache = self.cached_widths.borrow_mut();
-            if let Some(ref widths) = *cache {
-                widths.clone()
-            } else {
-                let widths = self.calculate_column_widths();
-                *cache = Some(widths.clone());
-                widths
-            }
-        };
-
-        self.render_with_widths(&column_widths)
+        self.invalidate_output();
     }
 
-    /// Internal method that renders the table with pre-calculated column widths.
-    fn render_with_widths(&self, column_widths: &[usize]) -> String {
-        let borders = self.style.border_chars();
-        let skip_outer_borders = matches!(
-            self.style,
-            TableStyle::Minimal | TableStyle::Compact | TableStyle::Markdown
-        );
-
-        let num_columns = column_widths.len();
-        let padding = self.padding.left + self.padding.right;
+    /// Selects the row at index `row` if it is not selected and deselects it
+    /// otherwise. Returns whether it is now selected.
+    pub fn toggle_selected(&mut self, row: usize) -> bool {
+        let selected = !self.is_selected(row);
+        self.set_selected(row, selected);
+        selected
+    }
 
-        // Pre-calculate approximate buffer size
-        let row_width: usize = column_widths.iter().sum::<usize>()
-            + padding * num_columns
-            + self.column_spacing * num_columns.saturating_sub(1)
-            + num_columns
-            + 2; // border chars + newline
+    #[must_use]
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&self.selection_key(row))
+    }
 
-        let num_rows = self.len();
-        let border_rows = if skip_outer_borders { 1 } else { 3 };
-        let estimated_lines = num_rows + border_rows + usize::from(self.headers().is_some());
-        let estimated_capacity = row_width * estimated_lines;
+    /// Returns the indices of the selected rows, in the current row order.
+    #[must_use]
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| self.selected.contains(&row.origin()))
+            .map(|(index, _)| index)
+            .collect()
+    }
 
-        let mut output = String::with_capacity(estimated_capacity);
+    /// Selects every row of the table.
+    pub fn select_all(&mut self) {
+        self.selected.extend(self.rows.iter().map(Row::origin));
+        self.invalidate_output();
+    }
 
-        let boundaries_for = |row: Option<&Row>| {
-            row.map_or_else(
-                || Self::all_boundaries(num_columns),
-                |row| Self::get_row_boundaries(row, num_columns),
-            )
-        };
+    /// Deselects every row, including provider rows selected by index.
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+        self.invalidate_output();
+    }
 
-        // Get the first row to determine top border boundaries
-        let first_row = self.headers().or_else(|| self.rows.first());
+    /// Draws a leading column with `[x]` for selected rows and `[ ]` for the others
+    /// when rendering with [`render`](Self::render) or
+    /// [`render_viewport`](Self::render_viewport). Column indices of all other settings
+    /// keep referring to the table's own columns.
+    pub fn set_selection_column(&mut self, show: bool) {
+        self.selection_column = show;
+        self.invalidate_output();
+    }
 
-        if !skip_outer_borders {
-            let first_boundaries = boundaries_for(first_row);
-            // For top border, only use first row boundaries (pass same for both)
-            output.push_str(&Self::render_horizontal_border_with_spans(
-                column_widths,
-                self.padding,
-                self.column_spacing,
-                borders.top_left,
-                borders.top_cross,
-                borders.top_right,
-                borders.horizontal,
-                borders.top_cross,    // T-down (for top border, same as top_cross)
-                borders.bottom_cross, // T-up (for top border, use bottom_cross)
-                &first_boundaries,
-                &first_boundaries, // Same boundaries - junction only if first row has boundary
-            ));
-        }
+    #[must_use]
+    pub fn has_selection_column(&self) -> bool {
+        self.selection_column
+    }
 
-        if let Some(headers) = self.headers() {
-            let header_boundaries = Self::get_row_boundaries(headers, num_columns);
-            output.push_str(&self.render_row_with_wrapping(
-                headers,
-                column_widths,
-                &borders,
-                &self.column_alignments,
-            ));
-            if self.style == TableStyle::Markdown {
-                output.push_str(&Self::render_markdown_header_separator(
-                    column_widths,
-                    self.padding,
-                    self.column_spacing,
-                ));
-            } else {
-                // Get first data row boundaries for the separator
-                let first_data_boundaries = boundaries_for(self.rows.first());
+    /// Returns the key under which the row at index `row` is selected.
+    fn selection_key(&self, row: usize) -> usize {
+        self.rows.as_slice().get(row).map_or(row, Row::origin)
+    }
 
-                output.push_str(&Self::render_horizontal_border_with_spans(
-                    column_widths,
-                    self.padding,
-                    self.column_spacing,
-                    borders.left_cross,
-                    borders.cross,
-                    borders.right_cross,
-                    borders.horizontal,
-                    borders.top_cross,      // T-down (row below has boundary)
-                    borders.bottom_cross,   // T-up (row above has boundary)
-                    &first_data_boundaries, // Row below (first data row)
-                    &header_boundaries,     // Row above (headers)
-                ));
+    /// Returns a copy of the table with the selection column in front, marking the
+    /// rows for which `is_selected` returns true given their index and row. Column
+    /// settings are shifted to follow their columns.
+    fn with_selection_column<F>(&self, is_selected: F) -> Self
+    where
+        F: Fn(usize, &Row) -> bool,
+    {
+        let mut view = self.filtered(|_| true);
+        view.selection_column = false;
+        view.memoize = false;
+        // The tree is drawn in the first column, which the markers now take
+        let tree = self.tree_prefixes();
+        for (index, row) in view.rows.iter_mut().enumerate() {
+            if let Some(branch) = tree.get(&row.origin())
+                && let Some(cell) = row.cell_mut(0)
+            {
+                let content = format!("{branch}{}", cell.content());
+                cell.set_content(&content);
             }
+            row.set_parent(None);
+            let marker = if is_selected(index, row) {
+                SELECTED_MARKER
+            } else {
+                UNSELECTED_MARKER
+            };
+            row.insert(0, Cell::new(marker, Alignment::Left));
         }
-
-        for row in self.rows() {
-            output.push_str(&self.render_row_with_wrapping(
-                row,
-                column_widths,
-                &borders,
-                &self.column_alignments,
-            ));
+        if let Some(headers) = &mut view.headers {
+            headers.insert(0, Cell::new("", Alignment::Left));
         }
 
-        if !skip_outer_borders {
-            let last_row = self.rows.last().or(self.headers());
-            let last_boundaries = boundaries_for(last_row);
-            // For bottom border, only use last row boundaries (pass same for both)
-            output.push_str(&Self::render_horizontal_border_with_spans(
-                column_widths,
-                self.padding,
-                self.column_spacing,
-                borders.bottom_left,
-                borders.bottom_cross,
-                borders.bottom_right,
-                borders.horizontal,
-                borders.top_cross,    // T-down
-                borders.bottom_cross, // T-up
-                &last_boundaries,     // Same boundaries - junction only if last row has boundary
-                &last_boundaries,
-            ));
+        if !view.constraints.is_empty() {
+            view.constraints.insert(0, WidthConstraint::Auto);
+        }
+        if !view.column_alignments.is_empty() {
+            view.column_alignments.insert(0, Alignment::Left);
+        }
+        if !view.wrap_policies.is_empty() {
+            view.wrap_policies.insert(0, None);
         }
+        if !view.column_formats.is_empty() {
+            view.column_formats.insert(0, None);
+        }
+        if !view.column_transforms.is_empty() {
+            view.column_transforms.insert(0, None);
+        }
+        if !view.column_links.is_empty() {
+            view.column_links.insert(0, None);
+        }
+        view.group_column = view.group_column.map(|column| column + 1);
+        for (column, _) in &mut view.aggregates {
+            *column += 1;
+        }
+        for (columns, _) in &mut view.column_groups {
+            *columns = columns.start + 1..columns.end + 1;
+        }
+        view
+    }
 
-        output
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
     }
 
-    /// Returns a vector indicating which column indices have a cell boundary.
-    /// Index 0 and `num_columns` are always true (left and right table edges).
-    fn get_row_boundaries(row: &Row, num_columns: usize) -> Vec<bool> {
-        let mut boundaries = vec![false; num_columns + 1];
-        boundaries[0] = true;
-        boundaries[num_columns] = true;
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.headers.is_none()
+    }
 
-        let mut col_idx = 0;
-        for cell in row.cells() {
-            if col_idx <= num_columns {
-                boundaries[col_idx] = true;
-            }
-            col_idx += cell.span().max(1);
-        }
-        if col_idx <= num_columns {
-            boundaries[col_idx] = true;
-        }
+    #[must_use]
+    pub fn row<R: Into<Row>>(mut self, cells: R) -> Self {
+        self.add_row(cells.into());
+        self
+    }
 
-        boundaries
+    #[must_use]
+    pub fn header<R: Into<Row>>(mut self, headers: R) -> Self {
+        self.set_headers(headers);
+        self
     }
 
-    /// Returns boundaries where all columns have separators (no colspan).
-    fn all_boundaries(num_columns: usize) -> Vec<bool> {
-        vec![true; num_columns + 1]
+    #[must_use]
+    pub fn truncate(mut self, limit: usize) -> Self {
+        self.truncate = Some(limit);
+        self.invalidate_cache();
+        self
     }
 
-    /// Forces recalculation of column widths on the next render.
+    /// Sets the marker that ends truncated content, both for the [`truncate`](Self::truncate)
+    /// limit and for cells cut to a fixed column width. `None` cuts the content hard.
+    /// The default is `"..."`, which fills columns of three or fewer characters with dots.
     ///
-    /// This method is primarily useful when the table has been modified externally
-    /// or when you want to ensure fresh calculations.
+    /// The `truncate` limit is applied when rows are inserted, so set the marker first.
     ///
     /// # Examples
     /// ```
-    /// # use crabular::{Table, Alignment};
-    /// let mut table = Table::new().header(&["A", "B"]).row(&["1", "2"]);
-    /// table.recalculate_widths();
+    /// # use crabular::Table;
+    /// let mut table = Table::new().truncate(6);
+    /// table.set_truncation_marker("…");
+    /// table.add_row(["Cherry Blossom"]);
+    /// assert_eq!(table.rows()[0].cells()[0].content(), "Cherr…");
     /// ```
-    pub fn recalculate_widths(&mut self) {
+    pub fn set_truncation_marker<'a>(&mut self, marker: impl Into<Option<&'a str>>) {
+        self.truncation_marker = marker.into().map(str::to_string);
         self.invalidate_cache();
     }
 
-    fn render_row_with_wrapping(
+    #[must_use]
+    pub fn truncation_marker(&self) -> Option<&str> {
+        self.truncation_marker.as_deref()
+    }
+
+    /// Strips ANSI escape codes from rows as they are inserted, so colored tool output
+    /// neither throws off column widths nor leaks stale colors into the table.
+    ///
+    /// Like [`truncate`](Self::truncate), this applies to rows inserted afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new();
+    /// table.set_strip_ansi(true);
+    /// table.add_row(["\x1b[32mok\x1b[0m"]);
+    /// assert_eq!(table.rows()[0].cells()[0].content(), "ok");
+    /// ```
+    pub fn set_strip_ansi(&mut self, strip: bool) {
+        self.strip_ansi = strip;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn strip_ansi(&self) -> bool {
+        self.strip_ansi
+    }
+
+    /// Sets the tab stop interval used to expand tabs in inserted rows, 4 by default.
+    /// A width of 0 removes tabs.
+    ///
+    /// Tabs are always expanded to spaces on insertion, since a terminal renders them
+    /// at a width that depends on where the cell starts. Like
+    /// [`truncate`](Self::truncate), this applies to rows inserted afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new();
+    /// table.set_tab_width(8);
+    /// table.add_row(["a\tb"]);
+    /// assert_eq!(table.rows()[0].cells()[0].content(), "a       b");
+    /// ```
+    pub fn set_tab_width(&mut self, width: usize) {
+        self.tab_width = width;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Applies the insertion-time options to an incoming row: ANSI stripping, tab
+    /// expansion, then truncation.
+    fn prepare_row(&self, mut row: Row) -> Row {
+        for index in 0..row.len() {
+            let Some(cell) = row.cell_mut(index) else {
+                continue;
+            };
+            if self.strip_ansi && cell.content().contains('\x1b') {
+                let stripped = ansi::strip(cell.content());
+                cell.set_content(&stripped);
+            }
+            if cell.content().contains('\t') {
+                let expanded = Self::expand_tabs(cell.content(), self.tab_width);
+                cell.set_content(&expanded);
+            }
+        }
+        match self.truncate {
+            Some(limit) => self.truncate_row(&row, limit),
+            None => row,
+        }
+    }
+
+    fn truncate_row(&self, row: &Row, limit: usize) -> Row {
+        let mut new_row = Row::new();
+        for cell in row.cells() {
+            let content = cell.content();
+            let truncated = if content.chars().count() > limit {
+                text::truncate(content, limit, self.truncation_marker.as_deref())
+            } else {
+                content.to_string()
+            };
+            new_row.push(Cell::new(&truncated, cell.alignment()));
+        }
+        new_row
+    }
+
+    /// Replaces each tab with spaces up to the next multiple of `width`, counting
+    /// columns from the start of each line.
+    fn expand_tabs(content: &str, width: usize) -> String {
+        let mut expanded = String::with_capacity(content.len());
+        let mut column = 0;
+        for c in content.chars() {
+            match c {
+                '\t' if width > 0 => {
+                    let spaces = width - column % width;
+                    expanded.extend(core::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                }
+                '\t' => {}
+                '\n' => {
+                    expanded.push(c);
+                    column = 0;
+                }
+                _ => {
+                    expanded.push(c);
+                    column += 1;
+                }
+            }
+        }
+        expanded
+    }
+
+    /// Writes the rendered table to stdout.
+    #[cfg(feature = "std")]
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+
+    /// Exports the headers and rows as CSV.
+    ///
+    /// Grouping, totals and `max_rows` only affect rendering and are not exported.
+    /// A spanned cell is followed by empty fields for the columns it covers, and short
+    /// rows are padded so every record has [`cols`](Self::cols) fields.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{CsvOptions, Table};
+    /// let table = Table::new().header(["City", "Note"]).row(["Berlin", "big, busy"]);
+    /// assert_eq!(table.to_csv(&CsvOptions::new()), "City,Note\nBerlin,\"big, busy\"\n");
+    /// ```
+    #[must_use]
+    pub fn to_csv(&self, options: &CsvOptions) -> String {
+        let num_columns = self.cols();
+        let mut output = String::new();
+        if let Some(headers) = self.headers.as_ref().filter(|_| options.has_header()) {
+            let fields = self.export_fields(headers, num_columns, false);
+            options.write_record(&mut output, fields.iter().map(Cow::as_ref));
+        }
+        for row in &self.rows {
+            let fields = self.export_fields(row, num_columns, options.is_wrapped());
+            options.write_record(&mut output, fields.iter().map(Cow::as_ref));
+        }
+        output
+    }
+
+    /// Exports each row as a JSON object on its own line, keyed by the header text.
+    ///
+    /// Columns without a header are keyed by their index. All values are strings.
+    /// As with [`to_csv`](Self::to_csv), grouping, totals and `max_rows` are not exported.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new().header(["name", "age"]).row(["Kata", "30"]);
+    /// assert_eq!(table.to_jsonl(), "{\"name\":\"Kata\",\"age\":\"30\"}\n");
+    /// ```
+    #[must_use]
+    pub fn to_jsonl(&self) -> String {
+        let keys = self.json_keys();
+        let mut output = String::new();
+        for row in &self.rows {
+            self.write_json_object(&mut output, &keys, row);
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Exports the rows as a JSON array of objects, one object per line, keyed like
+    /// [`to_jsonl`](Self::to_jsonl).
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new().header(["name"]).row(["Kata"]).row(["Kelana"]);
+    /// assert_eq!(table.to_json(), "[\n  {\"name\":\"Kata\"},\n  {\"name\":\"Kelana\"}\n]\n");
+    /// assert_eq!(Table::new().to_json(), "[]\n");
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        if self.rows.is_empty() {
+            return "[]\n".to_string();
+        }
+        let keys = self.json_keys();
+        let mut output = String::from("[\n");
+        for (index, row) in self.rows.iter().enumerate() {
+            if index > 0 {
+                output.push_str(",\n");
+            }
+            output.push_str("  ");
+            self.write_json_object(&mut output, &keys, row);
+        }
+        output.push_str("\n]\n");
+        output
+    }
+
+    /// Returns the JSON key of each column: its header, or its index if it has none.
+    fn json_keys(&self) -> Vec<Cow<'_, str>> {
+        (0..self.cols())
+            .map(|col| {
+                self.headers
+                    .as_ref()
+                    .and_then(|headers| headers.cells().get(col))
+                    .map_or_else(
+                        || Cow::Owned(col.to_string()),
+                        |cell| Cow::Borrowed(cell.content()),
+                    )
+            })
+            .collect()
+    }
+
+    fn write_json_object(&self, output: &mut String, keys: &[Cow<'_, str>], row: &Row) {
+        output.push('{');
+        for (index, (key, value)) in keys
+            .iter()
+            .zip(self.export_fields(row, keys.len(), false))
+            .enumerate()
+        {
+            if index > 0 {
+                output.push(',');
+            }
+            json::write_string(output, key);
+            output.push(':');
+            json::write_string(output, &value);
+        }
+        output.push('}');
+    }
+
+    /// Exports the headers and rows as an HTML `<table>`.
+    ///
+    /// Content is escaped, line breaks become `<br>`, spans become `colspan`, and
+    /// centered or right-aligned columns get a `text-align` style. As with
+    /// [`to_csv`](Self::to_csv), grouping, totals and `max_rows` are not exported.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Alignment, Table};
+    /// let mut table = Table::new().header(["Item", "Qty"]).row(["<b>", "2"]);
+    /// table.align(1, Alignment::Right);
+    /// assert_eq!(
+    ///     table.to_html(),
+    ///     "<table>\n<thead>\n<tr><th>Item</th><th style=\"text-align: right\">Qty</th></tr>\n</thead>\n\
+    ///      <tbody>\n<tr><td>&lt;b&gt;</td><td style=\"text-align: right\">2</td></tr>\n</tbody>\n</table>\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut output = String::from("<table>\n");
+        if let Some(headers) = &self.headers {
+            output.push_str("<thead>\n");
+            self.write_html_row(&mut output, headers, "th");
+            output.push_str("</thead>\n");
+        }
+        if !self.rows.is_empty() {
+            output.push_str("<tbody>\n");
+            for row in &self.rows {
+                self.write_html_row(&mut output, row, "td");
+            }
+            output.push_str("</tbody>\n");
+        }
+        output.push_str("</table>\n");
+        output
+    }
+
+    fn write_html_row(&self, output: &mut String, row: &Row, tag: &str) {
+        output.push_str("<tr>");
+        let mut column = 0;
+        for cell in row.cells() {
+            output.push('<');
+            output.push_str(tag);
+            let span = cell.span().max(1);
+            if span > 1 {
+                output.push_str(" colspan=\"");
+                output.push_str(&span.to_string());
+                output.push('"');
+            }
+            match self.column_alignments.get(column) {
+                Some(Alignment::Center) => output.push_str(" style=\"text-align: center\""),
+                Some(Alignment::Right | Alignment::Decimal) => {
+                    output.push_str(" style=\"text-align: right\"");
+                }
+                _ => {}
+            }
+            if let Some(note) = cell.note()
+                && self.render_options.get_notes() != NoteMode::Ignore
+            {
+                output.push_str(" title=\"");
+                html::write_escaped(output, note);
+                output.push('"');
+            }
+            output.push('>');
+            let link = if tag == "td" {
+                self.link_for(column, cell.content())
+            } else {
+                None
+            };
+            if let Some(url) = &link {
+                output.push_str("<a href=\"");
+                html::write_escaped(output, url);
+                output.push_str("\">");
+            }
+            for (index, line) in cell.content().lines().enumerate() {
+                if index > 0 {
+                    output.push_str("<br>");
+                }
+                html::write_escaped(output, line);
+            }
+            if link.is_some() {
+                output.push_str("</a>");
+            }
+            output.push_str("</");
+            output.push_str(tag);
+            output.push('>');
+            column += span;
+        }
+        output.push_str("</tr>\n");
+    }
+
+    /// Returns one field per column of `row`, with wrapped lines joined by `\n` if requested.
+    fn export_fields<'a>(
         &self,
-        row: &Row,
-        column_widths: &[usize],
-        borders: &BorderChars,
-        column_alignments: &[Alignment],
-    ) -> String {
-        let num_columns = column_widths.len();
-        let mut wrapped_cells: Vec<Vec<String>> = Vec::with_capacity(row.len());
-        let mut cell_spans: Vec<usize> = Vec::with_capacity(row.len());
-        let mut max_lines = 1;
+        row: &'a Row,
+        num_columns: usize,
+        wrapped: bool,
+    ) -> Vec<Cow<'a, str>> {
+        let mut fields: Vec<Cow<'a, str>> = Vec::with_capacity(num_columns);
+        for cell in row.cells() {
+            let content = cell.content();
+            let field = match self.wrap_content(content, fields.len()) {
+                Some(lines) if wrapped => Cow::Owned(lines.join("\n")),
+                _ => Cow::Borrowed(content),
+            };
+            fields.push(field);
+            for _ in 1..cell.span() {
+                fields.push(Cow::Borrowed(""));
+            }
+        }
+        fields.resize(num_columns.max(fields.len()), Cow::Borrowed(""));
+        fields
+    }
+
+    /// Renders the table into a provided byte buffer, reusing the allocation.
+    ///
+    /// This method allows for zero-allocation rendering when the buffer is reused
+    /// across multiple renders, making it ideal for repeated rendering scenarios
+    /// like pagination or filtering UI.
+    ///
+    /// # Arguments
+    /// * `buf` - A buffer to render into. Will be cleared and reused.
+    ///
+    /// # Returns
+    /// * `Ok(())` if rendering succeeded
+    ///
+    /// # Errors
+    /// This function currently never returns an error, but returns `Result` for
+    /// future compatibility with potential I/O operations.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let mut buffer = Vec::with_capacity(4096);
+    /// for item in items {
+    ///     buffer.clear();
+    ///     table.render_into(&mut buffer)?;
+    ///     stdout.write_all(&buffer)?;
+    /// }
+    /// ```
+    pub fn render_into(&self, buf: &mut Vec<u8>) -> core::fmt::Result {
+        buf.clear();
+        let rendered = self.render();
+        buf.extend_from_slice(rendered.as_bytes());
+        Ok(())
+    }
+
+    /// Formats a cell's content with the given width and alignment.
+    ///
+    /// This is a lower-level function that can be useful for custom formatting needs.
+    ///
+    /// # Arguments
+    /// * `content` - The cell content to format
+    /// * `width` - The target width for the formatted output
+    /// * `alignment` - The alignment to use
+    ///
+    /// # Returns
+    /// The formatted string with appropriate padding. Content that does not fit ends in
+    /// `"..."`, or is all dots when the width leaves no room for any content.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, Alignment};
+    /// let formatted = Table::format_cell("test", 10, Alignment::Left);
+    /// assert_eq!(formatted, "test      ");
+    /// assert_eq!(Table::format_cell("test", 2, Alignment::Left), "..");
+    /// ```
+    #[must_use]
+    pub fn format_cell(content: &str, width: usize, alignment: Alignment) -> String {
+        let marker = Some(DEFAULT_TRUNCATION_MARKER);
+        Self::dots(text::width(content), width, marker)
+            .unwrap_or_else(|| text::fit(content, width, alignment, marker))
+    }
+
+    /// Fills a cell with dots when content wider than `width` leaves no room for any of
+    /// it before the default marker. Custom markers cut hard instead.
+    fn dots(content_width: usize, width: usize, marker: Option<&str>) -> Option<String> {
+        (marker == Some(DEFAULT_TRUNCATION_MARKER)
+            && width <= DEFAULT_TRUNCATION_MARKER.len()
+            && content_width > width)
+            .then(|| ".".repeat(width))
+    }
+
+    /// Returns rows partitioned by the content of `column`, in order of first appearance.
+    fn group_rows(&self, column: usize) -> Vec<Vec<&Row>> {
+        let mut index_by_key: HashMap<&str, usize> = HashMap::new();
+        let mut groups: Vec<Vec<&Row>> = Vec::new();
+
+        for row in &self.rows {
+            let key = row.cells().get(column).map_or("", Cell::content);
+            let index = *index_by_key.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(row);
+        }
+
+        groups
+    }
+
+    /// Builds a summary row with `label` in `label_column` and the registered
+    /// aggregates computed over `rows`.
+    fn summary_row(&self, rows: &[&Row], label_column: usize, label: &str) -> Row {
+        let aggregate_cols = self.aggregates.iter().map(|(col, _)| col + 1).max();
+        let num_cells = aggregate_cols.unwrap_or(0).max(label_column + 1);
+
+        let mut summary = Row::new();
+        for col in 0..num_cells {
+            let alignment = self.column_alignments.get(col).copied().unwrap_or_default();
+            let content = match self.aggregates.iter().find(|(c, _)| *c == col) {
+                Some((_, aggregate)) => aggregate.compute(
+                    rows.iter()
+                        .map(|row| row.cells().get(col).map_or("", Cell::content)),
+                ),
+                None if col == label_column => label.to_string(),
+                None => String::new(),
+            };
+            summary.push(Cell::new(&content, alignment));
+        }
+        summary
+    }
+
+    /// Lays out the body rows in render order, cut off after `max_rows` with an
+    /// elision line spanning all columns.
+    fn body_rows(&self) -> Vec<BodyRow<'_>> {
+        let mut body = self.laid_out_rows();
+        if let Some(max_rows) = self.max_rows {
+            let mut shown = 0;
+            let cut = body.iter().position(|line| {
+                let past = line.rows > 0 && shown == max_rows;
+                shown += usize::from(line.rows > 0);
+                past
+            });
+            if let Some(cut) = cut {
+                // The grand total stays below the elision line
+                let total = body.pop_if(|line| line.rule == Some(Rule::Double));
+                let hidden = body.drain(cut..).map(|line| line.rows).sum();
+                body.push(self.spanning_message(&self.messages.format_more_rows(hidden)));
+                body.extend(total);
+            }
+        }
+        if body.is_empty()
+            && self.headers.is_some()
+            && let Some(no_data) = self.messages.get_no_data()
+        {
+            body.push(self.spanning_message(no_data));
+        }
+        self.format_body(&mut body);
+        self.align_decimal_points(&mut body);
+        body
+    }
+
+    /// Builds an unmeasured row holding `text` in one cell spanning all columns.
+    fn spanning_message(&self, text: &str) -> BodyRow<'_> {
+        let mut cell = Cell::new(text, Alignment::Left);
+        cell.set_span(self.cols().max(1));
+        let mut row = Row::new();
+        row.push(cell);
+        BodyRow {
+            rule: None,
+            row: Cow::Owned(row),
+            measured: false,
+            summary: false,
+            rows: 0,
+            decorations: Vec::new(),
+        }
+    }
+
+    /// Sets the hyperlinks and colors of a body row's cells: badge colors, or else the
+    /// color of the column's type.
+    fn decorate_row(
+        &self,
+        body_row: &mut BodyRow<'_>,
+        hyperlinks: bool,
+        badges: &[Option<Badges>],
+        type_colors: &[Option<Color>],
+    ) {
+        let mut column = 0;
+        for cell in body_row.row.cells() {
+            let url = if hyperlinks {
+                self.link_for(column, cell.content())
+            } else {
+                None
+            };
+            let color = badges
+                .get(column)
+                .and_then(Option::as_ref)
+                .and_then(|badges| badges.color(cell.content()))
+                .or_else(|| type_colors.get(column).copied().flatten());
+            if url.is_some() || color.is_some() {
+                if column >= body_row.decorations.len() {
+                    body_row
+                        .decorations
+                        .resize(column + 1, Decoration::default());
+                }
+                body_row.decorations[column] = Decoration { url, color };
+            }
+            column += cell.span().max(1);
+        }
+    }
+
+    /// Returns the theme color of each column by its detected type, or nothing when the
+    /// theme colors no types.
+    fn column_type_colors(&self) -> Vec<Option<Color>> {
+        if self.type_colors.is_empty() {
+            return Vec::new();
+        }
+        self.column_types()
+            .into_iter()
+            .map(|column_type| {
+                self.type_colors
+                    .iter()
+                    .find(|(existing, _)| *existing == column_type)
+                    .map(|(_, color)| *color)
+            })
+            .collect()
+    }
+
+    /// Replaces the content of cells in formatted or transformed columns with their
+    /// display text.
+    fn format_body(&self, body: &mut [BodyRow<'_>]) {
+        let formatters: Vec<Option<Formatter>> = self
+            .column_formats
+            .iter()
+            .map(|spec| {
+                spec.as_deref()
+                    .and_then(|spec| self.formatters.resolve(spec))
+            })
+            .collect();
+        let badges: Vec<Option<Badges>> = self
+            .column_formats
+            .iter()
+            .map(|spec| {
+                spec.as_deref()
+                    .and_then(|spec| self.formatters.resolve_badges(spec))
+            })
+            .collect();
+        let placeholder = !self.empty_placeholder.is_empty();
+        let hyperlinks =
+            self.render_options.get_hyperlinks() && self.column_links.iter().any(Option::is_some);
+        let notes = self.notes();
+        let tree = self.tree_prefixes();
+        let type_colors = self.column_type_colors();
+        let typed = type_colors.iter().any(Option::is_some);
+        let transformed = self.column_transforms.iter().any(Option::is_some);
+        if formatters.iter().all(Option::is_none)
+            && !transformed
+            && !placeholder
+            && !hyperlinks
+            && !typed
+            && notes.is_empty()
+            && tree.is_empty()
+        {
+            return;
+        }
+        let decorate = hyperlinks || typed || badges.iter().any(Option::is_some);
+        for body_row in body.iter_mut().filter(|body_row| body_row.measured) {
+            // Decorations are looked up from the stored content, before formatting
+            if decorate && !body_row.summary {
+                self.decorate_row(body_row, hyperlinks, &badges, &type_colors);
+            }
+            let fill = placeholder && !body_row.summary;
+            let has_empty = body_row
+                .row
+                .cells()
+                .iter()
+                .any(|cell| cell.content().is_empty());
+            let annotated = body_row
+                .row
+                .cells()
+                .iter()
+                .any(|cell| cell.note().is_some());
+            let branch = tree
+                .get(&body_row.row.origin())
+                .filter(|_| !body_row.summary);
+            if !(annotated || fill && has_empty || branch.is_some())
+                && formatters.iter().all(Option::is_none)
+                && !transformed
+            {
+                continue;
+            }
+            let row = body_row.row.to_mut();
+            let mut column = 0;
+            for index in 0..row.len() {
+                let Some(cell) = row.cell_mut(index) else {
+                    break;
+                };
+                if let Some(Some(formatter)) = formatters.get(column) {
+                    let content = formatter(cell.content());
+                    cell.set_content(&content);
+                }
+                if let Some(Some(transform)) = self.column_transforms.get(column) {
+                    let content = transform.apply(cell.content());
+                    cell.set_content(&content);
+                }
+                if fill && cell.content().is_empty() {
+                    cell.set_content(&self.empty_placeholder);
+                }
+                if let Some(marker) = Self::note_marker(&notes, cell) {
+                    let content = format!("{}{marker}", cell.content());
+                    cell.set_content(&content);
+                }
+                if let Some(branch) = branch.filter(|_| index == 0) {
+                    let content = format!("{branch}{}", cell.content());
+                    cell.set_content(&content);
+                }
+                column += cell.span();
+            }
+        }
+    }
+
+    /// Pads the cells of [`Alignment::Decimal`] columns on the right so that, once
+    /// right-aligned, their decimal points line up. Spanning cells are left alone.
+    fn align_decimal_points(&self, body: &mut [BodyRow<'_>]) {
+        let is_decimal = |column: usize, cell: &Cell| {
+            cell.span() <= 1
+                && self
+                    .column_alignments
+                    .get(column)
+                    .copied()
+                    .unwrap_or_else(|| cell.alignment())
+                    == Alignment::Decimal
+        };
+        let mut fractions: Vec<usize> = Vec::new();
+        for body_row in body.iter().filter(|body_row| body_row.measured) {
+            let mut column = 0;
+            for cell in body_row.row.cells() {
+                if is_decimal(column, cell)
+                    && let Some(fraction) = Self::fraction_width(cell.content())
+                {
+                    if column >= fractions.len() {
+                        fractions.resize(column + 1, 0);
+                    }
+                    fractions[column] = fractions[column].max(fraction);
+                }
+                column += cell.span().max(1);
+            }
+        }
+        if fractions.iter().all(|&fraction| fraction == 0) {
+            return;
+        }
+        for body_row in body.iter_mut().filter(|body_row| body_row.measured) {
+            let mut column = 0;
+            for index in 0..body_row.row.len() {
+                let Some(cell) = body_row.row.cells().get(index) else {
+                    break;
+                };
+                let span = cell.span().max(1);
+                let padding = fractions.get(column).copied().unwrap_or(0);
+                if padding > 0
+                    && is_decimal(column, cell)
+                    && let Some(fraction) = Self::fraction_width(cell.content())
+                    && fraction < padding
+                    && let Some(cell) = body_row.row.to_mut().cell_mut(index)
+                {
+                    let content = format!("{}{}", cell.content(), " ".repeat(padding - fraction));
+                    cell.set_content(&content);
+                }
+                column += span;
+            }
+        }
+    }
+
+    /// Returns the width of what follows the integer part of the first number in
+    /// `content`, decimal point included: 3 for `$1,234.50` and 1 for `12%`. Content
+    /// without digits has no integer part and returns `None`.
+    fn fraction_width(content: &str) -> Option<usize> {
+        let start = content.find(|c: char| c.is_ascii_digit())?;
+        let end = content[start..]
+            .find(|c: char| !c.is_ascii_digit() && c != ',')
+            .map_or(content.len(), |offset| start + offset);
+        Some(text::width(&content[end..]))
+    }
+
+    /// Lays out all body rows in render order, including group separators,
+    /// subtotal rows and the grand-total footer.
+    fn laid_out_rows(&self) -> Vec<BodyRow<'_>> {
+        let summarize = !self.aggregates.is_empty();
+
+        let Some(column) = self.group_column else {
+            let mut body: Vec<BodyRow<'_>> = self
+                .rows
+                .iter()
+                .map(|row| BodyRow {
+                    rule: None,
+                    row: Cow::Borrowed(row),
+                    measured: true,
+                    summary: false,
+                    rows: 1,
+                    decorations: Vec::new(),
+                })
+                .collect();
+            if self.grand_total && summarize {
+                let all: Vec<&Row> = self.rows.iter().collect();
+                body.push(BodyRow {
+                    rule: Some(Rule::Double),
+                    row: Cow::Owned(self.summary_row(&all, 0, self.messages.get_total())),
+                    measured: true,
+                    summary: true,
+                    rows: 0,
+                    decorations: Vec::new(),
+                });
+            }
+            return body;
+        };
+
+        let mut body = Vec::with_capacity(self.rows.len());
+        for (index, group) in self.group_rows(column).iter().enumerate() {
+            let key = group
+                .first()
+                .and_then(|row| row.cells().get(column))
+                .map_or("", Cell::content);
+            if self.collapsed_groups.contains(key) {
+                let mut line =
+                    self.spanning_message(&self.messages.format_collapsed_group(key, group.len()));
+                line.rule = (index > 0).then_some(Rule::Single);
+                line.rows = group.len();
+                body.push(line);
+            } else {
+                for (row_index, row) in group.iter().enumerate() {
+                    let rule = (index > 0 && row_index == 0).then_some(Rule::Single);
+                    body.push(BodyRow {
+                        rule,
+                        row: Cow::Borrowed(*row),
+                        measured: true,
+                        summary: false,
+                        rows: 1,
+                        decorations: Vec::new(),
+                    });
+                }
+            }
+            if self.subtotals && summarize {
+                body.push(BodyRow {
+                    rule: Some(Rule::Single),
+                    row: Cow::Owned(self.summary_row(group, column, self.messages.get_subtotal())),
+                    measured: true,
+                    summary: true,
+                    rows: 0,
+                    decorations: Vec::new(),
+                });
+            }
+        }
+
+        if self.grand_total && summarize {
+            let all: Vec<&Row> = self.rows.iter().collect();
+            body.push(BodyRow {
+                rule: Some(Rule::Double),
+                row: Cow::Owned(self.summary_row(&all, column, self.messages.get_total())),
+                measured: true,
+                summary: true,
+                rows: 0,
+                decorations: Vec::new(),
+            });
+        }
+
+        body
+    }
+
+    fn calculate_column_widths(&self, body: &[BodyRow<'_>]) -> Vec<usize> {
+        let natural = self.measure_column_widths(body);
+        self.constrain_column_widths(natural, body)
+    }
+
+    /// Collects the issues of rendering `body` at `widths` when its cells would
+    /// take the `natural` widths.
+    fn render_report(
+        &self,
+        body: &[BodyRow<'_>],
+        natural: &[usize],
+        widths: &[usize],
+    ) -> RenderReport {
+        let clipped_columns: Vec<usize> = widths
+            .iter()
+            .zip(natural)
+            .enumerate()
+            .filter(|(_, (width, natural))| width < natural)
+            .map(|(column, _)| column)
+            .collect();
+
+        let mut truncated_cells = 0;
+        if !clipped_columns.is_empty() {
+            let text_width = self.text_width();
+            let headers = self.display_headers();
+            let rows = headers.as_deref().into_iter().chain(
+                body.iter()
+                    .filter(|body_row| body_row.measured)
+                    .map(|body_row| body_row.row.as_ref()),
+            );
+            for row in rows {
+                let mut column = 0;
+                for cell in row.cells() {
+                    let span = cell.span().max(1);
+                    let clipped = (column..column + span).any(|c| clipped_columns.contains(&c));
+                    if clipped {
+                        let width = self.calculate_span_width(column, span, widths);
+                        let lines = self.wrap_cell(cell, column);
+                        if lines.iter().any(|line| text_width(line) > width) {
+                            truncated_cells += 1;
+                        }
+                    }
+                    column += span;
+                }
+            }
+        }
+
+        let proportional: usize = self
+            .constraints
+            .iter()
+            .map(|constraint| match constraint {
+                WidthConstraint::Proportional(percent) => usize::from(*percent),
+                _ => 0,
+            })
+            .sum();
+        let normalized_constraints = self
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|(column, constraint)| match constraint {
+                WidthConstraint::Auto => false,
+                WidthConstraint::Proportional(_) if proportional > 100 => true,
+                _ => *column >= widths.len(),
+            })
+            .map(|(column, _)| column)
+            .collect();
+
+        RenderReport {
+            truncated_cells,
+            clipped_columns,
+            normalized_constraints,
+        }
+    }
+
+    /// Returns the issues of the last [`render`](Self::render), such as values cut
+    /// to fit their column. See [`RenderReport`].
+    ///
+    /// Renders answered from the [memoized](Self::set_memoize) output keep the
+    /// report of the render that built it. Other outputs, such as pages and exports,
+    /// do not change it.
+    #[must_use]
+    pub fn last_render_report(&self) -> RenderReport {
+        self.last_report.borrow().clone()
+    }
+
+    /// Returns the width of the widest header and measured body cell of each column.
+    fn measure_column_widths(&self, body: &[BodyRow<'_>]) -> Vec<usize> {
+        #[cfg(feature = "instrumentation")]
+        self.record(|stats| stats.width_passes += 1);
+        let mut max_widths: Vec<usize> = Vec::new();
+        let text_width = self.text_width();
+
+        if let Some(headers) = self.display_headers() {
+            for (idx, cell) in headers.cells().iter().enumerate() {
+                let width = text_width(cell.content());
+                if max_widths.len() < idx + 1 {
+                    max_widths.resize(idx + 1, 0);
+                }
+                if width > max_widths[idx] {
+                    max_widths[idx] = width;
+                }
+            }
+        }
+
+        // When every stored row is drawn as stored, the tracked row widths stand in
+        // for them and only rows changed for display are measured
+        let stored = body
+            .iter()
+            .filter(|body_row| body_row.measured && matches!(body_row.row, Cow::Borrowed(_)))
+            .count();
+        let tracked = stored == self.rows.len();
+        if tracked {
+            max_widths = Self::widest(max_widths, self.row_widths());
+        }
+        let measure = |body_row: &BodyRow<'_>| {
+            body_row.measured && !(tracked && matches!(body_row.row, Cow::Borrowed(_)))
+        };
+        #[cfg(feature = "instrumentation")]
+        self.record(|stats| {
+            stats.cells_measured += body
+                .iter()
+                .filter(|body_row| measure(body_row))
+                .map(|body_row| body_row.row.cells().len())
+                .sum::<usize>();
+        });
+        // Measuring dominates the render of large tables, so it may run in parallel
+        let body_widths = parallel::fold(
+            body,
+            |widths, body_row| {
+                if measure(body_row) {
+                    Self::widen(text_width, widths, &body_row.row)
+                } else {
+                    widths
+                }
+            },
+            Self::widest,
+        );
+        Self::widest(max_widths, body_widths)
+    }
+
+    /// Applies the width constraints to the measured widths, then widens columns to
+    /// fit group labels and rows spanning the whole table.
+    fn constrain_column_widths(
+        &self,
+        mut max_widths: Vec<usize>,
+        body: &[BodyRow<'_>],
+    ) -> Vec<usize> {
+        self.apply_width_constraints(&mut max_widths);
+        self.apply_proportional_constraints(&mut max_widths);
+        self.apply_weight_constraints(&mut max_widths);
+
+        // Groups are widened to fit their label
+        for (columns, label) in self.drawn_column_groups() {
+            let end = columns.end.min(max_widths.len());
+            let Some(label) = label.as_deref().filter(|_| columns.start < end) else {
+                continue;
+            };
+            let needed = text::width(label);
+            let available =
+                self.calculate_span_width(columns.start, end - columns.start, &max_widths);
+            if available < needed {
+                max_widths[end - 1] += needed - available;
+            }
+        }
+
+        for body_row in body.iter().filter(|body_row| !body_row.measured) {
+            let Some(cell) = body_row.row.cells().first() else {
+                continue;
+            };
+            if max_widths.len() < cell.span() {
+                max_widths.resize(cell.span(), 0);
+            }
+            let needed = text::width(cell.content());
+            let available = self.calculate_span_width(0, max_widths.len(), &max_widths);
+            if let Some(last) = max_widths.last_mut()
+                && available < needed
+            {
+                *last += needed - available;
+            }
+        }
+        max_widths
+    }
+
+    /// Returns the tracked widest cell of each column over the stored rows, measuring
+    /// the rows first if they changed since.
+    fn row_widths(&self) -> Vec<usize> {
+        if let Some(widths) = self.row_widths.borrow().as_ref() {
+            return widths.clone();
+        }
+        #[cfg(feature = "instrumentation")]
+        self.record(|stats| {
+            stats.cells_measured += self.rows.iter().map(|row| row.cells().len()).sum::<usize>();
+        });
+        let width = self.text_width();
+        let widths = parallel::fold(
+            &self.rows,
+            |widths, row| Self::widen(width, widths, row),
+            Self::widest,
+        );
+        *self.row_widths.borrow_mut() = Some(widths.clone());
+        widths
+    }
+
+    /// Returns how cell content is measured: without its escape sequences when
+    /// [ANSI passthrough](RenderOptions::ansi_passthrough) is on.
+    fn text_width(&self) -> fn(&str) -> usize {
+        if self.render_options.get_ansi_passthrough() {
+            text::ansi_width
+        } else {
+            text::width
+        }
+    }
+
+    /// Widens `widths` to fit the cells of `row`, measured with `text_width`.
+    fn widen(text_width: fn(&str) -> usize, mut widths: Vec<usize>, row: &Row) -> Vec<usize> {
+        for (idx, cell) in row.cells().iter().enumerate() {
+            let width = text_width(cell.content());
+            if widths.len() < idx + 1 {
+                widths.resize(idx + 1, 0);
+            }
+            if width > widths[idx] {
+                widths[idx] = width;
+            }
+        }
+        widths
+    }
+
+    /// Combines two sets of column widths, keeping the wider of each column.
+    fn widest(mut widths: Vec<usize>, other: Vec<usize>) -> Vec<usize> {
+        if widths.len() < other.len() {
+            widths.resize(other.len(), 0);
+        }
+        for (width, other) in widths.iter_mut().zip(other) {
+            *width = (*width).max(other);
+        }
+        widths
+    }
+
+    fn apply_width_constraints(&self, widths: &mut [usize]) {
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if i < widths.len() && self.wrap_policy(i) != WrapPolicy::Never {
+                match constraint {
+                    WidthConstraint::Fixed(w) => {
+                        widths[i] = *w;
+                    }
+                    WidthConstraint::Min(m) => {
+                        if widths[i] < *m {
+                            widths[i] = *m;
+                        }
+                    }
+                    WidthConstraint::Max(m) => {
+                        if widths[i] > *m {
+                            widths[i] = *m;
+                        }
+                    }
+                    WidthConstraint::Wrap(w) => {
+                        if widths[i] > *w {
+                            widths[i] = *w;
+                        }
+                    }
+                    WidthConstraint::Auto
+                    | WidthConstraint::Proportional(_)
+                    | WidthConstraint::Weight(_) => {}
+                }
+            }
+        }
+    }
+
+    fn apply_proportional_constraints(&self, widths: &mut [usize]) {
+        let total_percentage: u8 = self
+            .constraints
+            .iter()
+            .filter_map(|c| {
+                if let WidthConstraint::Proportional(p) = c {
+                    Some(*p)
+                } else {
+                    None
+                }
+            })
+            .sum();
+
+        if total_percentage == 0 || total_percentage > 100 {
+            return;
+        }
+
+        let proportional_width = self.layout_width(widths.len());
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if i < widths.len()
+                && let WidthConstraint::Proportional(percentage) = constraint
+            {
+                let calculated_width = (proportional_width * *percentage as usize) / 100;
+                widths[i] = widths[i].max(calculated_width);
+            }
+        }
+    }
+
+    /// Shares the width not taken by the other columns among the `Weight` columns by
+    /// ratio. The remainder of the division goes to the leftmost weighted columns.
+    fn apply_weight_constraints(&self, widths: &mut [usize]) {
+        let weights: Vec<(usize, usize)> = self
+            .constraints
+            .iter()
+            .enumerate()
+            .take(widths.len())
+            .filter_map(|(i, constraint)| match constraint {
+                WidthConstraint::Weight(weight) if *weight > 0 => Some((i, *weight as usize)),
+                _ => None,
+            })
+            .collect();
+        let total_weight: usize = weights.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return;
+        }
+
+        let taken: usize = widths
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !weights.iter().any(|(column, _)| column == i))
+            .map(|(_, width)| width)
+            .sum();
+        let leftover = self.layout_width(widths.len()).saturating_sub(taken);
+        let mut remainder = leftover;
+        let mut shares: Vec<usize> = weights
+            .iter()
+            .map(|(_, weight)| {
+                let share = leftover * weight / total_weight;
+                remainder -= share;
+                share
+            })
+            .collect();
+        for share in shares.iter_mut().take(remainder) {
+            *share += 1;
+        }
+        for ((i, _), share) in weights.iter().zip(shares) {
+            widths[*i] = widths[*i].max(share);
+        }
+    }
+
+    /// Returns the content width available to `columns` columns within the 120
+    /// character layout width, after padding and column spacing.
+    fn layout_width(&self, columns: usize) -> usize {
+        let padding = self.padding().left + self.padding().right;
+        let spacing = self.get_spacing().saturating_mul(columns.saturating_sub(1));
+        let max_width: usize = 120;
+        max_width.saturating_sub(padding * columns + spacing)
+    }
+
+    #[must_use]
+    pub fn render(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        if self.memoize
+            && let Some(ref output) = *self.cached_output.borrow()
+        {
+            #[cfg(feature = "instrumentation")]
+            self.record(|stats| stats.memo_hits += 1);
+            return output.clone();
+        }
+
+        let output = if self.selection_column {
+            let view = self.with_selection_column(|_, row| self.selected.contains(&row.origin()));
+            let output = view.render();
+            *self.last_report.borrow_mut() = view.last_render_report().without_first_column();
+            output
+        } else {
+            let body = self.body_rows();
+            let natural = self.measure_column_widths(&body);
+            let column_widths = self.constrain_column_widths(natural.clone(), &body);
+            *self.last_report.borrow_mut() = self.render_report(&body, &natural, &column_widths);
+            let mut output = self.render_with_widths(&column_widths, &body);
+            output.push_str(&self.footnotes());
+            self.finish_render(output)
+        };
+        if self.memoize {
+            *self.cached_output.borrow_mut() = Some(output.clone());
+        }
+        output
+    }
+
+    /// Applies the render options to a finished render.
+    fn finish_render(&self, output: String) -> String {
+        let output = self.render_options.apply_to_lines(output);
+        #[cfg(feature = "instrumentation")]
+        self.record(|stats| {
+            stats.renders += 1;
+            stats.lines += output.lines().count();
+            stats.bytes += output.len();
+        });
+        output
+    }
+
+    /// Returns the work counted by this table's renders so far.
+    #[cfg(feature = "instrumentation")]
+    #[must_use]
+    pub fn render_stats(&self) -> RenderStats {
+        *self.stats.borrow()
+    }
+
+    /// Sets all render counters back to zero.
+    #[cfg(feature = "instrumentation")]
+    pub fn reset_render_stats(&self) {
+        *self.stats.borrow_mut() = RenderStats::default();
+    }
+
+    #[cfg(feature = "instrumentation")]
+    fn record(&self, update: impl FnOnce(&mut RenderStats)) {
+        update(&mut self.stats.borrow_mut());
+    }
+
+    /// Makes [`render`](Self::render), [`print`](Self::print) and `Display` return
+    /// the previous output when nothing has changed since, for UIs that re-render
+    /// every frame. Every method that changes the table discards the cached output.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::new().header(["A"]).row(["1"]);
+    /// table.set_memoize(true);
+    /// let first = table.render();
+    /// assert_eq!(table.render(), first); // served from the cache
+    ///
+    /// table.add_row(["2"]);
+    /// assert!(table.render().contains('2'));
+    /// ```
+    pub fn set_memoize(&mut self, memoize: bool) {
+        self.memoize = memoize;
+        self.invalidate_cache();
+    }
+
+    #[must_use]
+    pub fn memoize(&self) -> bool {
+        self.memoize
+    }
+
+    /// Stores equal cell contents of rows added from now on once, shared between the
+    /// cells, instead of once per cell. For large tables with repeated values, such as
+    /// status or category columns, this keeps one allocation per distinct value.
+    /// Turning it off forgets the shared strings; cells that share them keep doing so.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let mut table = Table::with_capacity(1000, 2);
+    /// table.set_interning(true);
+    /// for id in 0..1000 {
+    ///     table.add_row([id.to_string(), "active".to_string()]);
+    /// }
+    /// let status = |row: usize| table.rows()[row].cells()[1].content().as_ptr();
+    /// assert_eq!(status(0), status(999));
+    /// ```
+    pub fn set_interning(&mut self, interning: bool) {
+        if interning != self.interned.is_some() {
+            self.interned = interning.then(BTreeSet::new);
+            self.invalidate_output();
+        }
+    }
+
+    #[must_use]
+    pub fn interning(&self) -> bool {
+        self.interned.is_some()
+    }
+
+    /// Renders row `index` as a two-column field/value table, one line per column with
+    /// the header as the field name, like the expanded display of `psql`. Columns
+    /// without a header are named by their index. Column formatters and the empty
+    /// placeholder apply to the values; the style, padding and output options are
+    /// those of this table. Returns `None` if there is no such row.
+    ///
+    /// With both [`Messages::record`] headers empty, the header row is left out, as in
+    /// `psql`'s expanded display with tuples only.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new()
+    ///     .header(["id", "name"])
+    ///     .row(["1", "Kata"])
+    ///     .row(["2", "Kelana"]);
+    /// assert_eq!(
+    ///     table.render_record(1).as_deref(),
+    ///     Some(
+    ///         "+--------+--------+\n\
+    ///          | Field  | Value  |\n\
+    ///          +--------+--------+\n\
+    ///          | id     | 2      |\n\
+    ///          | name   | Kelana |\n\
+    ///          +--------+--------+\n"
+    ///     )
+    /// );
+    /// assert_eq!(table.render_record(2), None);
+    /// ```
+    #[must_use]
+    pub fn render_record(&self, index: usize) -> Option<String> {
+        let mut row = self.rows.as_slice().get(index)?.clone();
+        for column in 0..row.len() {
+            if let Some(cell) = row.cell_mut(column) {
+                cell.set_note(None);
+            }
+        }
+        let mut body = [BodyRow {
+            rule: None,
+            row: Cow::Owned(row),
+            measured: true,
+            summary: false,
+            rows: 1,
+            decorations: Vec::new(),
+        }];
+        self.format_body(&mut body);
+
+        let mut record = Table::new();
+        record.style = self.style;
+        record.padding = self.padding;
+        record.column_spacing = self.column_spacing;
+        record.tab_width = self.tab_width;
+        record.strip_ansi = self.strip_ansi;
+        record.header_style = self.header_style;
+        record.render_options = self.render_options.clone();
+        let (field, value) = (
+            self.messages.get_record_field(),
+            self.messages.get_record_value(),
+        );
+        if !field.is_empty() || !value.is_empty() {
+            record.set_headers([field, value]);
+        }
+        let headers = self.headers.as_ref().map(Row::cells).unwrap_or_default();
+        for (column, cell) in body[0].row.cells().iter().enumerate() {
+            let field = headers
+                .get(column)
+                .map_or_else(|| column.to_string(), |header| header.content().to_string());
+            record.add_row([field.as_str(), cell.content()]);
+        }
+        Some(record.render())
+    }
+
+    /// Renders a table that is too wide as a stack of narrower chunks.
+    ///
+    /// Columns are packed left to right into chunks whose lines fit within
+    /// [`SplitOptions::max_width`]. Frozen and label columns are repeated at the
+    /// start of every chunk, and chunks are separated by a blank line (followed by
+    /// the continued caption, if set). Column spans are not preserved across chunks.
+    #[must_use]
+    pub fn render_split(&self, options: &SplitOptions) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let body = self.body_rows();
+        let column_widths = self.calculate_column_widths(&body);
+        let repeated = options.repeated_columns(column_widths.len());
+
+        let chunk_width = |columns: &[usize]| {
+            1 + columns
+                .iter()
+                .map(|&col| self.padding().left + column_widths[col] + self.padding().right + 1)
+                .sum::<usize>()
+                + self.get_spacing() * columns.len().saturating_sub(1)
+        };
+
+        let mut chunks: Vec<Vec<usize>> = Vec::new();
+        let mut current = repeated.clone();
+        for col in (0..column_widths.len()).filter(|col| !repeated.contains(col)) {
+            current.push(col);
+            if current.len() > repeated.len() + 1 && chunk_width(&current) > options.max_width() {
+                current.pop();
+                chunks.push(core::mem::replace(&mut current, repeated.clone()));
+                current.push(col);
+            }
+        }
+        if current.len() > repeated.len() || chunks.is_empty() {
+            chunks.push(current);
+        }
+
+        let mut output = String::new();
+        for (index, columns) in chunks.iter().enumerate() {
+            if index > 0 {
+                output.push('\n');
+                if let Some(caption) = options.get_continued_caption() {
+                    output.push_str(caption);
+                    output.push('\n');
+                }
+            }
+
+            let mut chunk = self.filtered(|_| false);
+            chunk.headers = self
+                .display_headers()
+                .map(|headers| self.project_row(&headers, columns));
+            chunk.column_alignments.clear();
+            chunk.column_groups.clear();
+            chunk.constraints = columns
+                .iter()
+                .map(|&col| self.constraints.get(col).copied().unwrap_or_default())
+                .collect();
+
+            let chunk_body: Vec<BodyRow<'_>> = body
+                .iter()
+                .map(|body_row| BodyRow {
+                    rule: body_row.rule,
+                    row: Cow::Owned(if body_row.measured {
+                        self.project_row(&body_row.row, columns)
+                    } else {
+                        Self::spanning_row(&body_row.row, columns.len())
+                    }),
+                    measured: body_row.measured,
+                    summary: body_row.summary,
+                    rows: body_row.rows,
+                    decorations: columns
+                        .iter()
+                        .map(|&col| body_row.decorations.get(col).cloned().unwrap_or_default())
+                        .collect(),
+                })
+                .collect();
+            let chunk_widths: Vec<usize> = columns.iter().map(|&col| column_widths[col]).collect();
+            output.push_str(&chunk.render_with_widths(&chunk_widths, &chunk_body));
+        }
+
+        self.finish_render(output)
+    }
+
+    /// Renders the rows `rows` of an external [`RowProvider`], using this table as the
+    /// template for headers, style and all other settings. The table's own rows are
+    /// ignored.
+    ///
+    /// Only the rows in the range are fetched, so column widths are measured over
+    /// them alone; use `Fixed` or `Min` constraints to keep widths steady while
+    /// scrolling. The range is clamped to the provider's length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Row, Table};
+    /// let store: Vec<Row> = (0..100).map(|i| Row::from([i.to_string()])).collect();
+    /// let template = Table::new().header(["ID"]);
+    /// let output = template.render_viewport(&store, 40..42);
+    /// assert_eq!(output.lines().count(), 6);
+    /// assert!(output.contains("| 41 |"));
+    /// ```
+    #[must_use]
+    pub fn render_viewport<P>(&self, provider: &P, rows: Range<usize>) -> String
+    where
+        P: RowProvider + ?Sized,
+    {
+        let end = rows.end.min(provider.len());
+        let start = rows.start.min(end);
+        let mut view = self.filtered(|_| false);
+        view.rows.reserve(end - start);
+        for index in start..end {
+            let row = view.prepare_row(provider.get(index));
+            view.rows.push(row);
+        }
+        if self.selection_column {
+            // Provider rows are selected by their index in the provider
+            return view
+                .with_selection_column(|index, _| self.selected.contains(&(start + index)))
+                .render();
+        }
+        view.render()
+    }
+
+    /// Splits the rendered body into pages of `page_size` rows, each rendered with
+    /// the headers repeated at the top.
+    ///
+    /// Subtotal and grand-total rows count towards the page size. A `page_size` of
+    /// zero is treated as one. An empty table yields no pages; a table with headers
+    /// but no rows yields a single page.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new()
+    ///     .header(["ID"])
+    ///     .row(["1"])
+    ///     .row(["2"])
+    ///     .row(["3"]);
+    /// let pages: Vec<String> = table.paginate(2).map(|page| page.render()).collect();
+    /// assert_eq!(pages.len(), 2);
+    /// assert!(pages[1].contains("ID"));
+    /// ```
+    #[must_use]
+    pub fn paginate(&self, page_size: usize) -> Pages<'_> {
+        let body = self.body_rows();
+        let column_widths = self.calculate_column_widths(&body);
+        Pages::new(self, column_widths, body.len(), page_size)
+    }
+
+    /// Starts rendering the table piece by piece, for inputs too large to hold in
+    /// memory. Column widths are measured over the rows the table holds now, usually
+    /// a sample of the input, and then stay fixed; longer content streamed later is
+    /// cut with the truncation marker.
+    ///
+    /// Grouping, totals and `max_rows` do not apply to streamed rows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let sample = Table::new().header(["ID", "Name"]).row(["1", "Kelana"]);
+    /// let mut stream = sample.stream();
+    /// let mut output = stream.header();
+    /// output.push_str(&stream.rows(sample.rows().iter().cloned()));
+    /// output.push_str(&stream.rows([["2", "Kata"], ["3", "Cherry Blossom"]]));
+    /// output.push_str(&stream.footer());
+    /// assert!(output.contains("| 3   | Che... |"));
+    /// ```
+    #[must_use]
+    pub fn stream(&self) -> TableStream<'_> {
+        let column_widths = self.calculate_column_widths(&self.body_rows());
+        TableStream::new(self, column_widths)
+    }
+
+    /// Like [`stream`](Self::stream), with column widths measured elsewhere, for
+    /// example in a first pass over the input. Widths exclude padding.
+    #[must_use]
+    pub fn stream_with_widths(&self, column_widths: Vec<usize>) -> TableStream<'_> {
+        TableStream::new(self, column_widths)
+    }
+
+    /// Prepares `rows` as if they were inserted and renders them as body rows.
+    /// Cells starting past the last column are dropped.
+    pub(crate) fn render_stream_rows(
+        &self,
+        column_widths: &[usize],
+        rows: Vec<Row>,
+        first_index: usize,
+        previous_row: Option<&Row>,
+    ) -> String {
+        let num_columns = column_widths.len();
+        let mut body: Vec<BodyRow<'_>> = rows
+            .into_iter()
+            .map(|row| {
+                let mut row = self.prepare_row(row);
+                let mut start = 0;
+                let mut kept = 0;
+                for cell in row.cells() {
+                    if start >= num_columns {
+                        break;
+                    }
+                    start += cell.span().max(1);
+                    kept += 1;
+                }
+                while row.len() > kept {
+                    row.remove(kept);
+                }
+                BodyRow {
+                    rule: None,
+                    row: Cow::Owned(row),
+                    measured: true,
+                    summary: false,
+                    rows: 1,
+                    decorations: Vec::new(),
+                }
+            })
+            .collect();
+        self.format_body(&mut body);
+        self.render_body(column_widths, &body, first_index, previous_row)
+    }
+
+    /// Renders the body rows in `rows` using widths measured over the whole table.
+    pub(crate) fn render_page(&self, column_widths: &[usize], rows: Range<usize>) -> String {
+        let mut body = self.body_rows();
+        body.truncate(rows.end);
+        body.drain(..rows.start.min(body.len()));
+        if let Some(first) = body.first_mut() {
+            first.rule = None;
+        }
+        let output = self.render_with_widths(column_widths, &body);
+        self.finish_render(output)
+    }
+
+    /// Re-spans the single cell of an unmeasured row over `num_columns` columns.
+    fn spanning_row(row: &Row, num_columns: usize) -> Row {
+        let mut spanning = row.clone();
+        if let Some(cell) = spanning.cell_mut(0) {
+            cell.set_span(num_columns);
+        }
+        spanning
+    }
+
+    /// Picks `columns` out of `row`, baking in any column alignment and dropping spans.
+    fn project_row(&self, row: &Row, columns: &[usize]) -> Row {
+        let mut projected = Row::new();
+        for &col in columns {
+            let mut cell = row
+                .cells()
+                .get(col)
+                .cloned()
+                .unwrap_or_else(|| Cell::new("", Alignment::default()));
+            cell.set_span(1);
+            if let Some(alignment) = self.column_alignments.get(col) {
+                cell.set_alignment(*alignment);
+            }
+            projected.push(cell);
+        }
+        projected
+    }
+
+    /// Renders the table using cached column widths if available.
+    ///
+    /// This method provides improved performance for repeated renders of the same table.
+    /// The first call calculates and caches column widths. Subsequent calls reuse the cache
+    /// until the table is modified.
+    ///
+    /// # Returns
+    /// The rendered table as a `String`
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, Alignment};
+    /// let table = Table::new().header(&["A", "B"]).row(&["1", "2"]);
+    /// let _first = table.render_cached(); // Calculates and caches widths
+    /// let _second = table.render_cached(); // Uses cached widths (faster)
+    /// ```
+    #[must_use]
+    pub fn render_cached(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        if self.selection_column {
+            return self.render();
+        }
+
+        let body = self.body_rows();
+
+        // Use cached widths or calculate and cache them
+        let column_widths = {
+            let mut cache = self.cached_widths.borrow_mut();
+            if let Some(ref widths) = *cache {
+                widths.clone()
+            } else {
+                let widths = self.calculate_column_widths(&body);
+                *cache = Some(widths.clone());
+                widths
+            }
+        };
+
+        let output = self.render_with_widths(&column_widths, &body);
+        self.finish_render(output)
+    }
+
+    /// Returns the number of lines [`render`](Self::render) produces, including
+    /// borders, separators, wrapped cell lines and footnotes, without building the
+    /// output.
+    ///
+    /// Useful to decide between paging and inline display before paying the render cost.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::Table;
+    /// let table = Table::new().header(["A", "B"]).row(["1", "2"]);
+    /// assert_eq!(table.rendered_height(), table.render().lines().count());
+    /// ```
+    #[must_use]
+    pub fn rendered_height(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        self.table_height() + self.footnotes().lines().count()
+    }
+
+    /// Returns the number of lines of the rendered table, without its footnotes.
+    fn table_height(&self) -> usize {
+        let body = self.body_rows();
+        if self.is_compact_markdown() {
+            return body.len() + self.headers.as_ref().map_or(0, |_| 2);
+        }
+        if self.style == TableStyle::AsciiDoc {
+            let row_lines = |row: &Row| {
+                1 + row
+                    .cells()
+                    .iter()
+                    .map(|cell| cell.content().lines().count().saturating_sub(1))
+                    .sum::<usize>()
+            };
+            let attributes = usize::from(!self.column_alignments.is_empty());
+            let header = self
+                .headers
+                .as_ref()
+                .map_or(0, |headers| row_lines(headers) + 1);
+            return attributes
+                + 2
+                + header
+                + body
+                    .iter()
+                    .map(|body_row| row_lines(&body_row.row))
+                    .sum::<usize>();
+        }
+
+        let mut height = if self.has_outer_borders() {
+            usize::from(self.border_visibility.top) + usize::from(self.border_visibility.bottom)
+        } else {
+            0
+        };
+        let columns = self
+            .headers
+            .iter()
+            .chain(body.iter().map(|body_row| body_row.row.as_ref()))
+            .map(span_width)
+            .max()
+            .unwrap_or(0);
+        if let Some(label_row) = self.group_label_row(columns) {
+            // The label row and the rule under it
+            height += self.row_height(&label_row) + 1;
+        }
+        if let Some(headers) = self.display_headers() {
+            height += self.row_height(&headers) + usize::from(self.has_header_rule());
+        }
+        for (index, body_row) in body.iter().enumerate() {
+            if self.body_rule(index, body_row).is_some() {
+                height += 1;
+            }
+            height += self.row_height(&body_row.row);
+        }
+        height
+    }
+
+    /// Returns the rule under the headers: the style's header rule, or its double
+    /// rule when the header style asks for emphasis.
+    fn header_rule(&self) -> Rule {
+        if self.header_style.is_double_rule() {
+            Rule::Double
+        } else {
+            Rule::Header
+        }
+    }
+
+    /// Returns the rule drawn above a body row in the current style. Markdown and
+    /// plain tables have no rules, and reStructuredText needs a single rule between
+    /// every pair of rows. Hidden inner rules are never drawn.
+    fn body_rule(&self, index: usize, body_row: &BodyRow<'_>) -> Option<Rule> {
+        if !self.border_visibility.inner_horizontal {
+            return None;
+        }
+        match self.style {
+            TableStyle::Markdown | TableStyle::AsciiDoc | TableStyle::Plain => None,
+            TableStyle::Rest => (index > 0).then_some(Rule::Single),
+            _ => body_row.rule,
+        }
+    }
+
+    /// Returns true if the table renders as unpadded `|a|b|` Markdown.
+    fn is_compact_markdown(&self) -> bool {
+        self.style == TableStyle::Markdown
+            && self.render_options.get_markdown() == MarkdownMode::Compact
+    }
+
+    /// Writes one unpadded Markdown row.
+    fn push_compact_markdown_row(output: &mut String, row: &Row, num_columns: usize) {
+        output.push('|');
+        let mut columns = 0;
+        for cell in row.cells() {
+            output.push_str(cell.content());
+            output.push('|');
+            for _ in 1..cell.span() {
+                output.push('|');
+            }
+            columns += cell.span();
+        }
+        for _ in columns..num_columns {
+            output.push('|');
+        }
+        output.push('\n');
+    }
+
+    /// Renders the header and delimiter row of compact Markdown.
+    fn render_compact_markdown_head(&self, num_columns: usize) -> String {
+        let mut output = String::new();
+        if let Some(headers) = self.display_headers() {
+            Self::push_compact_markdown_row(&mut output, &headers, num_columns);
+            output.push('|');
+            for column in 0..num_columns {
+                let alignment = self.column_alignments.get(column).copied();
+                Self::push_markdown_delimiter(&mut output, alignment, 0, 3);
+                output.push('|');
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Writes one `AsciiDoc` row. Multi-line cells use hard line breaks (` +`).
+    fn push_asciidoc_row(&self, output: &mut String, row: &Row, num_columns: usize) {
+        let mut columns = 0;
+        for cell in row.cells() {
+            if columns > 0 {
+                output.push(' ');
+            }
+            let span = cell.span().max(1);
+            if span > 1 {
+                output.push_str(&span.to_string());
+                output.push('+');
+            }
+            if self.column_alignments.get(columns).is_none() {
+                match cell.alignment() {
+                    Alignment::Left => {}
+                    Alignment::Center => output.push('^'),
+                    Alignment::Right | Alignment::Decimal => output.push('>'),
+                }
+            }
+            output.push('|');
+            for (index, line) in cell.content().lines().enumerate() {
+                if index > 0 {
+                    output.push_str(" +\n");
+                }
+                output.push_str(&line.replace('|', "\\|"));
+            }
+            columns += span;
+        }
+        for column in columns..num_columns {
+            output.push_str(if column > 0 { " |" } else { "|" });
+        }
+        output.push('\n');
+    }
+
+    /// Renders the opening of an `AsciiDoc` table, where column alignments become a
+    /// `cols` attribute.
+    fn render_asciidoc_head(&self, num_columns: usize) -> String {
+        let mut output = String::new();
+        if !self.column_alignments.is_empty() {
+            let cols: Vec<&str> = (0..num_columns)
+                .map(|column| match self.column_alignments.get(column) {
+                    Some(Alignment::Center) => "^",
+                    Some(Alignment::Right | Alignment::Decimal) => ">",
+                    _ => "<",
+                })
+                .collect();
+            output.push_str("[cols=\"");
+            output.push_str(&cols.join(","));
+            output.push_str("\"]\n");
+        }
+        output.push_str("|===\n");
+        if let Some(headers) = self.display_headers() {
+            self.push_asciidoc_row(&mut output, &headers, num_columns);
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Returns true if the style draws the top and bottom borders.
+    fn has_outer_borders(&self) -> bool {
+        !matches!(
+            self.style,
+            TableStyle::Minimal
+                | TableStyle::Compact
+                | TableStyle::Markdown
+                | TableStyle::Org
+                | TableStyle::Plain
+        )
+    }
+
+    /// Returns true if the style draws a rule under the header row and it is not
+    /// hidden.
+    fn has_header_rule(&self) -> bool {
+        self.style != TableStyle::Plain && self.border_visibility.header_separator
+    }
+
+    /// Returns the style's border characters with the hidden lines taken out: hidden
+    /// edges become empty and hidden column junctions continue the rule. Hidden
+    /// separators within rows are handled where rows are drawn.
+    fn border_chars(&self) -> BorderChars {
+        let mut borders = self.style.border_chars();
+        let visibility = self.border_visibility;
+        if !visibility.left {
+            borders.top_left = "";
+            borders.bottom_left = "";
+            borders.left_cross = "";
+            borders.double_left_cross = "";
+            borders.header_left_cross = "";
+        }
+        if !visibility.right {
+            borders.top_right = "";
+            borders.bottom_right = "";
+            borders.right_cross = "";
+            borders.double_right_cross = "";
+            borders.header_right_cross = "";
+        }
+        if !visibility.inner_vertical {
+            borders.top_cross = borders.horizontal;
+            borders.bottom_cross = borders.horizontal;
+            borders.cross = borders.horizontal;
+            borders.double_top_cross = borders.double_horizontal;
+            borders.double_bottom_cross = borders.double_horizontal;
+            borders.double_cross = borders.double_horizontal;
+            borders.header_top_cross = borders.header_horizontal;
+            borders.header_bottom_cross = borders.header_horizontal;
+            borders.header_cross = borders.header_horizontal;
+        }
+        borders
+    }
+
+    /// Returns the separator drawn in a row at column boundary `column`: the vertical
+    /// border, nothing for a hidden edge, or a space for a hidden column separator.
+    fn row_separator(
+        &self,
+        borders: &BorderChars,
+        column: usize,
+        num_columns: usize,
+    ) -> &'static str {
+        let visibility = self.border_visibility;
+        if column == 0 {
+            if visibility.left {
+                borders.vertical
+            } else {
+                ""
+            }
+        } else if column >= num_columns {
+            if visibility.right {
+                borders.vertical
+            } else {
+                ""
+            }
+        } else if visibility.inner_vertical || borders.vertical.is_empty() {
+            borders.vertical
+        } else {
+            " "
+        }
+    }
+
+    /// Internal method that renders the table with pre-calculated column widths.
+    fn render_with_widths(&self, column_widths: &[usize], body: &[BodyRow<'_>]) -> String {
+        let num_columns = column_widths.len();
+        let padding = self.padding().left + self.padding().right;
+
+        // Pre-calculate approximate buffer size
+        let row_width: usize = column_widths.iter().sum::<usize>()
+            + padding * num_columns
+            + self.get_spacing() * num_columns.saturating_sub(1)
+            + num_columns
+            + 2; // border chars + newline
+        let estimated_capacity = row_width * (body.len() + 4);
+
+        let first_body_row = body.first().map(|body_row| body_row.row.as_ref());
+        let last_row = body
+            .last()
+            .map(|body_row| body_row.row.as_ref())
+            .or(self.headers());
+
+        let mut output = String::with_capacity(estimated_capacity);
+        output.push_str(&self.render_head(column_widths, first_body_row));
+        output.push_str(&self.render_body(column_widths, body, 0, None));
+        output.push_str(&self.render_tail(column_widths, last_row));
+        output
+    }
+
+    /// Renders everything above the first body row: the top border, the header row
+    /// and the rule below it.
+    pub(crate) fn render_head(
+        &self,
+        column_widths: &[usize],
+        first_body_row: Option<&Row>,
+    ) -> String {
+        let num_columns = column_widths.len();
+        if self.is_compact_markdown() {
+            return self.render_compact_markdown_head(num_columns);
+        }
+        if self.style == TableStyle::AsciiDoc {
+            return self.render_asciidoc_head(num_columns);
+        }
+
+        let borders = self.border_chars();
+        let headers = self.display_headers();
+        let mut output = String::new();
+
+        let boundaries_for = |row: Option<&Row>| {
+            row.map_or_else(
+                || Self::all_boundaries(num_columns),
+                |row| Self::get_row_boundaries(row, num_columns),
+            )
+        };
+
+        let group_boundaries = self.group_boundaries(num_columns);
+        let label_row = self.group_label_row(num_columns);
+
+        // Get the first row to determine top border boundaries
+        let first_row = label_row.as_ref().or(headers.as_deref()).or(first_body_row);
+
+        if self.has_outer_borders() && self.border_visibility.top {
+            let first_boundaries = boundaries_for(first_row);
+            // For top border, only use first row boundaries (pass same for both)
+            output.push_str(&Self::render_horizontal_border_with_spans(
+                column_widths,
+                self.padding(),
+                self.get_spacing(),
+                borders.top_left,
+                borders.top_cross,
+                borders.top_right,
+                borders.horizontal,
+                borders.top_cross,    // T-down (for top border, same as top_cross)
+                borders.bottom_cross, // T-up (for top border, use bottom_cross)
+                &first_boundaries,
+                &first_boundaries, // Same boundaries - junction only if first row has boundary
+                &group_boundaries,
+            ));
+        }
+
+        if let Some(label_row) = &label_row {
+            output.push_str(&self.render_row_with_wrapping(
+                label_row,
+                column_widths,
+                &borders,
+                &vec![Alignment::Center; num_columns],
+                self.header_style.sgr().as_deref(),
+                &[],
+            ));
+            output.push_str(&self.render_rule(
+                Rule::Single,
+                column_widths,
+                &borders,
+                &boundaries_for(headers.as_deref().or(first_body_row)),
+                &Self::get_row_boundaries(label_row, num_columns),
+            ));
+        }
+
+        if let Some(headers) = headers.as_deref() {
+            let header_boundaries = Self::get_row_boundaries(headers, num_columns);
+            let header_alignments = self
+                .header_style
+                .get_alignment()
+                .map(|alignment| vec![alignment; num_columns]);
+            output.push_str(
+                &self.render_row_with_wrapping(
+                    headers,
+                    column_widths,
+                    &borders,
+                    header_alignments
+                        .as_deref()
+                        .unwrap_or(&self.column_alignments),
+                    self.header_style.sgr().as_deref(),
+                    &[],
+                ),
+            );
+            if self.style == TableStyle::Markdown {
+                if self.has_header_rule() {
+                    output
+                        .push_str(&self.render_markdown_header_separator(column_widths, &borders));
+                }
+            } else if self.has_header_rule() {
+                // Separator between headers (above) and the first data row (below)
+                output.push_str(&self.render_rule(
+                    self.header_rule(),
+                    column_widths,
+                    &borders,
+                    &boundaries_for(first_body_row),
+                    &header_boundaries,
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// Renders everything below the last body row. `last_row` is the last row drawn,
+    /// which may be the header row.
+    pub(crate) fn render_tail(&self, column_widths: &[usize], last_row: Option<&Row>) -> String {
+        let num_columns = column_widths.len();
+        if self.style == TableStyle::AsciiDoc {
+            return "|===\n".to_string();
+        }
+        let mut output = String::new();
+        if self.has_outer_borders() && self.border_visibility.bottom && !self.is_compact_markdown()
+        {
+            let borders = self.border_chars();
+            let last_boundaries = last_row.map_or_else(
+                || Self::all_boundaries(num_columns),
+                |row| Self::get_row_boundaries(row, num_columns),
+            );
+            // For bottom border, only use last row boundaries (pass same for both)
+            output.push_str(&Self::render_horizontal_border_with_spans(
+                column_widths,
+                self.padding(),
+                self.get_spacing(),
+                borders.bottom_left,
+                borders.bottom_cross,
+                borders.bottom_right,
+                borders.horizontal,
+                borders.top_cross,    // T-down
+                borders.bottom_cross, // T-up
+                &last_boundaries,     // Same boundaries - junction only if last row has boundary
+                &last_boundaries,
+                &self.group_boundaries(num_columns),
+            ));
+        }
+
+        output
+    }
+
+    /// Renders body rows, drawing each row's rule above it. `first_index` is the
+    /// position of the first row in the whole body and `previous_row` the row drawn
+    /// above it, or `None` for the header row.
+    fn render_body(
+        &self,
+        column_widths: &[usize],
+        body: &[BodyRow<'_>],
+        first_index: usize,
+        previous_row: Option<&Row>,
+    ) -> String {
+        let num_columns = column_widths.len();
+        let mut output = String::new();
+        if self.is_compact_markdown() {
+            for body_row in body {
+                Self::push_compact_markdown_row(&mut output, &body_row.row, num_columns);
+            }
+            return output;
+        }
+        if self.style == TableStyle::AsciiDoc {
+            for body_row in body {
+                self.push_asciidoc_row(&mut output, &body_row.row, num_columns);
+            }
+            return output;
+        }
+
+        let borders = self.border_chars();
+        let stripe = self
+            .zebra
+            .map(|background| format!("\x1b[{}m", background.bg_code()));
+        let mut previous_row = previous_row.or(self.headers());
+        for (index, body_row) in body.iter().enumerate() {
+            if let Some(rule) = self.body_rule(first_index + index, body_row) {
+                let boundaries_above = previous_row.map_or_else(
+                    || Self::all_boundaries(num_columns),
+                    |row| Self::get_row_boundaries(row, num_columns),
+                );
+                output.push_str(&self.render_rule(
+                    rule,
+                    column_widths,
+                    &borders,
+                    &Self::get_row_boundaries(&body_row.row, num_columns),
+                    &boundaries_above,
+                ));
+            }
+            output.push_str(
+                &self.render_row_with_wrapping(
+                    &body_row.row,
+                    column_widths,
+                    &borders,
+                    &self.column_alignments,
+                    stripe
+                        .as_deref()
+                        .filter(|_| (first_index + index) % 2 == 1)
+                        .filter(|_| body_row.measured && !body_row.summary),
+                    &body_row.decorations,
+                ),
+            );
+            previous_row = Some(&body_row.row);
+        }
+        output
+    }
+
+    /// Renders a rule between two body rows using the characters for its weight.
+    fn render_rule(
+        &self,
+        rule: Rule,
+        column_widths: &[usize],
+        borders: &BorderChars,
+        boundaries_below: &[bool],
+        boundaries_above: &[bool],
+    ) -> String {
+        let (left, cross, right, horizontal, cross_down, cross_up) = match rule {
+            Rule::Single => (
+                borders.left_cross,
+                borders.cross,
+                borders.right_cross,
+                borders.horizontal,
+                borders.top_cross,
+                borders.bottom_cross,
+            ),
+            Rule::Double => (
+                borders.double_left_cross,
+                borders.double_cross,
+                borders.double_right_cross,
+                borders.double_horizontal,
+                borders.double_top_cross,
+                borders.double_bottom_cross,
+            ),
+            Rule::Header => (
+                borders.header_left_cross,
+                borders.header_cross,
+                borders.header_right_cross,
+                borders.header_horizontal,
+                borders.header_top_cross,
+                borders.header_bottom_cross,
+            ),
+        };
+        Self::render_horizontal_border_with_spans(
+            column_widths,
+            self.padding(),
+            self.get_spacing(),
+            left,
+            cross,
+            right,
+            horizontal,
+            cross_down,
+            cross_up,
+            boundaries_below,
+            boundaries_above,
+            &self.group_boundaries(column_widths.len()),
+        )
+    }
+
+    /// Returns a vector indicating which column indices have a cell boundary.
+    /// Index 0 and `num_columns` are always true (left and right table edges).
+    fn get_row_boundaries(row: &Row, num_columns: usize) -> Vec<bool> {
+        let mut boundaries = vec![false; num_columns + 1];
+        boundaries[0] = true;
+        boundaries[num_columns] = true;
+
+        let mut col_idx = 0;
+        for cell in row.cells() {
+            if col_idx <= num_columns {
+                boundaries[col_idx] = true;
+            }
+            col_idx += cell.span().max(1);
+        }
+        if col_idx <= num_columns {
+            boundaries[col_idx] = true;
+        }
+
+        boundaries
+    }
+
+    /// Returns boundaries where all columns have separators (no colspan).
+    fn all_boundaries(num_columns: usize) -> Vec<bool> {
+        vec![true; num_columns + 1]
+    }
+
+    /// Forces recalculation of column widths on the next render.
+    ///
+    /// This method is primarily useful when the table has been modified externally
+    /// or when you want to ensure fresh calculations.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, Alignment};
+    /// let mut table = Table::new().header(&["A", "B"]).row(&["1", "2"]);
+    /// table.recalculate_widths();
+    /// ```
+    pub fn recalculate_widths(&mut self) {
+        self.clear_caches();
+    }
+
+    fn render_row_with_wrapping(
+        &self,
+        row: &Row,
+        column_widths: &[usize],
+        borders: &BorderChars,
+        column_alignments: &[Alignment],
+        emphasis: Option<&str>,
+        decorations: &[Decoration],
+    ) -> String {
+        let num_columns = column_widths.len();
+        let mut wrapped_cells: Vec<Vec<String>> = Vec::with_capacity(row.len());
+        let mut cell_spans: Vec<usize> = Vec::with_capacity(row.len());
+        let mut max_lines = 1;
+
+        // Build a set of column boundaries for this row
+        // A boundary exists at column index `i` if a cell starts there
+        let mut boundaries = vec![false; num_columns + 1];
+        boundaries[0] = true; // Left edge always has boundary
+        boundaries[num_columns] = true; // Right edge always has boundary
+
+        let mut col_idx = 0;
+        for cell in row.cells() {
+            let span = cell.span().max(1);
+            cell_spans.push(span);
+            boundaries[col_idx] = true; // Cell starts here
+
+            let lines = self.wrap_cell(cell, col_idx);
+            max_lines = max_lines.max(lines.len());
+            wrapped_cells.push(lines);
+
+            col_idx += span;
+        }
+        // Mark boundary at end of last cell
+        if col_idx <= num_columns {
+            boundaries[col_idx] = true;
+        }
+
+        // Apply vertical alignment by calculating offset for each cell
+        let aligned_cells: Vec<Vec<String>> = wrapped_cells
+            .into_iter()
+            .map(|cell_lines| {
+                Self::apply_vertical_alignment(cell_lines, max_lines, self.vertical_alignment)
+            })
+            .collect();
+
+        // Pre-calculate row line width
+        let line_width: usize = column_widths.iter().sum::<usize>()
+            + (self.padding().left + self.padding().right) * num_columns
+            + self.get_spacing() * num_columns.saturating_sub(1)
+            + num_columns + 1 // border chars
+            + 1; // newline
+
+        let (top, bottom) = self.blank_lines();
+        let mut output = String::with_capacity(line_width * (top + max_lines + bottom));
+        #[cfg(feature = "instrumentation")]
+        self.record(|stats| {
+            stats.cells_formatted += aligned_cells.len() * (top + max_lines + bottom);
+        });
+
+        for line_idx in 0..top + max_lines + bottom {
+            output.push_str(self.row_separator(borders, 0, num_columns));
+
+            let mut col_idx = 0;
+            for (cell_idx, cell_lines) in aligned_cells.iter().enumerate() {
+                let span = cell_spans.get(cell_idx).copied().unwrap_or(1);
+                let combined_width = self.calculate_span_width(col_idx, span, column_widths);
+
+                let alignment = column_alignments.get(col_idx).copied().unwrap_or_else(|| {
+                    row.cells()
+                        .get(cell_idx)
+                        .map_or(Alignment::Left, Cell::alignment)
+                });
+
+                let content = line_idx
+                    .checked_sub(top)
+                    .and_then(|line_idx| cell_lines.get(line_idx))
+                    .map_or("", String::as_str);
+
+                // Left padding
+                for _ in 0..self.padding().left {
+                    output.push(' ');
+                }
+                let formatted = self.fit_line(content, combined_width, alignment);
+                let decoration = decorations.get(col_idx).filter(|_| !content.is_empty());
+                if let Some(sgr) = emphasis {
+                    output.push_str(sgr);
+                    match decoration {
+                        Some(decoration) => {
+                            Self::push_decorated(&mut output, &formatted, decoration, sgr);
+                        }
+                        None => output.push_str(&formatted),
+                    }
+                    output.push_str("\x1b[0m");
+                } else if let Some(decoration) = decoration {
+                    Self::push_decorated(&mut output, &formatted, decoration, "");
+                } else {
+                    output.push_str(&formatted);
+                }
+                // Right padding
+                for _ in 0..self.padding().right {
+                    output.push(' ');
+                }
+
+                col_idx += span;
+
+                // Add spacing and vertical border
+                // Only add spacing if not at the last column
+                if col_idx < num_columns {
+                    for _ in 0..self.get_spacing() {
+                        output.push(' ');
+                    }
+                }
+                let separator = self.row_separator(borders, col_idx, num_columns);
+                output.push_str(separator);
+                if self.is_group_boundary(col_idx, num_columns) {
+                    output.push_str(separator);
+                }
+            }
+            if self.style == TableStyle::Plain {
+                output.truncate(output.trim_end_matches(' ').len());
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Pads or truncates a cell line to `width`. Colors of passed-through content end
+    /// with the cell.
+    fn fit_line(&self, content: &str, width: usize, alignment: Alignment) -> String {
+        let marker = self.truncation_marker.as_deref();
+        if !self.render_options.get_ansi_passthrough() {
+            return Self::dots(text::width(content), width, marker)
+                .unwrap_or_else(|| text::fit(content, width, alignment, marker));
+        }
+        if let Some(dots) = Self::dots(text::ansi_width(content), width, marker) {
+            return dots;
+        }
+        let mut formatted = text::ansi_fit(content, width, alignment, marker);
+        if formatted.contains('\x1b') {
+            formatted.push_str(ansi::RESET);
+        }
+        formatted
+    }
+
+    /// Writes an aligned cell line with its color and hyperlink. Only the text is
+    /// decorated, not the alignment padding around it. `restore` is written after the
+    /// color's reset to bring back the line's emphasis, such as a zebra stripe.
+    fn push_decorated(
+        output: &mut String,
+        formatted: &str,
+        decoration: &Decoration,
+        restore: &str,
+    ) {
+        let text = formatted.trim();
+        let start = formatted.len() - formatted.trim_start().len();
+        output.push_str(&formatted[..start]);
+        if let Some(color) = decoration.color {
+            output.push_str("\x1b[");
+            output.push_str(&color.fg_code());
+            output.push('m');
+        }
+        match &decoration.url {
+            Some(url) => link::osc8(output, url, text),
+            None => output.push_str(text),
+        }
+        if decoration.color.is_some() {
+            output.push_str("\x1b[0m");
+            output.push_str(restore);
+        }
+        output.push_str(&formatted[start + text.len()..]);
+    }
+
+    /// Splits a cell's content into the lines it occupies when rendered.
+    /// Explicit newlines always start a new line; each line is then wrapped on its own.
+    fn wrap_cell(&self, cell: &Cell, col_idx: usize) -> Vec<String> {
+        let content = cell.content();
+        let lines = if content.contains('\n') {
+            content
+                .lines()
+                .flat_map(|line| {
+                    self.wrap_content(line, col_idx)
+                        .unwrap_or_else(|| vec![line.to_string()])
+                })
+                .collect()
+        } else {
+            self.wrap_content(content, col_idx)
+                .unwrap_or_else(|| vec![content.to_string()])
+        };
+        if self.render_options.get_ansi_passthrough() && lines.len() > 1 {
+            ansi::carry_styles(lines)
+        } else {
+            lines
+        }
+    }
+
+    /// Wraps `content` according to the column's policy, or returns `None` if it
+    /// stays on a single line.
+    fn wrap_content(&self, content: &str, column: usize) -> Option<Vec<String>> {
+        let width = self.get_wrap_width(column)?;
+        if self.render_options.get_ansi_passthrough() {
+            if text::ansi_width(content) <= width {
+                return None;
+            }
+            return Some(if self.wrap_policy(column) == WrapPolicy::Character {
+                text::ansi_wrap_chars(content, width)
+            } else {
+                text::ansi_wrap(content, width, self.hyphenate)
+            });
+        }
+        if content.chars().count() <= width {
+            return None;
+        }
+        Some(if self.wrap_policy(column) == WrapPolicy::Character {
+            text::wrap_chars(content, width)
+        } else {
+            text::wrap(content, width, self.hyphenate)
+        })
+    }
+
+    /// Returns the number of output lines a row occupies.
+    fn row_height(&self, row: &Row) -> usize {
+        let mut col_idx = 0;
+        let mut max_lines = 1;
+        for cell in row.cells() {
+            let span = cell.span().max(1);
+            if self.get_wrap_width(col_idx).is_some() || cell.content().contains('\n') {
+                max_lines = max_lines.max(self.wrap_cell(cell, col_idx).len());
+            }
+            col_idx += span;
+        }
+        let (top, bottom) = self.blank_lines();
+        top + max_lines + bottom
+    }
+
+    /// Returns the blank lines above and below each row. Markdown has no way to
+    /// draw them, so it gets none.
+    fn blank_lines(&self) -> (usize, usize) {
+        if self.style == TableStyle::Markdown {
+            (0, 0)
+        } else {
+            (self.vertical_padding.top, self.vertical_padding.bottom)
+        }
+    }
+
+    /// Calculates the combined width for a cell that spans multiple columns.
+    fn calculate_span_width(
+        &self,
+        start_col: usize,
+        span: usize,
+        column_widths: &[usize],
+    ) -> usize {
+        if span <= 1 {
+            return column_widths.get(start_col).copied().unwrap_or(0);
+        }
+
+        let mut total_width = 0;
+        for i in 0..span {
+            let col = start_col + i;
+            if col < column_widths.len() {
+                total_width += column_widths[col];
+                // Add padding and spacing for intermediate columns
+                if i < span - 1 {
+                    total_width +=
+                        self.padding().left + self.padding().right + self.get_spacing() + 1;
+                    if self.is_group_boundary(col + 1, column_widths.len()) {
+                        total_width += 1;
+                    }
+                }
+            }
+        }
+        total_width
+    }
+
+    pub(crate) fn apply_vertical_alignment(
+        cell_lines: Vec<String>,
+        max_lines: usize,
+        vertical_alignment: VerticalAlignment,
+    ) -> Vec<String> {
+        let cell_line_count = cell_lines.len();
+        if cell_line_count >= max_lines {
+            return cell_lines;
+        }
+
+        let padding_needed = max_lines - cell_line_count;
+        let mut result = Vec::with_capacity(max_lines);
+
+        match vertical_alignment {
+            VerticalAlignment::Top => {
+                result.extend(cell_lines);
+                result.extend(core::iter::repeat_n(String::new(), padding_needed));
+            }
+            VerticalAlignment::Middle => {
+                let top_padding = padding_needed / 2;
+                let bottom_padding = padding_needed - top_padding;
+                result.extend(core::iter::repeat_n(String::new(), top_padding));
+                result.extend(cell_lines);
+                result.extend(core::iter::repeat_n(String::new(), bottom_padding));
+            }
+            VerticalAlignment::Bottom => {
+                result.extend(core::iter::repeat_n(String::new(), padding_needed));
+                result.extend(cell_lines);
+            }
+        }
+
+        result
+    }
+
+    fn get_wrap_width(&self, column: usize) -> Option<usize> {
+        let limit = match self.constraints.get(column) {
+            Some(
+                WidthConstraint::Wrap(w) | WidthConstraint::Fixed(w) | WidthConstraint::Max(w),
+            ) => *w,
+            _ => return None,
+        };
+        matches!(
+            self.wrap_policy(column),
+            WrapPolicy::Word | WrapPolicy::Character
+        )
+        .then_some(limit)
+    }
+
+    /// Renders a horizontal border with proper handling of column spans.
+    ///
+    /// Uses different junction characters based on cell boundaries:
+    /// - Cross (┼) when both rows have boundary
+    /// - T-down (┬) when only row below has boundary
+    /// - T-up (┴) when only row above has boundary
+    /// - Horizontal (─) when neither has boundary
+    #[allow(clippy::too_many_arguments)]
+    fn render_horizontal_border_with_spans(
+        column_widths: &[usize],
+        padding: Padding,
+        column_spacing: usize,
+        left: &str,
+        cross: &str,
+        right: &str,
+        horizontal: &str,
+        cross_down: &str, // T pointing down (┬) - only row below has boundary
+        cross_up: &str,   // T pointing up (┴) - only row above has boundary
+        boundaries_below: &[bool],
+        boundaries_above: &[bool],
+        group_boundaries: &[bool],
+    ) -> String {
+        let num_columns = column_widths.len();
+
+        // Pre-calculate line width
+        let content_width: usize = column_widths.iter().sum::<usize>()
+            + (padding.left + padding.right) * num_columns
+            + column_spacing * num_columns.saturating_sub(1);
+        let border_chars = num_columns + 1;
+        let estimated_capacity = content_width + border_chars + 1;
+
+        let mut line = String::with_capacity(estimated_capacity);
+
+        line.push_str(left);
+
+        // Check if horizontal is a single character for optimization
+        let h_char = if horizontal.len() == 1 {
+            horizontal.chars().next()
+        } else {
+            None
+        };
+
+        for (index, &width) in column_widths.iter().enumerate() {
+            let cell_width = padding.left + width + padding.right;
+            if let Some(ch) = h_char {
+                for _ in 0..cell_width {
+                    line.push(ch);
+                }
+            } else {
+                for _ in 0..cell_width {
+                    line.push_str(horizontal);
+                }
+            }
+
+            if index < num_columns - 1 {
+                // Column boundary index (between column `index` and `index + 1`)
+                let boundary_idx = index + 1;
+                let has_boundary_below =
+                    boundaries_below.get(boundary_idx).copied().unwrap_or(true);
+                let has_boundary_above =
+                    boundaries_above.get(boundary_idx).copied().unwrap_or(true);
+
+                // Determine the junction character based on boundaries
+                let junction = match (has_boundary_above, has_boundary_below) {
+                    (true, true) => cross,        // Both have boundary: ┼
+                    (false, true) => cross_down,  // Only below: ┬
+                    (true, false) => cross_up,    // Only above: ┴
+                    (false, false) => horizontal, // Neither: ─ (continue horizontal)
+                };
+
+                if junction == horizontal {
+                    // No boundary on both sides - continue with horizontal line
+                    // Add spacing width + 1 (for the cross character position)
+                    let doubled = group_boundaries.get(boundary_idx).copied().unwrap_or(false);
+                    let span_width = column_spacing + 1 + usize::from(doubled);
+                    if let Some(ch) = h_char {
+                        for _ in 0..span_width {
+                            line.push(ch);
+                        }
+                    } else {
+                        for _ in 0..span_width {
+                            line.push_str(horizontal);
+                        }
+                    }
+                } else {
+                    // There's a junction character to render
+                    if let Some(ch) = h_char {
+                        for _ in 0..column_spacing {
+                            line.push(ch);
+                        }
+                    } else {
+                        for _ in 0..column_spacing {
+                            line.push_str(horizontal);
+                        }
+                    }
+                    line.push_str(junction);
+                    if group_boundaries.get(boundary_idx).copied().unwrap_or(false) {
+                        line.push_str(junction);
+                    }
+                }
+            }
+        }
+        line.push_str(right);
+        line.push('\n');
+
+        line
+    }
+
+    /// Renders the Markdown delimiter row, with colons marking each column's
+    /// alignment. Columns without an alignment get plain dashes. Its pipes follow the
+    /// rows' separators, so hidden edges and column separators line up.
+    fn render_markdown_header_separator(
+        &self,
+        column_widths: &[usize],
+        borders: &BorderChars,
+    ) -> String {
+        let column_alignments = &self.column_alignments;
+        let padding = self.padding();
+        let column_spacing = self.get_spacing();
+        let num_columns = column_widths.len();
+        let content_width: usize = column_widths.iter().sum::<usize>()
+            + (padding.left + padding.right) * num_columns
+            + column_spacing * num_columns.saturating_sub(1);
+        let border_chars = num_columns + 1;
+        let estimated_capacity = content_width + border_chars + 1;
+
+        let mut line = String::with_capacity(estimated_capacity);
+        line.push_str(self.row_separator(borders, 0, num_columns));
+
+        for (index, &width) in column_widths.iter().enumerate() {
+            let cell_width = padding.left + width + padding.right;
+            let alignment = column_alignments.get(index).copied();
+            Self::push_markdown_delimiter(&mut line, alignment, cell_width, 1);
+
+            if index < num_columns - 1 {
+                for _ in 0..column_spacing {
+                    line.push(' ');
+                }
+                line.push_str(self.row_separator(borders, index + 1, num_columns));
+            }
+        }
+
+        line.push_str(self.row_separator(borders, num_columns, num_columns));
+        line.push('\n');
+        line
+    }
+
+    /// Pushes one delimiter cell of `width` characters, such as `:---:`. It is
+    /// widened when needed to keep at least `min_dashes` dashes between the colons.
+    fn push_markdown_delimiter(
+        line: &mut String,
+        alignment: Option<Alignment>,
+        width: usize,
+        min_dashes: usize,
+    ) {
+        let (left, right) = match alignment {
+            None => (false, false),
+            Some(Alignment::Left) => (true, false),
+            Some(Alignment::Center) => (true, true),
+            Some(Alignment::Right | Alignment::Decimal) => (false, true),
+        };
+        let colons = usize::from(left) + usize::from(right);
+        if left {
+            line.push(':');
+        }
+        for _ in 0..width.saturating_sub(colons).max(min_dashes) {
+            line.push('-');
+        }
+        if right {
+            line.push(':');
+        }
+    }
+}
+
+impl core::fmt::Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Aggregate, Alignment, Badges, BorderVisibility, Cell, Color, ColorMode, ColumnType,
+        CsvOptions, FillMode, FormatterRegistry, HeaderStyle, MarkdownMode, Messages, Newline,
+        NoteMode, Padding, Quoting, RenderOptions, Row, RowProvider, SplitOptions, Table,
+        TableBuilder, TableStyle, TextTransform, Theme, VerticalAlignment, VerticalPadding,
+        WidthConstraint, WrapPolicy,
+    };
+
+    #[test]
+    fn from_parallel_chunks_preserves_chunk_order() {
+        let chunks: Vec<Vec<Row>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        (0..3)
+                            .map(|i| Row::from([format!("{}", chunk * 3 + i)]))
+                            .collect()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        let table = Table::from_parallel_chunks(chunks);
+        let contents: Vec<&str> = table
+            .rows()
+            .iter()
+            .map(|row| row.cells()[0].content())
+            .collect();
+        assert_eq!(
+            contents,
+            ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11"]
+        );
+    }
+
+    #[test]
+    fn from_parallel_chunks_prepares_rows() {
+        let table = Table::from_parallel_chunks([vec![["a\tb"]], vec![], vec![["c"]]]);
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.rows()[0].cells()[0].content(), "a   b");
+        assert!(Table::from_parallel_chunks(Vec::<Vec<Row>>::new()).is_empty());
+    }
+
+    #[test]
+    fn tables_and_rows_are_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Table>();
+        assert_send::<Row>();
+    }
+
+    #[test]
+    fn render_viewport_fetches_only_the_range() {
+        struct Counting(core::cell::Cell<usize>);
+        impl RowProvider for Counting {
+            fn len(&self) -> usize {
+                usize::MAX
+            }
+            fn get(&self, index: usize) -> Row {
+                self.0.set(self.0.get() + 1);
+                Row::from([index.to_string()])
+            }
+        }
+
+        let provider = Counting(core::cell::Cell::new(0));
+        let mut template = Table::new().header(["N"]);
+        template.set_style(TableStyle::Markdown);
+        let output = template.render_viewport(&provider, 10..13);
+        assert_eq!(output, "| N  |\n|----|\n| 10 |\n| 11 |\n| 12 |\n");
+        assert_eq!(provider.0.get(), 3);
+    }
+
+    #[test]
+    fn render_viewport_clamps_and_ignores_own_rows() {
+        let store = vec![Row::from(["a"]), Row::from(["b"])];
+        let template = Table::new().header(["X"]).row(["own"]).truncate(1);
+        let output = template.render_viewport(&store, 1..10);
+        assert!(output.contains("| b |"));
+        assert!(!output.contains("own") && !output.contains("| a |"));
+        assert_eq!(
+            template.render_viewport(&store, 5..10),
+            template.render_viewport(&store, 2..2)
+        );
+        assert_eq!(
+            template.render_viewport(&store[..0], 0..1).lines().count(),
+            4
+        );
+    }
+
+    #[test]
+    fn format_column_changes_display_only() {
+        let mut table = Table::new()
+            .header(["Name", "Size"])
+            .row(["b", "1048576"])
+            .row(["a", "512"]);
+        table.set_style(TableStyle::Markdown);
+        table.format_column(1, "bytes");
+        table.sort(0);
+        assert_eq!(
+            table.render(),
+            "| Name  | Size    |\n|------ |---------|\n| a     | 512 B   |\n| b     | 1.0 MiB |\n"
+        );
+        assert_eq!(table.rows()[1].cells()[1].content(), "1048576");
+        assert!(table.to_csv(&CsvOptions::new()).contains("b,1048576"));
+    }
+
+    #[test]
+    fn format_column_follows_spans_and_skips_headers() {
+        let mut table = Table::new().header(["a", "b"]);
+        let mut row = Row::new();
+        let mut wide = Cell::new("x", Alignment::Left);
+        wide.set_span(2);
+        row.push(wide);
+        table.add_row(row);
+        table.add_row(["y", "z"]);
+        table.format_column(1, "template:<{}>");
+        table.format_column(3, "unknown");
+        let output = table.render();
+        assert!(output.contains("| a "));
+        assert!(output.contains("| x "));
+        assert!(output.contains("<z>"));
+        assert!(!output.contains("<x>") && !output.contains("<y>"));
+    }
+
+    #[test]
+    fn format_column_skips_elision_row() {
+        let mut table = Table::new().row(["1"]).row(["2"]);
+        table.set_max_rows(Some(1));
+        table.format_column(0, "template:#{}");
+        let output = table.render();
+        assert!(output.contains("#1"));
+        assert!(output.contains("… (1 more row)") && !output.contains("#…"));
+    }
+
+    #[test]
+    fn column_index_counts_spanned_columns() {
+        let mut wide = Cell::new("Name", Alignment::Left);
+        wide.set_span(2);
+        let mut headers = Row::new();
+        headers.push(wide);
+        headers.push(Cell::new("Score", Alignment::Left));
+        let mut table = Table::new();
+        table.set_headers(headers);
+        assert_eq!(table.column_index("Name"), Some(0));
+        assert_eq!(table.column_index("Score"), Some(2));
+        assert_eq!(Table::new().column_index("Score"), None);
+    }
+
+    #[test]
+    fn badge_column_is_colored_and_padded() {
+        let mut table = Table::new()
+            .header(["Job", "Status"])
+            .row(["build", "ok"])
+            .row(["lint", "WARN"])
+            .row(["test", "flaky"]);
+        table.format_column(1, "badge");
+        let expected = "\
++--------+----------+
+| Job    | Status   |
++--------+----------+
+| build  | \x1b[32m[  OK  ]\x1b[0m |
+| lint   | \x1b[33m[ WARN ]\x1b[0m |
+| test   | flaky    |
++--------+----------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(
+            table.to_csv(&CsvOptions::new()),
+            "Job,Status\nbuild,ok\nlint,WARN\ntest,flaky\n"
+        );
+
+        let mut formatters = FormatterRegistry::new();
+        formatters.register_badges("badge", Badges::status().without_colors());
+        table.set_formatters(formatters);
+        assert!(!table.render().contains('\x1b'));
+    }
+
+    #[test]
+    fn empty_placeholder_fills_blank_body_cells() {
+        let mut table = Table::new()
+            .header(["City", "", "Sales"])
+            .row(["Berlin", "", "10"])
+            .row(["Paris", "x", ""]);
+        table.set_empty_placeholder("n/a");
+        assert_eq!(table.empty_placeholder(), "n/a");
+        let expected = "\
++---------+------+-------+
+| City    |      | Sales |
++---------+------+-------+
+| Berlin  | n/a  | 10    |
+| Paris   | x    | n/a   |
++---------+------+-------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rows()[0].cells()[1].content(), "");
+
+        // Summary rows keep their blank cells
+        table.aggregate(2, Aggregate::Sum);
+        table.set_grand_total(true);
+        let output = table.render();
+        assert!(output.contains("| Total   |      | 10    |"), "{output}");
+    }
+
+    #[test]
+    fn new_is_empty() {
+        let table = Table::new();
+        assert!(table.is_empty());
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.cols(), 0);
+        assert!(table.headers().is_none());
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let table = Table::default();
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn default_style_is_classic() {
+        let table = Table::new();
+        assert_eq!(table.style(), TableStyle::Classic);
+    }
+
+    #[test]
+    fn default_padding() {
+        let table = Table::new();
+        assert_eq!(table.padding().left, 1);
+        assert_eq!(table.padding().right, 1);
+    }
+
+    #[test]
+    fn vertical_padding() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+        table.set_vertical_padding(VerticalPadding::uniform(1));
+        assert_eq!(
+            table.render(),
+            "+---+\n|   |\n| A |\n|   |\n+---+\n|   |\n| 1 |\n|   |\n+---+\n"
+        );
+        assert_eq!(table.rendered_height(), 9);
+    }
+
+    #[test]
+    fn vertical_padding_with_multiline_rows() {
+        let mut table = Table::new().row(["a\nb", "c"]);
+        table.set_vertical_padding(VerticalPadding::new(0, 1));
+        table.valign(VerticalAlignment::Bottom);
+        let lines: Vec<_> = table.render().lines().map(str::to_string).collect();
+        assert_eq!(lines[1..4], ["| a  |   |", "| b  | c |", "|    |   |"]);
+        assert_eq!(table.rendered_height(), table.render().lines().count());
+    }
+
+    #[test]
+    fn vertical_padding_ignored_by_markdown() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+        table.set_style(TableStyle::Markdown);
+        table.set_vertical_padding(VerticalPadding::uniform(2));
+        assert_eq!(table.render().lines().count(), 3);
+        assert_eq!(table.rendered_height(), 3);
+    }
+
+    #[test]
+    fn default_spacing() {
+        let table = Table::new();
+        assert_eq!(table.get_spacing(), 1);
+    }
+
+    #[test]
+    fn style_padding_defaults() {
+        let mut table = Table::new().header(["Name", "Age"]).row(["Kata", "25"]);
+        table.set_style(TableStyle::Compact);
+        assert_eq!(table.padding(), Padding::uniform(0));
+        assert_eq!(table.get_spacing(), 0);
+        assert_eq!(table.render(), "│Name│Age│\n─────┼────\n│Kata│25 │\n");
+
+        // Explicit settings win over the style's defaults, whatever the order
+        table.set_padding(Padding::uniform(1));
+        table.set_style(TableStyle::Modern);
+        table.set_style(TableStyle::Compact);
+        assert_eq!(table.padding(), Padding::uniform(1));
+        assert_eq!(table.get_spacing(), 0);
+        assert_eq!(table.render().lines().next(), Some("│ Name │ Age │"));
+    }
+
+    #[test]
+    fn default_valign_is_top() {
+        let table = Table::new();
+        assert_eq!(table.get_valign(), VerticalAlignment::Top);
+    }
+
+    #[test]
+    fn set_headers() {
+        let mut table = Table::new();
+        table.set_headers(["A", "B"]);
+        assert!(table.headers().is_some());
+        assert_eq!(table.headers().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn add_row() {
+        let mut table = Table::new();
+        table.add_row(["1", "2"]);
+        assert_eq!(table.len(), 1);
+        assert!(!table.is_empty());
+    }
+
+    #[test]
+    fn insert_row() {
+        let mut table = Table::new();
+        table.add_row(["a", "1"]);
+        table.add_row(["c", "3"]);
+        table.insert_row(1, ["b", "2"]);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.rows()[1].cells()[0].content(), "b");
+    }
+
+    #[test]
+    fn remove_row() {
+        let mut table = Table::new();
+        table.add_row(["a", "1"]);
+        table.add_row(["b", "2"]);
+        let removed = table.remove_row(0);
+        assert!(removed.is_some());
+        assert_eq!(removed.unwrap().cells()[0].content(), "a");
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn remove_row_out_of_bounds() {
+        let mut table = Table::new();
+        table.add_row(["a"]);
+        assert!(table.remove_row(5).is_none());
+    }
+
+    #[test]
+    fn interning_shares_equal_contents() {
+        let mut table = Table::with_capacity(4, 2);
+        table.add_row(["1", "open"]);
+        let seen = table.generation();
+        table.set_interning(false);
+        assert_eq!(table.generation(), seen);
+        table.set_interning(true);
+        assert!(table.interning());
+        assert!(table.generation() > seen);
+        table.add_row(["2", "open"]);
+        table.insert_row(0, ["3", "open"]);
+        table.add_row(["4", "closed"]);
+        let status = |table: &Table, row: usize| table.rows()[row].cells()[1].content().as_ptr();
+        assert_eq!(status(&table, 0), status(&table, 2));
+        assert_ne!(status(&table, 0), status(&table, 1));
+
+        let copy = table.filtered(|_| true);
+        assert_eq!(status(&copy, 0), status(&table, 0));
+        table.set_interning(false);
+        table.add_row(["5", "open"]);
+        assert_ne!(status(&table, 4), status(&table, 0));
+        assert!(table.render().contains("| 5  | open   |"));
+    }
+
+    #[test]
+    fn widths_follow_added_and_removed_rows() {
+        // Renders the same as a table built from the remaining rows
+        let check = |table: &Table| {
+            let mut rebuilt = Table::new();
+            for row in table.rows() {
+                rebuilt.add_row(row.clone());
+            }
+            assert_eq!(table.render(), rebuilt.render());
+        };
+        let mut table = Table::new().row(["ab", "1"]).row(["abcd", "22"]);
+        let first = table.render();
+        table.add_row(["a", "333"]);
+        check(&table);
+        assert_ne!(table.render().lines().next(), first.lines().next());
+        table.remove_row(0);
+        check(&table);
+        table.remove_row(0);
+        check(&table);
+        table.insert_row(0, ["abcdef", "4"]);
+        table.sort_num(1);
+        check(&table);
+        table.filter(|row| row.cells()[1].content() != "4");
+        check(&table);
+    }
+
+    #[test]
+    fn normalize_rows() {
+        let mut spanned = Row::from(["a"]);
+        let mut wide = Cell::new("wide", Alignment::Left);
+        wide.set_span(3);
+        spanned.push(wide);
+        let mut table = Table::new()
+            .header(["A", "B"])
+            .row(spanned)
+            .row(["c", "d", "e", "f"])
+            .row(["g"]);
+        assert_eq!(table.normalize_rows(FillMode::TruncateExtra), 2);
+        assert_eq!(table.rows()[0].cells()[1].span(), 1);
+        assert_eq!(table.rows()[1].len(), 2);
+        assert_eq!(table.rows()[2].len(), 1);
+        assert_eq!(table.normalize_rows(FillMode::TruncateExtra), 0);
+        assert_eq!(table.normalize_rows(FillMode::Both), 1);
+        assert_eq!(table.validate(), Ok(()));
+
+        let mut table = Table::new().row(["a"]).row(["b", "c", "d"]);
+        table.normalize_rows(FillMode::PadEmpty);
+        assert_eq!(table.rows()[0].len(), 3);
+        table.set_expected_cols(2);
+        table.normalize_rows(FillMode::Both);
+        assert!(table.rows().iter().all(|row| row.len() == 2));
+        assert_eq!(Table::new().normalize_rows(FillMode::Both), 0);
+    }
+
+    #[test]
+    fn cols() {
+        let table = Table::new().header(["A", "B", "C"]).row(["1", "2", "3"]);
+        assert_eq!(table.cols(), 3);
+    }
+
+    #[test]
+    fn fluent_api() {
+        let table = Table::new()
+            .header(["ID", "Value"])
+            .row(["1", "100"])
+            .row(["2", "200"]);
+        assert_eq!(table.len(), 2);
+        assert!(table.headers().is_some());
+    }
+
+    // Sorting tests
+    #[test]
+    fn sort_ascending() {
+        let mut table = Table::new();
+        table.add_row(["Squidward"]);
+        table.add_row(["Kelana"]);
+        table.add_row(["Kata"]);
+        table.sort(0);
+        assert_eq!(table.rows()[0].cells()[0].content(), "Kata");
+        assert_eq!(table.rows()[1].cells()[0].content(), "Kelana");
+        assert_eq!(table.rows()[2].cells()[0].content(), "Squidward");
+    }
+
+    #[test]
+    fn sort_descending() {
+        let mut table = Table::new();
+        table.add_row(["Kelana"]);
+        table.add_row(["Squidward"]);
+        table.sort_desc(0);
+        assert_eq!(table.rows()[0].cells()[0].content(), "Squidward");
+        assert_eq!(table.rows()[1].cells()[0].content(), "Kelana");
+    }
+
+    #[test]
+    fn sort_num_ascending() {
+        let mut table = Table::new();
+        table.add_row(["100"]);
+        table.add_row(["25"]);
+        table.add_row(["50"]);
+        table.sort_num(0);
+        assert_eq!(table.rows()[0].cells()[0].content(), "25");
+        assert_eq!(table.rows()[1].cells()[0].content(), "50");
+        assert_eq!(table.rows()[2].cells()[0].content(), "100");
+    }
+
+    #[test]
+    fn sort_num_descending() {
+        let mut table = Table::new();
+        table.add_row(["25"]);
+        table.add_row(["100"]);
+        table.sort_num_desc(0);
+        assert_eq!(table.rows()[0].cells()[0].content(), "100");
+        assert_eq!(table.rows()[1].cells()[0].content(), "25");
+    }
+
+    #[test]
+    fn sort_preserves_headers() {
+        let mut table = Table::new();
+        table.set_headers(["Name"]);
+        table.add_row(["Squidward"]);
+        table.add_row(["Kelana"]);
+        table.sort(0);
+        assert_eq!(table.headers().unwrap().cells()[0].content(), "Name");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_operations_on_large_tables() {
+        let count = crate::parallel::THRESHOLD * 2;
+        let mut table = Table::new().header(["n", "label"]);
+        for n in (0..count).rev() {
+            table.add_row([(n % 1000).to_string(), format!("row {n}")]);
+        }
+        table.add_row(["7", "the widest label"]);
+        assert_eq!(
+            table.render().lines().next(),
+            Some("+------+------------------+")
+        );
+
+        // Both sorts are stable, so equal keys keep their previous order
+        table.sort_num(0);
+        assert_eq!(table.rows()[0].cells()[1].content(), "row 19000");
+        table.sort_num_desc(0);
+        assert_eq!(table.rows()[0].cells()[1].content(), "row 19999");
+
+        table.par_filter(|row| row.cells()[0].content().len() == 1);
+        assert_eq!(table.len(), 10 * 20 + 1);
+        assert_eq!(table.rows()[0].cells()[0].content(), "9");
+    }
+
+    // Filter tests
+    #[test]
+    fn filter() {
+        let mut table = Table::new();
+        table.add_row(["Kelana", "25"]);
+        table.add_row(["Kata", "30"]);
+        table.add_row(["Squidward", "25"]);
+        table.filter(|row| row.cells()[1].content() == "25");
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn filter_eq() {
+        let mut table = Table::new();
+        table.add_row(["Active"]);
+        table.add_row(["Inactive"]);
+        table.add_row(["Active"]);
+        table.filter_eq(0, "Active");
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn filter_col() {
+        let mut table = Table::new();
+        table.add_row(["100"]);
+        table.add_row(["50"]);
+        table.add_row(["75"]);
+        table.filter_col(0, |val| val.parse::<i32>().is_ok_and(|n| n > 60));
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn filter_has() {
+        let mut table = Table::new();
+        table.add_row(["Kelana Smith"]);
+        table.add_row(["Kata Jones"]);
+        table.add_row(["Squidward Smith"]);
+        table.filter_has(0, "Smith");
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn filter_has_fold() {
+        let mut table = Table::new()
+            .row(["Łódź"])
+            .row(["Malmö"])
+            .row(["Zürich"])
+            .row(["Lodève"]);
+        let mut matches = table.filtered(|_| true);
+        matches.filter_has_fold(0, "LOD");
+        assert_eq!(matches.len(), 2);
+
+        table.filter_has_fold(0, "zuri");
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.rows()[0].cells()[0].content(), "Zürich");
+        table.filter_has_fold(1, "");
+        assert!(table.is_empty());
+    }
+
+    #[cfg(feature = "fuzzy")]
+    #[test]
+    fn filter_fuzzy_ranks_rows() {
+        let mut table = Table::new()
+            .header(["Name"])
+            .row(["Cherry Blossom"])
+            .row(["Kelana"])
+            .row(["Kata"])
+            .row(["Squidward"]);
+        let mut all = table.filtered(|_| true);
+        all.filter_fuzzy("");
+        assert_eq!(all.len(), 4);
+        assert_eq!(all.rows()[0].cells()[0].content(), "Cherry Blossom");
+
+        table.filter_fuzzy("ka");
+        let names: Vec<&str> = table
+            .rows()
+            .iter()
+            .map(|row| row.cells()[0].content())
+            .collect();
+        assert_eq!(names[0], "Kata");
+        assert!(names.contains(&"Kelana"));
+        assert!(!names.contains(&"Squidward"));
+        assert!(table.render().contains("| Name "));
+    }
+
+    #[test]
+    fn comparisons_use_stored_content_by_default() {
+        let mut table = Table::new().row(["caf\u{e9}"]).row(["cafe\u{301}"]);
+        table.filter_eq(0, "caf\u{e9}");
+        assert_eq!(table.len(), 1);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn normalization_in_sort_and_filter() {
+        use crate::Normalization;
+
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        let mut table = Table::new()
+            .row([composed])
+            .row(["cafeteria"])
+            .row([decomposed])
+            .row(["caf\u{e8}"]);
+        let seen = table.generation();
+        table.set_normalization(Normalization::Nfc);
+        assert_eq!(table.normalization(), Normalization::Nfc);
+        assert!(table.generation() > seen);
+
+        table.sort(0);
+        let order: Vec<&str> = table
+            .rows()
+            .iter()
+            .map(|row| row.cells()[0].content())
+            .collect();
+        // Composed and decomposed forms sort next to each other
+        assert_eq!(order, ["cafeteria", "caf\u{e8}", composed, decomposed]);
+
+        let mut matches = table.filtered(|_| true);
+        matches.filter_has(0, "f\u{e9}");
+        assert_eq!(matches.len(), 2);
+
+        table.set_normalization(Normalization::Nfkc);
+        table.add_row(["\u{fb01}le"]);
+        table.filter_eq(0, "file");
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.rows()[0].cells()[0].content(), "\u{fb01}le");
+    }
+
+    #[test]
+    fn filtered_returns_new_table() {
+        let mut table = Table::new();
+        table.set_style(TableStyle::Modern);
+        table.add_row(["25"]);
+        table.add_row(["30"]);
+        table.add_row(["25"]);
+
+        let filtered = table.filtered(|row| row.cells()[0].content() == "25");
+        assert_eq!(table.len(), 3); // Original unchanged
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered.style(), TableStyle::Modern);
+    }
+
+    // Column operations tests
+    #[test]
+    fn add_column() {
+        let mut table = Table::new();
+        table.set_headers(["A", "B"]);
+        table.add_row(["1", "2"]);
+        table.add_column(&["C", "3"], Alignment::Right);
+        assert_eq!(table.cols(), 3);
+        assert_eq!(table.headers().unwrap().cells()[2].content(), "C");
+    }
+
+    #[test]
+    fn insert_column() {
+        let mut table = Table::new();
+        table.set_headers(["A", "C"]);
+        table.add_row(["1", "3"]);
+        table.insert_column(1, &["B", "2"], Alignment::Center);
+        assert_eq!(table.headers().unwrap().cells()[1].content(), "B");
+    }
+
+    #[test]
+    fn remove_column() {
+        let mut table = Table::new();
+        table.set_headers(["A", "B", "C"]);
+        table.add_row(["1", "2", "3"]);
+        assert!(table.remove_column(1));
+        assert_eq!(table.cols(), 2);
+        assert_eq!(table.headers().unwrap().cells()[1].content(), "C");
+    }
+
+    // Render tests
+    #[test]
+    fn render_into_reuses_buffer() {
+        let table = Table::new().header(["A", "B"]).row(["1", "2"]);
+
+        let mut buffer = Vec::with_capacity(10);
+        let original_capacity = buffer.capacity();
+
+        table.render_into(&mut buffer).unwrap();
+        let _first_capacity = buffer.capacity();
+
+        buffer.clear();
+        table.render_into(&mut buffer).unwrap();
+
+        assert!(buffer.capacity() >= original_capacity);
+        assert!(!buffer.is_empty());
+    }
+
+    #[test]
+    fn render_single_row() {
+        let table = Table::new().row(["a", "b"]);
+        let output = table.render();
+        assert!(!output.is_empty());
+        assert!(output.contains('a'));
+        assert!(output.contains('b'));
+    }
+
+    #[test]
+    fn render_with_headers() {
+        let table = Table::new().header(["X", "Y"]).row(["1", "2"]);
+        let output = table.render();
+        assert!(output.contains('X'));
+        assert!(output.contains('Y'));
+        assert!(output.contains('1'));
+    }
+
+    // Vertical alignment tests
+    #[test]
+    fn apply_vertical_alignment_top() {
+        let lines = vec!["a".to_string()];
+        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Top);
+        assert_eq!(result, vec!["a", "", ""]);
+    }
+
+    #[test]
+    fn apply_vertical_alignment_middle() {
+        let lines = vec!["a".to_string()];
+        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Middle);
+        assert_eq!(result, vec!["", "a", ""]);
+    }
+
+    #[test]
+    fn apply_vertical_alignment_bottom() {
+        let lines = vec!["a".to_string()];
+        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Bottom);
+        assert_eq!(result, vec!["", "", "a"]);
+    }
+
+    #[test]
+    fn display_trait_matches_render() {
+        let table = Table::new()
+            .header(["Name", "Value"])
+            .row(["Kata", "100"])
+            .row(["Kelana", "200"]);
+
+        let rendered = table.render();
+        let displayed = format!("{table}");
+
+        assert_eq!(rendered, displayed);
+    }
+
+    #[test]
+    fn display_trait_empty_table() {
+        let table = Table::new();
+        let displayed = format!("{table}");
+        assert_eq!(displayed, "");
+    }
+
+    #[test]
+    fn display_trait_with_style() {
+        let mut table = Table::new();
+        table.set_style(TableStyle::Modern);
+        table.set_headers(["A", "B"]);
+        table.add_row(["1", "2"]);
+
+        let rendered = table.render();
+        let displayed = format!("{table}");
+
+        assert_eq!(rendered, displayed);
+    }
+
+    #[test]
+    fn add_row_invalidates_cache() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+
+        let first = table.render_cached();
+
+        table.add_row(["2"]);
+
+        let second = table.render_cached();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn set_headers_invalidates_cache() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+
+        let first = table.render_cached();
+
+        table.set_headers(["B"]);
+
+        let second = table.render_cached();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn render_into_matches_render() {
+        let table = Table::new()
+            .header(["Name", "Value"])
+            .row(["Kata", "100"])
+            .row(["Kelana", "200"]);
+
+        let rendered = table.render();
+        let mut buffer = Vec::new();
+        table.render_into(&mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), rendered);
+    }
+
+    #[test]
+    fn format_cell_left_alignment() {
+        let result = Table::format_cell("test", 10, Alignment::Left);
+        assert_eq!(result, "test      ");
+    }
+
+    #[test]
+    fn format_cell_right_alignment() {
+        let result = Table::format_cell("test", 10, Alignment::Right);
+        assert_eq!(result, "      test");
+    }
+
+    #[test]
+    fn format_cell_center_alignment() {
+        let result = Table::format_cell("test", 10, Alignment::Center);
+        assert_eq!(result, "   test   ");
+    }
 
-        // Build a set of column boundaries for this row
-        // A boundary exists at column index `i` if a cell starts there
-        let mut boundaries = vec![false; num_columns + 1];
-        boundaries[0] = true; // Left edge always has boundary
-        boundaries[num_columns] = true; // Right edge always has boundary
+    #[test]
+    fn format_cell_truncation() {
+        let result = Table::format_cell("hello world", 8, Alignment::Left);
+        assert_eq!(result, "hello...");
+    }
 
-        let mut col_idx = 0;
-        for cell in row.cells() {
-            let span = cell.span().max(1);
-            cell_spans.push(span);
-            boundaries[col_idx] = true; // Cell starts here
+    #[test]
+    fn format_cell_narrower_than_marker() {
+        let cases = [(0, ""), (1, "."), (2, ".."), (3, "...")];
+        for (width, expected) in cases {
+            assert_eq!(
+                Table::format_cell("hello", width, Alignment::Left),
+                expected,
+                "{width}"
+            );
+        }
+        assert_eq!(Table::format_cell("hi", 2, Alignment::Left), "hi");
+    }
 
-            // Calculate combined width for spanned cells
-            let combined_width = self.calculate_span_width(col_idx, span, column_widths);
-            let wrap_width = self.get_wrap_width(col_idx);
+    #[test]
+    fn format_cell_exact_width() {
+        let result = Table::format_cell("test", 4, Alignment::Left);
+        assert_eq!(result, "test");
+    }
 
-            let effective_width = wrap_width.unwrap_or(combined_width);
-            let lines = if cell.content().chars().count() > effective_width && wrap_width.is_some()
-            {
-                Self::wrap_text(cell.content(), effective_width)
-            } else {
-                vec![cell.content().to_string()]
-            };
+    #[test]
+    fn recalculate_widths_forces_recalculation() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
 
-            max_lines = max_lines.max(lines.len());
-            wrapped_cells.push(lines);
+        let _ = table.render_cached();
 
-            col_idx += span;
-        }
-        // Mark boundary at end of last cell
-        if col_idx <= num_columns {
-            boundaries[col_idx] = true;
-        }
+        table.recalculate_widths();
+        let result = table.render_cached();
 
-        // Apply vertical alignment by calculating offset for each cell
-        let aligned_cells: Vec<Vec<String>> = wrapped_cells
-            .into_iter()
-            .map(|cell_lines| {
-                Self::apply_vertical_alignment(cell_lines, max_lines, self.vertical_alignment)
-            })
-            .collect();
+        assert!(!result.is_empty());
+    }
 
-        // Pre-calculate row line width
-        let line_width: usize = column_widths.iter().sum::<usize>()
-            + (self.padding.left + self.padding.right) * num_columns
-            + self.column_spacing * num_columns.saturating_sub(1)
-            + num_columns + 1 // border chars
-            + 1; // newline
+    #[test]
+    fn render_cached_reuses_cache() {
+        let table = Table::new().header(["A", "B"]).row(["1", "2"]);
 
-        let mut output = String::with_capacity(line_width * max_lines);
+        // First call populates cache
+        let first = table.render_cached();
 
-        for line_idx in 0..max_lines {
-            output.push_str(borders.vertical);
+        // Verify cache is populated
+        assert!(table.cached_widths.borrow().is_some());
 
-            let mut col_idx = 0;
-            for (cell_idx, cell_lines) in aligned_cells.iter().enumerate() {
-                let span = cell_spans.get(cell_idx).copied().unwrap_or(1);
-                let combined_width = self.calculate_span_width(col_idx, span, column_widths);
+        // Second call should return same result (using cache)
+        let second = table.render_cached();
 
-                let alignment = column_alignments.get(col_idx).copied().unwrap_or_else(|| {
-                    row.cells()
-                        .get(cell_idx)
-                        .map_or(Alignment::Left, Cell::alignment)
-                });
+        assert_eq!(first, second);
+    }
 
-                let content = cell_lines.get(line_idx).map_or("", String::as_str);
+    #[test]
+    fn render_cached_matches_render() {
+        let table = Table::new()
+            .header(["Name", "Age"])
+            .row(["Kata", "30"])
+            .row(["Kelana", "25"]);
 
-                // Left padding
-                for _ in 0..self.padding.left {
-                    output.push(' ');
-                }
-                output.push_str(&Self::format_cell(content, combined_width, alignment));
-                // Right padding
-                for _ in 0..self.padding.right {
-                    output.push(' ');
-                }
+        let rendered = table.render();
+        let cached = table.render_cached();
 
-                col_idx += span;
+        assert_eq!(rendered, cached);
+    }
 
-                // Add spacing and vertical border
-                // Only add spacing if not at the last column
-                if col_idx < num_columns {
-                    for _ in 0..self.column_spacing {
-                        output.push(' ');
-                    }
-                }
-                output.push_str(borders.vertical);
-            }
-            output.push('\n');
-        }
+    #[test]
+    fn memoize_reuses_output_until_changed() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+        assert!(!table.memoize());
+        let _ = table.render();
+        assert!(table.cached_output.borrow().is_none());
+
+        table.set_memoize(true);
+        let first = table.render();
+        assert_eq!(
+            table.cached_output.borrow().as_deref(),
+            Some(first.as_str())
+        );
+        assert_eq!(table.render(), first);
 
-        output
+        table.set_style(TableStyle::Modern);
+        assert!(table.cached_output.borrow().is_none());
+        assert!(table.render().starts_with('┌'));
     }
 
-    /// Calculates the combined width for a cell that spans multiple columns.
-    fn calculate_span_width(
-        &self,
-        start_col: usize,
-        span: usize,
-        column_widths: &[usize],
-    ) -> usize {
-        if span <= 1 {
-            return column_widths.get(start_col).copied().unwrap_or(0);
+    #[test]
+    fn memoize_invalidated_by_mutations() {
+        let base = || {
+            let mut table = Table::new().header(["N", "Name"]);
+            table.add_row(["2", "b"]);
+            table.add_row(["1", "a"]);
+            table.set_memoize(true);
+            table
+        };
+        let mutations: [fn(&mut Table); 8] = [
+            |table| table.sort_num(0),
+            |table| table.filter_eq(1, "a"),
+            |table| table.add_column(&["X", "x", "y"], Alignment::Left),
+            |table| {
+                table.remove_column(1);
+            },
+            |table| table.set_padding(Padding::uniform(2)),
+            |table| table.align(1, Alignment::Right),
+            |table| table.set_constraint(1, WidthConstraint::Fixed(6)),
+            |table| table.set_render_options(RenderOptions::new().line_prefix("> ")),
+        ];
+        for mutate in mutations {
+            let mut table = base();
+            let before = table.render();
+            mutate(&mut table);
+            let after = table.render();
+            assert_ne!(before, after);
+            table.set_memoize(false);
+            assert_eq!(after, table.render());
         }
+    }
 
-        let mut total_width = 0;
-        for i in 0..span {
-            let col = start_col + i;
-            if col < column_widths.len() {
-                total_width += column_widths[col];
-                // Add padding and spacing for intermediate columns
-                if i < span - 1 {
-                    total_width += self.padding.left + self.padding.right + self.column_spacing + 1;
-                }
-            }
+    #[test]
+    fn generation_changes_on_every_mutation() {
+        let mutations: [fn(&mut Table); 11] = [
+            |table| table.add_row(["3", "c"]),
+            |table| {
+                table.remove_row(0);
+            },
+            |table| table.sort(1),
+            |table| table.filter_has(1, "a"),
+            |table| table.set_headers(["M", "Label"]),
+            |table| table.set_style(TableStyle::Modern),
+            |table| table.valign(VerticalAlignment::Bottom),
+            |table| table.set_tab_width(8),
+            |table| table.set_strip_ansi(true),
+            |table| table.set_max_rows(Some(1)),
+            |table| table.set_interning(true),
+        ];
+        for mutate in mutations {
+            let mut table = Table::new().header(["N", "Name"]).row(["1", "a"]);
+            let before = table.generation();
+            mutate(&mut table);
+            assert!(table.generation() > before);
         }
-        total_width
     }
 
-    pub(crate) fn apply_vertical_alignment(
-        cell_lines: Vec<String>,
-        max_lines: usize,
-        vertical_alignment: VerticalAlignment,
-    ) -> Vec<String> {
-        let cell_line_count = cell_lines.len();
-        if cell_line_count >= max_lines {
-            return cell_lines;
-        }
+    #[test]
+    fn generation_ignores_reads() {
+        let mut table = Table::new().header(["N"]).row(["1"]);
+        let seen = table.generation();
+        let _ = table.render();
+        let _ = table.render_cached();
+        let _ = table.rendered_height();
+        let _ = table.to_jsonl();
+        table.recalculate_widths();
+        assert_eq!(table.generation(), seen);
+    }
 
-        let padding_needed = max_lines - cell_line_count;
-        let mut result = Vec::with_capacity(max_lines);
+    #[test]
+    fn render_cached_sees_filtering() {
+        let mut table = Table::new().row(["b"]).row(["wide"]);
+        let _ = table.render_cached();
+        table.filter_eq(0, "b");
+        assert_eq!(table.render_cached(), table.render());
+    }
 
-        match vertical_alignment {
-            VerticalAlignment::Top => {
-                result.extend(cell_lines);
-                result.extend(core::iter::repeat_n(String::new(), padding_needed));
-            }
-            VerticalAlignment::Middle => {
-                let top_padding = padding_needed / 2;
-                let bottom_padding = padding_needed - top_padding;
-                result.extend(core::iter::repeat_n(String::new(), top_padding));
-                result.extend(cell_lines);
-                result.extend(core::iter::repeat_n(String::new(), bottom_padding));
-            }
-            VerticalAlignment::Bottom => {
-                result.extend(core::iter::repeat_n(String::new(), padding_needed));
-                result.extend(cell_lines);
-            }
-        }
+    // Grouping tests
+    fn sales_table() -> Table {
+        Table::new()
+            .header(["City", "Sales"])
+            .row(["Berlin", "10.5"])
+            .row(["Paris", "3"])
+            .row(["Berlin", "4"])
+    }
 
-        result
+    #[test]
+    fn group_by_orders_rows_by_first_appearance() {
+        let mut table = sales_table();
+        table.group_by(0);
+        let output = table.render();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[3], "| Berlin  | 10.5  |");
+        assert_eq!(lines[4], "| Berlin  | 4     |");
+        assert_eq!(lines[5], "+---------+-------+");
+        assert_eq!(lines[6], "| Paris   | 3     |");
+        // Stored order is untouched
+        assert_eq!(table.rows()[1].cells()[0].content(), "Paris");
     }
 
-    fn get_wrap_width(&self, column: usize) -> Option<usize> {
-        if let Some(WidthConstraint::Wrap(w)) = self.constraints.get(column) {
-            return Some(*w);
-        }
-        None
+    #[test]
+    fn subtotals_and_grand_total() {
+        let mut table = sales_table();
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+
+        let expected = "\
++-----------+-------+
+| City      | Sales |
++-----------+-------+
+| Berlin    | 10.5  |
+| Berlin    | 4     |
++-----------+-------+
+| Subtotal  | 14.5  |
++-----------+-------+
+| Paris     | 3     |
++-----------+-------+
+| Subtotal  | 3     |
++===========+=======+
+| Total     | 17.5  |
++-----------+-------+
+";
+        assert_eq!(table.render(), expected);
     }
 
-    /// Renders a horizontal border with proper handling of column spans.
-    ///
-    /// Uses different junction characters based on cell boundaries:
-    /// - Cross (┼) when both rows have boundary
-    /// - T-down (┬) when only row below has boundary
-    /// - T-up (┴) when only row above has boundary
-    /// - Horizontal (─) when neither has boundary
-    #[allow(clippy::too_many_arguments)]
-    fn render_horizontal_border_with_spans(
-        column_widths: &[usize],
-        padding: Padding,
-        column_spacing: usize,
-        left: &str,
-        cross: &str,
-        right: &str,
-        horizontal: &str,
-        cross_down: &str, // T pointing down (┬) - only row below has boundary
-        cross_up: &str,   // T pointing up (┴) - only row above has boundary
-        boundaries_below: &[bool],
-        boundaries_above: &[bool],
-    ) -> String {
-        let num_columns = column_widths.len();
+    #[test]
+    fn collapsed_groups() {
+        let mut table = sales_table();
+        assert!(table.group_keys().is_empty());
+        table.group_by(0);
+        assert_eq!(table.group_keys(), ["Berlin", "Paris"]);
+        table.collapse_all_groups();
+        assert!(table.is_group_collapsed("Paris"));
+        assert!(!table.toggle_group("Paris"));
+
+        let expected = "\
++--------+----------+
+| City   | Sales    |
++--------+----------+
+| ▶ Berlin (2 rows) |
++--------+----------+
+| Paris  | 3        |
++--------+----------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rendered_height(), 7);
+
+        // Keys belong to the grouping column
+        table.group_by(0);
+        assert!(table.is_group_collapsed("Berlin"));
+        table.group_by(1);
+        assert!(!table.is_group_collapsed("Berlin"));
+    }
 
-        // Pre-calculate line width
-        let content_width: usize = column_widths.iter().sum::<usize>()
-            + (padding.left + padding.right) * num_columns
-            + column_spacing * num_columns.saturating_sub(1);
-        let border_chars = num_columns + 1;
-        let estimated_capacity = content_width + border_chars + 1;
+    #[test]
+    fn selection_follows_rows() {
+        let mut table = sales_table();
+        table.set_selected(0, true);
+        assert!(table.toggle_selected(3));
+        assert!(!table.toggle_selected(3));
+        table.set_selected(2, true);
+        table.sort_desc(1);
+        assert_eq!(table.selected_rows(), [0, 2]);
+        assert!(!table.is_selected(1));
+
+        table.filter_eq(0, "Berlin");
+        table.set_selected(0, false);
+        assert_eq!(table.selected_rows(), [1]);
+        table.select_all();
+        assert_eq!(table.selected_rows(), [0, 1]);
+        table.clear_selection();
+        assert!(table.selected_rows().is_empty());
+    }
 
-        let mut line = String::with_capacity(estimated_capacity);
+    #[test]
+    fn selection_column_shifts_column_settings() {
+        let mut table = Table::new()
+            .header(["Item", "Price"])
+            .row(["Tea", "3.5"])
+            .row(["Cake", "12"]);
+        table.align(1, Alignment::Right);
+        table.format_column(1, "template:${}");
+        table.set_selected(1, true);
+        let plain = table.render();
+        assert!(!plain.contains("[x]"));
+
+        table.set_selection_column(true);
+        assert!(table.has_selection_column());
+        assert_eq!(
+            table.render(),
+            "+------+-------+-------+\n\
+             |      | Item  | Price |\n\
+             +------+-------+-------+\n\
+             | [ ]  | Tea   |  $3.5 |\n\
+             | [x]  | Cake  |   $12 |\n\
+             +------+-------+-------+\n"
+        );
+        assert_eq!(table.render_cached(), table.render());
 
-        line.push_str(left);
+        table.set_selection_column(false);
+        assert_eq!(table.render(), plain);
+    }
 
-        // Check if horizontal is a single character for optimization
-        let h_char = if horizontal.len() == 1 {
-            horizontal.chars().next()
-        } else {
-            None
+    #[test]
+    fn selection_column_in_viewport() {
+        let store: Vec<Row> = (0..10).map(|i| Row::from([i.to_string()])).collect();
+        let mut template = Table::new().header(["ID"]);
+        template.set_selection_column(true);
+        template.set_selected(5, true);
+        assert!(template.is_selected(5));
+        let output = template.render_viewport(&store, 4..6);
+        assert!(output.contains("| [ ]  | 4  |"));
+        assert!(output.contains("| [x]  | 5  |"));
+    }
+
+    #[test]
+    fn grand_total_without_grouping() {
+        let mut table = sales_table();
+        table.aggregate(1, Aggregate::Max);
+        table.set_grand_total(true);
+        let output = table.render();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[6], "+=========+=======+");
+        assert_eq!(lines[7], "| Total   | 10.5  |");
+    }
+
+    #[test]
+    fn summaries_require_aggregates() {
+        let mut table = sales_table();
+        table.group_by(0);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+        assert!(!table.render().contains("Total"));
+    }
+
+    #[test]
+    fn render_record_formats_values() {
+        let mut table = Table::new().row(["1536", ""]);
+        table.set_style(TableStyle::Markdown);
+        table.format_column(0, "bytes");
+        table.set_empty_placeholder("-");
+        table.set_messages(Messages::new().record("Kolom", "Nilai"));
+        assert_eq!(
+            table.render_record(0).as_deref(),
+            Some(
+                "| Kolom  | Nilai   |\n\
+                 |------- |---------|\n\
+                 | 0      | 1.5 KiB |\n\
+                 | 1      | -       |\n"
+            )
+        );
+    }
+
+    #[test]
+    fn child_rows_render_as_tree() {
+        let mut table = Table::new();
+        table.add_row(["a"]);
+        table.add_child_row(0, ["b"]);
+        table.add_child_row(1, ["c"]);
+        assert_eq!(table.add_child_row(0, ["d"]), Some(3));
+        table.add_row(["e"]);
+        assert_eq!(table.add_child_row(9, ["f"]), None);
+
+        let first_column = |table: &Table| -> Vec<String> {
+            table
+                .render()
+                .lines()
+                .filter_map(|line| Some(line.split('|').nth(1)?.trim_end().to_string()))
+                .collect()
         };
+        assert_eq!(
+            first_column(&table),
+            [" a", " ├─ b", " │  └─ c", " └─ d", " e"]
+        );
 
-        for (index, &width) in column_widths.iter().enumerate() {
-            let cell_width = padding.left + width + padding.right;
-            if let Some(ch) = h_char {
-                for _ in 0..cell_width {
-                    line.push(ch);
-                }
-            } else {
-                for _ in 0..cell_width {
-                    line.push_str(horizontal);
-                }
-            }
+        // Children of a filtered-out row become roots
+        table.filter_col(0, |content| content != "b");
+        assert_eq!(first_column(&table), [" a", " c", " └─ d", " e"]);
+    }
+
+    #[test]
+    fn current_order_follows_sorts_and_filters() {
+        let mut table = Table::new().row(["3", "c"]).row(["1", "a"]).row(["2", "b"]);
+        assert_eq!(table.current_order(), [0, 1, 2]);
+        table.sort_num(0);
+        assert_eq!(table.current_order(), [1, 2, 0]);
+        table.filter(|row| row.cells()[1].content() != "b");
+        assert_eq!(table.current_order(), [1, 0]);
+        table.insert_row(0, ["4", "d"]);
+        assert_eq!(table.current_order(), [3, 1, 0]);
+        assert_eq!(table.filtered(|_| true).current_order(), [3, 1, 0]);
+
+        table.apply_order(&[0, 9, 3, 0]);
+        assert_eq!(table.current_order(), [0, 3]);
+        assert_eq!(table.rows()[1].cells()[1].content(), "d");
+    }
+
+    #[test]
+    fn aggregate_replaces_existing_column_entry() {
+        let mut table = Table::new();
+        table.aggregate(1, Aggregate::Sum);
+        table.aggregate(1, Aggregate::Avg);
+        assert_eq!(table.aggregates(), &[(1, Aggregate::Avg)]);
+    }
 
-            if index < num_columns - 1 {
-                // Column boundary index (between column `index` and `index + 1`)
-                let boundary_idx = index + 1;
-                let has_boundary_below =
-                    boundaries_below.get(boundary_idx).copied().unwrap_or(true);
-                let has_boundary_above =
-                    boundaries_above.get(boundary_idx).copied().unwrap_or(true);
+    #[test]
+    fn column_groups_double_separators() {
+        let mut table = Table::new()
+            .header(["Host", "Zone", "CPU", "Mem", "Owner"])
+            .row(["db1", "eu", "40%", "2G", "ops"]);
+        table.column_group(0..2, "Identity");
+        table.column_group(2..4, "Metrics");
+        assert_eq!(
+            table.render(),
+            "+---------------++-------------++-------+\n\
+             |   Identity    ||  Metrics    ||       |\n\
+             +-------+-------++------+------++-------+\n\
+             | Host  | Zone  || CPU  | Mem  || Owner |\n\
+             +-------+-------++------+------++-------+\n\
+             | db1   | eu    || 40%  | 2G   || ops   |\n\
+             +-------+-------++------+------++-------+\n"
+        );
 
-                // Determine the junction character based on boundaries
-                let junction = match (has_boundary_above, has_boundary_below) {
-                    (true, true) => cross,        // Both have boundary: ┼
-                    (false, true) => cross_down,  // Only below: ┬
-                    (true, false) => cross_up,    // Only above: ┴
-                    (false, false) => horizontal, // Neither: ─ (continue horizontal)
-                };
+        // Labels are optional, and groups widen to fit theirs
+        table.clear_column_groups();
+        table.column_group(0..1, "Hostname and zone");
+        table.column_group(2..4, None);
+        let output = table.render();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[1],
+            "| Hostname and zone  ||       ||             ||       |"
+        );
+        assert_eq!(
+            lines[3],
+            "| Host               || Zone  || CPU  | Mem  || Owner |"
+        );
 
-                if junction == horizontal {
-                    // No boundary on both sides - continue with horizontal line
-                    // Add spacing width + 1 (for the cross character position)
-                    let span_width = column_spacing + 1;
-                    if let Some(ch) = h_char {
-                        for _ in 0..span_width {
-                            line.push(ch);
-                        }
-                    } else {
-                        for _ in 0..span_width {
-                            line.push_str(horizontal);
-                        }
-                    }
-                } else {
-                    // There's a junction character to render
-                    if let Some(ch) = h_char {
-                        for _ in 0..column_spacing {
-                            line.push(ch);
-                        }
-                    } else {
-                        for _ in 0..column_spacing {
-                            line.push_str(horizontal);
-                        }
-                    }
-                    line.push_str(junction);
-                }
-            }
-        }
-        line.push_str(right);
-        line.push('\n');
+        table.set_style(TableStyle::Markdown);
+        assert!(!table.render().contains("||"));
+    }
 
-        line
+    #[test]
+    fn grouping_modern_double_rule() {
+        let mut table = sales_table();
+        table.set_style(TableStyle::Modern);
+        table.aggregate(1, Aggregate::Count);
+        table.set_grand_total(true);
+        assert!(table.render().contains("╞═════════╪═══════╡"));
     }
 
-    fn render_markdown_header_separator(
-        column_widths: &[usize],
-        padding: Padding,
-        column_spacing: usize,
-    ) -> String {
-        let num_columns = column_widths.len();
-        let content_width: usize = column_widths.iter().sum::<usize>()
-            + (padding.left + padding.right) * num_columns
-            + column_spacing * num_columns.saturating_sub(1);
-        let border_chars = num_columns + 1;
-        let estimated_capacity = content_width + border_chars + 1;
+    #[test]
+    fn ungroup_restores_stored_order() {
+        let mut table = sales_table();
+        table.group_by(0);
+        table.ungroup();
+        assert_eq!(table.group_column(), None);
+        let output = table.render();
+        assert_eq!(output.lines().nth(4), Some("| Paris   | 3     |"));
+    }
 
-        let mut line = String::with_capacity(estimated_capacity);
-        line.push('|');
+    // Split rendering tests
+    fn wide_table() -> Table {
+        Table::new()
+            .header(["ID", "Name", "January", "February", "March"])
+            .row(["1", "Kata", "10", "20", "30"])
+    }
 
-        for (index, &width) in column_widths.iter().enumerate() {
-            let cell_width = padding.left + width + padding.right;
-            if cell_width >= 2 {
-                line.push('-');
-                for _ in 0..cell_width.saturating_sub(2) {
-                    line.push('-');
-                }
-                line.push('-');
-            } else {
-                for _ in 0..cell_width.max(1) {
-                    line.push('-');
-                }
-            }
+    #[test]
+    fn render_split_fits_on_one_chunk() {
+        let table = wide_table();
+        assert_eq!(table.render_split(&SplitOptions::new(200)), table.render());
+    }
 
-            if index < num_columns - 1 {
-                for _ in 0..column_spacing {
-                    line.push(' ');
-                }
-                line.push('|');
-            }
-        }
+    #[test]
+    fn render_split_respects_max_width() {
+        let table = wide_table();
+        let output = table.render_split(&SplitOptions::new(30));
+        assert!(output.lines().all(|line| line.chars().count() <= 30));
+        assert!(output.contains("\n\n"));
+    }
 
-        line.push('|');
-        line.push('\n');
-        line
+    #[test]
+    fn render_split_repeats_label_column() {
+        let table = wide_table();
+        let options = SplitOptions::new(36)
+            .label_column(1)
+            .continued_caption("(continued)");
+
+        let expected = "\
++-------+-----+---------+
+| Name  | ID  | January |
++-------+-----+---------+
+| Kata  | 1   | 10      |
++-------+-----+---------+
+
+(continued)
++-------+-----------+-------+
+| Name  | February  | March |
++-------+-----------+-------+
+| Kata  | 20        | 30    |
++-------+-----------+-------+
+";
+        assert_eq!(table.render_split(&options), expected);
     }
-}
 
-impl core::fmt::Display for Table {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "{}", self.render())
+    #[test]
+    fn render_split_repeats_frozen_columns() {
+        let table = wide_table();
+        let output = table.render_split(&SplitOptions::new(36).frozen_columns(2));
+        let header_lines: Vec<&str> = output.lines().filter(|l| l.contains("Name")).collect();
+        assert!(header_lines.len() > 1);
+        assert!(header_lines.iter().all(|l| l.starts_with("| ID  | Name ")));
     }
-}
 
-impl Default for Table {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn render_split_keeps_column_alignment() {
+        let mut table = wide_table();
+        table.align(4, Alignment::Right);
+        let output = table.render_split(&SplitOptions::new(36).label_column(1));
+        assert!(output.contains("|    30 |"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{Alignment, Table, TableStyle, VerticalAlignment};
+    #[test]
+    fn render_split_empty_table() {
+        assert_eq!(Table::new().render_split(&SplitOptions::new(10)), "");
+    }
 
+    // Header style tests
     #[test]
-    fn new_is_empty() {
-        let table = Table::new();
-        assert!(table.is_empty());
-        assert_eq!(table.len(), 0);
-        assert_eq!(table.cols(), 0);
-        assert!(table.headers().is_none());
+    fn header_style_uppercase() {
+        let mut table = Table::new().header(["straße", "id"]).row(["x", "1"]);
+        table.set_header_style(HeaderStyle::new().uppercase());
+        let output = table.render();
+        assert_eq!(output.lines().nth(1), Some("| STRASSE  | ID |"));
+        // Stored headers are unchanged
+        assert_eq!(table.headers().unwrap().cells()[0].content(), "straße");
     }
 
     #[test]
-    fn default_is_empty() {
-        let table = Table::default();
-        assert!(table.is_empty());
+    fn header_style_alignment_overrides_columns() {
+        let mut table = Table::new().header(["Name", "N"]).row(["Kat", "1"]);
+        table.align(0, Alignment::Right);
+        table.set_header_style(HeaderStyle::new().align(Alignment::Left));
+        let output = table.render();
+        assert_eq!(output.lines().nth(1), Some("| Name  | N |"));
+        assert_eq!(output.lines().nth(3), Some("|  Kat  | 1 |"));
     }
 
     #[test]
-    fn default_style_is_classic() {
-        let table = Table::new();
-        assert_eq!(table.style(), TableStyle::Classic);
+    fn header_style_ansi_keeps_widths() {
+        let mut table = Table::new().header(["A", "B"]).row(["10", "20"]);
+        table.set_header_style(HeaderStyle::new().bold().color(Color::Green));
+        let output = table.render();
+        assert_eq!(
+            output.lines().nth(1),
+            Some("| \x1b[1;32mA \x1b[0m  | \x1b[1;32mB \x1b[0m |")
+        );
+        assert_eq!(output.lines().nth(3), Some("| 10  | 20 |"));
     }
 
     #[test]
-    fn default_padding() {
-        let table = Table::new();
-        assert_eq!(table.padding().left, 1);
-        assert_eq!(table.padding().right, 1);
+    fn ansi_passthrough_measures_visible_text() {
+        let mut table = Table::new()
+            .header(["Status", "Host"])
+            .row(["\x1b[31mdown", "db-1"])
+            .row(["\x1b[1;32mup and running\x1b[0m", "db-2"]);
+        // Without passthrough the escape sequences count as text
+        let measured = table.render();
+        assert!(measured.starts_with(&format!("+{}+", "-".repeat(28))));
+
+        table.set_render_options(RenderOptions::new().ansi_passthrough(true));
+        table.set_constraint(0, WidthConstraint::Wrap(7));
+        assert_eq!(
+            table.render(),
+            "+----------+------+\n\
+             | Status   | Host |\n\
+             +----------+------+\n\
+             | \x1b[31mdown   \x1b[0m  | db-1 |\n\
+             | \x1b[1;32mup and \x1b[0m  | db-2 |\n\
+             | \x1b[1;32mrunning\x1b[0m\x1b[0m  |      |\n\
+             +----------+------+\n"
+        );
+        assert_eq!(table.rendered_height(), 7);
+
+        table.set_constraint(0, WidthConstraint::Fixed(5));
+        assert!(
+            table
+                .render()
+                .contains("| \x1b[1;32mup...\x1b[0m  | db-2 |")
+        );
     }
 
     #[test]
-    fn default_spacing() {
-        let table = Table::new();
-        assert_eq!(table.get_spacing(), 1);
+    fn header_style_double_rule() {
+        let mut table = Table::new().header(["A", "B"]).row(["10", "20"]);
+        table.set_style(TableStyle::Modern);
+        assert_eq!(table.render().lines().nth(2), Some("├─────┼────┤"));
+        table.set_header_style(HeaderStyle::new().double_rule());
+        let output = table.render();
+        assert_eq!(output.lines().nth(2), Some("╞═════╪════╡"));
+        assert_eq!(output.lines().last(), Some("└─────┴────┘"));
+
+        // Markdown keeps its delimiter row
+        table.set_style(TableStyle::Markdown);
+        assert_eq!(table.render().lines().nth(1), Some("|---- |----|"));
     }
 
+    // Rendered height tests
     #[test]
-    fn default_valign_is_top() {
-        let table = Table::new();
-        assert_eq!(table.get_valign(), VerticalAlignment::Top);
+    fn rendered_height_empty() {
+        assert_eq!(Table::new().rendered_height(), 0);
     }
 
     #[test]
-    fn set_headers() {
-        let mut table = Table::new();
-        table.set_headers(["A", "B"]);
-        assert!(table.headers().is_some());
-        assert_eq!(table.headers().unwrap().len(), 2);
+    fn rendered_height_matches_render_for_all_styles() {
+        let styles = [
+            TableStyle::Classic,
+            TableStyle::Modern,
+            TableStyle::Minimal,
+            TableStyle::Compact,
+            TableStyle::Markdown,
+            TableStyle::Rest,
+            TableStyle::AsciiDoc,
+            TableStyle::Org,
+            TableStyle::Plain,
+        ];
+        for style in styles {
+            let mut table = sales_table();
+            table.set_style(style);
+            table.add_row(["Rome", "1\n2"]);
+            assert_eq!(table.rendered_height(), table.render().lines().count());
+            table.align(1, Alignment::Right);
+            table.group_by(0);
+            table.aggregate(1, Aggregate::Sum);
+            table.set_subtotals(true);
+            assert_eq!(table.rendered_height(), table.render().lines().count());
+            table.column_group(0..2, "Group");
+            assert_eq!(
+                table.rendered_height(),
+                table.render().lines().count(),
+                "{style:?}"
+            );
+        }
     }
 
     #[test]
-    fn add_row() {
-        let mut table = Table::new();
-        table.add_row(["1", "2"]);
-        assert_eq!(table.len(), 1);
-        assert!(!table.is_empty());
+    fn rendered_height_counts_wrapped_lines() {
+        let mut table = Table::new()
+            .header(["ID", "Description"])
+            .row(["1", "a fairly long description that wraps"])
+            .row(["2", "short"]);
+        table.set_constraint(1, WidthConstraint::Wrap(10));
+        assert_eq!(table.rendered_height(), 11);
+        assert_eq!(table.rendered_height(), table.render().lines().count());
     }
 
     #[test]
-    fn insert_row() {
-        let mut table = Table::new();
-        table.add_row(["a", "1"]);
-        table.add_row(["c", "3"]);
-        table.insert_row(1, ["b", "2"]);
-        assert_eq!(table.len(), 3);
-        assert_eq!(table.rows()[1].cells()[0].content(), "b");
+    fn rendered_height_counts_summary_rows() {
+        let mut table = sales_table();
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+        assert_eq!(table.rendered_height(), 14);
+        assert_eq!(table.rendered_height(), table.render().lines().count());
     }
 
+    // Documentation style tests
     #[test]
-    fn remove_row() {
-        let mut table = Table::new();
-        table.add_row(["a", "1"]);
-        table.add_row(["b", "2"]);
-        let removed = table.remove_row(0);
-        assert!(removed.is_some());
-        assert_eq!(removed.unwrap().cells()[0].content(), "a");
-        assert_eq!(table.len(), 1);
+    fn rest_grid_table() {
+        let mut table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kelana", "two\nlines"])
+            .row(["Kata", "one"]);
+        table.set_style(TableStyle::Rest);
+        let expected = "\
++---------+-------+
+| Name    | Note  |
++=========+=======+
+| Kelana  | two   |
+|         | lines |
++---------+-------+
+| Kata    | one   |
++---------+-------+
+";
+        assert_eq!(table.render(), expected);
     }
 
     #[test]
-    fn remove_row_out_of_bounds() {
-        let mut table = Table::new();
-        table.add_row(["a"]);
-        assert!(table.remove_row(5).is_none());
+    fn rest_uses_single_rules_for_summaries() {
+        let mut table = sales_table();
+        table.set_style(TableStyle::Rest);
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_grand_total(true);
+        let output = table.render();
+        assert_eq!(output.lines().filter(|line| line.contains('=')).count(), 1);
+        assert_eq!(output.lines().nth(2), Some("+=========+=======+"));
+        assert_eq!(
+            output.lines().filter(|line| line.starts_with("+-")).count(),
+            5
+        );
     }
 
     #[test]
-    fn cols() {
-        let table = Table::new().header(["A", "B", "C"]).row(["1", "2", "3"]);
-        assert_eq!(table.cols(), 3);
+    fn plain_table() {
+        let mut table = sales_table();
+        table.set_style(TableStyle::Plain);
+        table.add_row(["Rome", ""]);
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_grand_total(true);
+        let expected = "\
+City    Sales
+Berlin  10.5
+Berlin  4
+Paris   3
+Rome
+Total   17.5
+";
+        assert_eq!(table.render(), expected);
     }
 
     #[test]
-    fn fluent_api() {
-        let table = Table::new()
-            .header(["ID", "Value"])
-            .row(["1", "100"])
-            .row(["2", "200"]);
-        assert_eq!(table.len(), 2);
-        assert!(table.headers().is_some());
+    fn org_table() {
+        let mut table = sales_table();
+        table.set_style(TableStyle::Org);
+        table.group_by(0);
+        let expected = "\
+| City    | Sales |
+|---------+-------|
+| Berlin  | 10.5  |
+| Berlin  | 4     |
+|---------+-------|
+| Paris   | 3     |
+";
+        assert_eq!(table.render(), expected);
     }
 
-    // Sorting tests
     #[test]
-    fn sort_ascending() {
-        let mut table = Table::new();
-        table.add_row(["Squidward"]);
-        table.add_row(["Kelana"]);
-        table.add_row(["Kata"]);
-        table.sort(0);
-        assert_eq!(table.rows()[0].cells()[0].content(), "Kata");
-        assert_eq!(table.rows()[1].cells()[0].content(), "Kelana");
-        assert_eq!(table.rows()[2].cells()[0].content(), "Squidward");
+    fn asciidoc_table() {
+        let mut table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kelana", "two\nlines"])
+            .row(["Kata", "a|b"]);
+        table.set_style(TableStyle::AsciiDoc);
+        assert_eq!(
+            table.render(),
+            "|===\n|Name |Note\n\n|Kelana |two +\nlines\n|Kata |a\\|b\n|===\n"
+        );
     }
 
     #[test]
-    fn sort_descending() {
-        let mut table = Table::new();
-        table.add_row(["Kelana"]);
-        table.add_row(["Squidward"]);
-        table.sort_desc(0);
-        assert_eq!(table.rows()[0].cells()[0].content(), "Squidward");
-        assert_eq!(table.rows()[1].cells()[0].content(), "Kelana");
+    fn asciidoc_alignment_spans_and_short_rows() {
+        let mut table = Table::new().row(["a", "b", "c"]).row(["short"]);
+        table.set_style(TableStyle::AsciiDoc);
+        let mut row = Row::new();
+        let mut cell = Cell::new("wide", Alignment::Right);
+        cell.set_span(2);
+        row.push(cell);
+        row.push(Cell::new("x", Alignment::Left));
+        table.add_row(row);
+        assert_eq!(
+            table.render(),
+            "|===\n|a |b |c\n|short | |\n2+>|wide |x\n|===\n"
+        );
+
+        table.align(1, Alignment::Center);
+        assert!(table.render().starts_with("[cols=\"<,^,<\"]\n|===\n"));
     }
 
     #[test]
-    fn sort_num_ascending() {
-        let mut table = Table::new();
-        table.add_row(["100"]);
-        table.add_row(["25"]);
-        table.add_row(["50"]);
-        table.sort_num(0);
-        assert_eq!(table.rows()[0].cells()[0].content(), "25");
-        assert_eq!(table.rows()[1].cells()[0].content(), "50");
-        assert_eq!(table.rows()[2].cells()[0].content(), "100");
+    fn decimal_alignment_lines_up_points() {
+        let mut table = Table::new()
+            .header(["Item", "Amount"])
+            .row(["a", "1234.5"])
+            .row(["b", "3"])
+            .row(["c", "0.125"])
+            .row(["d", "n/a"]);
+        table.set_style(TableStyle::Markdown);
+        table.align(1, Alignment::Decimal);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_grand_total(true);
+        let mut row = Row::new();
+        let mut wide = Cell::new("9.99", Alignment::Left);
+        wide.set_span(2);
+        row.push(wide);
+        table.add_row(row);
+        assert_eq!(
+            table.render(),
+            "| Item   |   Amount |\n|:------ |---------:|\n| a      | 1234.5   |\n\
+             | b      |    3     |\n| c      |    0.125 |\n| d      |      n/a |\n\
+             | 9.99              |\n| Total  | 1237.625 |\n"
+        );
+    }
+
+    // Markdown mode tests
+    fn markdown_table(mode: MarkdownMode) -> Table {
+        let mut table = Table::new()
+            .header(["Name", "Age"])
+            .row(["Kelana", "30"])
+            .row(["Kata", "25"]);
+        table.set_style(TableStyle::Markdown);
+        table.set_render_options(RenderOptions::new().markdown(mode));
+        table
     }
 
     #[test]
-    fn sort_num_descending() {
-        let mut table = Table::new();
-        table.add_row(["25"]);
-        table.add_row(["100"]);
-        table.sort_num_desc(0);
-        assert_eq!(table.rows()[0].cells()[0].content(), "100");
-        assert_eq!(table.rows()[1].cells()[0].content(), "25");
+    fn markdown_compact() {
+        let table = markdown_table(MarkdownMode::Compact);
+        let expected = "|Name|Age|\n|---|---|\n|Kelana|30|\n|Kata|25|\n";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.render_cached(), expected);
+        assert_eq!(table.rendered_height(), 4);
     }
 
     #[test]
-    fn sort_preserves_headers() {
-        let mut table = Table::new();
-        table.set_headers(["Name"]);
-        table.add_row(["Squidward"]);
-        table.add_row(["Kelana"]);
-        table.sort(0);
-        assert_eq!(table.headers().unwrap().cells()[0].content(), "Name");
+    fn markdown_delimiter_row_alignment() {
+        for mode in [MarkdownMode::Pretty, MarkdownMode::Compact] {
+            let mut table = Table::new()
+                .header(["L", "C", "R", "N"])
+                .row(["a", "b", "c", "d"]);
+            table.set_style(TableStyle::Markdown);
+            table.set_render_options(RenderOptions::new().markdown(mode));
+            table.align(0, Alignment::Left);
+            table.align(1, Alignment::Center);
+            table.align(2, Alignment::Right);
+            let expected = match mode {
+                MarkdownMode::Pretty => "|:-- |:-: |--: |---|",
+                MarkdownMode::Compact => "|:---|:---:|---:|---|",
+            };
+            assert_eq!(table.render().lines().nth(1), Some(expected));
+        }
     }
 
-    // Filter tests
     #[test]
-    fn filter() {
-        let mut table = Table::new();
-        table.add_row(["Kelana", "25"]);
-        table.add_row(["Kata", "30"]);
-        table.add_row(["Squidward", "25"]);
-        table.filter(|row| row.cells()[1].content() == "25");
-        assert_eq!(table.len(), 2);
+    fn markdown_delimiter_keeps_a_dash() {
+        let mut table = Table::new().header(["", "x"]).row(["", "y"]);
+        table.set_style(TableStyle::Markdown);
+        table.set_padding(Padding::uniform(0));
+        table.spacing(0);
+        table.align(0, Alignment::Center);
+        assert_eq!(table.render().lines().nth(1), Some("|:-:|-|"));
     }
 
     #[test]
-    fn filter_eq() {
-        let mut table = Table::new();
-        table.add_row(["Active"]);
-        table.add_row(["Inactive"]);
-        table.add_row(["Active"]);
-        table.filter_eq(0, "Active");
-        assert_eq!(table.len(), 2);
+    fn markdown_has_single_delimiter_row() {
+        let mut table = sales_table();
+        table.set_style(TableStyle::Markdown);
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+        let output = table.render();
+        let delimiters = output.lines().filter(|line| line.contains("--")).count();
+        assert_eq!(delimiters, 1, "{output}");
+    }
+
+    #[test]
+    fn markdown_pretty_is_default() {
+        let pretty = markdown_table(MarkdownMode::Pretty);
+        let mut default = markdown_table(MarkdownMode::Pretty);
+        default.set_render_options(RenderOptions::default());
+        assert_eq!(pretty.render(), default.render());
+        assert!(pretty.render().starts_with("| Name "));
+    }
+
+    #[test]
+    fn markdown_compact_pads_short_rows_and_spans() {
+        let mut table = markdown_table(MarkdownMode::Compact);
+        table.add_row(["Solo"]);
+        let mut row = Row::new();
+        let mut wide = Cell::new("Both", Alignment::Left);
+        wide.set_span(2);
+        row.push(wide);
+        table.add_row(row);
+        let output = table.render();
+        assert!(output.contains("|Solo||\n"));
+        assert!(output.contains("|Both||\n"));
+    }
+
+    #[test]
+    fn markdown_mode_ignored_for_other_styles() {
+        let mut table = markdown_table(MarkdownMode::Compact);
+        table.set_style(TableStyle::Classic);
+        assert!(table.render().starts_with("+--"));
+    }
+
+    #[test]
+    fn line_prefix_and_newline() {
+        let mut table = Table::new().header(["A"]).row(["1"]);
+        table.set_render_options(
+            RenderOptions::new()
+                .line_prefix("    ")
+                .newline(Newline::CrLf),
+        );
+        let expected = "    +---+\r\n    | A |\r\n    +---+\r\n    | 1 |\r\n    +---+\r\n";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.render_cached(), expected);
+        assert_eq!(table.to_string(), expected);
+        assert_eq!(table.rendered_height(), 5);
     }
 
     #[test]
-    fn filter_col() {
-        let mut table = Table::new();
-        table.add_row(["100"]);
-        table.add_row(["50"]);
-        table.add_row(["75"]);
-        table.filter_col(0, |val| val.parse::<i32>().is_ok_and(|n| n > 60));
-        assert_eq!(table.len(), 2);
+    fn line_prefix_in_split_and_pages() {
+        let mut table = wide_table();
+        table.set_render_options(RenderOptions::new().line_prefix("# "));
+        let split = table.render_split(&SplitOptions::new(30));
+        assert!(split.lines().all(|line| line.starts_with('#')));
+        assert!(split.contains("\n#\n"));
+        for page in table.paginate(1) {
+            assert!(page.render().lines().all(|line| line.starts_with("# ")));
+        }
     }
 
+    // Max rows tests
     #[test]
-    fn filter_has() {
-        let mut table = Table::new();
-        table.add_row(["Kelana Smith"]);
-        table.add_row(["Kata Jones"]);
-        table.add_row(["Squidward Smith"]);
-        table.filter_has(0, "Smith");
-        assert_eq!(table.len(), 2);
+    fn max_rows_elides_remaining_rows() {
+        let mut table = Table::new().header(["ID", "Name"]);
+        for i in 1..=5 {
+            table.add_row([i.to_string(), format!("Item {i}")]);
+        }
+        table.set_max_rows(Some(2));
+        let expected = "\
++-----+-----------+
+| ID  | Name      |
++-----+-----------+
+| 1   | Item 1    |
+| 2   | Item 2    |
+| … (3 more rows) |
++-----------------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rendered_height(), 7);
     }
 
     #[test]
-    fn filtered_returns_new_table() {
-        let mut table = Table::new();
-        table.set_style(TableStyle::Modern);
-        table.add_row(["25"]);
-        table.add_row(["30"]);
-        table.add_row(["25"]);
+    fn max_rows_counts_only_table_rows() {
+        let mut table = sales_table().row(["Rome", "1"]).row(["Paris", "2"]);
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+        table.set_max_rows(Some(2));
+        let expected = "\
++-----------+-------+
+| City      | Sales |
++-----------+-------+
+| Berlin    | 10.5  |
+| Berlin    | 4     |
++-----------+-------+
+| Subtotal  | 14.5  |
+| … (3 more rows)   |
++===========+=======+
+| Total     | 20.5  |
++-----------+-------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rendered_height(), expected.lines().count());
+
+        table.set_max_rows(Some(3));
+        let output = table.render();
+        assert!(output.contains("| Paris     | 3     |"), "{output}");
+        assert!(output.contains("… (2 more rows)"), "{output}");
+        assert!(!output.contains("| Subtotal  | 5 "), "{output}");
 
-        let filtered = table.filtered(|row| row.cells()[0].content() == "25");
-        assert_eq!(table.len(), 3); // Original unchanged
-        assert_eq!(filtered.len(), 2);
-        assert_eq!(filtered.style(), TableStyle::Modern);
+        table.collapse_group("Berlin");
+        table.set_max_rows(Some(1));
+        let output = table.render();
+        assert!(output.contains("… (3 more rows)"), "{output}");
+        assert!(output.contains("| Total     | 20.5  |"), "{output}");
+
+        table.set_max_rows(Some(5));
+        assert!(!table.render().contains('…'));
     }
 
-    // Column operations tests
     #[test]
-    fn add_column() {
-        let mut table = Table::new();
-        table.set_headers(["A", "B"]);
-        table.add_row(["1", "2"]);
-        table.add_column(&["C", "3"], Alignment::Right);
-        assert_eq!(table.cols(), 3);
-        assert_eq!(table.headers().unwrap().cells()[2].content(), "C");
+    fn max_rows_singular_and_no_elision() {
+        let mut table = Table::new().row(["a"]).row(["b"]).row(["c"]);
+        table.set_max_rows(Some(3));
+        assert!(!table.render().contains('…'));
+        table.set_max_rows(Some(2));
+        assert!(table.render().contains("… (1 more row)"));
+        table.set_max_rows(None);
+        assert_eq!(table.render().lines().count(), 5);
     }
 
     #[test]
-    fn insert_column() {
-        let mut table = Table::new();
-        table.set_headers(["A", "C"]);
-        table.add_row(["1", "3"]);
-        table.insert_column(1, &["B", "2"], Alignment::Center);
-        assert_eq!(table.headers().unwrap().cells()[1].content(), "B");
+    fn messages_localize_injected_text() {
+        let mut table = sales_table();
+        table.group_by(0);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_subtotals(true);
+        table.set_grand_total(true);
+        table.set_messages(
+            Messages::new()
+                .more_rows("… (noch {} Zeile)", "… (noch {} Zeilen)")
+                .subtotal("Zwischensumme")
+                .total("Summe"),
+        );
+        let output = table.render();
+        assert!(output.contains("| Zwischensumme  | 14.5  |"), "{output}");
+        assert!(output.contains("| Summe          | 17.5  |"), "{output}");
+        assert!(!output.contains("Subtotal"));
+        assert_eq!(table.messages().get_total(), "Summe");
+
+        table.set_max_rows(Some(1));
+        assert!(table.render().contains("… (noch 2 Zeilen)"));
+        table.set_max_rows(Some(2));
+        assert!(table.render().contains("… (noch 1 Zeile)"));
     }
 
     #[test]
-    fn remove_column() {
-        let mut table = Table::new();
-        table.set_headers(["A", "B", "C"]);
-        table.add_row(["1", "2", "3"]);
-        assert!(table.remove_column(1));
-        assert_eq!(table.cols(), 2);
-        assert_eq!(table.headers().unwrap().cells()[1].content(), "C");
+    fn messages_no_data_placeholder() {
+        let mut table = Table::new().header(["ID", "Name"]);
+        assert!(!table.render().contains("no data"));
+        table.set_messages(Messages::new().no_data("(no data)"));
+        let expected = "\
++-----+------+
+| ID  | Name |
++-----+------+
+| (no data)  |
++------------+
+";
+        assert_eq!(table.render(), expected);
+        table.add_row(["1", "Kata"]);
+        assert!(!table.render().contains("no data"));
+        assert!(Table::new().render().is_empty());
     }
 
-    // Render tests
     #[test]
-    fn render_into_reuses_buffer() {
-        let table = Table::new().header(["A", "B"]).row(["1", "2"]);
+    fn max_rows_widens_narrow_table() {
+        let mut table = Table::new().row(["a"]).row(["b"]).row(["c"]);
+        table.set_max_rows(Some(0));
+        let output = table.render();
+        let widths: Vec<usize> = output.lines().map(|line| line.chars().count()).collect();
+        assert!(widths.iter().all(|&width| width == widths[0]), "{output}");
+        assert!(output.contains("… (3 more rows)"));
+    }
 
-        let mut buffer = Vec::with_capacity(10);
-        let original_capacity = buffer.capacity();
+    // Truncation marker tests
+    #[test]
+    fn truncation_marker_default() {
+        let table = Table::new();
+        assert_eq!(table.truncation_marker(), Some("..."));
+    }
 
-        table.render_into(&mut buffer).unwrap();
-        let _first_capacity = buffer.capacity();
+    #[test]
+    fn truncation_marker_on_insert() {
+        let cases = [
+            (Some("…"), "Cherry Bl…"),
+            (Some("..."), "Cherry ..."),
+            (Some(" [+]"), "Cherry [+]"),
+            (None, "Cherry Blo"),
+        ];
+        for (marker, expected) in cases {
+            let mut table = Table::new().truncate(10);
+            table.set_truncation_marker(marker);
+            table.add_row(["Cherry Blossom"]);
+            assert_eq!(table.rows()[0].cells()[0].content(), expected);
+        }
+    }
 
-        buffer.clear();
-        table.render_into(&mut buffer).unwrap();
+    #[test]
+    fn truncation_marker_wider_than_limit_cuts_hard() {
+        let mut table = Table::new().truncate(2);
+        table.set_truncation_marker("…");
+        table.add_row(["Kelana"]);
+        assert_eq!(table.rows()[0].cells()[0].content(), "K…");
 
-        assert!(buffer.capacity() >= original_capacity);
-        assert!(!buffer.is_empty());
+        table.set_truncation_marker("...");
+        table.add_row(["Kelana"]);
+        assert_eq!(table.rows()[1].cells()[0].content(), "Ke");
     }
 
     #[test]
-    fn render_single_row() {
-        let table = Table::new().row(["a", "b"]);
-        let output = table.render();
-        assert!(!output.is_empty());
-        assert!(output.contains('a'));
-        assert!(output.contains('b'));
+    fn truncate_multibyte_content() {
+        let table = Table::new().truncate(5).row(["日本語のテキスト"]);
+        assert_eq!(table.rows()[0].cells()[0].content(), "日本...");
     }
 
     #[test]
-    fn render_with_headers() {
-        let table = Table::new().header(["X", "Y"]).row(["1", "2"]);
-        let output = table.render();
-        assert!(output.contains('X'));
-        assert!(output.contains('Y'));
-        assert!(output.contains('1'));
+    fn truncation_marker_on_fixed_width() {
+        let mut table = Table::new().row(["Cherry Blossom"]);
+        table.constrain(WidthConstraint::Fixed(8));
+        table.set_truncation_marker("…");
+        assert!(table.render().contains("| Cherry … |"));
+        table.set_truncation_marker(None);
+        assert!(table.render().contains("| Cherry B |"));
     }
 
-    // Text wrapping tests
     #[test]
-    fn wrap_text_short() {
-        let lines = Table::wrap_text("hello", 10);
-        assert_eq!(lines, vec!["hello"]);
+    fn fixed_width_narrower_than_marker_fills_with_dots() {
+        let cases = [(1, "| . |"), (2, "| .. |"), (3, "| ... |")];
+        for (width, expected) in cases {
+            let mut table = Table::new().row(["Cherry"]).row(["K"]);
+            table.constrain(WidthConstraint::Fixed(width));
+            let output = table.render();
+            assert!(output.contains(expected), "{width}: {output}");
+            table.set_truncation_marker("…");
+            assert!(!table.render().contains(expected), "{width}");
+        }
     }
 
     #[test]
-    fn wrap_text_multiple_words() {
-        let lines = Table::wrap_text("hello world foo", 10);
-        assert!(lines.len() >= 2);
+    fn weight_constraints_share_leftover_width() {
+        let mut table = Table::new()
+            .header(["id", "name", "notes"])
+            .row(["1", "Kata", "x"]);
+        table.set_constraint(0, WidthConstraint::Fixed(4));
+        table.set_constraint(1, WidthConstraint::Weight(1));
+        table.set_constraint(2, WidthConstraint::Weight(2));
+        let output = table.render();
+        let cells: Vec<usize> = output
+            .lines()
+            .nth(1)
+            .unwrap()
+            .split('|')
+            .map(str::len)
+            .collect();
+        // 108 columns are left after the fixed column, split 1:2
+        assert_eq!(cells, [0, 7, 39, 74, 0]);
+
+        table.set_constraint(1, WidthConstraint::Weight(0));
+        table.set_constraint(2, WidthConstraint::Weight(0));
+        assert!(table.render().contains("| 1     | Kata  | x     |"));
     }
 
+    // CSV export tests
     #[test]
-    fn wrap_text_long_word() {
-        let lines = Table::wrap_text("supercalifragilisticexpialidocious", 10);
-        assert!(lines.len() > 1);
+    fn to_csv() {
+        let table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kelana", "likes \"quotes\""])
+            .row(["Kata"]);
+        assert_eq!(
+            table.to_csv(&CsvOptions::new()),
+            "Name,Note\nKelana,\"likes \"\"quotes\"\"\"\nKata,\n"
+        );
     }
 
     #[test]
-    fn wrap_text_unicode() {
-        // Test with multi-byte UTF-8 characters (Japanese)
-        let lines = Table::wrap_text("こんにちは世界", 5);
-        assert_eq!(lines.len(), 2);
-        assert_eq!(lines[0], "こんにちは");
-        assert_eq!(lines[1], "世界");
+    fn to_csv_dialect() {
+        let table = Table::new().header(["A", "B"]).row(["1", "x;y"]);
+        let options = CsvOptions::new()
+            .delimiter(';')
+            .quoting(Quoting::Always)
+            .newline(Newline::CrLf)
+            .no_header();
+        assert_eq!(table.to_csv(&options), "\"1\";\"x;y\"\r\n");
     }
 
     #[test]
-    fn wrap_text_unicode_long_word() {
-        // Test wrapping a long word with multi-byte characters
-        let lines = Table::wrap_text("日本語テスト文字列", 4);
-        assert_eq!(lines.len(), 3);
-        assert_eq!(lines[0], "日本語テ");
-        assert_eq!(lines[1], "スト文字");
-        assert_eq!(lines[2], "列");
+    fn to_csv_spans_and_empty() {
+        assert_eq!(Table::new().to_csv(&CsvOptions::new()), "");
+
+        let mut row = Row::new();
+        let mut wide = Cell::new("Both", Alignment::Left);
+        wide.set_span(2);
+        row.push(wide);
+        let table = Table::new().header(["A", "B", "C"]).row(row);
+        assert_eq!(table.to_csv(&CsvOptions::new()), "A,B,C\nBoth,,\n");
     }
 
     #[test]
-    fn wrap_text_emoji() {
-        // Test with emoji (4-byte UTF-8 characters)
-        let lines = Table::wrap_text("🎉🎊🎁🎄🎅", 3);
-        assert_eq!(lines.len(), 2);
-        assert_eq!(lines[0], "🎉🎊🎁");
-        assert_eq!(lines[1], "🎄🎅");
+    fn to_csv_wrapped() {
+        let mut table = Table::new()
+            .header(["ID", "Description"])
+            .row(["1", "wrapped into lines"]);
+        table.set_constraint(1, WidthConstraint::Wrap(8));
+        assert_eq!(
+            table.to_csv(&CsvOptions::new()),
+            "ID,Description\n1,wrapped into lines\n"
+        );
+        assert_eq!(
+            table.to_csv(&CsvOptions::new().wrapped()),
+            "ID,Description\n1,\"wrapped\ninto\nlines\"\n"
+        );
     }
 
-    // Vertical alignment tests
-    #[test]
-    fn apply_vertical_alignment_top() {
-        let lines = vec!["a".to_string()];
-        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Top);
-        assert_eq!(result, vec!["a", "", ""]);
+    // Wrap policy tests
+    fn policy_table(constraint: WidthConstraint, policy: Option<WrapPolicy>) -> Table {
+        let mut table = Table::new().row(["abcdef ghi"]);
+        table.set_constraint(0, constraint);
+        if let Some(policy) = policy {
+            table.set_wrap_policy(0, policy);
+        }
+        table
     }
 
-    #[test]
-    fn apply_vertical_alignment_middle() {
-        let lines = vec!["a".to_string()];
-        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Middle);
-        assert_eq!(result, vec!["", "a", ""]);
+    fn cell_lines(table: &Table) -> Vec<String> {
+        let output = table.render();
+        let lines: Vec<&str> = output.lines().collect();
+        lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| line.trim_matches(|c| c == '|' || c == ' ').to_string())
+            .collect()
     }
 
     #[test]
-    fn apply_vertical_alignment_bottom() {
-        let lines = vec!["a".to_string()];
-        let result = Table::apply_vertical_alignment(lines, 3, VerticalAlignment::Bottom);
-        assert_eq!(result, vec!["", "", "a"]);
+    fn wrap_policy_defaults() {
+        let table = policy_table(WidthConstraint::Wrap(4), None);
+        assert_eq!(table.wrap_policy(0), WrapPolicy::Word);
+        assert_eq!(table.wrap_policy(1), WrapPolicy::Truncate);
+        let table = policy_table(WidthConstraint::Fixed(4), None);
+        assert_eq!(table.wrap_policy(0), WrapPolicy::Truncate);
     }
 
     #[test]
-    fn display_trait_matches_render() {
-        let table = Table::new()
-            .header(["Name", "Value"])
-            .row(["Kata", "100"])
-            .row(["Kelana", "200"]);
+    fn wrap_policies_on_wrap_column() {
+        let cases = [
+            (None, vec!["abcd", "ef", "ghi"]),
+            (Some(WrapPolicy::Word), vec!["abcd", "ef", "ghi"]),
+            (Some(WrapPolicy::Character), vec!["abcd", "ef g", "hi"]),
+            (Some(WrapPolicy::Truncate), vec!["a..."]),
+            (Some(WrapPolicy::Never), vec!["abcdef ghi"]),
+        ];
+        for (policy, expected) in cases {
+            let table = policy_table(WidthConstraint::Wrap(4), policy);
+            assert_eq!(cell_lines(&table), expected, "{policy:?}");
+        }
+    }
 
-        let rendered = table.render();
-        let displayed = format!("{table}");
+    #[test]
+    fn wrap_policies_on_fixed_column() {
+        let cases = [
+            (None, vec!["a..."]),
+            (Some(WrapPolicy::Word), vec!["abcd", "ef", "ghi"]),
+            (Some(WrapPolicy::Character), vec!["abcd", "ef g", "hi"]),
+            (Some(WrapPolicy::Never), vec!["abcdef ghi"]),
+        ];
+        for (policy, expected) in cases {
+            let table = policy_table(WidthConstraint::Fixed(4), policy);
+            assert_eq!(cell_lines(&table), expected, "{policy:?}");
+        }
+    }
 
-        assert_eq!(rendered, displayed);
+    #[test]
+    fn wrap_policies_shift_with_columns() {
+        let mut table = Table::new().header(["A", "B"]);
+        table.set_wrap_policy(1, WrapPolicy::Never);
+        table.insert_column(0, &["Z"], Alignment::Left);
+        assert_eq!(table.wrap_policy(2), WrapPolicy::Never);
+        table.remove_column(0);
+        assert_eq!(table.wrap_policy(1), WrapPolicy::Never);
     }
 
+    // Hyphenation tests
     #[test]
-    fn display_trait_empty_table() {
-        let table = Table::new();
-        let displayed = format!("{table}");
-        assert_eq!(displayed, "");
+    fn hyphenate_in_wrap_column() {
+        let mut table = Table::new().row(["see abcdefghij"]);
+        table.set_constraint(0, WidthConstraint::Wrap(5));
+        assert!(!table.hyphenate());
+        assert_eq!(cell_lines(&table), vec!["see", "abcde", "fghij"]);
+        table.set_hyphenate(true);
+        assert_eq!(cell_lines(&table), vec!["see", "abcd-", "efgh-", "ij"]);
+
+        table.set_wrap_policy(0, WrapPolicy::Character);
+        assert_eq!(cell_lines(&table), vec!["see a", "bcdef", "ghij"]);
     }
 
+    // ANSI stripping tests
     #[test]
-    fn display_trait_with_style() {
+    fn strip_ansi_on_insert() {
         let mut table = Table::new();
-        table.set_style(TableStyle::Modern);
-        table.set_headers(["A", "B"]);
-        table.add_row(["1", "2"]);
-
-        let rendered = table.render();
-        let displayed = format!("{table}");
+        assert!(!table.strip_ansi());
+        table.add_row(["\x1b[1mkept\x1b[0m"]);
+        table.set_strip_ansi(true);
+        table.set_headers(["\x1b[4mName\x1b[0m"]);
+        table.add_row(["\x1b[31mKelana\x1b[0m"]);
+        table.insert_row(0, ["\x1b[32mKata\x1b[0m"]);
 
-        assert_eq!(rendered, displayed);
+        assert_eq!(table.headers().unwrap().cells()[0].content(), "Name");
+        assert_eq!(table.rows()[0].cells()[0].content(), "Kata");
+        assert_eq!(table.rows()[1].cells()[0].content(), "\x1b[1mkept\x1b[0m");
+        assert_eq!(table.rows()[2].cells()[0].content(), "Kelana");
     }
 
     #[test]
-    fn add_row_invalidates_cache() {
-        let mut table = Table::new().header(["A"]).row(["1"]);
-
-        let first = table.render_cached();
+    fn strip_ansi_before_truncate_keeps_spans() {
+        let mut table = Table::new().truncate(5);
+        table.set_strip_ansi(true);
+        let mut row = Row::new();
+        let mut cell = Cell::new("\x1b[31mKelana\x1b[0m", Alignment::Right);
+        cell.set_span(2);
+        row.push(cell);
+        table.add_row(row);
+        assert_eq!(table.rows()[0].cells()[0].content(), "Ke...");
 
-        table.add_row(["2"]);
+        let mut table = Table::new();
+        table.set_strip_ansi(true);
+        let mut row = Row::new();
+        let mut cell = Cell::new("\x1b[31mKelana\x1b[0m", Alignment::Right);
+        cell.set_span(2);
+        row.push(cell);
+        table.add_row(row);
+        let cell = &table.rows()[0].cells()[0];
+        assert_eq!((cell.content(), cell.span()), ("Kelana", 2));
+    }
 
-        let second = table.render_cached();
+    // Tab expansion tests
+    #[test]
+    fn expand_tabs() {
+        let cases = [
+            ("a\tb", 4, "a   b"),
+            ("\tx", 4, "    x"),
+            ("abcd\te", 4, "abcd    e"),
+            ("a \t b", 4, "a    b"),
+            ("ab\t\tc", 2, "ab    c"),
+            ("a\tb\n\tc", 4, "a   b\n    c"),
+            ("a\tb", 0, "ab"),
+            ("é\tx", 4, "é   x"),
+        ];
+        for (content, width, expected) in cases {
+            assert_eq!(Table::expand_tabs(content, width), expected, "{content:?}");
+        }
+    }
 
-        assert_ne!(first, second);
+    #[test]
+    fn tabs_expanded_on_insert() {
+        let mut table = Table::new();
+        assert_eq!(table.tab_width(), 4);
+        table.set_headers(["Key\tValue"]);
+        table.add_row(["a\t1"]);
+        table.set_tab_width(2);
+        table.insert_row(0, ["a\t1"]);
+        assert_eq!(table.headers().unwrap().cells()[0].content(), "Key Value");
+        assert_eq!(table.rows()[0].cells()[0].content(), "a 1");
+        assert_eq!(table.rows()[1].cells()[0].content(), "a   1");
     }
 
     #[test]
-    fn set_headers_invalidates_cache() {
-        let mut table = Table::new().header(["A"]).row(["1"]);
+    fn tabs_keep_columns_aligned() {
+        let mut table = Table::new().header(["Text", "N"]);
+        table.add_row(["\tx", "1"]);
+        table.add_row(["    y", "2"]);
+        let output = table.render();
+        let widths: Vec<_> = output.lines().map(|line| line.chars().count()).collect();
+        assert!(widths.iter().all(|&width| width == widths[0]), "{output}");
+        assert!(!output.contains('\t'));
+    }
 
-        let first = table.render_cached();
+    #[test]
+    fn tabs_expanded_before_truncate() {
+        let mut table = Table::new().truncate(6);
+        table.add_row(["\tabcdef"]);
+        assert_eq!(table.rows()[0].cells()[0].content(), "   ...");
+
+        let mut table = Table::new().truncate(8);
+        table.set_strip_ansi(true);
+        table.add_row(["\x1b[1mab\x1b[0m\tc"]);
+        assert_eq!(table.rows()[0].cells()[0].content(), "ab  c");
+    }
 
-        table.set_headers(["B"]);
+    // Explicit newline tests
+    #[test]
+    fn explicit_newlines_render_multiline() {
+        let table = Table::new()
+            .header(["Name", "Address"])
+            .row(["Kelana", "Main St 1\nBerlin"]);
+        let expected = "\
++---------+-----------+
+| Name    | Address   |
++---------+-----------+
+| Kelana  | Main St 1 |
+|         | Berlin    |
++---------+-----------+
+";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rendered_height(), 6);
+    }
 
-        let second = table.render_cached();
+    #[test]
+    fn explicit_newlines_with_wrapping() {
+        let mut table = Table::new().row(["one two\r\nthree"]);
+        table.set_constraint(0, WidthConstraint::Wrap(4));
+        assert_eq!(cell_lines(&table), vec!["one", "two", "thre", "e"]);
+    }
 
-        assert_ne!(first, second);
+    #[test]
+    fn explicit_newlines_in_header() {
+        let table = Table::new().header(["Total\n(EUR)"]).row(["5"]);
+        let output = table.render();
+        assert!(output.contains("| Total |\n| (EUR) |"));
     }
 
+    // JSON Lines export tests
     #[test]
-    fn render_into_matches_render() {
+    fn to_jsonl() {
         let table = Table::new()
-            .header(["Name", "Value"])
-            .row(["Kata", "100"])
-            .row(["Kelana", "200"]);
+            .header(["name", "note"])
+            .row(["Kelana", "says \"hi\""])
+            .row(["Kata"]);
+        assert_eq!(
+            table.to_jsonl(),
+            "{\"name\":\"Kelana\",\"note\":\"says \\\"hi\\\"\"}\n{\"name\":\"Kata\",\"note\":\"\"}\n"
+        );
+    }
 
-        let rendered = table.render();
-        let mut buffer = Vec::new();
-        table.render_into(&mut buffer).unwrap();
+    #[test]
+    fn to_jsonl_without_headers() {
+        let table = Table::new().header(["a"]).row(["1", "2"]);
+        assert_eq!(table.to_jsonl(), "{\"a\":\"1\",\"1\":\"2\"}\n");
+        assert_eq!(Table::new().header(["a"]).to_jsonl(), "");
+    }
 
-        assert_eq!(String::from_utf8(buffer).unwrap(), rendered);
+    #[test]
+    fn to_jsonl_follows_sort_and_filter() {
+        let mut table = sales_table();
+        table.filter_eq(0, "Berlin");
+        table.sort_num_desc(1);
+        assert_eq!(
+            table.to_jsonl(),
+            "{\"City\":\"Berlin\",\"Sales\":\"10.5\"}\n{\"City\":\"Berlin\",\"Sales\":\"4\"}\n"
+        );
     }
 
     #[test]
-    fn format_cell_left_alignment() {
-        let result = Table::format_cell("test", 10, Alignment::Left);
-        assert_eq!(result, "test      ");
+    fn to_json() {
+        let table = Table::new()
+            .header(["name"])
+            .row(["Kelana"])
+            .row(["Kata", "x"]);
+        assert_eq!(
+            table.to_json(),
+            "[\n  {\"name\":\"Kelana\",\"1\":\"\"},\n  {\"name\":\"Kata\",\"1\":\"x\"}\n]\n"
+        );
+        assert_eq!(Table::new().header(["a"]).to_json(), "[]\n");
     }
 
+    // HTML export tests
     #[test]
-    fn format_cell_right_alignment() {
-        let result = Table::format_cell("test", 10, Alignment::Right);
-        assert_eq!(result, "      test");
+    fn to_html() {
+        let mut table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kelana & Kata", "two\nlines"]);
+        let mut row = Row::new();
+        let mut cell = Cell::new("both", Alignment::Left);
+        cell.set_span(2);
+        row.push(cell);
+        table.add_row(row);
+        table.align(1, Alignment::Center);
+        assert_eq!(
+            table.to_html(),
+            "<table>\n<thead>\n\
+             <tr><th>Name</th><th style=\"text-align: center\">Note</th></tr>\n</thead>\n\
+             <tbody>\n\
+             <tr><td>Kelana &amp; Kata</td><td style=\"text-align: center\">two<br>lines</td></tr>\n\
+             <tr><td colspan=\"2\">both</td></tr>\n</tbody>\n</table>\n"
+        );
     }
 
     #[test]
-    fn format_cell_center_alignment() {
-        let result = Table::format_cell("test", 10, Alignment::Center);
-        assert_eq!(result, "   test   ");
+    fn to_html_without_headers_or_rows() {
+        assert_eq!(Table::new().to_html(), "<table>\n</table>\n");
+        assert_eq!(
+            Table::new().row(["1"]).to_html(),
+            "<table>\n<tbody>\n<tr><td>1</td></tr>\n</tbody>\n</table>\n"
+        );
     }
 
     #[test]
-    fn format_cell_truncation() {
-        let result = Table::format_cell("hello world", 8, Alignment::Left);
-        assert_eq!(result, "hello...");
+    fn to_html_links_columns() {
+        let mut table = Table::new()
+            .header(["Issue", "Title"])
+            .row(["12", "a&b"])
+            .row(["", "x"]);
+        table.link_column(0, "https://issues/{value}?a=1&b=2");
+        assert_eq!(table.column_link(0), Some("https://issues/{value}?a=1&b=2"));
+        assert_eq!(table.column_link(1), None);
+        assert_eq!(
+            table.to_html(),
+            "<table>\n<thead>\n<tr><th>Issue</th><th>Title</th></tr>\n</thead>\n<tbody>\n\
+             <tr><td><a href=\"https://issues/12?a=1&amp;b=2\">12</a></td><td>a&amp;b</td></tr>\n\
+             <tr><td></td><td>x</td></tr>\n</tbody>\n</table>\n"
+        );
     }
 
     #[test]
-    fn format_cell_exact_width() {
-        let result = Table::format_cell("test", 4, Alignment::Left);
-        assert_eq!(result, "test");
+    fn hyperlinks_wrap_linked_cells_in_osc8() {
+        let mut table = Table::new()
+            .header(["Issue", "Qty"])
+            .row(["12", "3"])
+            .row(["7", "4"]);
+        table.link_column(0, "https://issues/{value}");
+        table.align(0, Alignment::Right);
+        let plain = table.render();
+        assert!(!plain.contains('\x1b'));
+
+        table.aggregate(1, Aggregate::Sum);
+        table.set_grand_total(true);
+        table.set_render_options(RenderOptions::new().hyperlinks(true));
+        let output = table.render();
+        assert!(output.contains("|    \x1b]8;;https://issues/12\x1b\\12\x1b]8;;\x1b\\  |"));
+        assert!(output.contains("\x1b]8;;https://issues/7\x1b\\7\x1b]8;;\x1b\\  |"));
+        // The total row and header carry no link
+        assert_eq!(output.matches("\x1b]8;;https").count(), 2);
+
+        let mut stream = table.stream();
+        let streamed = stream.header() + &stream.rows(table.rows().to_vec()) + &stream.footer();
+        assert_eq!(streamed.matches("\x1b]8;;https").count(), 2);
     }
 
     #[test]
-    fn recalculate_widths_forces_recalculation() {
-        let mut table = Table::new().header(["A"]).row(["1"]);
+    fn cell_notes_render_as_footnotes_and_titles() {
+        let mut headers = Row::from(["Host", "Load"]);
+        if let Some(cell) = headers.cell_mut(1) {
+            cell.set_note("5 minute average");
+        }
+        let mut table = Table::new();
+        table.set_headers(headers);
+        for (host, load) in [("db1", "0.4"), ("db2", "9.8"), ("db3", "9.9")] {
+            let mut row = Row::from([host, load]);
+            if host != "db1"
+                && let Some(cell) = row.cell_mut(1)
+            {
+                cell.set_note("<spike>");
+            }
+            table.add_row(row);
+        }
 
-        let _ = table.render_cached();
+        assert_eq!(
+            table.render(),
+            "+-------+---------+\n\
+             | Host  | Load[1] |\n\
+             +-------+---------+\n\
+             | db1   | 0.4     |\n\
+             | db2   | 9.8[2]  |\n\
+             | db3   | 9.9[2]  |\n\
+             +-------+---------+\n\
+             [1] 5 minute average\n\
+             [2] <spike>\n"
+        );
+        let html = table.to_html();
+        assert!(html.contains("<th title=\"5 minute average\">Load</th>"));
+        assert!(html.contains("<td title=\"&lt;spike&gt;\">9.8</td>"));
 
-        table.recalculate_widths();
-        let result = table.render_cached();
+        table.set_render_options(RenderOptions::new().notes(NoteMode::Ignore));
+        assert!(!table.render().contains('['));
+        assert!(!table.to_html().contains("title="));
+    }
 
-        assert!(!result.is_empty());
+    #[test]
+    fn rendered_height_counts_footnotes() {
+        let mut row = Row::from(["db2", "9.8"]);
+        if let Some(cell) = row.cell_mut(1) {
+            cell.set_note("spike");
+        }
+        let mut table = Table::new()
+            .header(["Host", "Load"])
+            .row(["db1", "0.4"])
+            .row(["db3", "0.5"]);
+        table.add_row(row);
+        assert_eq!(table.rendered_height(), 8);
+        assert_eq!(table.rendered_height(), table.render().lines().count());
+
+        table.set_render_options(RenderOptions::new().notes(NoteMode::Ignore));
+        assert_eq!(table.rendered_height(), 7);
+        assert_eq!(table.rendered_height(), table.render().lines().count());
     }
 
     #[test]
-    fn render_cached_reuses_cache() {
-        let table = Table::new().header(["A", "B"]).row(["1", "2"]);
+    fn color_modes() {
+        let mut table = Table::new().header(["Job", "State"]).row(["a<b", "ok"]);
+        table.set_header_style(HeaderStyle::new().bold());
+        table.format_column(1, "badge");
+
+        table.set_render_options(RenderOptions::new().color_mode(ColorMode::Html));
+        let expected = "\
++------+----------+
+| <span style=\"font-weight: bold\">Job</span>  | <span style=\"font-weight: bold\">State   </span> |
++------+----------+
+| a&lt;b  | <span style=\"color: #00cd00\">[  OK  ]</span> |
++------+----------+
+";
+        assert_eq!(table.render(), expected);
+
+        table.set_render_options(RenderOptions::new().color_mode(ColorMode::None));
+        assert!(table.render().contains("| a<b  | [  OK  ] |"));
+        assert!(!table.render().contains('\x1b'));
+    }
 
-        // First call populates cache
-        let first = table.render_cached();
+    #[test]
+    fn copy_config_keeps_data() {
+        let mut template = TableBuilder::new()
+            .style(TableStyle::Compact)
+            .format_column(1, "bytes")
+            .empty_placeholder("-")
+            .group_by(0)
+            .build();
+        template.set_border_visibility(BorderVisibility::none());
+
+        let mut table = Table::new()
+            .header(["File", "Size", "Owner"])
+            .row(["a.bin", "2048", ""]);
+        table.copy_config_from(&template);
+        assert_eq!(table.style(), TableStyle::Compact);
+        assert_eq!(table.border_visibility(), BorderVisibility::none());
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.render(), "File  Size    Owner\na.bin 2.0 KiB -    \n");
 
-        // Verify cache is populated
-        assert!(table.cached_widths.borrow().is_some());
+        let mut copy = Table::new();
+        copy.copy_config_from(&template);
+        assert_eq!(copy.group_column(), None);
+    }
 
-        // Second call should return same result (using cache)
-        let second = table.render_cached();
+    #[test]
+    fn last_render_report() {
+        let mut table = Table::new()
+            .header(["Name", "Note"])
+            .row(["Kata", "a long note"])
+            .row(["Kelana", "short"]);
+        let _ = table.render();
+        assert!(table.last_render_report().is_empty());
+
+        table.set_constraint(0, WidthConstraint::Max(4));
+        table.set_constraint(1, WidthConstraint::Wrap(6));
+        table.set_constraint(3, WidthConstraint::Fixed(2));
+        let _ = table.render();
+        let report = table.last_render_report();
+        assert_eq!(report.truncated_cells, 1);
+        assert_eq!(report.clipped_columns, [0, 1]);
+        assert_eq!(report.normalized_constraints, [3]);
+
+        // The selection column is not counted
+        table.set_selection_column(true);
+        let _ = table.render();
+        assert_eq!(table.last_render_report(), report);
+    }
 
-        assert_eq!(first, second);
+    #[test]
+    fn transform_column_keeps_stored_content() {
+        let mut formatters = FormatterRegistry::new();
+        formatters.register("greet", |value| format!("hello {value}"));
+        let mut table = TableBuilder::new()
+            .style(TableStyle::Compact)
+            .formatters(formatters)
+            .format_column(0, "greet")
+            .transform_column(0, TextTransform::TitleCase)
+            .transform_column(1, TextTransform::Upper)
+            .header(["name", "city"])
+            .row(["ada", "london"])
+            .build();
+        assert_eq!(table.column_transform(1), Some(TextTransform::Upper));
+        assert_eq!(table.column_transform(2), None);
+        assert_eq!(
+            table.render(),
+            "│name     │city  │\n──────────┼───────\n│Hello Ada│LONDON│\n"
+        );
+        assert_eq!(table.rows()[0].cells()[1].content(), "london");
+
+        table.filter_eq(1, "london");
+        assert_eq!(table.len(), 1);
     }
 
     #[test]
-    fn render_cached_matches_render() {
-        let table = Table::new()
+    fn border_visibility_hides_lines() {
+        let mut table = Table::new()
             .header(["Name", "Age"])
             .row(["Kata", "30"])
-            .row(["Kelana", "25"]);
+            .row(["Kelana", "7"]);
+        table.set_style(TableStyle::Modern);
+        table.aggregate(1, Aggregate::Sum);
+        table.set_grand_total(true);
 
-        let rendered = table.render();
-        let cached = table.render_cached();
+        table.set_border_visibility(BorderVisibility {
+            inner_horizontal: false,
+            ..BorderVisibility::all().without_frame()
+        });
+        let expected = "\
+\x20Name    │ Age \n\
+─────────┼─────\n\
+\x20Kata    │ 30  \n\
+\x20Kelana  │ 7   \n\
+\x20Total   │ 37  \n";
+        assert_eq!(table.render(), expected);
+        assert_eq!(table.rendered_height(), 5);
+
+        table.set_border_visibility(BorderVisibility {
+            inner_vertical: false,
+            ..BorderVisibility::all()
+        });
+        let expected = "\
+┌───────────────┐
+│ Name      Age │
+├───────────────┤
+│ Kata      30  │
+│ Kelana    7   │
+╞═══════════════╡
+│ Total     37  │
+└───────────────┘
+";
+        assert_eq!(table.render(), expected);
+
+        table.set_border_visibility(BorderVisibility::none().header_rule_only());
+        assert_eq!(table.render().lines().nth(1), Some("───────────────"));
+        table.set_border_visibility(BorderVisibility::none());
+        assert_eq!(table.rendered_height(), 4);
+
+        let mut markdown = Table::new().header(["Name", "Qty"]).row(["a", "1"]);
+        markdown.set_style(TableStyle::Markdown);
+        markdown.set_border_visibility(BorderVisibility::all().without_frame());
+        assert_eq!(
+            markdown.render(),
+            " Name  | Qty \n------ |-----\n a     | 1   \n"
+        );
+        markdown.set_border_visibility(BorderVisibility::none());
+        assert_eq!(markdown.render(), " Name    Qty \n a       1   \n");
+        assert_eq!(markdown.rendered_height(), 2);
+    }
 
-        assert_eq!(rendered, cached);
+    #[test]
+    fn theme_stripes_rows_and_colors_types() {
+        let mut table = Table::new()
+            .header(["Name", "Age", "State"])
+            .row(["Kata", "30", "ok"])
+            .row(["Kelana", "7", "fail"])
+            .row(["Rin", "12", "ok"]);
+        table.format_column(2, "badge");
+        table.set_theme(
+            Theme::new(TableStyle::Classic)
+                .zebra(Color::Fixed(236))
+                .type_color(ColumnType::Integer, Color::Blue),
+        );
+        let lines: Vec<String> = table.render().lines().map(str::to_string).collect();
+        assert_eq!(
+            lines[3],
+            "| Kata    | \x1b[34m30\x1b[0m   | \x1b[32m[  OK  ]\x1b[0m |"
+        );
+        // Colored cells bring the stripe back after their reset
+        assert_eq!(
+            lines[4],
+            "| \x1b[48;5;236mKelana\x1b[0m  \
+             | \x1b[48;5;236m\x1b[34m7\x1b[0m\x1b[48;5;236m  \x1b[0m  \
+             | \x1b[48;5;236m\x1b[31m[ ERR  ]\x1b[0m\x1b[48;5;236m\x1b[0m |"
+        );
+        assert!(!lines[5].contains("48;5"));
+
+        table.set_theme(Theme::plain());
+        let plain = table.render();
+        assert!(!plain.contains("48;5") && !plain.contains("[34m"));
+        assert!(plain.contains("[31m[ ERR  ]"));
     }
 }
@@ -0,0 +1,484 @@
+//! The text helpers tables use to measure, wrap, truncate and pad cell content.
+//!
+//! The functions at the top level count every `char` as one column, like tables do.
+//! With the `unicode-width` feature, [`display`] has the same functions measuring
+//! terminal columns over grapheme clusters, so wide CJK characters take two columns and
+//! combining accents or emoji sequences are never split.
+//!
+//! # Example
+//! ```
+//! use crabular::Alignment;
+//! use crabular::text;
+//!
+//! assert_eq!(text::wrap("the quick brown fox", 10, false), ["the quick", "brown fox"]);
+//! assert_eq!(text::truncate("Cherry Blossom", 8, Some("…")), "Cherry …");
+//! assert_eq!(text::fit("42", 5, Alignment::Right, None), "   42");
+//! assert_eq!(text::width("one\nthree"), 5);
+//! ```
+
+use crate::alignment::Alignment;
+use crate::alloc_prelude::*;
+use crate::ansi;
+
+/// How text is split into units and how many columns each unit takes.
+trait Measure {
+    fn units(text: &str) -> impl Iterator<Item = &str>;
+
+    fn unit_width(unit: &str) -> usize;
+
+    fn width(text: &str) -> usize {
+        Self::units(text).map(Self::unit_width).sum()
+    }
+}
+
+/// Every `char` is one column.
+struct Chars;
+
+impl Measure for Chars {
+    fn units(text: &str) -> impl Iterator<Item = &str> {
+        text.char_indices()
+            .map(move |(index, c)| &text[index..index + c.len_utf8()])
+    }
+
+    fn unit_width(_: &str) -> usize {
+        1
+    }
+
+    fn width(text: &str) -> usize {
+        text.chars().count()
+    }
+}
+
+/// Every visible `char` is one column. ANSI escape sequences take none and stay with the
+/// character after them, so wrapping and truncating never split them.
+struct Ansi;
+
+impl Measure for Ansi {
+    fn units(text: &str) -> impl Iterator<Item = &str> {
+        let mut rest = text;
+        core::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let escapes = ansi::escape_len(rest);
+            let end = escapes + rest[escapes..].chars().next().map_or(0, char::len_utf8);
+            let (unit, tail) = rest.split_at(end);
+            rest = tail;
+            Some(unit)
+        })
+    }
+
+    fn unit_width(unit: &str) -> usize {
+        usize::from(ansi::escape_len(unit) < unit.len())
+    }
+}
+
+/// Returns the width of the widest line of `text`, in characters.
+#[must_use]
+pub fn width(text: &str) -> usize {
+    widest_line::<Chars>(text)
+}
+
+/// Word-wraps `text` onto lines of at most `width` characters. Words longer than a line
+/// are broken, ending every chunk but the last with a hyphen when `hyphenate` is set.
+///
+/// Always returns at least one line.
+#[must_use]
+pub fn wrap(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    wrap_words::<Chars>(text, width, hyphenate)
+}
+
+/// Breaks `text` into lines of exactly `width` characters, the last possibly shorter,
+/// ignoring word boundaries.
+#[must_use]
+pub fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    wrap_units::<Chars>(text, width)
+}
+
+/// Cuts `text` to `limit` characters, ending in `marker` when the marker fits.
+/// Text within the limit is returned unchanged.
+#[must_use]
+pub fn truncate(text: &str, limit: usize, marker: Option<&str>) -> String {
+    truncate_units::<Chars>(text, limit, marker)
+}
+
+/// Pads `text` to exactly `width` characters with the given alignment, truncating it
+/// with `marker` when it is longer.
+#[must_use]
+pub fn fit(text: &str, width: usize, alignment: Alignment, marker: Option<&str>) -> String {
+    fit_units::<Chars>(text, width, alignment, marker)
+}
+
+/// Terminal-column variants of the text helpers, which measure grapheme clusters by
+/// their display width.
+///
+/// # Example
+/// ```
+/// use crabular::Alignment;
+/// use crabular::text::display;
+///
+/// assert_eq!(display::width("日本語"), 6);
+/// assert_eq!(display::wrap("日本語テスト", 5, false), ["日本", "語テ", "スト"]);
+/// assert_eq!(display::fit("日本", 6, Alignment::Center, None), " 日本 ");
+/// // "e" followed by a combining accent stays one unit
+/// assert_eq!(display::truncate("cafe\u{301}s", 4, None), "cafe\u{301}");
+/// ```
+#[cfg(feature = "unicode-width")]
+pub mod display {
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+
+    use super::Measure;
+    use crate::alignment::Alignment;
+
+    /// Grapheme clusters, as wide as they display in a terminal.
+    struct Graphemes;
+
+    impl Measure for Graphemes {
+        fn units(text: &str) -> impl Iterator<Item = &str> {
+            text.graphemes(true)
+        }
+
+        fn unit_width(unit: &str) -> usize {
+            unit.width()
+        }
+
+        fn width(text: &str) -> usize {
+            text.width()
+        }
+    }
+
+    /// Returns the display width of the widest line of `text`, in terminal columns.
+    #[must_use]
+    pub fn width(text: &str) -> usize {
+        super::widest_line::<Graphemes>(text)
+    }
+
+    /// Word-wraps `text` onto lines of at most `width` columns, like [`super::wrap`].
+    #[must_use]
+    pub fn wrap(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+        super::wrap_words::<Graphemes>(text, width, hyphenate)
+    }
+
+    /// Breaks `text` into lines of at most `width` columns between grapheme clusters.
+    /// A cluster wider than `width` gets a line of its own.
+    #[must_use]
+    pub fn wrap_graphemes(text: &str, width: usize) -> Vec<String> {
+        super::wrap_units::<Graphemes>(text, width)
+    }
+
+    /// Cuts `text` to at most `limit` columns, like [`super::truncate`].
+    #[must_use]
+    pub fn truncate(text: &str, limit: usize, marker: Option<&str>) -> String {
+        super::truncate_units::<Graphemes>(text, limit, marker)
+    }
+
+    /// Pads or truncates `text` to exactly `width` columns, like [`super::fit`]. When a
+    /// wide character does not fit, the gap it leaves is padded too.
+    #[must_use]
+    pub fn fit(text: &str, width: usize, alignment: Alignment, marker: Option<&str>) -> String {
+        super::fit_units::<Graphemes>(text, width, alignment, marker)
+    }
+}
+
+/// Like [`width`], not counting ANSI escape sequences.
+pub(crate) fn ansi_width(text: &str) -> usize {
+    widest_line::<Ansi>(text)
+}
+
+/// Like [`wrap`], not counting ANSI escape sequences.
+pub(crate) fn ansi_wrap(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    wrap_words::<Ansi>(text, width, hyphenate)
+}
+
+/// Like [`wrap_chars`], not counting ANSI escape sequences.
+pub(crate) fn ansi_wrap_chars(text: &str, width: usize) -> Vec<String> {
+    wrap_units::<Ansi>(text, width)
+}
+
+/// Like [`fit`], not counting ANSI escape sequences. Sequences after the cut are lost.
+pub(crate) fn ansi_fit(
+    text: &str,
+    width: usize,
+    alignment: Alignment,
+    marker: Option<&str>,
+) -> String {
+    fit_units::<Ansi>(text, width, alignment, marker)
+}
+
+fn widest_line<M: Measure>(text: &str) -> usize {
+    text.lines().map(M::width).max().unwrap_or(0)
+}
+
+fn wrap_words<M: Measure>(text: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    if text.is_empty() || width == 0 {
+        return vec![String::new()];
+    }
+
+    if M::width(text) <= width {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current_line = String::with_capacity(width);
+    let mut current_width = 0;
+
+    for word in text.split_whitespace() {
+        let word_width = M::width(word);
+
+        if current_width > 0 {
+            if current_width + 1 + word_width <= width {
+                current_line.push(' ');
+                current_line.push_str(word);
+                current_width += 1 + word_width;
+                continue;
+            }
+            // Line is full, start a new one
+            lines.push(core::mem::take(&mut current_line));
+            current_width = 0;
+        }
+
+        if word_width > width {
+            break_word::<M>(word, width, hyphenate, &mut lines);
+        } else {
+            current_line.push_str(word);
+            current_width = word_width;
+        }
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+}
+
+fn wrap_units<M: Measure>(text: &str, width: usize) -> Vec<String> {
+    if text.is_empty() || width == 0 {
+        return vec![String::new()];
+    }
+    let mut lines = Vec::new();
+    break_word::<M>(text, width, false, &mut lines);
+    lines
+}
+
+/// Breaks a word that exceeds the line width into chunks of at most `width` columns
+/// and appends them to `lines`. With `hyphenate`, every chunk but the last holds
+/// `width - 1` columns and a hyphen.
+fn break_word<M: Measure>(word: &str, width: usize, hyphenate: bool, lines: &mut Vec<String>) {
+    let hyphenate = hyphenate && width > 1;
+    let mut remaining = M::width(word);
+    let mut units = M::units(word).peekable();
+
+    while units.peek().is_some() {
+        let hyphenated = hyphenate && remaining > width;
+        let limit = if hyphenated { width - 1 } else { width };
+        let mut chunk = String::new();
+        let mut chunk_width = 0;
+        while let Some(unit) =
+            units.next_if(|unit| chunk_width == 0 || chunk_width + M::unit_width(unit) <= limit)
+        {
+            chunk.push_str(unit);
+            chunk_width += M::unit_width(unit);
+            if chunk_width >= limit {
+                break;
+            }
+        }
+        remaining = remaining.saturating_sub(chunk_width);
+        if hyphenated && units.peek().is_some() {
+            chunk.push('-');
+        }
+        lines.push(chunk);
+    }
+}
+
+fn truncate_units<M: Measure>(text: &str, limit: usize, marker: Option<&str>) -> String {
+    if M::width(text) <= limit {
+        return text.to_string();
+    }
+    let marker = marker.unwrap_or_default();
+    let marker_width = M::width(marker);
+    let (kept, marker) = if marker_width == 0 || limit <= marker_width {
+        (limit, "")
+    } else {
+        (limit - marker_width, marker)
+    };
+
+    let mut truncated = String::with_capacity(text.len().min(limit * 4));
+    let mut truncated_width = 0;
+    for unit in M::units(text) {
+        let unit_width = M::unit_width(unit);
+        if truncated_width + unit_width > kept {
+            break;
+        }
+        truncated.push_str(unit);
+        truncated_width += unit_width;
+    }
+    truncated.push_str(marker);
+    truncated
+}
+
+fn fit_units<M: Measure>(
+    text: &str,
+    width: usize,
+    alignment: Alignment,
+    marker: Option<&str>,
+) -> String {
+    let truncated;
+    let mut text = text;
+    let mut text_width = M::width(text);
+    if text_width > width {
+        truncated = truncate_units::<M>(text, width, marker);
+        text = &truncated;
+        text_width = M::width(text);
+    }
+
+    if text_width >= width {
+        return text.to_string();
+    }
+
+    let padding = width - text_width;
+    let (left, right) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Right | Alignment::Decimal => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    let mut result = String::with_capacity(text.len() + padding);
+    result.extend(core::iter::repeat_n(' ', left));
+    result.push_str(text);
+    result.extend(core::iter::repeat_n(' ', right));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Alignment;
+    use crate::text;
+
+    #[test]
+    fn wrap_short() {
+        assert_eq!(text::wrap("hello", 10, false), ["hello"]);
+        assert_eq!(text::wrap("", 10, false), [""]);
+    }
+
+    #[test]
+    fn wrap_multiple_words() {
+        assert_eq!(
+            text::wrap("hello world foo", 10, false),
+            ["hello", "world foo"]
+        );
+    }
+
+    #[test]
+    fn wrap_long_word() {
+        let lines = text::wrap("supercalifragilisticexpialidocious", 10, false);
+        assert_eq!(lines, ["supercalif", "ragilistic", "expialidoc", "ious"]);
+    }
+
+    #[test]
+    fn wrap_unicode() {
+        // Multi-byte UTF-8 characters (Japanese)
+        assert_eq!(
+            text::wrap("こんにちは世界", 5, false),
+            ["こんにちは", "世界"]
+        );
+        assert_eq!(
+            text::wrap("日本語テスト文字列", 4, false),
+            ["日本語テ", "スト文字", "列"]
+        );
+    }
+
+    #[test]
+    fn wrap_emoji() {
+        // Emoji are 4-byte UTF-8 characters
+        assert_eq!(text::wrap("🎉🎊🎁🎄🎅", 3, false), ["🎉🎊🎁", "🎄🎅"]);
+    }
+
+    #[test]
+    fn wrap_hyphenated() {
+        let cases = [
+            (
+                "supercalifragilistic",
+                6,
+                vec!["super-", "calif-", "ragil-", "istic"],
+            ),
+            ("abcdefghij", 5, vec!["abcd-", "efgh-", "ij"]),
+            ("abcdef", 5, vec!["abcd-", "ef"]),
+            ("short words only", 6, vec!["short", "words", "only"]),
+            ("日本語テスト", 3, vec!["日本-", "語テ-", "スト"]),
+            ("abc", 1, vec!["a", "b", "c"]),
+        ];
+        for (input, width, expected) in cases {
+            assert_eq!(text::wrap(input, width, true), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn wrap_chars() {
+        assert_eq!(text::wrap_chars("a0f3c9e1d2", 4), ["a0f3", "c9e1", "d2"]);
+        assert_eq!(text::wrap_chars("", 4), [""]);
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(text::truncate("hello world", 8, Some("...")), "hello...");
+        assert_eq!(text::truncate("hello world", 8, None), "hello wo");
+        assert_eq!(text::truncate("hello world", 2, Some("...")), "he");
+        assert_eq!(text::truncate("short", 8, Some("...")), "short");
+    }
+
+    #[test]
+    fn fit() {
+        assert_eq!(text::fit("test", 8, Alignment::Left, None), "test    ");
+        assert_eq!(text::fit("test", 8, Alignment::Right, None), "    test");
+        assert_eq!(text::fit("test", 9, Alignment::Center, None), "  test   ");
+        assert_eq!(text::fit("test", 4, Alignment::Left, None), "test");
+        assert_eq!(
+            text::fit("hello world", 8, Alignment::Left, Some("...")),
+            "hello..."
+        );
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(text::width(""), 0);
+        assert_eq!(text::width("ab\nabcd\nabc"), 4);
+        assert_eq!(text::width("日本"), 2);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn display_width_and_wrapping() {
+        use crate::text::display;
+
+        assert_eq!(display::width("日本\nabc"), 4);
+        assert_eq!(display::wrap("日本 語テ", 4, false), ["日本", "語テ"]);
+        assert_eq!(
+            display::wrap("日本語テ", 3, true),
+            ["日-", "本-", "語-", "テ"]
+        );
+        assert_eq!(display::wrap_graphemes("a日本", 2), ["a", "日", "本"]);
+        assert_eq!(display::wrap_graphemes("日本", 1), ["日", "本"]);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn display_truncate_and_fit() {
+        use crate::text::display;
+
+        assert_eq!(display::truncate("日本語", 5, None), "日本");
+        assert_eq!(display::truncate("日本語", 5, Some("…")), "日本…");
+        // Family emoji joined with zero-width joiners is kept whole
+        let family = "👨\u{200d}👩\u{200d}👧";
+        assert_eq!(
+            display::truncate(&format!("{family}ab"), 3, None),
+            format!("{family}a")
+        );
+        assert_eq!(display::fit("日本語", 5, Alignment::Left, None), "日本 ");
+        assert_eq!(display::fit("日本", 5, Alignment::Right, None), " 日本");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::alloc_prelude::*;
+
+/// A change of case or whitespace applied to a column's body cells when the table is
+/// rendered, set with [`Table::transform_column`](crate::Table::transform_column).
+///
+/// Stored content is never changed, so sorting, filtering and exports still see it as
+/// it was added. The transform runs after the column's formatter, if any.
+///
+/// # Example
+/// ```
+/// use crabular::{Table, TextTransform};
+///
+/// let mut table = Table::new().header(["Name"]).row(["ada LOVELACE"]);
+/// table.transform_column(0, TextTransform::TitleCase);
+/// assert!(table.render().contains("| Ada Lovelace |"));
+/// assert_eq!(table.rows()[0].cells()[0].content(), "ada LOVELACE");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextTransform {
+    /// Every letter in upper case
+    Upper,
+    /// Every letter in lower case
+    Lower,
+    /// The first letter of each word in upper case and the rest in lower case
+    TitleCase,
+    /// Leading and trailing whitespace removed
+    Trim,
+}
+
+impl TextTransform {
+    /// Returns `text` transformed.
+    #[must_use]
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            TextTransform::Upper => text.to_uppercase(),
+            TextTransform::Lower => text.to_lowercase(),
+            TextTransform::TitleCase => title_case(text),
+            TextTransform::Trim => text.trim().to_string(),
+        }
+    }
+}
+
+/// Upper-cases the first letter after whitespace and lower-cases the others, keeping
+/// the whitespace as it is.
+fn title_case(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut word_start = true;
+    for ch in text.chars() {
+        if word_start {
+            output.extend(ch.to_uppercase());
+        } else {
+            output.extend(ch.to_lowercase());
+        }
+        word_start = ch.is_whitespace();
+    }
+    output
+}
+
+impl core::str::FromStr for TextTransform {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "upper" | "uppercase" => Ok(TextTransform::Upper),
+            "lower" | "lowercase" => Ok(TextTransform::Lower),
+            "title" | "titlecase" => Ok(TextTransform::TitleCase),
+            "trim" => Ok(TextTransform::Trim),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TextTransform;
+
+    #[test]
+    fn apply() {
+        let cases = [
+            (TextTransform::Upper, "straße", "STRASSE"),
+            (TextTransform::Lower, "ÉCOLE", "école"),
+            (
+                TextTransform::TitleCase,
+                "ada  LOVELACE\tx",
+                "Ada  Lovelace\tX",
+            ),
+            (TextTransform::Trim, " \tada \n", "ada"),
+        ];
+        for (transform, input, expected) in cases {
+            assert_eq!(transform.apply(input), expected, "{transform:?}");
+        }
+        assert_eq!("Title".parse(), Ok(TextTransform::TitleCase));
+        assert_eq!("snake".parse::<TextTransform>(), Err(()));
+    }
+}
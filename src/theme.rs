@@ -0,0 +1,163 @@
+use crate::alloc_prelude::*;
+use crate::color::Color;
+use crate::column_type::ColumnType;
+use crate::header_style::HeaderStyle;
+use crate::style::TableStyle;
+
+/// A named look for a table: its border style, header style, zebra striping and the
+/// colors of columns by their [detected type](crate::Table::column_type), applied
+/// together with [`Table::set_theme`](crate::Table::set_theme).
+///
+/// Themes can also be looked up by name, `plain` or `dracula`, for configuration files
+/// and command-line flags.
+///
+/// # Example
+/// ```
+/// use crabular::{Color, ColumnType, TableBuilder, TableStyle, Theme};
+///
+/// let table = TableBuilder::new()
+///     .theme(Theme::dracula())
+///     .header(["Name", "Score"])
+///     .row(["Kata", "95"])
+///     .build();
+/// assert_eq!(table.style(), TableStyle::Modern);
+///
+/// let theme: Theme = "plain".parse().unwrap();
+/// let theme = theme.zebra(Color::Fixed(236)).type_color(ColumnType::Date, Color::Cyan);
+/// assert_eq!(theme.get_type_color(ColumnType::Date), Some(Color::Cyan));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    style: TableStyle,
+    header_style: HeaderStyle,
+    zebra: Option<Color>,
+    type_colors: Vec<(ColumnType, Color)>,
+}
+
+impl Theme {
+    /// A theme that only sets the border style.
+    #[must_use]
+    pub fn new(style: TableStyle) -> Self {
+        Self {
+            style,
+            header_style: HeaderStyle::default(),
+            zebra: None,
+            type_colors: Vec::new(),
+        }
+    }
+
+    /// Classic borders without any colors, which is how a new table looks.
+    #[must_use]
+    pub fn plain() -> Self {
+        Self::new(TableStyle::Classic)
+    }
+
+    /// Modern borders with the colors of the Dracula palette: a bold pink header,
+    /// numbers in purple, booleans in orange, dates in cyan and every other row on
+    /// the palette's current-line gray.
+    #[must_use]
+    pub fn dracula() -> Self {
+        Self::new(TableStyle::Modern)
+            .header_style(HeaderStyle::new().bold().color(Color::Rgb(255, 121, 198)))
+            .zebra(Color::Rgb(68, 71, 90))
+            .type_color(ColumnType::Integer, Color::Rgb(189, 147, 249))
+            .type_color(ColumnType::Decimal, Color::Rgb(189, 147, 249))
+            .type_color(ColumnType::Boolean, Color::Rgb(255, 184, 108))
+            .type_color(ColumnType::Date, Color::Rgb(139, 233, 253))
+    }
+
+    /// Sets how the header row is presented.
+    #[must_use]
+    pub fn header_style(mut self, header_style: HeaderStyle) -> Self {
+        self.header_style = header_style;
+        self
+    }
+
+    /// Draws every other body row, starting with the second, on `background`.
+    #[must_use]
+    pub fn zebra(mut self, background: Color) -> Self {
+        self.zebra = Some(background);
+        self
+    }
+
+    /// Draws the body cells of columns detected as `column_type` in `color`, replacing
+    /// any color set for that type before. Badge colors take precedence.
+    #[must_use]
+    pub fn type_color(mut self, column_type: ColumnType, color: Color) -> Self {
+        self.type_colors
+            .retain(|(existing, _)| *existing != column_type);
+        self.type_colors.push((column_type, color));
+        self
+    }
+
+    #[must_use]
+    pub fn get_style(&self) -> TableStyle {
+        self.style
+    }
+
+    #[must_use]
+    pub fn get_header_style(&self) -> HeaderStyle {
+        self.header_style
+    }
+
+    #[must_use]
+    pub fn get_zebra(&self) -> Option<Color> {
+        self.zebra
+    }
+
+    #[must_use]
+    pub fn get_type_color(&self, column_type: ColumnType) -> Option<Color> {
+        self.type_colors
+            .iter()
+            .find(|(existing, _)| *existing == column_type)
+            .map(|(_, color)| *color)
+    }
+
+    pub(crate) fn into_type_colors(self) -> Vec<(ColumnType, Color)> {
+        self.type_colors
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+/// Parses the name of a built-in theme, in any case.
+impl core::str::FromStr for Theme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "plain" => Ok(Theme::plain()),
+            "dracula" => Ok(Theme::dracula()),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, ColumnType, TableStyle, Theme};
+
+    #[test]
+    fn type_colors_replace() {
+        let theme = Theme::new(TableStyle::Compact)
+            .type_color(ColumnType::Integer, Color::Red)
+            .type_color(ColumnType::Integer, Color::Blue);
+        assert_eq!(theme.get_type_color(ColumnType::Integer), Some(Color::Blue));
+        assert_eq!(theme.get_type_color(ColumnType::Text), None);
+        assert_eq!(theme.get_style(), TableStyle::Compact);
+        assert_eq!(theme.into_type_colors().len(), 1);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("Dracula".parse(), Ok(Theme::dracula()));
+        assert_eq!("plain".parse(), Ok(Theme::default()));
+        assert_eq!("solarized".parse::<Theme>(), Err(()));
+        assert!(Theme::plain().get_zebra().is_none());
+        assert!(Theme::dracula().get_header_style().is_bold());
+    }
+}
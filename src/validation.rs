@@ -0,0 +1,227 @@
+use crate::cell::Cell;
+use crate::constraint::WidthConstraint;
+use crate::row::Row;
+use crate::table::Table;
+
+/// Why a table's shape or settings are inconsistent, returned by [`Table::validate`]
+/// and [`TableBuilder::try_build`](crate::TableBuilder::try_build).
+///
+/// Rendering accepts all of these, padding short rows and ignoring settings that do
+/// not apply, so they are only reported when asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    /// A row covers a different number of columns than the headers, or than the first
+    /// row when there are no headers. Spanned cells count every column they cover.
+    RowLength {
+        /// Index of the row among the table's rows
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A width constraint other than `Auto` is set for a column the table does not have
+    ConstraintOutOfRange { column: usize, columns: usize },
+    /// The `Proportional` constraints add up to more than 100 percent
+    ProportionsExceed { total: usize },
+}
+
+impl core::fmt::Display for TableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            TableError::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} columns, expected {expected}"),
+            TableError::ConstraintOutOfRange { column, columns } => write!(
+                f,
+                "width constraint for column {column}, but the table has {columns} columns"
+            ),
+            TableError::ProportionsExceed { total } => {
+                write!(f, "proportional widths add up to {total}%, more than 100%")
+            }
+        }
+    }
+}
+
+impl core::error::Error for TableError {}
+
+/// The number of columns `row` covers.
+pub(crate) fn span_width(row: &Row) -> usize {
+    row.cells().iter().map(Cell::span).sum()
+}
+
+impl Table {
+    /// Checks that no row was refused by [strict column mode](Table::set_strict_columns),
+    /// that every row covers as many columns as the headers (or the first row without
+    /// headers), that width constraints only name existing columns, and that
+    /// proportional widths add up to at most 100 percent.
+    ///
+    /// # Errors
+    /// Returns the first inconsistency found, in the order above.
+    ///
+    /// # Examples
+    /// ```
+    /// # use crabular::{Table, TableError, WidthConstraint};
+    /// let mut table = Table::new().header(["Name", "Age"]).row(["Kata", "30"]);
+    /// assert_eq!(table.validate(), Ok(()));
+    ///
+    /// table.set_constraint(2, WidthConstraint::Fixed(10));
+    /// assert_eq!(
+    ///     table.validate(),
+    ///     Err(TableError::ConstraintOutOfRange { column: 2, columns: 2 })
+    /// );
+    ///
+    /// table.add_row(["Kelana"]);
+    /// assert_eq!(
+    ///     table.validate().unwrap_err().to_string(),
+    ///     "row 1 has 1 columns, expected 2"
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), TableError> {
+        if let Some(error) = self.rejected_rows().first() {
+            return Err(error.clone());
+        }
+        let expected = self.expected_cols().unwrap_or_else(|| {
+            self.headers()
+                .or_else(|| self.rows().first())
+                .map_or(0, span_width)
+        });
+        for (index, row) in self.rows().iter().enumerate() {
+            let found = span_width(row);
+            if found != expected {
+                return Err(TableError::RowLength {
+                    row: index,
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        let columns = self.cols().max(expected);
+        if let Some(column) = self
+            .constraints()
+            .iter()
+            .enumerate()
+            .skip(columns)
+            .find(|(_, constraint)| **constraint != WidthConstraint::Auto)
+            .map(|(column, _)| column)
+        {
+            return Err(TableError::ConstraintOutOfRange { column, columns });
+        }
+
+        let total: usize = self
+            .constraints()
+            .iter()
+            .map(|constraint| match constraint {
+                WidthConstraint::Proportional(percent) => usize::from(*percent),
+                _ => 0,
+            })
+            .sum();
+        if total > 100 {
+            return Err(TableError::ProportionsExceed { total });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, Cell, Row, Table, TableBuilder, TableError, WidthConstraint};
+
+    #[test]
+    fn row_lengths() {
+        let mut table = Table::new().row(["a", "b"]).row(["c", "d"]);
+        assert_eq!(table.validate(), Ok(()));
+
+        let mut spanned = Row::new();
+        let mut cell = Cell::new("wide", Alignment::Left);
+        cell.set_span(2);
+        spanned.push(cell);
+        table.add_row(spanned);
+        assert_eq!(table.validate(), Ok(()));
+
+        table.add_row(["e", "f", "g"]);
+        assert_eq!(
+            table.validate(),
+            Err(TableError::RowLength {
+                row: 3,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(Table::new().validate(), Ok(()));
+    }
+
+    #[test]
+    fn strict_columns() {
+        let mut table = Table::new();
+        table.set_strict_columns(true);
+        assert_eq!(table.expected_cols(), None);
+        table.add_row(["a", "b"]);
+        assert_eq!(table.expected_cols(), Some(2));
+        table.insert_row(0, ["c"]);
+        assert_eq!(table.add_child_row(0, ["d", "e", "f"]), None);
+        assert_eq!(table.add_child_row(0, ["d", "e"]), Some(1));
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table.rejected_rows(),
+            [
+                TableError::RowLength {
+                    row: 0,
+                    expected: 2,
+                    found: 1
+                },
+                TableError::RowLength {
+                    row: 1,
+                    expected: 2,
+                    found: 3
+                }
+            ]
+        );
+        assert_eq!(table.validate(), Err(table.rejected_rows()[0].clone()));
+        assert_eq!(table.take_rejected_rows().len(), 2);
+        assert_eq!(table.validate(), Ok(()));
+
+        table.set_strict_columns(false);
+        table.add_row(["g"]);
+        assert!(table.rejected_rows().is_empty());
+        assert_eq!(table.len(), 3);
+
+        let built = TableBuilder::new()
+            .strict_columns()
+            .header(["A", "B"])
+            .row(["1", "2", "3"])
+            .row(["4", "5"])
+            .try_build();
+        assert_eq!(
+            built.err(),
+            Some(TableError::RowLength {
+                row: 0,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn try_build() {
+        let built = TableBuilder::new()
+            .header(["A", "B"])
+            .constrain(0, WidthConstraint::Proportional(60))
+            .constrain(1, WidthConstraint::Proportional(50))
+            .row(["1", "2"])
+            .try_build();
+        assert_eq!(
+            built.err(),
+            Some(TableError::ProportionsExceed { total: 110 })
+        );
+
+        let built = TableBuilder::new()
+            .header(["A", "B"])
+            .constrain(1, WidthConstraint::Proportional(50))
+            .constrain(3, WidthConstraint::Auto)
+            .row(["1", "2"])
+            .try_build();
+        assert!(built.is_ok());
+    }
+}
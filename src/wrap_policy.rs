@@ -0,0 +1,59 @@
+/// How a width-limited column handles content longer than its limit.
+///
+/// Applies to columns constrained with `Wrap`, `Fixed` or `Max`. Without an explicit
+/// policy, `Wrap` columns use [`Word`](WrapPolicy::Word) and `Fixed`/`Max` columns use
+/// [`Truncate`](WrapPolicy::Truncate).
+///
+/// # Example
+/// ```
+/// use crabular::{Table, WidthConstraint, WrapPolicy};
+///
+/// let mut table = Table::new().row(["abcdef ghi"]);
+/// table.set_constraint(0, WidthConstraint::Wrap(4));
+/// table.set_wrap_policy(0, WrapPolicy::Character);
+/// assert_eq!(table.rendered_height(), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapPolicy {
+    /// Break lines between words, splitting words longer than the limit
+    Word,
+    /// Break lines at exactly the limit, ignoring word boundaries
+    Character,
+    /// Keep a single line, cut at the limit with the truncation marker
+    Truncate,
+    /// Keep a single line and let the column grow past the limit
+    Never,
+}
+
+impl core::str::FromStr for WrapPolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "word" => Ok(WrapPolicy::Word),
+            "character" | "char" => Ok(WrapPolicy::Character),
+            "truncate" => Ok(WrapPolicy::Truncate),
+            "never" | "none" => Ok(WrapPolicy::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::WrapPolicy;
+
+    #[test]
+    fn from_str() {
+        let cases = [
+            ("word", Ok(WrapPolicy::Word)),
+            ("Char", Ok(WrapPolicy::Character)),
+            ("truncate", Ok(WrapPolicy::Truncate)),
+            ("never", Ok(WrapPolicy::Never)),
+            ("hyphen", Err(())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<WrapPolicy>(), expected);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Byte-for-byte comparisons with the output of the tools some styles imitate.
+//!
+//! The files in `tests/reference` are recorded from the tools themselves:
+//!
+//! - `psql.txt`: `psql` 15 with `\pset border 2` and `\pset footer off`
+//! - `psql_expanded.txt`: `psql` 15 with `\pset border 2`, `\x` and `\t`
+//! - `column_t.txt`: util-linux 2.38 `column -t`, through the `libsmartcols` table it
+//!   prints with
+//!
+//! `psql` ends every result with a blank line, which the tests add to crabular's output.
+
+use crabular::{Alignment, HeaderStyle, Messages, Table, TableStyle};
+
+fn people() -> Table {
+    Table::new()
+        .header(["name", "role", "city", "age"])
+        .row(["Kata", "Engineer", "Jakarta", "30"])
+        .row(["Kelana", "Designer", "Bandung", "7"])
+        .row(["Ayu", "Product Manager", "Surabaya", "125"])
+}
+
+/// Classic with the header centered, numbers on the right and no column spacing.
+fn psql(mut table: Table) -> Table {
+    table.set_style(TableStyle::Classic);
+    table.spacing(0);
+    table.set_header_style(HeaderStyle::new().align(Alignment::Center));
+    table
+}
+
+#[test]
+fn classic_matches_psql() {
+    let mut table = psql(people());
+    table.align(3, Alignment::Right);
+    assert_eq!(
+        format!("{}\n", table.render()),
+        include_str!("reference/psql.txt")
+    );
+}
+
+#[test]
+fn render_record_matches_psql_expanded() {
+    let mut table = psql(people());
+    table.set_messages(Messages::new().record("", ""));
+    assert_eq!(
+        format!("{}\n", table.render_record(1).unwrap()),
+        include_str!("reference/psql_expanded.txt")
+    );
+}
+
+#[test]
+fn plain_matches_column_t() {
+    let mut table = Table::new()
+        .header(["name", "role", "city", "age"])
+        .row(["Kata", "Engineer", "Jakarta", "30"])
+        .row(["Kelana", "Designer", "Bandung", "7"])
+        .row(["Ayu", "Manager", "Surabaya", "125"]);
+    table.set_style(TableStyle::Plain);
+    assert_eq!(table.render(), include_str!("reference/column_t.txt"));
+}
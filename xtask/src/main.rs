@@ -0,0 +1,40 @@
+//! Repository tasks, run with `cargo xtask <task>`.
+//!
+//! - `gallery` rewrites the golden files in `examples/gallery`
+//! - `gallery --check` fails if any of them is out of date
+
+use std::env;
+use std::process::{Command, ExitCode};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(("gallery", rest)) = args.split_first().map(|(task, rest)| (task.as_str(), rest)) {
+        return gallery(rest);
+    }
+    eprintln!("usage: cargo xtask gallery [--check]");
+    ExitCode::FAILURE
+}
+
+fn gallery(args: &[String]) -> ExitCode {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args([
+            "run",
+            "--quiet",
+            "--package",
+            "crabular",
+            "--example",
+            "gallery",
+            "--",
+        ])
+        .args(args)
+        .status();
+    match status {
+        Ok(status) if status.success() => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("failed to run cargo: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}